@@ -0,0 +1,308 @@
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+
+/// JSON keys holding decimal amounts in order detail responses.
+pub(crate) const ORDER_DETAIL_AMOUNT_KEYS: &[&str] = &[
+    "inputVaultBalance",
+    "outputVaultBalance",
+    "vaultBalance",
+    "ioRatio",
+    "inputAmount",
+    "outputAmount",
+];
+
+/// Amount keys that get a `<key>Display` companion, each paired with the
+/// token object whose `symbol` suffixes it. The token is looked up on the
+/// same object first, then on its ancestors.
+const DISPLAY_AMOUNT_KEYS: &[(&str, &str)] = &[
+    ("inputVaultBalance", "inputToken"),
+    ("outputVaultBalance", "outputToken"),
+    ("vaultBalance", "token"),
+    ("inputAmount", "inputToken"),
+    ("outputAmount", "outputToken"),
+];
+
+const DISPLAY_SUFFIX: &str = "Display";
+
+/// Key added to the response listing amount paths that stayed strings.
+pub(crate) const STRING_AMOUNTS_KEY: &str = "stringAmounts";
+
+/// Rewrites decimal string amounts under `amount_keys` as JSON numbers when
+/// an `f64` represents them exactly. Amounts that would lose precision stay
+/// strings and their paths are listed under [`STRING_AMOUNTS_KEY`]; values
+/// that aren't decimals, like `"-"`, are left alone and not listed.
+pub(crate) fn emit_numeric_amounts(value: &mut Value, amount_keys: &[&str]) {
+    let mut string_paths = Vec::new();
+    convert(value, amount_keys, "", &mut string_paths);
+    string_paths.sort();
+    if let Value::Object(object) = value {
+        object.insert(
+            STRING_AMOUNTS_KEY.to_string(),
+            Value::Array(string_paths.into_iter().map(Value::String).collect()),
+        );
+    }
+}
+
+fn convert(value: &mut Value, amount_keys: &[&str], path: &str, string_paths: &mut Vec<String>) {
+    match value {
+        Value::Object(object) => {
+            for (key, child) in object.iter_mut() {
+                let child_path = join_path(path, key);
+                if amount_keys.contains(&key.as_str()) {
+                    if let Value::String(amount) = child {
+                        // Placeholders such as `"-"` for an unquoted ratio
+                        // aren't amounts, so they stay as-is and unlisted.
+                        if canonical_decimal(amount).is_none() {
+                            continue;
+                        }
+                        match safe_f64_number(amount) {
+                            Some(number) => *child = Value::Number(number),
+                            None => string_paths.push(child_path),
+                        }
+                        continue;
+                    }
+                }
+                convert(child, amount_keys, &child_path, string_paths);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                convert(
+                    item,
+                    amount_keys,
+                    &join_path(path, &index.to_string()),
+                    string_paths,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Adds a presentation copy of every amount under [`DISPLAY_AMOUNT_KEYS`],
+/// e.g. `"inputAmountDisplay": "1,234,567.5 USDC"`. The canonical amount is
+/// left untouched; values that aren't plain decimals get no companion.
+pub(crate) fn emit_display_amounts(value: &mut Value) {
+    add_display_amounts(value, &HashMap::new());
+}
+
+fn add_display_amounts(value: &mut Value, inherited: &HashMap<&'static str, String>) {
+    match value {
+        Value::Object(object) => {
+            let mut symbols = inherited.clone();
+            for (_, token_key) in DISPLAY_AMOUNT_KEYS {
+                if let Some(symbol) = token_symbol(object, token_key) {
+                    symbols.insert(*token_key, symbol);
+                }
+            }
+
+            let display: Vec<(String, String)> = DISPLAY_AMOUNT_KEYS
+                .iter()
+                .filter_map(|(amount_key, token_key)| {
+                    let amount = object.get(*amount_key)?.as_str()?;
+                    let grouped = group_thousands(amount)?;
+                    let rendered = match symbols.get(token_key) {
+                        Some(symbol) => format!("{grouped} {symbol}"),
+                        None => grouped,
+                    };
+                    Some((format!("{amount_key}{DISPLAY_SUFFIX}"), rendered))
+                })
+                .collect();
+
+            for child in object.values_mut() {
+                add_display_amounts(child, &symbols);
+            }
+            for (key, rendered) in display {
+                object.insert(key, Value::String(rendered));
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                add_display_amounts(item, inherited);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn token_symbol(object: &Map<String, Value>, token_key: &str) -> Option<String> {
+    object
+        .get(token_key)?
+        .get("symbol")?
+        .as_str()
+        .filter(|symbol| !symbol.is_empty())
+        .map(str::to_string)
+}
+
+/// Formats a plain decimal with `,` between thousands and without
+/// redundant zeros, so `"-1234567.500"` becomes `"-1,234,567.5"`.
+fn group_thousands(amount: &str) -> Option<String> {
+    let canonical = canonical_decimal(amount)?;
+    let (sign, digits) = match canonical.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", canonical.as_str()),
+    };
+    let (int_part, frac_part) = match digits.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+        None => (digits, None),
+    };
+
+    let mut grouped = String::with_capacity(canonical.len() + int_part.len() / 3);
+    grouped.push_str(sign);
+    for (index, digit) in int_part.chars().enumerate() {
+        if index > 0 && (int_part.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    if let Some(frac_part) = frac_part {
+        grouped.push('.');
+        grouped.push_str(frac_part);
+    }
+    Some(grouped)
+}
+
+fn join_path(path: &str, segment: &str) -> String {
+    if path.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{path}.{segment}")
+    }
+}
+
+/// Returns the amount as a JSON number only if the `f64` round-trips to the
+/// same decimal value.
+fn safe_f64_number(amount: &str) -> Option<Number> {
+    let canonical = canonical_decimal(amount)?;
+    let parsed: f64 = amount.trim().parse().ok()?;
+    if !parsed.is_finite() || canonical_decimal(&parsed.to_string())? != canonical {
+        return None;
+    }
+    Number::from_f64(parsed)
+}
+
+/// Normalizes a plain decimal string (no exponent) by stripping redundant
+/// zeros and signs, so `"-0.500"` and `"-0.5"` compare equal.
+fn canonical_decimal(amount: &str) -> Option<String> {
+    let amount = amount.trim();
+    let (negative, digits) = match amount.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, amount),
+    };
+    let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+    if int_part.is_empty() && frac_part.is_empty() {
+        return None;
+    }
+    if !int_part
+        .chars()
+        .chain(frac_part.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let int_part = int_part.trim_start_matches('0');
+    let frac_part = frac_part.trim_end_matches('0');
+    let int_part = if int_part.is_empty() { "0" } else { int_part };
+    let is_zero = int_part == "0" && frac_part.is_empty();
+    let sign = if negative && !is_zero { "-" } else { "" };
+
+    Some(if frac_part.is_empty() {
+        format!("{sign}{int_part}")
+    } else {
+        format!("{sign}{int_part}.{frac_part}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_small_values_become_numbers() {
+        let mut value = json!({
+            "inputVaultBalance": "1.500000",
+            "trades": [{ "inputAmount": "-0.250000000000000000", "id": "1" }]
+        });
+
+        emit_numeric_amounts(&mut value, ORDER_DETAIL_AMOUNT_KEYS);
+
+        assert_eq!(
+            value,
+            json!({
+                "inputVaultBalance": 1.5,
+                "trades": [{ "inputAmount": -0.25, "id": "1" }],
+                "stringAmounts": []
+            })
+        );
+    }
+
+    #[test]
+    fn test_large_or_precise_values_stay_strings() {
+        let mut value = json!({
+            "inputVaultBalance": "123456789012345678901234",
+            "outputVaultBalance": "0.123456789012345678",
+            "ioRatio": "-",
+            "trades": [{ "outputAmount": "9007199254740993" }]
+        });
+
+        emit_numeric_amounts(&mut value, ORDER_DETAIL_AMOUNT_KEYS);
+
+        assert_eq!(value["inputVaultBalance"], "123456789012345678901234");
+        assert_eq!(value["outputVaultBalance"], "0.123456789012345678");
+        assert_eq!(value["ioRatio"], "-");
+        assert_eq!(value["trades"][0]["outputAmount"], "9007199254740993");
+        assert_eq!(
+            value[STRING_AMOUNTS_KEY],
+            json!([
+                "inputVaultBalance",
+                "outputVaultBalance",
+                "trades.0.outputAmount"
+            ])
+        );
+    }
+
+    #[test]
+    fn test_display_amounts_group_thousands_with_symbol() {
+        let mut value = json!({
+            "inputToken": { "symbol": "USDC" },
+            "outputToken": { "symbol": "" },
+            "inputVaultBalance": "1234567.500000",
+            "outputVaultBalance": "1000",
+            "ioRatio": "2500.0",
+            "inputs": [{ "token": { "symbol": "wtMSTR" }, "vaultBalance": "-9876543210.25" }],
+            "trades": [{ "inputAmount": "999", "outputAmount": "-" }]
+        });
+
+        emit_display_amounts(&mut value);
+
+        assert_eq!(value["inputVaultBalance"], "1234567.500000");
+        assert_eq!(value["inputVaultBalanceDisplay"], "1,234,567.5 USDC");
+        assert_eq!(value["outputVaultBalanceDisplay"], "1,000");
+        assert!(value.get("ioRatioDisplay").is_none());
+        assert_eq!(
+            value["inputs"][0]["vaultBalanceDisplay"],
+            "-9,876,543,210.25 wtMSTR"
+        );
+        assert_eq!(value["trades"][0]["inputAmountDisplay"], "999 USDC");
+        assert!(value["trades"][0].get("outputAmountDisplay").is_none());
+    }
+
+    #[test]
+    fn test_group_thousands() {
+        assert_eq!(group_thousands("0.000100").as_deref(), Some("0.0001"));
+        assert_eq!(group_thousands("123456").as_deref(), Some("123,456"));
+        assert_eq!(group_thousands("1234567").as_deref(), Some("1,234,567"));
+        assert_eq!(group_thousands("-"), None);
+    }
+
+    #[test]
+    fn test_canonical_decimal() {
+        assert_eq!(canonical_decimal("001.2500").as_deref(), Some("1.25"));
+        assert_eq!(canonical_decimal("-0.000").as_deref(), Some("0"));
+        assert_eq!(canonical_decimal(".5").as_deref(), Some("0.5"));
+        assert_eq!(canonical_decimal("1e5"), None);
+        assert_eq!(canonical_decimal("-"), None);
+    }
+}
@@ -1,19 +1,103 @@
-use crate::cache::RouteResponseCaches;
+use crate::cache::{AppCache, RouteResponseCaches};
+use crate::config::PageSizeDefaults;
+use crate::denylist::AddressDenylist;
+use crate::error::IndexingRetryHint;
+use crate::fairings::ErrorRateWindow;
+use crate::owner_allowlist::DeployOwnerAllowlist;
 use crate::registry_artifact::RegistryArtifactStore;
+use crate::telemetry::LogFilterHandle;
+use crate::types::health::ReadinessResponse;
 
 pub(crate) struct ApplicationState {
     pub registry_artifact_store: RegistryArtifactStore,
     pub response_caches: RouteResponseCaches,
+    pub log_filter: LogFilterHandle,
+    /// `eth_call` swap calldata before returning it and reject reverts.
+    pub simulate_calldata: bool,
+    /// Default minimum output-token depth for quotes; `"0"` disables it.
+    pub min_quote_liquidity: String,
+    /// Most orders a quote uses for a pair, cheapest first.
+    pub max_quote_orders: u16,
+    pub denylist: AddressDenylist,
+    pub indexing_retry_hint: IndexingRetryHint,
+    pub page_size_defaults: PageSizeDefaults,
+    pub allowed_owners: DeployOwnerAllowlist,
+    /// Reject unknown query parameters unless a request passes `strict=false`.
+    pub strict_query_params: bool,
+    /// Require an API key on health routes other than bare liveness.
+    pub health_requires_auth: bool,
+    /// Sub-queries a batch endpoint runs at the same time.
+    pub batch_concurrency: usize,
+    /// Chains an order listing fetches quotes for at the same time.
+    pub quote_chain_concurrency: usize,
+    /// Most vaults per order a cancel summarizes.
+    pub max_cancel_vaults: usize,
+    /// Last subgraph readiness probe, reused briefly so frequent health
+    /// checks don't query every subgraph each time.
+    pub readiness_probe: AppCache<(), ReadinessResponse>,
+    /// Answer `GET /` with a redirect to Swagger UI rather than JSON.
+    pub root_redirects_to_swagger: bool,
+    /// Hide orders with no known strategy from order listings.
+    pub strict_strategies: bool,
+    /// Widest time range, in seconds, a trade query accepts.
+    pub max_trade_window_secs: u64,
+    /// Trades asked for per subgraph query when assembling trade pages.
+    pub subgraph_page_size: u16,
+    /// Recent response statuses; readiness degrades when too many are 5xx.
+    pub error_rate: ErrorRateWindow,
+    /// Subgraph lag, in blocks, above which a missing order answers
+    /// `NotYetIndexed` instead of `NotFound`. `None` never checks.
+    pub not_found_max_lag_blocks: Option<u64>,
 }
 
 impl ApplicationState {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         registry_artifact_store: RegistryArtifactStore,
         response_caches: RouteResponseCaches,
+        log_filter: LogFilterHandle,
+        simulate_calldata: bool,
+        min_quote_liquidity: String,
+        max_quote_orders: u16,
+        denylist: AddressDenylist,
+        indexing_retry_hint: IndexingRetryHint,
+        page_size_defaults: PageSizeDefaults,
+        allowed_owners: DeployOwnerAllowlist,
+        strict_query_params: bool,
+        health_requires_auth: bool,
+        batch_concurrency: usize,
+        quote_chain_concurrency: usize,
+        max_cancel_vaults: usize,
+        root_redirects_to_swagger: bool,
+        strict_strategies: bool,
+        max_trade_window_secs: u64,
+        subgraph_page_size: u16,
+        health_error_rate_threshold: Option<f64>,
+        not_found_max_lag_blocks: Option<u64>,
     ) -> Self {
         Self {
             registry_artifact_store,
             response_caches,
+            log_filter,
+            simulate_calldata,
+            min_quote_liquidity,
+            max_quote_orders,
+            denylist,
+            indexing_retry_hint,
+            page_size_defaults,
+            allowed_owners,
+            strict_query_params,
+            health_requires_auth,
+            batch_concurrency,
+            quote_chain_concurrency,
+            max_cancel_vaults,
+            readiness_probe: AppCache::new(1, crate::routes::health::READINESS_PROBE_TTL),
+            root_redirects_to_swagger,
+            strict_strategies,
+            max_trade_window_secs,
+            subgraph_page_size,
+            error_rate: ErrorRateWindow::new(health_error_rate_threshold),
+            not_found_max_lag_blocks,
         }
     }
 }
@@ -1,6 +1,6 @@
-use crate::db::DbPool;
+use crate::db::Db;
 use crate::error::ApiError;
-use crate::fairings::rate_limiter::CachedRateLimitInfo;
+use crate::fairings::rate_limiter::{CachedRateLimitInfo, InFlightPermit, InFlightRejected};
 use crate::fairings::RateLimiter;
 use argon2::password_hash::rand_core::OsRng;
 use argon2::password_hash::SaltString;
@@ -20,6 +20,7 @@ pub struct ApiKeyRow {
     pub owner: String,
     pub active: bool,
     pub is_admin: bool,
+    pub max_in_flight: Option<i64>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -40,8 +41,10 @@ impl<'r> FromRequest<'r> for AuthenticatedKey {
     type Error = ApiError;
 
     async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        let Some(pool) = req.rocket().state::<DbPool>() else {
-            tracing::error!("DbPool not found in managed state");
+        // Keys are looked up on the primary: a replica lagging behind a
+        // revocation would otherwise keep authenticating the revoked key.
+        let Some(pool) = req.rocket().state::<Db>().map(Db::writes) else {
+            tracing::error!("Db not found in managed state");
             return Outcome::Error((
                 Status::InternalServerError,
                 ApiError::Internal("database unavailable".into()),
@@ -88,8 +91,8 @@ impl<'r> FromRequest<'r> for AuthenticatedKey {
         };
 
         let row: Option<ApiKeyRow> = match sqlx::query_as::<_, ApiKeyRow>(
-            "SELECT id, key_id, secret_hash, label, owner, active, is_admin, created_at, updated_at \
-             FROM api_keys WHERE key_id = ? AND active = 1",
+            "SELECT id, key_id, secret_hash, label, owner, active, is_admin, max_in_flight, \
+             created_at, updated_at FROM api_keys WHERE key_id = ? AND active = 1",
         )
         .bind(key_id)
         .fetch_optional(pool)
@@ -178,6 +181,25 @@ impl<'r> FromRequest<'r> for AuthenticatedKey {
             }
         }
 
+        let in_flight = req.local_cache(|| InFlightPermit(Mutex::new(None)));
+        if let Ok(mut held) = in_flight.0.lock() {
+            if held.is_none() {
+                let limit_override = row.max_in_flight.and_then(|n| u32::try_from(n).ok());
+                match rl.acquire_in_flight(row.id, limit_override) {
+                    Ok(permit) => *held = permit,
+                    Err(e @ ApiError::TooManyConcurrent(_)) => {
+                        req.local_cache(|| InFlightRejected(true));
+                        tracing::warn!(key_id = %row.key_id, "per-key in-flight limit exceeded");
+                        return Outcome::Error((Status::TooManyRequests, e));
+                    }
+                    Err(e) => {
+                        tracing::error!(key_id = %row.key_id, error = %e, "in-flight limiter failed");
+                        return Outcome::Error((Status::InternalServerError, e));
+                    }
+                }
+            }
+        }
+
         Outcome::Success(AuthenticatedKey {
             id: row.id,
             key_id: row.key_id,
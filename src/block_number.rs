@@ -0,0 +1,67 @@
+use crate::error::ApiError;
+use std::fmt::Display;
+
+/// Converts a block number as a source reports it, a 256-bit quantity from
+/// the subgraph or a signed column from the database, to the `u64` every
+/// response carries. A value that doesn't fit is an error, never a silent
+/// zero.
+pub(crate) fn block_number_u64<T>(value: T) -> Result<u64, ApiError>
+where
+    T: TryInto<u64> + Display + Copy,
+{
+    value.try_into().map_err(|_| {
+        tracing::error!(block_number = %value, "block number does not fit in u64");
+        ApiError::Internal("block number overflow".into())
+    })
+}
+
+/// Converts a block number to the signed column the database stores it in,
+/// rejecting values past `i64::MAX` the same way.
+pub(crate) fn block_number_i64<T>(value: T) -> Result<i64, ApiError>
+where
+    T: TryInto<i64> + Display + Copy,
+{
+    value.try_into().map_err(|_| {
+        tracing::error!(block_number = %value, "block number does not fit in i64");
+        ApiError::Internal("block number overflow".into())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+
+    #[test]
+    fn test_block_number_in_range_converts() {
+        assert_eq!(
+            block_number_u64(U256::from(12_345_678u64)).unwrap(),
+            12_345_678
+        );
+        assert_eq!(block_number_u64(U256::from(u64::MAX)).unwrap(), u64::MAX);
+        assert_eq!(block_number_u64(100i64).unwrap(), 100);
+    }
+
+    #[test]
+    fn test_overflowing_block_number_is_an_error() {
+        let overflowing = U256::from(u64::MAX) + U256::from(1);
+        assert!(matches!(
+            block_number_u64(overflowing),
+            Err(ApiError::Internal(message)) if message == "block number overflow"
+        ));
+        assert!(matches!(
+            block_number_u64(-1i64),
+            Err(ApiError::Internal(_))
+        ));
+    }
+
+    #[test]
+    fn test_block_number_i64_rejects_values_past_i64_max() {
+        assert_eq!(block_number_i64(12_345_678u64).unwrap(), 12_345_678);
+        assert_eq!(block_number_i64(i64::MAX as u64).unwrap(), i64::MAX);
+        assert!(matches!(
+            block_number_i64(u64::MAX),
+            Err(ApiError::Internal(message)) if message == "block number overflow"
+        ));
+    }
+}
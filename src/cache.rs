@@ -3,10 +3,14 @@ use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
+use alloy::primitives::B256;
 use rain_orderbook_common::raindex_client::order_quotes::RaindexOrderQuote;
+use rain_orderbook_common::raindex_client::orders::RaindexOrder;
 use rain_orderbook_common::take_orders::TakeOrderCandidate;
 
+use crate::routes::tokens::TokenDetailsResponse;
 use crate::types::orders::OrdersListResponse;
+use crate::types::stats::{OpenInterestResponse, VolumeStatsResponse};
 use crate::types::trades::TradesByAddressResponse;
 
 pub(crate) struct AppCache<K, V>(pub(crate) Cache<K, V>)
@@ -53,28 +57,35 @@ where
 
 pub(crate) struct RouteResponseCaches {
     enabled: bool,
+    lookups_enabled: bool,
     pub order_quotes: AppCache<String, Vec<RaindexOrderQuote>>,
     pub orders_by_token: AppCache<String, OrdersListResponse>,
     pub swap_candidates: AppCache<String, Vec<TakeOrderCandidate>>,
     pub trades_by_token: AppCache<String, TradesByAddressResponse>,
     pub trades_by_taker: AppCache<String, TradesByAddressResponse>,
+    pub volume_stats: AppCache<String, VolumeStatsResponse>,
+    pub open_interest: AppCache<String, OpenInterestResponse>,
+    pub orders_by_hash: AppCache<B256, Vec<RaindexOrder>>,
+    pub token_details: AppCache<String, TokenDetailsResponse>,
     group: CacheGroup,
 }
 
 impl RouteResponseCaches {
-    pub(crate) fn new(max_capacity: u64, ttl: Duration) -> Self {
+    pub(crate) fn new(max_capacity: u64, ttl: Duration, lookup_ttl: Duration) -> Self {
         let enabled = max_capacity > 0 && !ttl.is_zero();
+        let lookups_enabled = max_capacity > 0 && !lookup_ttl.is_zero();
         let max_capacity = max_capacity.max(1);
-        let ttl = if ttl.is_zero() {
-            Duration::from_nanos(1)
-        } else {
-            ttl
-        };
+        let ttl = non_zero_ttl(ttl);
+        let lookup_ttl = non_zero_ttl(lookup_ttl);
         let order_quotes = AppCache::new(max_capacity, ttl);
         let orders_by_token = AppCache::new(max_capacity, ttl);
         let swap_candidates = AppCache::new(max_capacity, ttl);
         let trades_by_token = AppCache::new(max_capacity, ttl);
         let trades_by_taker = AppCache::new(max_capacity, ttl);
+        let volume_stats = AppCache::new(max_capacity, ttl);
+        let open_interest = AppCache::new(max_capacity, ttl);
+        let orders_by_hash = AppCache::new(max_capacity, lookup_ttl);
+        let token_details = AppCache::new(max_capacity, lookup_ttl);
 
         let mut group = CacheGroup::new();
         group.register(&order_quotes);
@@ -82,14 +93,23 @@ impl RouteResponseCaches {
         group.register(&swap_candidates);
         group.register(&trades_by_token);
         group.register(&trades_by_taker);
+        group.register(&volume_stats);
+        group.register(&open_interest);
+        group.register(&orders_by_hash);
+        group.register(&token_details);
 
         Self {
             enabled,
+            lookups_enabled,
             order_quotes,
             orders_by_token,
             swap_candidates,
             trades_by_token,
             trades_by_taker,
+            volume_stats,
+            open_interest,
+            orders_by_hash,
+            token_details,
             group,
         }
     }
@@ -98,11 +118,24 @@ impl RouteResponseCaches {
         self.enabled
     }
 
+    /// Whether the order-by-hash and token-details lookup caches are active.
+    pub(crate) fn lookups_enabled(&self) -> bool {
+        self.lookups_enabled
+    }
+
     pub(crate) fn invalidate_all(&self) {
         self.group.invalidate_all();
     }
 }
 
+fn non_zero_ttl(ttl: Duration) -> Duration {
+    if ttl.is_zero() {
+        Duration::from_nanos(1)
+    } else {
+        ttl
+    }
+}
+
 trait Invalidatable: Send + Sync {
     fn invalidate_all(&self);
 }
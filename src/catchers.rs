@@ -1,5 +1,6 @@
 use crate::error::{ApiErrorDetail, ApiErrorResponse};
-use crate::fairings::{request_id_for, request_span_for};
+use crate::fairings::rate_limiter::InFlightRejected;
+use crate::fairings::{request_id_for, request_span_for, QueryParamsRejection};
 use rocket::http::Header;
 use rocket::response::Responder;
 use rocket::serde::json::Json;
@@ -12,12 +13,19 @@ pub fn bad_request(req: &Request<'_>) -> Json<ApiErrorResponse> {
     span.in_scope(|| {
         tracing::warn!("bad request (invalid content type, missing headers, or malformed input)")
     });
+    let message = req
+        .local_cache(|| QueryParamsRejection(None))
+        .0
+        .clone()
+        .unwrap_or_else(|| "The request was invalid or malformed".to_string());
 
     Json(ApiErrorResponse {
         request_id: request_id_for(req),
         error: ApiErrorDetail {
             code: "BAD_REQUEST".to_string(),
-            message: "The request was invalid or malformed".to_string(),
+            message,
+            retry_after_ms: None,
+            reason: None,
         },
     })
 }
@@ -32,6 +40,8 @@ pub fn unauthorized(req: &Request<'_>) -> Json<ApiErrorResponse> {
         error: ApiErrorDetail {
             code: "UNAUTHORIZED".to_string(),
             message: "Missing or invalid credentials".to_string(),
+            retry_after_ms: None,
+            reason: None,
         },
     })
 }
@@ -46,6 +56,8 @@ pub fn forbidden(req: &Request<'_>) -> Json<ApiErrorResponse> {
         error: ApiErrorDetail {
             code: "FORBIDDEN".to_string(),
             message: "Insufficient permissions".to_string(),
+            retry_after_ms: None,
+            reason: None,
         },
     })
 }
@@ -60,6 +72,24 @@ pub fn not_found(req: &Request<'_>) -> Json<ApiErrorResponse> {
         error: ApiErrorDetail {
             code: "NOT_FOUND".to_string(),
             message: "The requested resource was not found".to_string(),
+            retry_after_ms: None,
+            reason: None,
+        },
+    })
+}
+
+#[catch(415)]
+pub fn unsupported_media_type(req: &Request<'_>) -> Json<ApiErrorResponse> {
+    let span = request_span_for(req);
+    span.in_scope(|| tracing::warn!("unsupported media type (request body is not JSON)"));
+
+    Json(ApiErrorResponse {
+        request_id: request_id_for(req),
+        error: ApiErrorDetail {
+            code: "UNSUPPORTED_MEDIA_TYPE".to_string(),
+            message: "Request body must be sent with Content-Type: application/json".to_string(),
+            retry_after_ms: None,
+            reason: None,
         },
     })
 }
@@ -74,16 +104,21 @@ pub fn unprocessable_entity(req: &Request<'_>) -> Json<ApiErrorResponse> {
         error: ApiErrorDetail {
             code: "UNPROCESSABLE_ENTITY".to_string(),
             message: "Request body could not be parsed".to_string(),
+            retry_after_ms: None,
+            reason: None,
         },
     })
 }
 
-pub(crate) struct RateLimitedResponse(Json<ApiErrorResponse>);
+pub(crate) struct RateLimitedResponse {
+    body: Json<ApiErrorResponse>,
+    retry_after: &'static str,
+}
 
 impl<'r, 'o: 'r> Responder<'r, 'o> for RateLimitedResponse {
     fn respond_to(self, req: &'r Request<'_>) -> rocket::response::Result<'o> {
-        let mut res = self.0.respond_to(req)?;
-        res.set_header(Header::new("Retry-After", "60"));
+        let mut res = self.body.respond_to(req)?;
+        res.set_header(Header::new("Retry-After", self.retry_after));
         Ok(res)
     }
 }
@@ -91,15 +126,34 @@ impl<'r, 'o: 'r> Responder<'r, 'o> for RateLimitedResponse {
 #[catch(429)]
 pub fn too_many_requests(req: &Request<'_>) -> RateLimitedResponse {
     let span = request_span_for(req);
-    span.in_scope(|| tracing::warn!("rate limit exceeded"));
-
-    RateLimitedResponse(Json(ApiErrorResponse {
-        request_id: request_id_for(req),
-        error: ApiErrorDetail {
-            code: "RATE_LIMITED".to_string(),
-            message: "Too many requests, please try again later".to_string(),
-        },
-    }))
+    let (code, message, retry_after) = if req.local_cache(|| InFlightRejected(false)).0 {
+        span.in_scope(|| tracing::warn!("per-key in-flight limit exceeded"));
+        (
+            "TOO_MANY_CONCURRENT",
+            "Too many concurrent requests for this API key",
+            "1",
+        )
+    } else {
+        span.in_scope(|| tracing::warn!("rate limit exceeded"));
+        (
+            "RATE_LIMITED",
+            "Too many requests, please try again later",
+            "60",
+        )
+    };
+
+    RateLimitedResponse {
+        body: Json(ApiErrorResponse {
+            request_id: request_id_for(req),
+            error: ApiErrorDetail {
+                code: code.to_string(),
+                message: message.to_string(),
+                retry_after_ms: None,
+                reason: None,
+            },
+        }),
+        retry_after,
+    }
 }
 
 #[catch(500)]
@@ -112,6 +166,8 @@ pub fn internal_server_error(req: &Request<'_>) -> Json<ApiErrorResponse> {
         error: ApiErrorDetail {
             code: "INTERNAL_ERROR".to_string(),
             message: "Internal server error".to_string(),
+            retry_after_ms: None,
+            reason: None,
         },
     })
 }
@@ -123,6 +179,7 @@ pub fn catchers() -> Vec<Catcher> {
         forbidden,
         not_found,
         too_many_requests,
+        unsupported_media_type,
         unprocessable_entity,
         internal_server_error
     ]
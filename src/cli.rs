@@ -3,8 +3,14 @@ use crate::db::DbPool;
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use clap::{Parser, Subcommand};
 use rand::RngCore;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
+/// Shortest secret `keys create --secret` accepts by default.
+pub(crate) const DEFAULT_MIN_SECRET_LENGTH: usize = 32;
+/// Estimated entropy a provided secret must carry, matching a 128-bit key.
+const MIN_SECRET_ENTROPY_BITS: f64 = 128.0;
+
 #[derive(Parser)]
 #[command(name = "st0x_rest_api")]
 #[command(about = "st0x REST API server and key management")]
@@ -39,6 +45,15 @@ pub enum KeysCommand {
         owner: String,
         #[arg(long, default_value_t = false)]
         admin: bool,
+        #[arg(long, help = "Override the configured per-key in-flight request cap")]
+        max_in_flight: Option<u32>,
+        #[arg(
+            long,
+            help = "Use this secret instead of generating one; it must pass the strength check"
+        )]
+        secret: Option<String>,
+        #[arg(long, default_value_t = DEFAULT_MIN_SECRET_LENGTH, help = "Minimum length of a provided secret")]
+        min_secret_length: usize,
     },
     #[command(about = "List all API keys")]
     List,
@@ -67,7 +82,19 @@ pub async fn handle_keys_command(
             label,
             owner,
             admin,
-        } => create_key(&pool, &label, &owner, admin).await,
+            max_in_flight,
+            secret,
+            min_secret_length,
+        } => {
+            let secret = match secret {
+                Some(secret) => {
+                    check_secret_strength(&secret, min_secret_length)?;
+                    secret
+                }
+                None => generate_secret(),
+            };
+            create_key(&pool, &label, &owner, admin, max_in_flight, &secret).await
+        }
         KeysCommand::List => list_keys(&pool).await,
         KeysCommand::Revoke { key_id } => revoke_key(&pool, &key_id).await,
         KeysCommand::Delete { key_id } => delete_key(&pool, &key_id).await,
@@ -79,23 +106,23 @@ async fn create_key(
     label: &str,
     owner: &str,
     admin: bool,
+    max_in_flight: Option<u32>,
+    secret: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let key_id = uuid::Uuid::new_v4().to_string();
-    let mut secret_bytes = [0u8; 32];
-    rand::rng().fill_bytes(&mut secret_bytes);
-    let secret = URL_SAFE_NO_PAD.encode(secret_bytes);
-
     let secret_hash =
-        auth::hash_secret(&secret).map_err(|e| format!("failed to hash secret: {e}"))?;
+        auth::hash_secret(secret).map_err(|e| format!("failed to hash secret: {e}"))?;
 
     sqlx::query(
-        "INSERT INTO api_keys (key_id, secret_hash, label, owner, is_admin) VALUES (?, ?, ?, ?, ?)",
+        "INSERT INTO api_keys (key_id, secret_hash, label, owner, is_admin, max_in_flight) \
+         VALUES (?, ?, ?, ?, ?, ?)",
     )
     .bind(&key_id)
     .bind(&secret_hash)
     .bind(label)
     .bind(owner)
     .bind(admin)
+    .bind(max_in_flight)
     .execute(pool)
     .await
     .map_err(|e| format!("failed to insert API key: {e}"))?;
@@ -110,6 +137,9 @@ async fn create_key(
     println!("Label:   {label}");
     println!("Owner:   {owner}");
     println!("Admin:   {admin}");
+    if let Some(max_in_flight) = max_in_flight {
+        println!("Max in-flight: {max_in_flight}");
+    }
     println!();
     println!("IMPORTANT: Store the secret securely. It will not be shown again.");
     println!();
@@ -117,10 +147,45 @@ async fn create_key(
     Ok(())
 }
 
+fn generate_secret() -> String {
+    let mut secret_bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut secret_bytes);
+    URL_SAFE_NO_PAD.encode(secret_bytes)
+}
+
+/// Rejects a user-provided secret shorter than `min_length` or whose
+/// character distribution carries less than [`MIN_SECRET_ENTROPY_BITS`].
+/// The estimate is per-character Shannon entropy times length, so repeated
+/// or low-variety secrets fail even when long.
+fn check_secret_strength(secret: &str, min_length: usize) -> Result<(), String> {
+    let length = secret.chars().count();
+    if length < min_length {
+        return Err(format!(
+            "secret is too short: {length} characters, at least {min_length} required"
+        ));
+    }
+
+    let mut counts = HashMap::new();
+    for c in secret.chars() {
+        *counts.entry(c).or_insert(0usize) += 1;
+    }
+    let per_char: f64 = counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / length as f64;
+            -p * p.log2()
+        })
+        .sum();
+    if per_char * (length as f64) < MIN_SECRET_ENTROPY_BITS {
+        return Err("secret is too predictable: use a longer, more varied secret".into());
+    }
+    Ok(())
+}
+
 async fn list_keys(pool: &DbPool) -> Result<(), Box<dyn std::error::Error>> {
     let rows = sqlx::query_as::<_, auth::ApiKeyRow>(
-        "SELECT id, key_id, secret_hash, label, owner, active, is_admin, created_at, updated_at \
-         FROM api_keys ORDER BY created_at DESC",
+        "SELECT id, key_id, secret_hash, label, owner, active, is_admin, max_in_flight, \
+         created_at, updated_at FROM api_keys ORDER BY created_at DESC",
     )
     .fetch_all(pool)
     .await
@@ -190,7 +255,7 @@ async fn delete_key(pool: &DbPool, key_id: &str) -> Result<(), Box<dyn std::erro
 #[cfg(test)]
 mod tests {
     use super::*;
-    use argon2::PasswordHash;
+    use argon2::{PasswordHash, PasswordVerifier};
 
     async fn test_pool() -> DbPool {
         let id = uuid::Uuid::new_v4();
@@ -246,6 +311,9 @@ mod tests {
                 label: "partner-x".into(),
                 owner: "contact@example.com".into(),
                 admin: false,
+                max_in_flight: None,
+                secret: None,
+                min_secret_length: DEFAULT_MIN_SECRET_LENGTH,
             },
             pool.clone(),
         )
@@ -253,8 +321,8 @@ mod tests {
         .expect("create key");
 
         let row = sqlx::query_as::<_, auth::ApiKeyRow>(
-            "SELECT id, key_id, secret_hash, label, owner, active, is_admin, created_at, updated_at \
-             FROM api_keys",
+            "SELECT id, key_id, secret_hash, label, owner, active, is_admin, max_in_flight, \
+             created_at, updated_at FROM api_keys",
         )
         .fetch_one(&pool)
         .await
@@ -267,6 +335,59 @@ mod tests {
         assert!(PasswordHash::new(&row.secret_hash).is_ok());
     }
 
+    fn create_with_secret(secret: &str) -> KeysCommand {
+        KeysCommand::Create {
+            label: "partner-x".into(),
+            owner: "contact@example.com".into(),
+            admin: false,
+            max_in_flight: None,
+            secret: Some(secret.into()),
+            min_secret_length: DEFAULT_MIN_SECRET_LENGTH,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_key_rejects_weak_secret() {
+        let pool = test_pool().await;
+
+        for weak in [
+            "hunter2",
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "abcdabcdabcdabcdabcdabcdabcdabcd",
+        ] {
+            let result = handle_keys_command(create_with_secret(weak), pool.clone()).await;
+            assert!(result.is_err(), "{weak} should be rejected");
+        }
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM api_keys")
+            .fetch_one(&pool)
+            .await
+            .expect("count");
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_create_key_accepts_strong_secret() {
+        let pool = test_pool().await;
+        let secret = "Vq8rT2xLm9Kp4WzN7bHc3JdF6gYs1AeU5oRi0tMnQwE";
+
+        handle_keys_command(create_with_secret(secret), pool.clone())
+            .await
+            .expect("create key");
+
+        let row = sqlx::query_as::<_, auth::ApiKeyRow>(
+            "SELECT id, key_id, secret_hash, label, owner, active, is_admin, max_in_flight, \
+             created_at, updated_at FROM api_keys",
+        )
+        .fetch_one(&pool)
+        .await
+        .expect("fetch row");
+        let parsed = PasswordHash::new(&row.secret_hash).expect("parse hash");
+        assert!(argon2::Argon2::default()
+            .verify_password(secret.as_bytes(), &parsed)
+            .is_ok());
+    }
+
     #[tokio::test]
     async fn test_list_keys_empty() {
         let pool = test_pool().await;
@@ -1,6 +1,7 @@
 mod migrate;
 mod pool;
 pub(crate) mod registry_history;
+pub(crate) mod retention;
 pub(crate) mod wrapped_exchange_rate_history;
 
 pub type DbPool = sqlx::Pool<sqlx::Sqlite>;
@@ -10,3 +11,131 @@ pub async fn init(database_url: &str, max_connections: u32) -> Result<DbPool, sq
     migrate::run(&pool).await?;
     Ok(pool)
 }
+
+pub async fn init_read(database_url: &str, max_connections: u32) -> Result<DbPool, sqlx::Error> {
+    pool::create_read(database_url, max_connections).await
+}
+
+/// Picks the pool a query runs on. Writes always go to the primary; reads
+/// use the read pool when one is configured, so heavy reads don't queue
+/// behind writes such as usage logging.
+#[derive(Clone)]
+pub struct Db {
+    primary: DbPool,
+    read: Option<DbPool>,
+}
+
+impl Db {
+    pub fn new(primary: DbPool, read: Option<DbPool>) -> Self {
+        Self { primary, read }
+    }
+
+    pub fn reads(&self) -> &DbPool {
+        self.read.as_ref().unwrap_or(&self.primary)
+    }
+
+    pub fn writes(&self) -> &DbPool {
+        &self.primary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{basic_auth_header, TestClientBuilder};
+    use rocket::http::{Header, Status};
+
+    async fn insert_key(pool: &DbPool, key_id: &str, secret_hash: &str) {
+        sqlx::query(
+            "INSERT INTO api_keys (id, key_id, secret_hash, label, owner) VALUES (1, ?, ?, ?, ?)",
+        )
+        .bind(key_id)
+        .bind(secret_hash)
+        .bind("replica-test")
+        .bind("owner")
+        .execute(pool)
+        .await
+        .expect("insert api key");
+    }
+
+    async fn usage_log_count(pool: &DbPool) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM usage_logs")
+            .fetch_one(pool)
+            .await
+            .expect("count usage logs")
+    }
+
+    async fn client_with_replica() -> (rocket::local::asynchronous::Client, DbPool) {
+        let replica_url = format!(
+            "sqlite:file:{}?mode=memory&cache=shared",
+            uuid::Uuid::new_v4()
+        );
+        let replica = init(&replica_url, 1).await.expect("replica init");
+        let client = TestClientBuilder::new()
+            .read_database_url(replica_url)
+            .build()
+            .await;
+        (client, replica)
+    }
+
+    fn new_credentials() -> (String, String, String) {
+        let key_id = uuid::Uuid::new_v4().to_string();
+        let secret = uuid::Uuid::new_v4().to_string();
+        let hash = crate::auth::hash_secret(&secret).expect("hash secret");
+        (key_id, secret, hash)
+    }
+
+    #[rocket::async_test]
+    async fn test_reads_use_read_pool_and_writes_use_primary() {
+        let (client, replica) = client_with_replica().await;
+        let db = client.rocket().state::<Db>().expect("db in state");
+
+        let (key_id, secret, hash) = new_credentials();
+        insert_key(&replica, &key_id, &hash).await;
+        let replica_keys: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM api_keys")
+            .fetch_one(db.reads())
+            .await
+            .expect("count replica keys");
+        assert_eq!(replica_keys, 1);
+
+        insert_key(db.writes(), &key_id, &hash).await;
+        let response = client
+            .get("/v1/tokens")
+            .header(Header::new(
+                "Authorization",
+                basic_auth_header(&key_id, &secret),
+            ))
+            .dispatch()
+            .await;
+        assert_ne!(response.status(), Status::Unauthorized);
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert_eq!(usage_log_count(db.writes()).await, 1);
+        assert_eq!(usage_log_count(&replica).await, 0);
+    }
+
+    #[rocket::async_test]
+    async fn test_key_revoked_on_primary_is_rejected_despite_replica_lag() {
+        let (client, replica) = client_with_replica().await;
+        let db = client.rocket().state::<Db>().expect("db in state");
+
+        let (key_id, secret, hash) = new_credentials();
+        insert_key(db.writes(), &key_id, &hash).await;
+        insert_key(&replica, &key_id, &hash).await;
+        sqlx::query("UPDATE api_keys SET active = 0 WHERE key_id = ?")
+            .bind(&key_id)
+            .execute(db.writes())
+            .await
+            .expect("revoke key on primary");
+
+        let response = client
+            .get("/v1/tokens")
+            .header(Header::new(
+                "Authorization",
+                basic_auth_header(&key_id, &secret),
+            ))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+}
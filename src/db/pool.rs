@@ -24,3 +24,27 @@ pub(super) async fn create(
 
     Ok(pool)
 }
+
+/// Opens `database_url` read-only. The database must already exist; its
+/// schema is owned by the primary.
+pub(super) async fn create_read(
+    database_url: &str,
+    max_connections: u32,
+) -> Result<DbPool, sqlx::Error> {
+    let options = SqliteConnectOptions::from_str(database_url)?
+        .create_if_missing(false)
+        .read_only(true);
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(max_connections)
+        .connect_with(options)
+        .await?;
+
+    tracing::info!(
+        database_url = %database_url,
+        max_connections,
+        "database read pool created"
+    );
+
+    Ok(pool)
+}
@@ -0,0 +1,180 @@
+use super::registry_history::VALIDATION_STATUS_SUCCESS;
+use super::DbPool;
+use std::time::Duration;
+
+/// How often the background retention task purges old rows.
+const RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Rows removed from each log table by a purge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct PurgedLogs {
+    pub usage_logs: u64,
+    pub registry_url_history: u64,
+    pub private_registry_history: u64,
+}
+
+impl PurgedLogs {
+    pub(crate) fn total(&self) -> u64 {
+        self.usage_logs + self.registry_url_history + self.private_registry_history
+    }
+}
+
+/// Deletes usage and admin audit rows older than `older_than_secs` in one
+/// transaction. The latest successful private registry upload is kept
+/// whatever its age, since startup restores the registry from it.
+pub(crate) async fn purge_logs_older_than(
+    pool: &DbPool,
+    older_than_secs: u64,
+) -> Result<PurgedLogs, sqlx::Error> {
+    let modifier = format!("-{older_than_secs} seconds");
+    let mut tx = pool.begin().await?;
+
+    let usage_logs = sqlx::query("DELETE FROM usage_logs WHERE created_at < datetime('now', ?)")
+        .bind(&modifier)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    let registry_url_history =
+        sqlx::query("DELETE FROM registry_url_history WHERE changed_at < datetime('now', ?)")
+            .bind(&modifier)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+    let private_registry_history = sqlx::query(
+        "DELETE FROM private_registry_history \
+         WHERE changed_at < datetime('now', ?) \
+         AND id NOT IN ( \
+             SELECT id FROM private_registry_history \
+             WHERE validation_status = ? \
+             ORDER BY changed_at DESC, id DESC \
+             LIMIT 1 \
+         )",
+    )
+    .bind(&modifier)
+    .bind(VALIDATION_STATUS_SUCCESS)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    tx.commit().await?;
+    Ok(PurgedLogs {
+        usage_logs,
+        registry_url_history,
+        private_registry_history,
+    })
+}
+
+/// Purges rows older than `retention` every [`RETENTION_INTERVAL`] for the
+/// life of the process. Failures are logged and retried on the next tick.
+pub(crate) fn spawn_retention_task(pool: DbPool, retention: Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(RETENTION_INTERVAL);
+        loop {
+            interval.tick().await;
+            match purge_logs_older_than(&pool, retention.as_secs()).await {
+                Ok(purged) => tracing::info!(
+                    usage_logs = purged.usage_logs,
+                    registry_url_history = purged.registry_url_history,
+                    private_registry_history = purged.private_registry_history,
+                    "purged logs past retention"
+                ),
+                Err(e) => tracing::error!(error = %e, "failed to purge logs past retention"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn pool() -> DbPool {
+        let url = format!(
+            "sqlite:file:{}?mode=memory&cache=shared",
+            uuid::Uuid::new_v4()
+        );
+        crate::db::init(&url, 1).await.expect("init db")
+    }
+
+    async fn count(pool: &DbPool, table: &str) -> i64 {
+        sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {table}"))
+            .fetch_one(pool)
+            .await
+            .expect("count rows")
+    }
+
+    async fn insert_registry_upload(pool: &DbPool, commit: &str, status: &str, age: &str) {
+        sqlx::query(
+            "INSERT INTO private_registry_history \
+             (source_commit, payload_sha256, actor_key_id, validation_status, changed_at) \
+             VALUES (?, 'sha', 'admin', ?, datetime('now', ?))",
+        )
+        .bind(commit)
+        .bind(status)
+        .bind(age)
+        .execute(pool)
+        .await
+        .expect("insert registry history");
+    }
+
+    #[rocket::async_test]
+    async fn test_purge_removes_only_rows_past_cutoff() {
+        let pool = pool().await;
+        sqlx::query(
+            "INSERT INTO api_keys (id, key_id, secret_hash, label, owner) \
+             VALUES (1, 'key', 'hash', 'label', 'owner')",
+        )
+        .execute(&pool)
+        .await
+        .expect("insert api key");
+        for age in ["-40 days", "-31 days", "-1 day", "-1 minute"] {
+            sqlx::query(
+                "INSERT INTO usage_logs (api_key_id, method, path, status_code, latency_ms, created_at) \
+                 VALUES (1, 'GET', '/health', 200, 1.0, datetime('now', ?))",
+            )
+            .bind(age)
+            .execute(&pool)
+            .await
+            .expect("insert usage log");
+        }
+        for age in ["-60 days", "-2 days"] {
+            sqlx::query(
+                "INSERT INTO registry_url_history (previous_url, new_url, actor_key_id, changed_at) \
+                 VALUES ('a', 'b', 'admin', datetime('now', ?))",
+            )
+            .bind(age)
+            .execute(&pool)
+            .await
+            .expect("insert registry url history");
+        }
+
+        let purged = purge_logs_older_than(&pool, 30 * 24 * 60 * 60)
+            .await
+            .expect("purge");
+
+        assert_eq!(purged.usage_logs, 2);
+        assert_eq!(purged.registry_url_history, 1);
+        assert_eq!(purged.total(), 3);
+        assert_eq!(count(&pool, "usage_logs").await, 2);
+        assert_eq!(count(&pool, "registry_url_history").await, 1);
+    }
+
+    #[rocket::async_test]
+    async fn test_purge_keeps_latest_successful_registry_upload() {
+        let pool = pool().await;
+        insert_registry_upload(&pool, "old", "success", "-90 days").await;
+        insert_registry_upload(&pool, "current", "success", "-60 days").await;
+        insert_registry_upload(&pool, "rejected", "failed", "-45 days").await;
+
+        let purged = purge_logs_older_than(&pool, 30 * 24 * 60 * 60)
+            .await
+            .expect("purge");
+
+        assert_eq!(purged.private_registry_history, 2);
+        let latest = crate::db::registry_history::latest_successful_private_registry(&pool)
+            .await
+            .expect("latest upload")
+            .expect("upload kept");
+        assert_eq!(latest.source_commit, "current");
+    }
+}
@@ -0,0 +1,77 @@
+use crate::auth::AuthenticatedKey;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Key the raw subgraph payload is attached under.
+const RAW_KEY: &str = "_raw";
+
+/// `debug_raw` is an admin diagnostic. Other keys get the normal response
+/// rather than an error, so the param is harmless on a shared client.
+pub(crate) fn debug_raw_enabled(requested: Option<bool>, key: &AuthenticatedKey) -> bool {
+    if !requested.unwrap_or(false) {
+        return false;
+    }
+    if !key.is_admin {
+        tracing::info!(key_id = %key.key_id, "ignoring debug_raw for non-admin key");
+        return false;
+    }
+    true
+}
+
+/// Attaches the serialized `raw` payload to a response object under
+/// [`RAW_KEY`]. A payload that fails to serialize is logged and left out,
+/// since the response itself is still good.
+pub(crate) fn attach_raw(body: &mut Value, raw: &impl Serialize) {
+    let Value::Object(object) = body else {
+        return;
+    };
+    match serde_json::to_value(raw) {
+        Ok(raw) => {
+            object.insert(RAW_KEY.to_string(), raw);
+        }
+        Err(e) => tracing::warn!(error = %e, "failed to serialize raw subgraph payload"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::order::test_fixtures::{mock_order, mock_trade};
+    use serde_json::json;
+
+    fn key(is_admin: bool) -> AuthenticatedKey {
+        AuthenticatedKey {
+            id: 1,
+            key_id: "key".into(),
+            label: "test".into(),
+            owner: "owner".into(),
+            is_admin,
+        }
+    }
+
+    fn respond(requested: Option<bool>, key: &AuthenticatedKey) -> Value {
+        let mut body = json!({ "orderHash": "0xabcd" });
+        if debug_raw_enabled(requested, key) {
+            attach_raw(&mut body, &vec![mock_order()]);
+        }
+        body
+    }
+
+    #[test]
+    fn test_raw_field_appears_only_for_admin_keys() {
+        let admin = respond(Some(true), &key(true));
+        assert_eq!(admin[RAW_KEY].as_array().map(Vec::len), Some(1));
+        assert_eq!(admin["orderHash"], "0xabcd");
+
+        assert!(respond(Some(true), &key(false)).get(RAW_KEY).is_none());
+        assert!(respond(None, &key(true)).get(RAW_KEY).is_none());
+        assert!(respond(Some(false), &key(true)).get(RAW_KEY).is_none());
+    }
+
+    #[test]
+    fn test_attach_raw_serializes_trades() {
+        let mut body = json!({ "trades": [] });
+        attach_raw(&mut body, &vec![mock_trade()]);
+        assert!(body[RAW_KEY][0].is_object());
+    }
+}
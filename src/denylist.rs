@@ -0,0 +1,101 @@
+use crate::error::ApiError;
+use alloy::primitives::Address;
+use std::collections::BTreeSet;
+use std::sync::{RwLock, RwLockReadGuard};
+
+/// Addresses the API refuses to build swaps, deployments or cancellations
+/// for. Seeded from config and replaceable at runtime by admins.
+#[derive(Debug, Default)]
+pub(crate) struct AddressDenylist(RwLock<BTreeSet<Address>>);
+
+impl AddressDenylist {
+    pub(crate) fn new(addresses: impl IntoIterator<Item = Address>) -> Self {
+        Self(RwLock::new(addresses.into_iter().collect()))
+    }
+
+    pub(crate) fn parse(entries: &[String]) -> Result<Self, String> {
+        let addresses = entries
+            .iter()
+            .map(|entry| {
+                entry
+                    .trim()
+                    .parse::<Address>()
+                    .map_err(|e| format!("invalid denylisted address {entry:?}: {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(addresses))
+    }
+
+    fn read(&self) -> Result<RwLockReadGuard<'_, BTreeSet<Address>>, ApiError> {
+        self.0.read().map_err(|e| {
+            tracing::error!(error = %e, "denylist lock poisoned");
+            ApiError::Internal("denylist unavailable".into())
+        })
+    }
+
+    pub(crate) fn addresses(&self) -> Result<Vec<Address>, ApiError> {
+        Ok(self.read()?.iter().copied().collect())
+    }
+
+    /// Swaps in a new list and returns how many addresses the old one held.
+    pub(crate) fn replace(
+        &self,
+        addresses: impl IntoIterator<Item = Address>,
+    ) -> Result<usize, ApiError> {
+        let mut guard = self.0.write().map_err(|e| {
+            tracing::error!(error = %e, "denylist lock poisoned");
+            ApiError::Internal("denylist unavailable".into())
+        })?;
+        let previous = std::mem::replace(&mut *guard, addresses.into_iter().collect());
+        Ok(previous.len())
+    }
+
+    /// Rejects the request if any of `addresses` is denylisted.
+    pub(crate) fn check(&self, addresses: &[Address]) -> Result<(), ApiError> {
+        let denied = self.read()?;
+        if let Some(address) = addresses.iter().find(|address| denied.contains(address)) {
+            tracing::warn!(address = %address, "request involves denylisted address");
+            return Err(ApiError::ForbiddenAddress(
+                "request involves a restricted address".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    const DENIED: Address = address!("1111111111111111111111111111111111111111");
+    const CLEAN: Address = address!("2222222222222222222222222222222222222222");
+
+    #[test]
+    fn test_check_rejects_denylisted_address() {
+        let denylist = AddressDenylist::new([DENIED]);
+        assert!(denylist.check(&[CLEAN]).is_ok());
+        assert!(matches!(
+            denylist.check(&[CLEAN, DENIED]),
+            Err(ApiError::ForbiddenAddress(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_accepts_any_case_and_rejects_garbage() {
+        let denylist =
+            AddressDenylist::parse(&[" 0x1111111111111111111111111111111111111111 ".to_string()])
+                .unwrap();
+        assert_eq!(denylist.addresses().unwrap(), vec![DENIED]);
+
+        assert!(AddressDenylist::parse(&["not-an-address".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_replace_swaps_list() {
+        let denylist = AddressDenylist::new([DENIED]);
+        assert_eq!(denylist.replace([CLEAN]).unwrap(), 1);
+        assert!(denylist.check(&[DENIED]).is_ok());
+        assert!(denylist.check(&[CLEAN]).is_err());
+    }
+}
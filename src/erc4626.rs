@@ -1,5 +1,5 @@
+use crate::rpc::RpcClient;
 use alloy::primitives::Address;
-use alloy::providers::ProviderBuilder;
 use rain_erc::erc4626::{self, Erc4626BatchResponse, Erc4626BatchVault};
 use thiserror::Error;
 use url::Url;
@@ -23,8 +23,14 @@ pub(crate) async fn batch_share_ratios(
 
     let mut errors = Vec::new();
     for rpc in rpcs {
-        let provider = ProviderBuilder::new().connect_http(rpc.clone());
-        match erc4626::batch_share_ratios(&provider, vaults.clone(), multicall3_address).await {
+        let client = RpcClient::new(rpc.clone());
+        let vaults = vaults.clone();
+        match client
+            .with_provider("eth_call", |provider| {
+                erc4626::batch_share_ratios(provider, vaults, multicall3_address)
+            })
+            .await
+        {
             Ok(response) => return Ok(response),
             Err(error) => errors.push(format!("{rpc}: {error}")),
         }
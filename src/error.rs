@@ -1,3 +1,4 @@
+use crate::app_state::ApplicationState;
 use crate::fairings::{request_id_for, request_span_for};
 use rocket::http::{Header, Status};
 use rocket::response::Responder;
@@ -12,6 +13,26 @@ pub struct ApiErrorDetail {
     pub code: String,
     #[schema(example = "Something went wrong")]
     pub message: String,
+    /// Suggested wait before polling again, set on `NOT_YET_INDEXED`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 4000)]
+    pub retry_after_ms: Option<u64>,
+    /// Why a `NOT_FOUND` was returned, for clients that branch on it
+    /// instead of matching `message`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<NotFoundReason>,
+}
+
+/// Machine-readable cause of a `NOT_FOUND` response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotFoundReason {
+    /// No order has the requested hash.
+    OrderNotFound,
+    /// The pair has no orders, or none that can fill the request.
+    NoLiquidity,
+    /// The transaction exists but has no trades.
+    NoTrades,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -29,14 +50,108 @@ pub enum ApiError {
     Unauthorized(String),
     #[error("Forbidden: {0}")]
     Forbidden(String),
-    #[error("Not found: {0}")]
-    NotFound(String),
+    #[error("Forbidden address: {0}")]
+    ForbiddenAddress(String),
+    #[error("Not found: {message}")]
+    NotFound {
+        message: String,
+        reason: Option<NotFoundReason>,
+    },
     #[error("Internal error: {0}")]
     Internal(String),
+    #[error("Service unavailable: {0}")]
+    ServiceUnavailable(String),
     #[error("Rate limited: {0}")]
     RateLimited(String),
-    #[error("Not yet indexed: {0}")]
-    NotYetIndexed(String),
+    #[error("Too many concurrent requests: {0}")]
+    TooManyConcurrent(String),
+    #[error("Not yet indexed: {message}")]
+    NotYetIndexed { message: String, attempts: u32 },
+}
+
+impl ApiError {
+    pub(crate) fn not_found(message: impl Into<String>) -> Self {
+        ApiError::NotFound {
+            message: message.into(),
+            reason: None,
+        }
+    }
+
+    pub(crate) fn not_found_because(reason: NotFoundReason, message: impl Into<String>) -> Self {
+        ApiError::NotFound {
+            message: message.into(),
+            reason: Some(reason),
+        }
+    }
+}
+
+/// Backoff suggested to clients polling a transaction the subgraph has not
+/// indexed yet: `base_ms` per indexing attempt already spent, capped at
+/// `max_ms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IndexingRetryHint {
+    pub base_ms: u64,
+    pub max_ms: u64,
+}
+
+impl Default for IndexingRetryHint {
+    fn default() -> Self {
+        Self {
+            base_ms: 1_000,
+            max_ms: 30_000,
+        }
+    }
+}
+
+impl IndexingRetryHint {
+    pub(crate) fn retry_after_ms(&self, attempts: u32) -> u64 {
+        self.base_ms
+            .saturating_mul(u64::from(attempts.max(1)))
+            .min(self.max_ms)
+    }
+}
+
+/// Seconds clients are told to wait after an upstream outage.
+const UPSTREAM_RETRY_AFTER_SECS: u64 = 5;
+
+/// Maps a failed subgraph or RPC call to an `ApiError`. Timeouts and
+/// connection failures anywhere in the source chain mean the upstream is
+/// unreachable, which is `ServiceUnavailable`; anything else is `Internal`.
+/// `message` is the client-facing text either way.
+pub(crate) fn upstream_error(error: &(dyn std::error::Error + 'static), message: &str) -> ApiError {
+    if is_upstream_unavailable(error) {
+        ApiError::ServiceUnavailable(message.to_string())
+    } else {
+        ApiError::Internal(message.to_string())
+    }
+}
+
+fn is_upstream_unavailable(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut current = Some(error);
+    while let Some(error) = current {
+        if let Some(e) = error.downcast_ref::<reqwest::Error>() {
+            if e.is_timeout() || e.is_connect() {
+                return true;
+            }
+        }
+        if let Some(e) = error.downcast_ref::<std::io::Error>() {
+            if matches!(
+                e.kind(),
+                std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::ConnectionRefused
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::NotConnected
+            ) {
+                return true;
+            }
+        }
+        if error.is::<tokio::time::error::Elapsed>() {
+            return true;
+        }
+        current = error.source();
+    }
+    false
 }
 
 impl<'r> Responder<'r, 'static> for ApiError {
@@ -45,10 +160,23 @@ impl<'r> Responder<'r, 'static> for ApiError {
             ApiError::BadRequest(msg) => (Status::BadRequest, "BAD_REQUEST", msg.clone()),
             ApiError::Unauthorized(msg) => (Status::Unauthorized, "UNAUTHORIZED", msg.clone()),
             ApiError::Forbidden(msg) => (Status::Forbidden, "FORBIDDEN", msg.clone()),
-            ApiError::NotFound(msg) => (Status::NotFound, "NOT_FOUND", msg.clone()),
+            ApiError::ForbiddenAddress(msg) => {
+                (Status::Forbidden, "FORBIDDEN_ADDRESS", msg.clone())
+            }
+            ApiError::NotFound { message, .. } => (Status::NotFound, "NOT_FOUND", message.clone()),
             ApiError::Internal(msg) => (Status::InternalServerError, "INTERNAL_ERROR", msg.clone()),
+            ApiError::ServiceUnavailable(msg) => (
+                Status::ServiceUnavailable,
+                "SERVICE_UNAVAILABLE",
+                msg.clone(),
+            ),
             ApiError::RateLimited(msg) => (Status::TooManyRequests, "RATE_LIMITED", msg.clone()),
-            ApiError::NotYetIndexed(msg) => (Status::Accepted, "NOT_YET_INDEXED", msg.clone()),
+            ApiError::TooManyConcurrent(msg) => {
+                (Status::TooManyRequests, "TOO_MANY_CONCURRENT", msg.clone())
+            }
+            ApiError::NotYetIndexed { message, .. } => {
+                (Status::Accepted, "NOT_YET_INDEXED", message.clone())
+            }
         };
         let span = request_span_for(req);
         span.in_scope(|| {
@@ -59,12 +187,12 @@ impl<'r> Responder<'r, 'static> for ApiError {
                     error_message = %message,
                     "request failed"
                 );
-            } else if matches!(self, ApiError::NotYetIndexed(_)) {
+            } else if matches!(self, ApiError::NotYetIndexed { .. }) {
                 tracing::info!(
                     status = status.code,
                     code = %code,
                     error_message = %message,
-                    "transaction not yet indexed"
+                    "not yet indexed"
                 );
             } else {
                 tracing::warn!(
@@ -76,12 +204,28 @@ impl<'r> Responder<'r, 'static> for ApiError {
             }
         });
 
+        let retry_after_ms = match &self {
+            ApiError::NotYetIndexed { attempts, .. } => Some(
+                req.rocket()
+                    .state::<ApplicationState>()
+                    .map(|state| state.indexing_retry_hint)
+                    .unwrap_or_default()
+                    .retry_after_ms(*attempts),
+            ),
+            _ => None,
+        };
+        let reason = match &self {
+            ApiError::NotFound { reason, .. } => *reason,
+            _ => None,
+        };
         let request_id = request_id_for(req);
         let body = ApiErrorResponse {
             request_id,
             error: ApiErrorDetail {
                 code: code.to_string(),
                 message,
+                retry_after_ms,
+                reason,
             },
         };
         let json_response = match Json(body).respond_to(req) {
@@ -94,8 +238,17 @@ impl<'r> Responder<'r, 'static> for ApiError {
         let mut response = Response::build_from(json_response)
             .status(status)
             .finalize();
-        if matches!(self, ApiError::RateLimited(_)) {
-            response.set_header(Header::new("Retry-After", "60"));
+        let retry_after = match self {
+            ApiError::RateLimited(_) => Some("60".to_string()),
+            ApiError::TooManyConcurrent(_) => Some("1".to_string()),
+            ApiError::ServiceUnavailable(_) => Some(UPSTREAM_RETRY_AFTER_SECS.to_string()),
+            ApiError::NotYetIndexed { .. } => {
+                retry_after_ms.map(|ms| ms.div_ceil(1_000).to_string())
+            }
+            _ => None,
+        };
+        if let Some(retry_after) = retry_after {
+            response.set_header(Header::new("Retry-After", retry_after));
         }
         Ok(response)
     }
@@ -116,17 +269,47 @@ mod tests {
     }
     #[get("/not-found")]
     fn not_found() -> Result<(), ApiError> {
-        Err(ApiError::NotFound("order not found".into()))
+        Err(ApiError::not_found("order not found"))
+    }
+    #[get("/order-not-found")]
+    fn order_not_found() -> Result<(), ApiError> {
+        Err(ApiError::not_found_because(
+            NotFoundReason::OrderNotFound,
+            "order not found",
+        ))
     }
     #[get("/internal")]
     fn internal() -> Result<(), ApiError> {
         Err(ApiError::Internal("something broke".into()))
     }
 
+    #[get("/service-unavailable")]
+    fn service_unavailable() -> Result<(), ApiError> {
+        Err(ApiError::ServiceUnavailable(
+            "failed to query trades".into(),
+        ))
+    }
+
+    #[get("/not-yet-indexed/<attempts>")]
+    fn not_yet_indexed(attempts: u32) -> Result<(), ApiError> {
+        Err(ApiError::NotYetIndexed {
+            message: "transaction not yet indexed".into(),
+            attempts,
+        })
+    }
+
     fn error_client() -> Client {
         let rocket = rocket::build().mount(
             "/",
-            rocket::routes![bad_request, unauthorized, not_found, internal],
+            rocket::routes![
+                bad_request,
+                unauthorized,
+                not_found,
+                order_not_found,
+                internal,
+                service_unavailable,
+                not_yet_indexed
+            ],
         );
         Client::tracked(rocket).expect("valid rocket instance")
     }
@@ -165,6 +348,21 @@ mod tests {
         assert_error_response(&client, "/not-found", 404, "NOT_FOUND", "order not found");
     }
 
+    #[test]
+    fn test_not_found_reason_is_serialized_when_set() {
+        let client = error_client();
+        let body = |path: &str| -> serde_json::Value {
+            let response = client.get(path).dispatch();
+            assert_eq!(response.status().code, 404);
+            serde_json::from_str(&response.into_string().unwrap()).unwrap()
+        };
+
+        let with_reason = body("/order-not-found");
+        assert_eq!(with_reason["error"]["code"], "NOT_FOUND");
+        assert_eq!(with_reason["error"]["reason"], "ORDER_NOT_FOUND");
+        assert!(body("/not-found")["error"].get("reason").is_none());
+    }
+
     #[test]
     fn test_internal_returns_500() {
         let client = error_client();
@@ -176,4 +374,98 @@ mod tests {
             "something broke",
         );
     }
+
+    #[test]
+    fn test_not_yet_indexed_retry_hint_grows_with_attempts() {
+        let client = error_client();
+        let hint = |attempts: u32| {
+            let response = client
+                .get(format!("/not-yet-indexed/{attempts}"))
+                .dispatch();
+            assert_eq!(response.status().code, 202);
+            let retry_after: u64 = response
+                .headers()
+                .get_one("Retry-After")
+                .expect("Retry-After header")
+                .parse()
+                .unwrap();
+            let body: serde_json::Value =
+                serde_json::from_str(&response.into_string().unwrap()).unwrap();
+            assert_eq!(body["error"]["code"], "NOT_YET_INDEXED");
+            (
+                retry_after,
+                body["error"]["retry_after_ms"].as_u64().unwrap(),
+            )
+        };
+
+        assert_eq!(hint(2), (2, 2_000));
+        assert_eq!(hint(6), (6, 6_000));
+        assert_eq!(hint(1_000), (30, 30_000));
+    }
+
+    #[test]
+    fn test_other_errors_have_no_retry_after_ms() {
+        let client = error_client();
+        let response = client.get("/not-found").dispatch();
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().unwrap()).unwrap();
+        assert!(body["error"].get("retry_after_ms").is_none());
+    }
+
+    #[test]
+    fn test_service_unavailable_returns_503_with_retry_after() {
+        let client = error_client();
+        let response = client.get("/service-unavailable").dispatch();
+        assert_eq!(response.status().code, 503);
+        assert_eq!(response.headers().get_one("Retry-After"), Some("5"));
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().unwrap()).unwrap();
+        assert_eq!(body["error"]["code"], "SERVICE_UNAVAILABLE");
+        assert_eq!(body["error"]["message"], "failed to query trades");
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("subgraph request failed")]
+    struct WrappedError(#[source] std::io::Error);
+
+    fn is_unavailable(error: &(dyn std::error::Error + 'static)) -> bool {
+        matches!(
+            upstream_error(error, "failed"),
+            ApiError::ServiceUnavailable(msg) if msg == "failed"
+        )
+    }
+
+    #[test]
+    fn test_upstream_io_timeouts_and_connection_failures_are_unavailable() {
+        use std::io::{Error, ErrorKind};
+
+        assert!(is_unavailable(&Error::from(ErrorKind::TimedOut)));
+        assert!(is_unavailable(&WrappedError(Error::from(
+            ErrorKind::ConnectionRefused
+        ))));
+        assert!(matches!(
+            upstream_error(&WrappedError(Error::from(ErrorKind::InvalidData)), "failed"),
+            ApiError::Internal(msg) if msg == "failed"
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_upstream_elapsed_is_unavailable() {
+        let elapsed = tokio::time::timeout(std::time::Duration::ZERO, std::future::pending::<()>())
+            .await
+            .unwrap_err();
+        assert!(is_unavailable(&elapsed));
+    }
+
+    #[rocket::async_test]
+    async fn test_upstream_refused_connection_is_unavailable() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        drop(listener);
+
+        let error = reqwest::get(format!("http://{addr}/subgraph"))
+            .await
+            .unwrap_err();
+        assert!(is_unavailable(&error));
+    }
 }
@@ -0,0 +1,67 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Header;
+use rocket::{Request, Response};
+
+pub(crate) const API_VERSION_HEADER: &str = "X-API-Version";
+/// Version reported in [`API_VERSION_HEADER`], the same crate version the
+/// OpenAPI document advertises.
+pub(crate) const API_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Stamps every response, errors included, with the API version so clients
+/// can detect version skew from headers alone.
+pub struct ApiVersionHeader;
+
+#[rocket::async_trait]
+impl Fairing for ApiVersionHeader {
+    fn info(&self) -> Info {
+        Info {
+            name: "API Version Header",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, _req: &'r Request<'_>, res: &mut Response<'r>) {
+        res.set_header(Header::new(API_VERSION_HEADER, API_VERSION));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+
+    #[get("/test")]
+    fn test_route() -> &'static str {
+        "ok"
+    }
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .mount("/", rocket::routes![test_route])
+            .attach(ApiVersionHeader);
+        Client::tracked(rocket).expect("valid rocket instance")
+    }
+
+    #[test]
+    fn sets_version_header_on_success() {
+        let client = client();
+        let response = client.get("/test").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(
+            response.headers().get_one(API_VERSION_HEADER),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[test]
+    fn sets_version_header_on_unmatched_route() {
+        let client = client();
+        let response = client.get("/missing").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(
+            response.headers().get_one(API_VERSION_HEADER),
+            Some(API_VERSION)
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket::Request;
+
+/// Request guard for routes taking a JSON body. `Json<T>` parses whatever
+/// body it is given, so without this a `text/plain` or form-encoded request
+/// would be accepted or fail as an unrelated parse error. Failing here lets
+/// the `415` catcher answer with the standard error envelope.
+pub(crate) struct JsonContentType;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for JsonContentType {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match req.content_type() {
+            Some(content_type) if content_type.is_json() => Outcome::Success(JsonContentType),
+            content_type => {
+                tracing::warn!(
+                    content_type = content_type
+                        .map(ToString::to_string)
+                        .as_deref()
+                        .unwrap_or("none"),
+                    "rejecting non-JSON request body"
+                );
+                Outcome::Error((Status::UnsupportedMediaType, ()))
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Most responses the rolling error rate is computed over.
+const WINDOW_SIZE: usize = 200;
+
+/// Responses older than this drop out of the window. Once readiness fails,
+/// the load balancer stops sending traffic, so without ageing the window
+/// would stay frozen above the threshold and never recover.
+const WINDOW_MAX_AGE: Duration = Duration::from_secs(120);
+
+/// Responses needed before the rate can mark the service degraded, so a
+/// single early failure after a restart doesn't pull it from rotation.
+const MIN_SAMPLES: usize = 20;
+
+/// Ring buffer of the most recent response statuses from the last
+/// [`WINDOW_MAX_AGE`], recorded by [`super::RequestLogger`]. Readiness
+/// reports degraded while the share of 5xx responses in the window is above
+/// `threshold`.
+pub(crate) struct ErrorRateWindow {
+    threshold: Option<f64>,
+    samples: Mutex<Samples>,
+}
+
+struct Samples {
+    is_error: VecDeque<(Instant, bool)>,
+    errors: usize,
+}
+
+impl Samples {
+    fn pop_oldest(&mut self) {
+        if let Some((_, true)) = self.is_error.pop_front() {
+            self.errors -= 1;
+        }
+    }
+
+    fn expire(&mut self, now: Instant) {
+        while self
+            .is_error
+            .front()
+            .is_some_and(|(at, _)| now.saturating_duration_since(*at) > WINDOW_MAX_AGE)
+        {
+            self.pop_oldest();
+        }
+    }
+}
+
+impl ErrorRateWindow {
+    /// `threshold` is a fraction in `0.0..=1.0`; `None` never degrades.
+    pub(crate) fn new(threshold: Option<f64>) -> Self {
+        Self {
+            threshold,
+            samples: Mutex::new(Samples {
+                is_error: VecDeque::with_capacity(WINDOW_SIZE),
+                errors: 0,
+            }),
+        }
+    }
+
+    pub(crate) fn record(&self, status: u16) {
+        self.record_at(status, Instant::now());
+    }
+
+    fn record_at(&self, status: u16, now: Instant) {
+        let is_error = status >= 500;
+        let Ok(mut samples) = self.samples.lock() else {
+            return;
+        };
+        samples.expire(now);
+        if samples.is_error.len() == WINDOW_SIZE {
+            samples.pop_oldest();
+        }
+        samples.is_error.push_back((now, is_error));
+        if is_error {
+            samples.errors += 1;
+        }
+    }
+
+    /// Share of 5xx responses in the window, or `None` while fewer than
+    /// [`MIN_SAMPLES`] recent responses have been seen.
+    pub(crate) fn rate(&self) -> Option<f64> {
+        self.rate_at(Instant::now())
+    }
+
+    fn rate_at(&self, now: Instant) -> Option<f64> {
+        let mut samples = self.samples.lock().ok()?;
+        samples.expire(now);
+        let total = samples.is_error.len();
+        (total >= MIN_SAMPLES).then(|| samples.errors as f64 / total as f64)
+    }
+
+    pub(crate) fn is_degraded(&self) -> bool {
+        self.is_degraded_at(Instant::now())
+    }
+
+    fn is_degraded_at(&self, now: Instant) -> bool {
+        match (self.threshold, self.rate_at(now)) {
+            (Some(threshold), Some(rate)) => rate > threshold,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_waits_for_min_samples() {
+        let window = ErrorRateWindow::new(Some(0.1));
+        for _ in 0..MIN_SAMPLES - 1 {
+            window.record(500);
+        }
+        assert_eq!(window.rate(), None);
+        assert!(!window.is_degraded());
+
+        window.record(500);
+        assert_eq!(window.rate(), Some(1.0));
+        assert!(window.is_degraded());
+    }
+
+    #[test]
+    fn test_synthetic_500s_trip_threshold_then_recover() {
+        let window = ErrorRateWindow::new(Some(0.25));
+        for _ in 0..WINDOW_SIZE {
+            window.record(200);
+        }
+        assert!(!window.is_degraded());
+
+        for _ in 0..WINDOW_SIZE / 2 {
+            window.record(500);
+        }
+        assert_eq!(window.rate(), Some(0.5));
+        assert!(window.is_degraded());
+
+        for _ in 0..WINDOW_SIZE {
+            window.record(200);
+        }
+        assert_eq!(window.rate(), Some(0.0));
+        assert!(!window.is_degraded());
+    }
+
+    #[test]
+    fn test_readiness_recovers_after_idle_period_without_requests() {
+        let window = ErrorRateWindow::new(Some(0.25));
+        let start = Instant::now();
+        for _ in 0..WINDOW_SIZE {
+            window.record_at(500, start);
+        }
+        assert!(window.is_degraded_at(start));
+        assert!(window.is_degraded_at(start + WINDOW_MAX_AGE));
+
+        let idle = start + WINDOW_MAX_AGE + Duration::from_secs(1);
+        assert_eq!(window.rate_at(idle), None);
+        assert!(!window.is_degraded_at(idle));
+    }
+
+    #[test]
+    fn test_old_errors_age_out_of_the_window() {
+        let window = ErrorRateWindow::new(Some(0.25));
+        let start = Instant::now();
+        for _ in 0..MIN_SAMPLES {
+            window.record_at(500, start);
+        }
+        assert!(window.is_degraded_at(start));
+
+        let later = start + WINDOW_MAX_AGE + Duration::from_secs(1);
+        for _ in 0..MIN_SAMPLES {
+            window.record_at(200, later);
+        }
+        assert_eq!(window.rate_at(later), Some(0.0));
+        assert!(!window.is_degraded_at(later));
+    }
+
+    #[test]
+    fn test_client_errors_do_not_count() {
+        let window = ErrorRateWindow::new(Some(0.0));
+        for _ in 0..MIN_SAMPLES {
+            window.record(404);
+        }
+        assert_eq!(window.rate(), Some(0.0));
+        assert!(!window.is_degraded());
+    }
+
+    #[test]
+    fn test_no_threshold_never_degrades() {
+        let window = ErrorRateWindow::new(None);
+        for _ in 0..WINDOW_SIZE {
+            window.record(503);
+        }
+        assert!(!window.is_degraded());
+    }
+}
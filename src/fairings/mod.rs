@@ -1,7 +1,17 @@
+mod api_version;
+mod content_type;
+mod error_rate;
+mod query_params;
 pub(crate) mod rate_limiter;
 mod request_logger;
+pub(crate) mod response_cache;
 mod usage_logger;
 
+pub use api_version::ApiVersionHeader;
+pub(crate) use api_version::API_VERSION;
+pub(crate) use content_type::JsonContentType;
+pub(crate) use error_rate::ErrorRateWindow;
+pub(crate) use query_params::{KnownQueryParams, QueryParamsRejection};
 pub(crate) use rate_limiter::GlobalRateLimit;
 pub use rate_limiter::RateLimitHeadersFairing;
 pub use rate_limiter::RateLimiter;
@@ -9,4 +19,6 @@ pub(crate) use request_logger::request_id_for;
 pub(crate) use request_logger::request_span_for;
 pub use request_logger::RequestLogger;
 pub use request_logger::TracingSpan;
+pub(crate) use request_logger::{record_orderbook, record_token_pair, short_address};
+pub use response_cache::ResponseCache;
 pub use usage_logger::UsageLogger;
@@ -0,0 +1,125 @@
+use crate::app_state::ApplicationState;
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket::Request;
+use std::marker::PhantomData;
+use utoipa::openapi::path::ParameterIn;
+use utoipa::IntoParams;
+
+/// Query parameter that switches strict checking on or off per request.
+const STRICT_PARAM: &str = "strict";
+
+/// Request guard checking the query string against the parameters `T`
+/// documents. Rocket's `params..` binding ignores keys it does not know, so
+/// a misspelled `page_size` silently falls back to the default. In strict
+/// mode (`?strict=true`, or `strict_query_params` in config) unknown keys
+/// fail the request with a `400` listing them.
+pub(crate) struct KnownQueryParams<T>(PhantomData<T>);
+
+/// Message for the `400` catcher when the guard rejects a query string.
+pub(crate) struct QueryParamsRejection(pub Option<String>);
+
+#[rocket::async_trait]
+impl<'r, T: IntoParams> FromRequest<'r> for KnownQueryParams<T> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let default = req
+            .rocket()
+            .state::<ApplicationState>()
+            .is_some_and(|state| state.strict_query_params);
+        let keys: Vec<&str> = req
+            .uri()
+            .query()
+            .map(|query| query.segments().map(|(key, _)| key).collect())
+            .unwrap_or_default();
+        let known: Vec<String> = T::into_params(|| Some(ParameterIn::Query))
+            .into_iter()
+            .map(|param| param.name)
+            .collect();
+
+        match check_query_keys(
+            &keys,
+            &known,
+            req.query_value::<&str>(STRICT_PARAM),
+            default,
+        ) {
+            Ok(()) => Outcome::Success(KnownQueryParams(PhantomData)),
+            Err(message) => {
+                tracing::warn!(message = %message, "rejecting query parameters");
+                req.local_cache(|| QueryParamsRejection(Some(message)));
+                Outcome::Error((Status::BadRequest, ()))
+            }
+        }
+    }
+}
+
+/// Returns the rejection message when strict mode is on and `keys` holds
+/// names outside `known`. Nested keys like `filter.owner` or `ids[0]` are
+/// checked by their top-level name.
+fn check_query_keys(
+    keys: &[&str],
+    known: &[String],
+    strict: Option<rocket::form::Result<'_, &str>>,
+    default: bool,
+) -> Result<(), String> {
+    let strict = match strict {
+        None => default,
+        Some(Ok("true")) => true,
+        Some(Ok("false")) => false,
+        Some(_) => return Err(format!("{STRICT_PARAM} must be true or false")),
+    };
+    if !strict {
+        return Ok(());
+    }
+
+    let mut unknown: Vec<&str> = keys
+        .iter()
+        .map(|key| key.split(['.', '[']).next().unwrap_or(key))
+        .filter(|key| *key != STRICT_PARAM && !known.iter().any(|name| name == key))
+        .collect();
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    unknown.sort_unstable();
+    unknown.dedup();
+    Err(format!("unknown query parameters: {}", unknown.join(", ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn known() -> Vec<String> {
+        vec!["page".into(), "pageSize".into()]
+    }
+
+    #[test]
+    fn test_lenient_mode_ignores_unknown_keys() {
+        assert!(check_query_keys(&["page_size"], &known(), None, false).is_ok());
+        assert!(check_query_keys(&["page_size"], &known(), Some(Ok("false")), true).is_ok());
+    }
+
+    #[test]
+    fn test_strict_mode_lists_unknown_keys() {
+        assert_eq!(
+            check_query_keys(
+                &["pageSize", "page_size", "strict", "sort", "page_size"],
+                &known(),
+                Some(Ok("true")),
+                false,
+            ),
+            Err("unknown query parameters: page_size, sort".to_string())
+        );
+        assert!(check_query_keys(&["page", "pageSize"], &known(), None, true).is_ok());
+        assert!(check_query_keys(&["page.inner"], &known(), None, true).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_strict_value_is_rejected() {
+        assert_eq!(
+            check_query_keys(&[], &known(), Some(Ok("yes")), false),
+            Err("strict must be true or false".to_string())
+        );
+    }
+}
@@ -5,8 +5,9 @@ use rocket::request::{FromRequest, Outcome};
 use rocket::{Request, Response};
 use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
 const WINDOW_DURATION: Duration = Duration::from_secs(60);
 const PER_KEY_CLEANUP_EVERY: u64 = 1024;
@@ -21,14 +22,78 @@ pub struct RateLimitInfo {
 
 pub struct CachedRateLimitInfo(pub Mutex<Option<RateLimitInfo>>);
 
+/// Requests counted in a window and when its oldest one expires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitBucket {
+    pub count: u64,
+    pub limit: u64,
+    pub reset: u64,
+}
+
+/// Read-only view of the limiter's windows, for diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitSnapshot {
+    pub global: RateLimitBucket,
+    pub per_key_limit: u64,
+    /// `(key id, requests in window)`, busiest first.
+    pub top_keys: Vec<(i64, u64)>,
+}
+
+/// The key's in-flight slot for this request. Kept in the request's local
+/// cache so it is released when Rocket drops the request after responding.
+pub(crate) struct InFlightPermit(pub Mutex<Option<OwnedSemaphorePermit>>);
+
+/// Marks a request rejected by the in-flight cap rather than an RPM limit,
+/// so the 429 catcher can report it with its own code.
+pub(crate) struct InFlightRejected(pub bool);
+
 pub struct RateLimitHeadersFairing;
 
+/// A key's in-flight semaphore and the cap it was sized for. Shrinking the
+/// cap while permits are held leaves `unreclaimed` permits to forget as they
+/// come back.
+struct InFlightSlots {
+    limit: u32,
+    unreclaimed: usize,
+    semaphore: Arc<Semaphore>,
+}
+
+impl InFlightSlots {
+    fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            unreclaimed: 0,
+            semaphore: Arc::new(Semaphore::new(limit as usize)),
+        }
+    }
+
+    fn resize(&mut self, limit: u32) {
+        if limit > self.limit {
+            let grow = (limit - self.limit) as usize;
+            let repaid = grow.min(self.unreclaimed);
+            self.unreclaimed -= repaid;
+            self.semaphore.add_permits(grow - repaid);
+        } else {
+            self.unreclaimed += (self.limit - limit) as usize;
+        }
+        self.limit = limit;
+    }
+
+    fn reclaim(&mut self) {
+        if self.unreclaimed > 0 {
+            self.unreclaimed -= self.semaphore.forget_permits(self.unreclaimed);
+        }
+    }
+}
+
 pub struct RateLimiter {
     global_rpm: u64,
     per_key_rpm: u64,
     global_window: Mutex<VecDeque<Instant>>,
     per_key_windows: Mutex<HashMap<i64, VecDeque<Instant>>>,
     per_key_check_count: AtomicU64,
+    max_in_flight_per_key: u32,
+    in_flight: Mutex<HashMap<i64, InFlightSlots>>,
 }
 
 impl RateLimiter {
@@ -39,9 +104,18 @@ impl RateLimiter {
             global_window: Mutex::new(VecDeque::new()),
             per_key_windows: Mutex::new(HashMap::new()),
             per_key_check_count: AtomicU64::new(0),
+            max_in_flight_per_key: 0,
+            in_flight: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Caps how many requests a single key may have in flight at once. Zero
+    /// disables the cap.
+    pub fn with_max_in_flight_per_key(mut self, max_in_flight: u32) -> Self {
+        self.max_in_flight_per_key = max_in_flight;
+        self
+    }
+
     fn prune_window(window: &mut VecDeque<Instant>, cutoff: Instant) {
         while window.front().is_some_and(|t| *t < cutoff) {
             window.pop_front();
@@ -49,11 +123,15 @@ impl RateLimiter {
     }
 
     fn compute_reset(window: &VecDeque<Instant>, now: Instant) -> u64 {
+        Self::reset_after(window.front(), now)
+    }
+
+    fn reset_after(oldest: Option<&Instant>, now: Instant) -> u64 {
         let now_unix = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs();
-        match window.front() {
+        match oldest {
             Some(&oldest) => {
                 let delta = (oldest + WINDOW_DURATION)
                     .saturating_duration_since(now)
@@ -153,6 +231,92 @@ impl RateLimiter {
             ))
         }
     }
+
+    /// Counts the requests still inside each window without recording one,
+    /// returning the `top` busiest keys.
+    pub fn snapshot(&self, top: usize) -> Result<RateLimitSnapshot, ApiError> {
+        let now = Instant::now();
+        let cutoff = now - WINDOW_DURATION;
+        let live = |window: &VecDeque<Instant>| {
+            let mut live = window.iter().filter(|t| **t >= cutoff);
+            let oldest = live.next();
+            (
+                oldest.map_or(0, |_| 1 + live.count() as u64),
+                oldest.copied(),
+            )
+        };
+
+        let global = {
+            let window = self.global_window.lock().map_err(|e| {
+                tracing::error!(error = %e, "global rate limiter lock poisoned");
+                ApiError::Internal("rate limiter unavailable".into())
+            })?;
+            let (count, oldest) = live(&window);
+            RateLimitBucket {
+                count,
+                limit: self.global_rpm,
+                reset: Self::reset_after(oldest.as_ref(), now),
+            }
+        };
+
+        let mut top_keys: Vec<(i64, u64)> = {
+            let windows = self.per_key_windows.lock().map_err(|e| {
+                tracing::error!(error = %e, "per-key rate limiter lock poisoned");
+                ApiError::Internal("rate limiter unavailable".into())
+            })?;
+            windows
+                .iter()
+                .map(|(key_id, window)| (*key_id, live(window).0))
+                .filter(|(_, count)| *count > 0)
+                .collect()
+        };
+        top_keys.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_keys.truncate(top);
+
+        Ok(RateLimitSnapshot {
+            global,
+            per_key_limit: self.per_key_rpm,
+            top_keys,
+        })
+    }
+
+    /// Takes one of `key_id`'s in-flight slots, or `None` when the key is
+    /// uncapped. `limit_override` is the key's own cap and replaces the
+    /// configured default when set; a changed cap resizes the key's existing
+    /// semaphore so requests already in flight still count against it.
+    pub fn acquire_in_flight(
+        &self,
+        key_id: i64,
+        limit_override: Option<u32>,
+    ) -> Result<Option<OwnedSemaphorePermit>, ApiError> {
+        let limit = limit_override.unwrap_or(self.max_in_flight_per_key);
+        if limit == 0 {
+            return Ok(None);
+        }
+        let semaphore = {
+            let mut semaphores = match self.in_flight.lock() {
+                Ok(s) => s,
+                Err(e) => {
+                    tracing::error!(error = %e, "in-flight limiter lock poisoned");
+                    return Err(ApiError::Internal("rate limiter unavailable".into()));
+                }
+            };
+            let slots = semaphores
+                .entry(key_id)
+                .or_insert_with(|| InFlightSlots::new(limit));
+            if slots.limit != limit {
+                slots.resize(limit);
+            }
+            slots.reclaim();
+            Arc::clone(&slots.semaphore)
+        };
+        match semaphore.try_acquire_owned() {
+            Ok(permit) => Ok(Some(permit)),
+            Err(_) => Err(ApiError::TooManyConcurrent(
+                "Too many concurrent requests for this API key".into(),
+            )),
+        }
+    }
 }
 
 #[rocket::async_trait]
@@ -242,6 +406,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_snapshot_counts_requests_without_recording() {
+        let rl = RateLimiter::new(100, 50);
+        for _ in 0..3 {
+            rl.check_global().unwrap();
+        }
+        rl.check_per_key(7).unwrap();
+        rl.check_per_key(7).unwrap();
+        rl.check_per_key(9).unwrap();
+
+        let snapshot = rl.snapshot(10).unwrap();
+        assert_eq!(snapshot.global.count, 3);
+        assert_eq!(snapshot.global.limit, 100);
+        assert_eq!(snapshot.per_key_limit, 50);
+        assert_eq!(snapshot.top_keys, vec![(7, 2), (9, 1)]);
+
+        assert_eq!(rl.snapshot(1).unwrap().top_keys, vec![(7, 2)]);
+        assert_eq!(rl.snapshot(10).unwrap().global.count, 3);
+    }
+
     #[test]
     fn test_global_check_blocks_over_limit() {
         let rl = RateLimiter::new(3, 5);
@@ -657,6 +841,140 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_in_flight_cap_blocks_until_permit_released() {
+        let rl = RateLimiter::new(100, 100).with_max_in_flight_per_key(2);
+        let first = rl.acquire_in_flight(1, None).expect("acquire");
+        assert!(first.is_some());
+        let _second = rl.acquire_in_flight(1, None).expect("acquire");
+        assert!(matches!(
+            rl.acquire_in_flight(1, None),
+            Err(ApiError::TooManyConcurrent(_))
+        ));
+        assert!(matches!(rl.acquire_in_flight(2, None), Ok(Some(_))));
+
+        drop(first);
+        assert!(matches!(rl.acquire_in_flight(1, None), Ok(Some(_))));
+    }
+
+    #[test]
+    fn test_in_flight_override_replaces_default() {
+        let rl = RateLimiter::new(100, 100);
+        assert!(matches!(rl.acquire_in_flight(1, None), Ok(None)));
+
+        let _held = rl.acquire_in_flight(1, Some(1)).expect("acquire");
+        assert!(matches!(
+            rl.acquire_in_flight(1, Some(1)),
+            Err(ApiError::TooManyConcurrent(_))
+        ));
+    }
+
+    #[test]
+    fn test_in_flight_shrink_counts_permits_already_held() {
+        let rl = RateLimiter::new(100, 100).with_max_in_flight_per_key(2);
+        let first = rl.acquire_in_flight(1, None).expect("acquire");
+        let second = rl.acquire_in_flight(1, None).expect("acquire");
+
+        assert!(matches!(
+            rl.acquire_in_flight(1, Some(1)),
+            Err(ApiError::TooManyConcurrent(_))
+        ));
+        drop(first);
+        assert!(matches!(
+            rl.acquire_in_flight(1, Some(1)),
+            Err(ApiError::TooManyConcurrent(_))
+        ));
+        drop(second);
+        let third = rl.acquire_in_flight(1, Some(1)).expect("acquire");
+        assert!(third.is_some());
+        assert!(matches!(
+            rl.acquire_in_flight(1, Some(1)),
+            Err(ApiError::TooManyConcurrent(_))
+        ));
+    }
+
+    #[test]
+    fn test_in_flight_grow_keeps_held_permits_counted() {
+        let rl = RateLimiter::new(100, 100).with_max_in_flight_per_key(1);
+        let _first = rl.acquire_in_flight(1, None).expect("acquire");
+
+        let _second = rl.acquire_in_flight(1, Some(2)).expect("acquire");
+        assert!(matches!(
+            rl.acquire_in_flight(1, Some(2)),
+            Err(ApiError::TooManyConcurrent(_))
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_concurrent_requests_over_in_flight_cap_return_429() {
+        let client = TestClientBuilder::new()
+            .rate_limiter(RateLimiter::new(10000, 10000).with_max_in_flight_per_key(2))
+            .build()
+            .await;
+        let (key_id, secret) = seed_api_key(&client).await;
+        let header_val = basic_auth_header(&key_id, &secret);
+
+        let requests = (0..5).map(|_| {
+            client
+                .get("/v1/tokens")
+                .header(HttpHeader::new("Authorization", header_val.clone()))
+                .dispatch()
+        });
+        let responses = futures::future::join_all(requests).await;
+
+        let (rejected, admitted): (Vec<_>, Vec<_>) = responses
+            .into_iter()
+            .partition(|response| response.status() == Status::TooManyRequests);
+        assert_eq!(admitted.len(), 2);
+        assert!(admitted.iter().all(|r| r.status() == Status::Ok));
+        assert_eq!(rejected.len(), 3);
+
+        for response in rejected {
+            assert_eq!(response.headers().get_one("Retry-After"), Some("1"));
+            let body = response.into_string().await.expect("response body");
+            let json: serde_json::Value = serde_json::from_str(&body).expect("valid json");
+            assert_eq!(json["error"]["code"], "TOO_MANY_CONCURRENT");
+        }
+
+        drop(admitted);
+        let response = client
+            .get("/v1/tokens")
+            .header(HttpHeader::new("Authorization", header_val))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    #[rocket::async_test]
+    async fn test_per_key_in_flight_override_applies() {
+        let client = TestClientBuilder::new()
+            .rate_limiter(RateLimiter::new(10000, 10000).with_max_in_flight_per_key(10))
+            .build()
+            .await;
+        let (key_id, secret) = seed_api_key(&client).await;
+        let pool = client.rocket().state::<crate::db::DbPool>().expect("pool");
+        sqlx::query("UPDATE api_keys SET max_in_flight = 1 WHERE key_id = ?")
+            .bind(&key_id)
+            .execute(pool)
+            .await
+            .expect("set override");
+        let header_val = basic_auth_header(&key_id, &secret);
+
+        let first = client
+            .get("/v1/tokens")
+            .header(HttpHeader::new("Authorization", header_val.clone()))
+            .dispatch()
+            .await;
+        assert_eq!(first.status(), Status::Ok);
+
+        let second = client
+            .get("/v1/tokens")
+            .header(HttpHeader::new("Authorization", header_val))
+            .dispatch()
+            .await;
+        assert_eq!(second.status(), Status::TooManyRequests);
+    }
+
     #[rocket::async_test]
     async fn test_rate_limit_headers_on_successful_request() {
         let client = client().await;
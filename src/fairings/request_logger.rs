@@ -1,3 +1,5 @@
+use crate::app_state::ApplicationState;
+use alloy::primitives::Address;
 use rocket::fairing::{Fairing, Info, Kind};
 use rocket::http::Header;
 use rocket::request::{FromRequest, Outcome};
@@ -49,6 +51,24 @@ pub(crate) fn request_id_for(req: &Request<'_>) -> String {
     req.local_cache(fallback_meta).request_id.clone()
 }
 
+/// `0x8335..2913`: enough to tell tokens and orderbooks apart in logs while
+/// keeping span fields low-cardinality.
+pub(crate) fn short_address(address: Address) -> String {
+    let hex = format!("{address:#x}");
+    format!("{}..{}", &hex[..6], &hex[hex.len() - 4..])
+}
+
+/// Tags `span` with the token pair a request trades, as symbols or
+/// [`short_address`]es.
+pub(crate) fn record_token_pair(span: &tracing::Span, input_token: &str, output_token: &str) {
+    span.record("input_token", input_token);
+    span.record("output_token", output_token);
+}
+
+pub(crate) fn record_orderbook(span: &tracing::Span, orderbook: Address) {
+    span.record("orderbook", short_address(orderbook).as_str());
+}
+
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for TracingSpan {
     type Error = ();
@@ -74,6 +94,9 @@ impl Fairing for RequestLogger {
             method = %req.method(),
             uri = %req.uri(),
             request_id = %request_id,
+            input_token = tracing::field::Empty,
+            output_token = tracing::field::Empty,
+            orderbook = tracing::field::Empty,
         );
         span.in_scope(|| tracing::info!("request started"));
         req.local_cache(|| RequestMeta {
@@ -98,6 +121,14 @@ impl Fairing for RequestLogger {
             }
         });
 
+        // Readiness answers 503 while degraded; counting those would keep
+        // the rate up and the service out of rotation.
+        if !req.uri().path().starts_with("/health") {
+            if let Some(app_state) = req.rocket().state::<ApplicationState>() {
+                app_state.error_rate.record(status);
+            }
+        }
+
         res.set_header(Header::new(REQUEST_ID_HEADER, meta.request_id.clone()));
     }
 }
@@ -212,6 +243,14 @@ mod tests {
         assert!(logs_contain("request completed"));
     }
 
+    #[test]
+    fn short_address_keeps_prefix_and_suffix() {
+        let address: Address = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"
+            .parse()
+            .unwrap();
+        assert_eq!(short_address(address), "0x8335..2913");
+    }
+
     #[traced_test]
     #[test]
     fn logs_contain_request_id_field() {
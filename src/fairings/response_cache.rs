@@ -0,0 +1,428 @@
+use crate::auth::{AuthKeyId, AuthenticatedKey};
+use crate::fairings::GlobalRateLimit;
+use base64::Engine;
+use moka::future::Cache;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::uri::Origin;
+use rocket::http::{Method, Status};
+use rocket::request::{FromRequest, Outcome};
+use rocket::response::{self, Responder};
+use rocket::{Data, Request, Response, Route};
+use std::io::Cursor;
+use std::time::Duration;
+
+const CACHE_HEADER: &str = "X-Cache";
+/// Headers that describe the request being served rather than the cached
+/// payload; the hit route and the outer fairings set fresh values for these.
+const PER_REQUEST_HEADERS: [&str; 6] = [
+    CACHE_HEADER,
+    "Content-Length",
+    "X-Request-Id",
+    "X-RateLimit-Limit",
+    "X-RateLimit-Remaining",
+    "X-RateLimit-Reset",
+];
+const HIT_PATH: &str = "/__response-cache/hit";
+const CACHEABLE_PREFIXES: [&str; 2] = ["/v1/", "/v2/"];
+
+#[derive(Clone)]
+struct CachedResponse {
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+#[derive(Default)]
+struct ResponseCacheState {
+    key: Option<String>,
+    key_id: Option<String>,
+    hit: Option<CachedResponse>,
+    original_path: Option<String>,
+}
+
+/// Short-lived cache of successful authenticated `GET` responses keyed by
+/// path, query and API key id, so client retries inside the TTL don't repeat
+/// subgraph queries. Hits are rerouted to [`serve_cached_response`], which
+/// still authenticates and rate limits the request.
+pub struct ResponseCache {
+    entries: Option<Cache<String, CachedResponse>>,
+}
+
+impl ResponseCache {
+    pub fn new(max_capacity: u64, ttl: Duration) -> Self {
+        let entries = (max_capacity > 0 && !ttl.is_zero()).then(|| {
+            Cache::builder()
+                .max_capacity(max_capacity)
+                .time_to_live(ttl)
+                .build()
+        });
+        Self { entries }
+    }
+}
+
+fn basic_auth_key_id(header: &str) -> Option<String> {
+    const BASIC_PREFIX: &str = "Basic ";
+    let prefix = header.get(..BASIC_PREFIX.len())?;
+    if !prefix.eq_ignore_ascii_case(BASIC_PREFIX) {
+        return None;
+    }
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(&header[BASIC_PREFIX.len()..])
+        .ok()?;
+    let credentials = String::from_utf8(decoded).ok()?;
+    let (key_id, _) = credentials.split_once(':')?;
+    Some(key_id.to_string())
+}
+
+fn cacheable_headers(res: &Response<'_>) -> Vec<(String, String)> {
+    res.headers()
+        .iter()
+        .filter(|header| {
+            !PER_REQUEST_HEADERS
+                .iter()
+                .any(|name| header.name().as_str().eq_ignore_ascii_case(name))
+        })
+        .map(|header| (header.name().to_string(), header.value().to_string()))
+        .collect()
+}
+
+fn cache_key(key_id: &str, req: &Request<'_>) -> String {
+    match req.uri().query() {
+        Some(query) => format!("{key_id}\n{}?{query}", req.uri().path()),
+        None => format!("{key_id}\n{}", req.uri().path()),
+    }
+}
+
+/// Path the client asked for, before a cache hit was rerouted.
+pub(crate) fn request_path_for(req: &Request<'_>) -> String {
+    req.local_cache(ResponseCacheState::default)
+        .original_path
+        .clone()
+        .unwrap_or_else(|| req.uri().path().to_string())
+}
+
+#[rocket::async_trait]
+impl Fairing for ResponseCache {
+    fn info(&self) -> Info {
+        Info {
+            name: "Response Cache",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _data: &mut Data<'_>) {
+        let Some(entries) = &self.entries else {
+            return;
+        };
+        let path = req.uri().path().to_string();
+        if req.method() != Method::Get
+            || !CACHEABLE_PREFIXES
+                .iter()
+                .any(|prefix| path.starts_with(prefix))
+        {
+            return;
+        }
+        let Some(key_id) = req
+            .headers()
+            .get_one("Authorization")
+            .and_then(basic_auth_key_id)
+        else {
+            return;
+        };
+
+        let key = cache_key(&key_id, req);
+        let hit = entries.get(&key).await;
+        if hit.is_some() {
+            tracing::info!(path = %path, "serving cached response");
+            match Origin::parse(HIT_PATH) {
+                Ok(uri) => req.set_uri(uri),
+                Err(e) => {
+                    tracing::error!(error = %e, "invalid response cache route");
+                    return;
+                }
+            }
+        }
+
+        let original_path = hit.is_some().then_some(path);
+        req.local_cache(|| ResponseCacheState {
+            key: Some(key),
+            key_id: Some(key_id),
+            hit,
+            original_path,
+        });
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let Some(entries) = &self.entries else {
+            return;
+        };
+        let state = req.local_cache(ResponseCacheState::default);
+        let Some(key) = &state.key else {
+            return;
+        };
+        if state.hit.is_some()
+            || res.status() != Status::Ok
+            || req.local_cache(|| AuthKeyId(None)).0.is_none()
+        {
+            return;
+        }
+
+        let body = match res.body_mut().to_bytes().await {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to read response body for caching");
+                return;
+            }
+        };
+        res.set_sized_body(body.len(), Cursor::new(body.clone()));
+        res.set_raw_header(CACHE_HEADER, "MISS");
+        entries
+            .insert(
+                key.clone(),
+                CachedResponse {
+                    headers: cacheable_headers(res),
+                    body,
+                },
+            )
+            .await;
+    }
+}
+
+pub(crate) struct CachedResponseHit(CachedResponse);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for CachedResponseHit {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match &req.local_cache(ResponseCacheState::default).hit {
+            Some(hit) => Outcome::Success(CachedResponseHit(hit.clone())),
+            None => Outcome::Forward(Status::NotFound),
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for CachedResponseHit {
+    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'static> {
+        let CachedResponse { headers, body } = self.0;
+        let mut response = Response::build();
+        for (name, value) in headers {
+            response.raw_header_adjoin(name, value);
+        }
+        response
+            .sized_body(body.len(), Cursor::new(body))
+            .raw_header(CACHE_HEADER, "HIT");
+        response.ok()
+    }
+}
+
+pub(crate) struct CachedKeyId(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for CachedKeyId {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match &req.local_cache(ResponseCacheState::default).key_id {
+            Some(key_id) => Outcome::Success(CachedKeyId(key_id.clone())),
+            None => Outcome::Forward(Status::NotFound),
+        }
+    }
+}
+
+#[get("/__response-cache/hit")]
+pub(crate) fn serve_cached_response(
+    _global: GlobalRateLimit,
+    key: AuthenticatedKey,
+    hit: CachedResponseHit,
+    cached_key_id: CachedKeyId,
+) -> Option<CachedResponseHit> {
+    (cached_key_id.0 == key.key_id).then_some(hit)
+}
+
+pub fn routes() -> Vec<Route> {
+    rocket::routes![serve_cached_response]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cacheable_headers, CachedResponse, CachedResponseHit};
+    use crate::test_helpers::{basic_auth_header, seed_api_key, TestClientBuilder};
+    use rocket::http::{ContentType, Header, Status};
+    use rocket::local::asynchronous::Client;
+    use rocket::response::Responder;
+    use rocket::Response;
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    #[test]
+    fn test_cacheable_headers_keep_payload_headers_and_drop_per_request_ones() {
+        let body = b"[]".to_vec();
+        let response = Response::build()
+            .header(ContentType::JSON)
+            .raw_header("Link", "</v1/tokens?page=2>; rel=\"next\"")
+            .raw_header("Cache-Control", "max-age=5")
+            .raw_header_adjoin("Vary", "Accept")
+            .raw_header_adjoin("Vary", "Authorization")
+            .raw_header("X-Request-Id", "req-1")
+            .raw_header("X-RateLimit-Remaining", "9")
+            .raw_header("X-Cache", "MISS")
+            .sized_body(body.len(), Cursor::new(body))
+            .finalize();
+
+        let headers = cacheable_headers(&response);
+        let names: Vec<&str> = headers.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Content-Type", "Link", "Cache-Control", "Vary", "Vary"]
+        );
+        assert!(headers.contains(&("Vary".to_string(), "Authorization".to_string())));
+    }
+
+    #[rocket::async_test]
+    async fn test_cached_response_hit_replays_every_stored_header() {
+        let client = Client::untracked(rocket::build()).await.unwrap();
+        let request = client.get("/");
+        let hit = CachedResponseHit(CachedResponse {
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Cache-Control".to_string(), "max-age=5".to_string()),
+                ("Vary".to_string(), "Accept".to_string()),
+                ("Vary".to_string(), "Authorization".to_string()),
+            ],
+            body: b"[]".to_vec(),
+        });
+
+        let response = hit.respond_to(request.inner()).unwrap();
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+        assert_eq!(
+            response.headers().get_one("Cache-Control"),
+            Some("max-age=5")
+        );
+        assert_eq!(
+            response.headers().get("Vary").collect::<Vec<_>>(),
+            vec!["Accept", "Authorization"]
+        );
+        assert_eq!(response.headers().get_one("X-Cache"), Some("HIT"));
+    }
+
+    #[rocket::async_test]
+    async fn test_repeated_get_is_served_from_cache_within_ttl() {
+        let client = TestClientBuilder::new()
+            .response_cache_ttl(Duration::from_secs(60))
+            .build()
+            .await;
+        let (key_id, secret) = seed_api_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+
+        let first = client
+            .get("/v1/tokens")
+            .header(Header::new("Authorization", header.clone()))
+            .dispatch()
+            .await;
+        assert_eq!(first.status(), Status::Ok);
+        assert_eq!(first.headers().get_one("X-Cache"), Some("MISS"));
+        let first_body = first.into_string().await.unwrap();
+
+        let second = client
+            .get("/v1/tokens")
+            .header(Header::new("Authorization", header))
+            .dispatch()
+            .await;
+        assert_eq!(second.status(), Status::Ok);
+        assert_eq!(second.headers().get_one("X-Cache"), Some("HIT"));
+        assert_eq!(second.content_type(), Some(rocket::http::ContentType::JSON));
+        assert_eq!(second.into_string().await.unwrap(), first_body);
+    }
+
+    #[rocket::async_test]
+    async fn test_cached_get_misses_after_ttl() {
+        let client = TestClientBuilder::new()
+            .response_cache_ttl(Duration::from_millis(200))
+            .build()
+            .await;
+        let (key_id, secret) = seed_api_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+
+        let first = client
+            .get("/v1/tokens")
+            .header(Header::new("Authorization", header.clone()))
+            .dispatch()
+            .await;
+        assert_eq!(first.headers().get_one("X-Cache"), Some("MISS"));
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+
+        let second = client
+            .get("/v1/tokens")
+            .header(Header::new("Authorization", header))
+            .dispatch()
+            .await;
+        assert_eq!(second.status(), Status::Ok);
+        assert_eq!(second.headers().get_one("X-Cache"), Some("MISS"));
+    }
+
+    #[rocket::async_test]
+    async fn test_error_responses_are_not_cached() {
+        let client = TestClientBuilder::new()
+            .response_cache_ttl(Duration::from_secs(60))
+            .build()
+            .await;
+        let (key_id, secret) = seed_api_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+
+        for _ in 0..2 {
+            let response = client
+                .get("/v1/orders/owner/not-an-address")
+                .header(Header::new("Authorization", header.clone()))
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::BadRequest);
+            assert_eq!(response.headers().get_one("X-Cache"), None);
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_cache_hit_still_requires_valid_credentials() {
+        let client = TestClientBuilder::new()
+            .response_cache_ttl(Duration::from_secs(60))
+            .build()
+            .await;
+        let (key_id, secret) = seed_api_key(&client).await;
+
+        let first = client
+            .get("/v1/tokens")
+            .header(Header::new(
+                "Authorization",
+                basic_auth_header(&key_id, &secret),
+            ))
+            .dispatch()
+            .await;
+        assert_eq!(first.status(), Status::Ok);
+
+        let forged = client
+            .get("/v1/tokens")
+            .header(Header::new(
+                "Authorization",
+                basic_auth_header(&key_id, "wrong-secret"),
+            ))
+            .dispatch()
+            .await;
+        assert_eq!(forged.status(), Status::Unauthorized);
+        assert_eq!(forged.headers().get_one("X-Cache"), None);
+    }
+
+    #[rocket::async_test]
+    async fn test_disabled_cache_sets_no_header() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_api_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+
+        let response = client
+            .get("/v1/tokens")
+            .header(Header::new("Authorization", header))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("X-Cache"), None);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::auth::AuthKeyId;
-use crate::db::DbPool;
+use crate::db::Db;
 use rocket::fairing::{Fairing, Info, Kind};
 use rocket::{Data, Request, Response};
 use std::sync::Arc;
@@ -39,15 +39,15 @@ impl Fairing for UsageLogger {
             None => return,
         };
 
-        let pool = match req.rocket().state::<DbPool>() {
-            Some(p) => p.clone(),
+        let pool = match req.rocket().state::<Db>() {
+            Some(db) => db.writes().clone(),
             None => return,
         };
 
         let start = &req.local_cache(|| UsageStart(Instant::now())).0;
         let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
         let method = req.method().as_str().to_owned();
-        let path = req.uri().path().to_string();
+        let path = super::response_cache::request_path_for(req);
         let status_code = res.status().code as i32;
         let permit = match self.semaphore.clone().try_acquire_owned() {
             Ok(permit) => permit,
@@ -0,0 +1,160 @@
+use crate::error::ApiError;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Response types that can be projected down to a subset of their top-level
+/// fields with `?fields=`.
+pub(crate) trait SparseFields: Serialize {
+    /// Serialized (camelCase) names of the top-level fields.
+    const FIELDS: &'static [&'static str];
+}
+
+/// Top-level fields requested by a client, in serialized (camelCase) form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FieldSelection(Vec<&'static str>);
+
+impl FieldSelection {
+    /// Parses a comma-separated `fields` value for `T`. Names may be given in
+    /// snake_case or camelCase; unknown names are rejected.
+    pub(crate) fn parse<T: SparseFields>(raw: Option<&str>) -> Result<Option<Self>, ApiError> {
+        let Some(raw) = raw else {
+            return Ok(None);
+        };
+
+        let mut selected: Vec<&'static str> = Vec::new();
+        for name in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            let camel = snake_to_camel(name);
+            let field = T::FIELDS
+                .iter()
+                .copied()
+                .find(|field| *field == camel)
+                .ok_or_else(|| {
+                    tracing::warn!(field = name, "unknown field requested");
+                    ApiError::BadRequest(format!(
+                        "unknown field '{name}'; expected one of: {}",
+                        T::FIELDS.join(", ")
+                    ))
+                })?;
+            if !selected.contains(&field) {
+                selected.push(field);
+            }
+        }
+
+        if selected.is_empty() {
+            return Err(ApiError::BadRequest(
+                "fields must name at least one field".into(),
+            ));
+        }
+        Ok(Some(Self(selected)))
+    }
+}
+
+/// Serializes `value`, keeping only the selected top-level fields.
+pub(crate) fn project<T: SparseFields>(
+    value: &T,
+    selection: Option<&FieldSelection>,
+) -> Result<Value, ApiError> {
+    let serialized = serde_json::to_value(value).map_err(|e| {
+        tracing::error!(error = %e, "failed to serialize response");
+        ApiError::Internal("failed to serialize response".into())
+    })?;
+    let Some(selection) = selection else {
+        return Ok(serialized);
+    };
+
+    let Value::Object(mut object) = serialized else {
+        tracing::error!("sparse fieldset requested for non-object response");
+        return Err(ApiError::Internal("failed to serialize response".into()));
+    };
+    let projected: Map<String, Value> = selection
+        .0
+        .iter()
+        .filter_map(|field| object.remove(*field).map(|v| ((*field).to_string(), v)))
+        .collect();
+    Ok(Value::Object(projected))
+}
+
+fn snake_to_camel(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    let mut upper_next = false;
+    for ch in name.chars() {
+        if ch == '_' {
+            upper_next = true;
+        } else if upper_next {
+            camel.extend(ch.to_uppercase());
+            upper_next = false;
+        } else {
+            camel.push(ch);
+        }
+    }
+    camel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Sample {
+        order_hash: String,
+        io_ratio: String,
+        created_at: u64,
+    }
+
+    impl SparseFields for Sample {
+        const FIELDS: &'static [&'static str] = &["orderHash", "ioRatio", "createdAt"];
+    }
+
+    fn sample() -> Sample {
+        Sample {
+            order_hash: "0xabc".into(),
+            io_ratio: "2.0".into(),
+            created_at: 1,
+        }
+    }
+
+    #[test]
+    fn test_parse_accepts_snake_and_camel_case() {
+        let selection = FieldSelection::parse::<Sample>(Some("order_hash, ioRatio,order_hash"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(selection, FieldSelection(vec!["orderHash", "ioRatio"]));
+    }
+
+    #[test]
+    fn test_parse_without_fields_selects_everything() {
+        assert_eq!(FieldSelection::parse::<Sample>(None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_and_empty_fields() {
+        let err = FieldSelection::parse::<Sample>(Some("order_hash,status")).unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::BadRequest(msg)
+                if msg == "unknown field 'status'; expected one of: orderHash, ioRatio, createdAt"
+        ));
+
+        let err = FieldSelection::parse::<Sample>(Some(" , ")).unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest(_)));
+    }
+
+    #[test]
+    fn test_project_keeps_only_selected_fields() {
+        let selection = FieldSelection::parse::<Sample>(Some("io_ratio,created_at")).unwrap();
+        let projected = project(&sample(), selection.as_ref()).unwrap();
+        assert_eq!(projected, json!({ "ioRatio": "2.0", "createdAt": 1 }));
+
+        let full = project(&sample(), None).unwrap();
+        assert_eq!(
+            full,
+            json!({ "orderHash": "0xabc", "ioRatio": "2.0", "createdAt": 1 })
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use crate::rpc::{RpcClient, RpcError};
+use alloy::network::{Ethereum, Network};
+use alloy::primitives::U256;
+use std::time::Duration;
+use url::Url;
+
+/// Longest a response waits for fee hints across every configured RPC.
+/// Hints are optional, so a slow node only costs the response this much.
+const FEE_HINTS_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// EIP-1559 fee suggestion, fetched once per request and shared by every
+/// transaction in the response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FeeHints {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+}
+
+/// Best-effort fee hints from the first RPC that answers both
+/// `eth_maxPriorityFeePerGas` and `eth_feeHistory`. `maxFeePerGas` is twice
+/// the next block's base fee plus the priority fee. Returns `None` when no
+/// RPC answers within [`FEE_HINTS_TIMEOUT`].
+pub(crate) async fn fetch_fee_hints(rpcs: &[Url]) -> Option<FeeHints> {
+    fetch_fee_hints_within(rpcs, FEE_HINTS_TIMEOUT).await
+}
+
+async fn fetch_fee_hints_within(rpcs: &[Url], timeout: Duration) -> Option<FeeHints> {
+    let fetch = fetch_fee_hints_from_any(rpcs);
+    match tokio::time::timeout(timeout, fetch).await {
+        Ok(hints) => hints,
+        Err(_) => {
+            tracing::warn!(?timeout, "timed out fetching fee hints");
+            None
+        }
+    }
+}
+
+async fn fetch_fee_hints_from_any(rpcs: &[Url]) -> Option<FeeHints> {
+    if rpcs.is_empty() {
+        tracing::warn!("no RPC URLs configured for fee hints");
+        return None;
+    }
+
+    for (index, rpc) in rpcs.iter().enumerate() {
+        match fee_hints_from_rpc(rpc).await {
+            Ok(hints) => return Some(hints),
+            Err(error) => {
+                tracing::warn!(rpc_index = index, error = %error, "failed to fetch fee hints")
+            }
+        }
+    }
+    None
+}
+
+/// Best-effort `eth_estimateGas` for `tx` from the first RPC that answers.
+/// Returns `None` when every RPC fails, including when the transaction would
+/// revert because it depends on one not yet mined.
+pub(crate) async fn estimate_gas(
+    rpcs: &[Url],
+    tx: <Ethereum as Network>::TransactionRequest,
+) -> Option<U256> {
+    for (index, rpc) in rpcs.iter().enumerate() {
+        match RpcClient::new(rpc.clone()).estimate_gas(tx.clone()).await {
+            Ok(gas) => return Some(U256::from(gas)),
+            Err(error) => {
+                tracing::warn!(rpc_index = index, error = %error, "failed to estimate gas")
+            }
+        }
+    }
+    None
+}
+
+async fn fee_hints_from_rpc(rpc: &Url) -> Result<FeeHints, RpcError> {
+    let client = RpcClient::new(rpc.clone());
+    let priority_fee = client.max_priority_fee_per_gas().await?;
+    let base_fee = client.next_block_base_fee().await?;
+
+    Ok(FeeHints {
+        max_fee_per_gas: U256::from(base_fee.saturating_mul(2).saturating_add(priority_fee)),
+        max_priority_fee_per_gas: U256::from(priority_fee),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    async fn mock_fee_rpc() -> Url {
+        crate::test_helpers::mock_json_rpc_server(|request| {
+            let result = if request.contains("eth_maxPriorityFeePerGas") {
+                json!("0x5f5e100")
+            } else if request.contains("eth_feeHistory") {
+                json!({
+                    "oldestBlock": "0x64",
+                    "baseFeePerGas": ["0x3b9aca00", "0x77359400"],
+                    "gasUsedRatio": [0.5],
+                    "reward": []
+                })
+            } else {
+                Value::Null
+            };
+            json!({ "result": result })
+        })
+        .await
+        .parse()
+        .expect("mock rpc url")
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_fee_hints_from_mock_rpc() {
+        let rpc = mock_fee_rpc().await;
+
+        let hints = fetch_fee_hints(&[rpc]).await.expect("fee hints");
+
+        // Next base fee 2 gwei, priority fee 0.1 gwei.
+        assert_eq!(hints.max_priority_fee_per_gas, U256::from(100_000_000u64));
+        assert_eq!(hints.max_fee_per_gas, U256::from(4_100_000_000u64));
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_fee_hints_falls_back_to_next_rpc() {
+        let unreachable: Url = "http://127.0.0.1:1/rpc".parse().unwrap();
+        let rpc = mock_fee_rpc().await;
+
+        let hints = fetch_fee_hints(&[unreachable, rpc]).await;
+
+        assert!(hints.is_some());
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_fee_hints_is_none_when_rpcs_fail() {
+        let unreachable: Url = "http://127.0.0.1:1/rpc".parse().unwrap();
+        assert_eq!(fetch_fee_hints(&[unreachable]).await, None);
+        assert_eq!(fetch_fee_hints(&[]).await, None);
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_fee_hints_gives_up_on_slow_rpc() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind silent rpc");
+        let addr = listener.local_addr().expect("silent rpc address");
+        tokio::spawn(async move {
+            let _held = listener.accept().await;
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+        let silent: Url = format!("http://{addr}/rpc").parse().unwrap();
+
+        let started = std::time::Instant::now();
+        let hints = fetch_fee_hints_within(&[silent], Duration::from_millis(100)).await;
+
+        assert_eq!(hints, None);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
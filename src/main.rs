@@ -1,22 +1,34 @@
 #[macro_use]
 extern crate rocket;
 
+mod amounts;
 mod app_state;
 mod auth;
+mod block_number;
 mod cache;
 mod catchers;
 mod cli;
 mod config;
 mod db;
+mod debug_raw;
 mod denomination;
+mod denylist;
 mod erc4626;
 mod error;
 mod fairings;
+mod fields;
+mod gas;
+mod owner_allowlist;
 mod raindex;
 mod registry_artifact;
 mod routes;
+mod rpc;
+mod simulation;
 mod telemetry;
+mod timestamps;
+mod tx_value;
 mod types;
+mod vault_ids;
 mod wrap_ratio;
 
 pub(crate) const CHAIN_ID: u32 = 8453;
@@ -73,13 +85,17 @@ enum StartupRegistryError {
     PrivateRegistryLoad(#[source] raindex::RaindexProviderError),
     #[error("failed to load configured registry")]
     ConfiguredRegistryLoad(#[source] raindex::RaindexProviderError),
+    #[error("{0}")]
+    InvalidRegistryHeaders(String),
 }
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
+        routes::health::get_root,
         routes::health::get_health,
         routes::health::get_health_detailed,
+        routes::health::get_health_ready,
         routes::tokens::get_tokens,
         routes::tokens::get_wrap_ratios,
         routes::tokens::get_wrap_ratio_by_address,
@@ -90,23 +106,41 @@ enum StartupRegistryError {
         routes::swap::post_swap_quote,
         routes::swap::post_swap_calldata,
         routes::swap::post_swap_calldata_v2,
+        routes::swap::post_swap_execute_plan,
+        routes::swap::post_swap_quote_both,
+        routes::swap::post_swap_curve,
         routes::order::post_order_dca,
         routes::order::post_order_solver,
         routes::order::get_order,
+        routes::order::post_order_lookup,
+        routes::order::get_order_next_fill,
         routes::order::post_order_cancel,
+        routes::order::post_order_cancel_all,
         routes::orders::get_orders_by_tx,
         routes::orders::get_orders_by_address,
         routes::orders::get_orders_by_token,
         routes::vaults::get_vaults,
         routes::vaults::get_vault_totals,
         routes::admin::put_registry,
+        routes::admin::post_registry_validate,
+        routes::admin::put_log_level,
+        routes::admin::post_tokens_refresh,
+        routes::admin::get_denylist,
+        routes::admin::put_denylist,
+        routes::admin::get_rate_limit,
+        routes::admin::delete_logs,
         routes::trades::get_by_tx::get_trades_by_tx,
+        routes::trades::get_by_tx_batch::get_trades_by_tx_batch,
         routes::trades::get_by_order_hashes::get_trades_by_order_hashes,
         routes::trades::get_by_token::get_trades_by_token,
         routes::trades::get_by_taker::get_trades_by_taker,
         routes::trades::get_by_address::get_trades_by_address,
+        routes::stats::get_volume_stats,
+        routes::stats::get_open_interest,
+        routes::status::get_indexing_status,
         routes::registry::get_registry,
         routes::registry::get_registry_history,
+        routes::deployers::get_deployers,
     ),
     components(),
     modifiers(&SecurityAddon),
@@ -120,6 +154,8 @@ enum StartupRegistryError {
         (name = "Admin", description = "Administrative endpoints"),
         (name = "Trades", description = "Trade listing and query endpoints"),
         (name = "Registry", description = "Registry information endpoints"),
+        (name = "Stats", description = "Aggregate trading statistics endpoints"),
+        (name = "Status", description = "Data freshness endpoints"),
     ),
     info(
         title = "st0x REST API",
@@ -148,6 +184,9 @@ fn configure_cors() -> Result<rocket_cors::Cors, StartupError> {
             "X-RateLimit-Limit".to_string(),
             "X-RateLimit-Remaining".to_string(),
             "X-RateLimit-Reset".to_string(),
+            "X-Cache".to_string(),
+            "Link".to_string(),
+            "X-API-Version".to_string(),
         ]),
         ..Default::default()
     }
@@ -155,10 +194,11 @@ fn configure_cors() -> Result<rocket_cors::Cors, StartupError> {
 }
 
 pub(crate) fn rocket(
-    pool: db::DbPool,
+    db: db::Db,
     rate_limiter: fairings::RateLimiter,
     raindex_config: raindex::SharedRaindexProvider,
     app_state: app_state::ApplicationState,
+    response_cache: fairings::ResponseCache,
     docs_dir: String,
     usage_log_max_concurrency: usize,
 ) -> Result<rocket::Rocket<rocket::Build>, StartupError> {
@@ -169,7 +209,8 @@ pub(crate) fn rocket(
     let options = Options::Index | Options::NormalizeDirs;
 
     Ok(rocket::custom(figment)
-        .manage(pool)
+        .manage(db.writes().clone())
+        .manage(db)
         .manage(rate_limiter)
         .manage(raindex_config)
         .manage(app_state)
@@ -181,8 +222,12 @@ pub(crate) fn rocket(
         .mount("/v1/orders", routes::orders::routes())
         .mount("/v1/vaults", routes::vaults::routes())
         .mount("/v1/trades", routes::trades::routes())
+        .mount("/v1/stats", routes::stats::routes())
+        .mount("/v1/status", routes::status::routes())
         .mount("/", routes::registry::routes())
+        .mount("/v1/deployers", routes::deployers::routes())
         .mount("/admin", routes::admin::routes())
+        .mount("/", fairings::response_cache::routes())
         .mount("/docs", FileServer::new(docs_dir, options))
         .mount(
             "/",
@@ -192,9 +237,18 @@ pub(crate) fn rocket(
         .attach(fairings::RequestLogger)
         .attach(fairings::UsageLogger::new(usage_log_max_concurrency))
         .attach(fairings::RateLimitHeadersFairing)
+        .attach(fairings::ApiVersionHeader)
+        .attach(response_cache)
         .attach(cors))
 }
 
+fn registry_headers(
+    cfg: &config::Config,
+) -> Result<raindex::RegistryHeaders, StartupRegistryError> {
+    raindex::RegistryHeaders::from_config(&cfg.registry_headers)
+        .map_err(StartupRegistryError::InvalidRegistryHeaders)
+}
+
 async fn load_configured_raindex(
     cfg: &config::Config,
     local_db_path: PathBuf,
@@ -203,10 +257,44 @@ async fn load_configured_raindex(
         return Err(StartupRegistryError::MissingConfiguredRegistry);
     }
 
-    tracing::info!("loading raindex registry from config");
-    raindex::RaindexProvider::load(&cfg.registry_url, Some(local_db_path))
-        .await
-        .map_err(StartupRegistryError::ConfiguredRegistryLoad)
+    tracing::info!(
+        fallbacks = cfg.fallback_registry_urls.len(),
+        "loading raindex registry from config"
+    );
+    let registries = cfg.registry_urls();
+    let headers = registry_headers(cfg)?;
+    let loaded = raindex::RaindexProvider::load_first_with_headers(
+        &registries,
+        Some(local_db_path.clone()),
+        headers.clone(),
+    )
+    .await;
+    let e = match loaded {
+        Ok(provider) => return Ok(provider),
+        Err(e) if cfg.token_list_fail_fast => {
+            return Err(StartupRegistryError::ConfiguredRegistryLoad(e))
+        }
+        Err(e) => e,
+    };
+
+    // The registry may only have failed on an unreachable token list; start
+    // without the lists rather than refuse to serve.
+    match raindex::RaindexProvider::load_first_without_token_lists(
+        &registries,
+        Some(local_db_path),
+        headers,
+    )
+    .await
+    {
+        Ok(provider) => {
+            tracing::warn!(
+                error = %e.safe_summary(),
+                "token lists unavailable; starting without them and retrying in the background"
+            );
+            Ok(provider)
+        }
+        Err(_) => Err(StartupRegistryError::ConfiguredRegistryLoad(e)),
+    }
 }
 
 async fn load_startup_raindex(
@@ -278,8 +366,12 @@ async fn load_startup_raindex(
             path = %registry_artifact_store.path().display(),
             "loading private registry artifact from file"
         );
-        match raindex::RaindexProvider::load(&private_registry_source, Some(local_db_path.clone()))
-            .await
+        match raindex::RaindexProvider::load_with_headers(
+            &private_registry_source,
+            Some(local_db_path.clone()),
+            registry_headers(cfg)?,
+        )
+        .await
         {
             Ok(provider) => {
                 tracing::info!("loaded private raindex registry");
@@ -323,8 +415,8 @@ async fn main() {
         }
     };
 
-    let log_guard = match telemetry::init(&cfg.log_dir) {
-        Ok(guard) => guard,
+    let (log_guard, log_filter) = match telemetry::init(&cfg.log_dir) {
+        Ok(init) => init,
         Err(e) => {
             eprintln!("failed to initialize telemetry: {e}");
             std::process::exit(1);
@@ -340,15 +432,7 @@ async fn main() {
         }
     };
 
-    tracing::info!(
-        global_rpm = cfg.rate_limit_global_rpm,
-        per_key_rpm = cfg.rate_limit_per_key_rpm,
-        database_max_connections = cfg.database_max_connections,
-        usage_log_max_concurrency = cfg.usage_log_max_concurrency,
-        response_cache_max_entries = cfg.response_cache_max_entries,
-        response_cache_ttl_seconds = cfg.response_cache_ttl_seconds,
-        "rate limiter configured"
-    );
+    cfg.log_effective();
 
     match command {
         cli::Command::Serve { .. } => {
@@ -358,6 +442,7 @@ async fn main() {
             let response_caches = cache::RouteResponseCaches::new(
                 cfg.response_cache_max_entries,
                 std::time::Duration::from_secs(cfg.response_cache_ttl_seconds),
+                std::time::Duration::from_secs(cfg.lookup_cache_ttl_seconds),
             );
 
             let local_db_path = std::path::PathBuf::from(&cfg.local_db_path);
@@ -387,9 +472,29 @@ async fn main() {
                     }
                 };
 
-            let shared_raindex = tokio::sync::RwLock::new(raindex_config);
+            if cfg.startup_self_test {
+                if let Err(e) = raindex_config.self_test().await {
+                    tracing::error!(
+                        error = %e,
+                        "startup self-test failed; check the registry subgraph URLs"
+                    );
+                    drop(log_guard);
+                    std::process::exit(1);
+                }
+                tracing::info!("startup self-test passed");
+            }
+
+            let token_lists_deferred = raindex_config.token_lists_deferred();
+            let shared_raindex = std::sync::Arc::new(tokio::sync::RwLock::new(raindex_config));
+            if token_lists_deferred {
+                raindex::spawn_token_list_retry(
+                    shared_raindex.clone(),
+                    raindex::TOKEN_LIST_RETRY_INTERVAL,
+                );
+            }
             let rate_limiter =
-                fairings::RateLimiter::new(cfg.rate_limit_global_rpm, cfg.rate_limit_per_key_rpm);
+                fairings::RateLimiter::new(cfg.rate_limit_global_rpm, cfg.rate_limit_per_key_rpm)
+                    .with_max_in_flight_per_key(cfg.rate_limit_max_in_flight_per_key);
 
             if !std::path::Path::new(&cfg.docs_dir).is_dir() {
                 tracing::error!(docs_dir = %cfg.docs_dir, "docs_dir is not a valid directory");
@@ -398,14 +503,159 @@ async fn main() {
             }
             tracing::info!(docs_dir = %cfg.docs_dir, "serving documentation at /docs");
 
-            let app_state =
-                app_state::ApplicationState::new(registry_artifact_store, response_caches);
+            let denylist = match denylist::AddressDenylist::parse(&cfg.denied_addresses) {
+                Ok(denylist) => denylist,
+                Err(e) => {
+                    tracing::error!(error = %e, "invalid denied_addresses config");
+                    drop(log_guard);
+                    std::process::exit(1);
+                }
+            };
+            tracing::info!(
+                denied_addresses = cfg.denied_addresses.len(),
+                "address denylist loaded"
+            );
+
+            let allowed_owners = match owner_allowlist::DeployOwnerAllowlist::parse(&cfg.allowed_owners) {
+                Ok(allowed_owners) => allowed_owners,
+                Err(e) => {
+                    tracing::error!(error = %e, "invalid allowed_owners config");
+                    drop(log_guard);
+                    std::process::exit(1);
+                }
+            };
+            tracing::info!(
+                allowed_owners = allowed_owners.len(),
+                "deploy owner allowlist loaded"
+            );
+
+            if let Err(e) = cfg.default_page_sizes.validate() {
+                tracing::error!(error = %e, "invalid default_page_sizes config");
+                drop(log_guard);
+                std::process::exit(1);
+            }
+
+            if let Err(e) = routes::swap::parse_min_liquidity(&cfg.min_quote_liquidity) {
+                tracing::error!(error = %e, "invalid min_quote_liquidity config");
+                drop(log_guard);
+                std::process::exit(1);
+            }
+
+            if cfg.max_quote_orders == 0 {
+                tracing::error!("max_quote_orders must be greater than zero");
+                drop(log_guard);
+                std::process::exit(1);
+            }
+
+            if cfg.batch_concurrency == 0 {
+                tracing::error!("batch_concurrency must be greater than zero");
+                drop(log_guard);
+                std::process::exit(1);
+            }
+
+            if cfg.quote_chain_concurrency == 0 {
+                tracing::error!("quote_chain_concurrency must be greater than zero");
+                drop(log_guard);
+                std::process::exit(1);
+            }
+
+            if cfg.max_cancel_vaults == 0 {
+                tracing::error!("max_cancel_vaults must be greater than zero");
+                drop(log_guard);
+                std::process::exit(1);
+            }
+
+            if cfg.max_trade_window_days == 0 {
+                tracing::error!("max_trade_window_days must be greater than zero");
+                drop(log_guard);
+                std::process::exit(1);
+            }
+
+            if cfg.subgraph_page_size == 0 {
+                tracing::error!("subgraph_page_size must be greater than zero");
+                drop(log_guard);
+                std::process::exit(1);
+            }
+
+            if let Some(threshold) = cfg.health_error_rate_threshold {
+                if !(0.0..=1.0).contains(&threshold) {
+                    tracing::error!(
+                        threshold,
+                        "health_error_rate_threshold must be between 0 and 1"
+                    );
+                    drop(log_guard);
+                    std::process::exit(1);
+                }
+            }
+
+            let response_cache = fairings::ResponseCache::new(
+                cfg.response_cache_max_entries,
+                std::time::Duration::from_secs(cfg.get_response_cache_ttl_seconds),
+            );
+
+            let app_state = app_state::ApplicationState::new(
+                registry_artifact_store,
+                response_caches,
+                log_filter,
+                cfg.simulate_calldata,
+                cfg.min_quote_liquidity.clone(),
+                cfg.max_quote_orders,
+                denylist,
+                error::IndexingRetryHint {
+                    base_ms: cfg.not_yet_indexed_retry_base_ms,
+                    max_ms: cfg.not_yet_indexed_retry_max_ms,
+                },
+                cfg.default_page_sizes,
+                allowed_owners,
+                cfg.strict_query_params,
+                cfg.health_requires_auth,
+                cfg.batch_concurrency,
+                cfg.quote_chain_concurrency,
+                cfg.max_cancel_vaults,
+                cfg.root_redirects_to_swagger,
+                cfg.strict_strategies,
+                cfg.max_trade_window_days * 24 * 60 * 60,
+                cfg.subgraph_page_size,
+                cfg.health_error_rate_threshold,
+                cfg.not_found_max_lag_blocks,
+            );
+
+            let read_pool = match &cfg.database_read_url {
+                Some(url) => {
+                    let max_connections = cfg
+                        .database_read_max_connections
+                        .unwrap_or(cfg.database_max_connections);
+                    match db::init_read(url, max_connections).await {
+                        Ok(p) => Some(p),
+                        Err(e) => {
+                            tracing::error!(error = %e, "failed to initialize database read pool");
+                            drop(log_guard);
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                None => None,
+            };
+
+            if let Some(days) = cfg.log_retention_days {
+                if days == 0 {
+                    tracing::error!("log_retention_days must be greater than zero");
+                    drop(log_guard);
+                    std::process::exit(1);
+                }
+                db::retention::spawn_retention_task(
+                    pool.clone(),
+                    std::time::Duration::from_secs(days * 24 * 60 * 60),
+                );
+                tracing::info!(log_retention_days = days, "log retention enabled");
+            }
 
             let rocket = match rocket(
-                pool,
+                db::Db::new(pool, read_pool),
                 rate_limiter,
                 shared_raindex,
                 app_state,
+                response_cache,
                 cfg.docs_dir,
                 cfg.usage_log_max_concurrency,
             ) {
@@ -437,6 +687,7 @@ async fn main() {
 
 #[cfg(test)]
 mod tests {
+    use super::StartupRegistryError;
     use crate::test_helpers::{basic_auth_header, client, mock_raindex_registry_url, seed_api_key};
     use rocket::http::{Header, Status};
     use utoipa::OpenApi;
@@ -451,6 +702,17 @@ mod tests {
         assert_eq!(body["status"], "ok");
     }
 
+    #[rocket::async_test]
+    async fn test_responses_carry_api_version_header() {
+        let client = client().await;
+        let response = client.get("/health").dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(
+            response.headers().get_one("X-API-Version"),
+            Some(super::ApiDoc::openapi().info.version.as_str())
+        );
+    }
+
     #[test]
     fn test_openapi_includes_token_proofs_schema() {
         let openapi = serde_json::to_value(super::ApiDoc::openapi()).expect("serialize openapi");
@@ -504,6 +766,23 @@ mod tests {
             .any(|parameter| parameter["name"] == "activity_limit"));
     }
 
+    #[test]
+    fn test_openapi_documents_timestamps_as_unix_or_iso() {
+        let openapi = serde_json::to_value(super::ApiDoc::openapi()).expect("serialize openapi");
+        let schemas = &openapi["components"]["schemas"];
+
+        assert_eq!(
+            schemas["TradesByTxResponse"]["properties"]["timestamp"]["$ref"],
+            "#/components/schemas/Timestamp"
+        );
+        let variants = schemas["Timestamp"]["oneOf"]
+            .as_array()
+            .expect("Timestamp is a oneOf");
+        assert_eq!(variants.len(), 2);
+        assert!(variants.iter().any(|variant| variant["type"] == "integer"));
+        assert!(variants.iter().any(|variant| variant["type"] == "string"));
+    }
+
     fn test_config(
         registry_url: String,
         private_registry_path: std::path::PathBuf,
@@ -514,16 +793,45 @@ mod tests {
             log_dir: "./logs".to_string(),
             database_url: "sqlite::memory:".to_string(),
             database_max_connections: 5,
+            database_read_url: None,
+            database_read_max_connections: None,
             usage_log_max_concurrency: 2,
             response_cache_max_entries: 0,
             response_cache_ttl_seconds: 0,
+            lookup_cache_ttl_seconds: 0,
+            get_response_cache_ttl_seconds: 0,
+            simulate_calldata: false,
+            min_quote_liquidity: "0".to_string(),
+            max_quote_orders: 100,
             registry_url,
+            fallback_registry_urls: Vec::new(),
+            registry_headers: Default::default(),
             private_registry_path: private_registry_path.to_string_lossy().into_owned(),
             allow_registry_fallback,
+            startup_self_test: false,
+            denied_addresses: Vec::new(),
+            allowed_owners: Vec::new(),
             rate_limit_global_rpm: 600,
             rate_limit_per_key_rpm: 60,
+            rate_limit_max_in_flight_per_key: 8,
+            batch_concurrency: 8,
+            quote_chain_concurrency: 4,
+            max_cancel_vaults: 64,
+            max_trade_window_days: 365,
+            subgraph_page_size: 100,
+            not_yet_indexed_retry_base_ms: 1000,
+            not_yet_indexed_retry_max_ms: 30000,
             docs_dir: "./docs/book".to_string(),
             local_db_path: local_db_path.to_string_lossy().into_owned(),
+            default_page_sizes: crate::config::PageSizeDefaults::default(),
+            strict_query_params: false,
+            log_retention_days: None,
+            health_requires_auth: false,
+            root_redirects_to_swagger: false,
+            strict_strategies: false,
+            health_error_rate_threshold: None,
+            not_found_max_lag_blocks: None,
+            token_list_fail_fast: false,
         }
     }
 
@@ -573,6 +881,109 @@ mod tests {
         assert!(provider.is_ok());
     }
 
+    #[rocket::async_test]
+    async fn test_load_startup_raindex_uses_fallback_registry_url() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let private_registry_path = dir.path().join("private-registry.data");
+        let local_db_path = dir.path().join("raindex.db");
+        let fallback_registry_url = mock_raindex_registry_url().await;
+        let mut cfg = test_config(
+            "http://127.0.0.1:1/registry.txt".to_string(),
+            private_registry_path.clone(),
+            local_db_path.clone(),
+            false,
+        );
+        cfg.fallback_registry_urls = vec![fallback_registry_url.clone()];
+        let pool = crate::db::init("sqlite::memory:", 5)
+            .await
+            .expect("database init");
+        let store = crate::registry_artifact::RegistryArtifactStore::new(private_registry_path);
+
+        let provider = super::load_startup_raindex(&cfg, &pool, &store, local_db_path)
+            .await
+            .expect("fallback registry loads");
+
+        assert_eq!(provider.registry(), fallback_registry_url);
+        assert_eq!(provider.registry_index(), 1);
+    }
+
+    async fn registry_with_unreachable_token_list() -> String {
+        let settings = r#"version: 6
+networks:
+  base:
+    rpcs:
+      - https://mainnet.base.org
+    chain-id: 8453
+    currency: ETH
+subgraphs:
+  base: https://api.goldsky.com/api/public/project_clv14x04y9kzi01saerx7bxpg/subgraphs/ob4-base/0.9/gn
+raindexes:
+  base:
+    address: 0xd2938e7c9fe3597f78832ce780feb61945c377d7
+    network: base
+    subgraph: base
+    deployment-block: 0
+using-tokens-from:
+  - http://127.0.0.1:1/tokens.json
+tokens:
+  token1:
+    address: 0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913
+    network: base
+"#;
+        crate::test_helpers::mock_raindex_registry_url_with_settings(settings).await
+    }
+
+    #[rocket::async_test]
+    async fn test_load_startup_raindex_fails_fast_on_unreachable_token_list() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let private_registry_path = dir.path().join("private-registry.data");
+        let local_db_path = dir.path().join("raindex.db");
+        let mut cfg = test_config(
+            registry_with_unreachable_token_list().await,
+            private_registry_path.clone(),
+            local_db_path.clone(),
+            false,
+        );
+        cfg.token_list_fail_fast = true;
+        let pool = crate::db::init("sqlite::memory:", 5)
+            .await
+            .expect("database init");
+        let store = crate::registry_artifact::RegistryArtifactStore::new(private_registry_path);
+
+        let result = super::load_startup_raindex(&cfg, &pool, &store, local_db_path).await;
+
+        assert!(matches!(
+            result,
+            Err(StartupRegistryError::ConfiguredRegistryLoad(_))
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_load_startup_raindex_defers_unreachable_token_list() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let private_registry_path = dir.path().join("private-registry.data");
+        let local_db_path = dir.path().join("raindex.db");
+        let registry_url = registry_with_unreachable_token_list().await;
+        let cfg = test_config(
+            registry_url.clone(),
+            private_registry_path.clone(),
+            local_db_path.clone(),
+            false,
+        );
+        let pool = crate::db::init("sqlite::memory:", 5)
+            .await
+            .expect("database init");
+        let store = crate::registry_artifact::RegistryArtifactStore::new(private_registry_path);
+
+        let provider = super::load_startup_raindex(&cfg, &pool, &store, local_db_path)
+            .await
+            .expect("starts without token lists");
+
+        assert!(provider.token_lists_deferred());
+        assert_eq!(provider.registry(), registry_url);
+        assert_eq!(provider.orderbook_sources().len(), 1);
+    }
+
     #[rocket::async_test]
     async fn test_load_startup_raindex_errors_when_fallback_disabled() {
         let dir = tempfile::tempdir().expect("temp dir");
@@ -0,0 +1,80 @@
+use crate::error::ApiError;
+use alloy::primitives::Address;
+use std::collections::BTreeSet;
+
+/// Owners allowed to deploy orders. Empty means anyone may deploy.
+#[derive(Debug, Default)]
+pub(crate) struct DeployOwnerAllowlist(BTreeSet<Address>);
+
+impl DeployOwnerAllowlist {
+    pub(crate) fn new(owners: impl IntoIterator<Item = Address>) -> Self {
+        Self(owners.into_iter().collect())
+    }
+
+    pub(crate) fn parse(entries: &[String]) -> Result<Self, String> {
+        let owners = entries
+            .iter()
+            .map(|entry| {
+                entry
+                    .trim()
+                    .parse::<Address>()
+                    .map_err(|e| format!("invalid allowed owner {entry:?}: {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(owners))
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Rejects the deployment unless the list is empty or `owner` is on it.
+    pub(crate) fn check(&self, owner: Option<Address>) -> Result<(), ApiError> {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        match owner {
+            Some(owner) if self.0.contains(&owner) => Ok(()),
+            Some(owner) => {
+                tracing::warn!(owner = %owner, "deployment owner is not allowed");
+                Err(ApiError::Forbidden(
+                    "owner is not allowed to deploy orders".into(),
+                ))
+            }
+            None => {
+                tracing::warn!("deployment without owner rejected by owner allowlist");
+                Err(ApiError::Forbidden(
+                    "owner is required to deploy orders".into(),
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    const ALLOWED: Address = address!("2222222222222222222222222222222222222222");
+    const OTHER: Address = address!("1111111111111111111111111111111111111111");
+
+    #[test]
+    fn test_restricts_only_when_configured() {
+        let unrestricted = DeployOwnerAllowlist::default();
+        assert!(unrestricted.check(Some(OTHER)).is_ok());
+        assert!(unrestricted.check(None).is_ok());
+
+        let allowlist = DeployOwnerAllowlist::parse(&[format!("{ALLOWED:#x}")]).unwrap();
+        assert!(allowlist.check(Some(ALLOWED)).is_ok());
+        assert!(matches!(
+            allowlist.check(Some(OTHER)),
+            Err(ApiError::Forbidden(msg)) if msg == "owner is not allowed to deploy orders"
+        ));
+        assert!(matches!(
+            allowlist.check(None),
+            Err(ApiError::Forbidden(msg)) if msg == "owner is required to deploy orders"
+        ));
+        assert!(DeployOwnerAllowlist::parse(&["not-an-address".to_string()]).is_err());
+    }
+}
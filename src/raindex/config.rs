@@ -1,17 +1,34 @@
+use super::registry_fetch::{
+    inline_authenticated_registry, registry_without_token_lists, RegistryHeaders,
+};
+use super::SharedRaindexProvider;
 use crate::error::ApiError;
+use crate::telemetry::{self, PanicDetails};
+use alloy::primitives::Address;
 use rain_orderbook_app_settings::yaml::{
     raindex::{RaindexYaml, RaindexYamlValidation},
     YamlParsable,
 };
 use rain_orderbook_common::raindex_client::RaindexClient;
 use rain_orderbook_common::registry::DotrainRegistry;
+use std::any::Any;
+use std::panic::AssertUnwindSafe;
 use std::path::PathBuf;
+use std::time::Duration;
+use url::Url;
+
+/// How often a provider started without its token lists retries them.
+pub(crate) const TOKEN_LIST_RETRY_INTERVAL: Duration = Duration::from_secs(60);
 
 #[derive(Debug)]
 pub(crate) struct RaindexProvider {
     client: RaindexClient,
     raindex_yaml: RaindexYaml,
     db_path: Option<PathBuf>,
+    registry: String,
+    registry_index: usize,
+    registry_headers: RegistryHeaders,
+    token_lists_deferred: bool,
 }
 
 impl RaindexProvider {
@@ -19,25 +36,29 @@ impl RaindexProvider {
         registry_url: &str,
         db_path: Option<PathBuf>,
     ) -> Result<Self, RaindexProviderError> {
+        Self::load_with_headers(registry_url, db_path, RegistryHeaders::default()).await
+    }
+
+    /// Like [`RaindexProvider::load`], but fetches the registry and its
+    /// same-origin files with `registry_headers`.
+    pub(crate) async fn load_with_headers(
+        registry_url: &str,
+        db_path: Option<PathBuf>,
+        registry_headers: RegistryHeaders,
+    ) -> Result<Self, RaindexProviderError> {
+        let source = inline_authenticated_registry(registry_url, &registry_headers).await?;
         let url = registry_url.to_string();
         let db = db_path.clone();
 
-        let (tx, rx) = tokio::sync::oneshot::channel();
+        let provider = run_on_worker(move || {
+            let runtime = build_worker_runtime(|| {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+            })?;
 
-        std::thread::spawn(move || {
-            let runtime = match tokio::runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()
-            {
-                Ok(rt) => rt,
-                Err(e) => {
-                    let _ = tx.send(Err(RaindexProviderError::RegistryLoad(e.to_string())));
-                    return;
-                }
-            };
-
-            let result = runtime.block_on(async {
-                let registry = DotrainRegistry::new(url)
+            runtime.block_on(async {
+                let registry = DotrainRegistry::new(source)
                     .await
                     .map_err(|e| RaindexProviderError::RegistryLoad(e.to_string()))?;
 
@@ -53,13 +74,124 @@ impl RaindexProvider {
                     client,
                     raindex_yaml,
                     db_path: db,
+                    registry: url,
+                    registry_index: 0,
+                    registry_headers,
+                    token_lists_deferred: false,
                 })
-            });
+            })
+        })
+        .await?;
+        provider.warn_networks_without_rpcs();
+        Ok(provider)
+    }
 
-            let _ = tx.send(result);
-        });
+    /// Logs each network an orderbook or deployer uses that lists no RPCs.
+    /// The registry still loads, since subgraph reads don't need them, but
+    /// on-chain features on those networks will answer with an error.
+    fn warn_networks_without_rpcs(&self) {
+        let mut networks: Vec<(String, u32)> = Vec::new();
+        if let Ok(raindexes) = self.raindex_yaml.get_raindexes() {
+            networks.extend(
+                raindexes
+                    .into_values()
+                    .filter(|raindex| raindex.network.rpcs.is_empty())
+                    .map(|raindex| (raindex.network.key.clone(), raindex.network.chain_id)),
+            );
+        }
+        if let Ok(deployers) = self.raindex_yaml.get_deployers() {
+            networks.extend(
+                deployers
+                    .into_values()
+                    .filter(|deployer| deployer.network.rpcs.is_empty())
+                    .map(|deployer| (deployer.network.key.clone(), deployer.network.chain_id)),
+            );
+        }
+        networks.sort();
+        networks.dedup();
+        for (network, chain_id) in networks {
+            tracing::warn!(
+                registry = %self.registry,
+                network = %network,
+                chain_id,
+                "registry network has no RPCs; on-chain features on it are unavailable"
+            );
+        }
+    }
 
-        rx.await.map_err(|_| RaindexProviderError::WorkerPanicked)?
+    /// Loads the first of `registries` that loads, trying them in order so
+    /// entries after the primary act as its fallbacks. Fails with the last
+    /// entry's error when none load.
+    pub(crate) async fn load_first_with_headers(
+        registries: &[String],
+        db_path: Option<PathBuf>,
+        registry_headers: RegistryHeaders,
+    ) -> Result<Self, RaindexProviderError> {
+        let mut last_error = RaindexProviderError::RegistryLoad("no registry configured".into());
+        for (index, registry) in registries.iter().enumerate() {
+            match Self::load_with_headers(registry, db_path.clone(), registry_headers.clone()).await
+            {
+                Ok(provider) => {
+                    if index > 0 {
+                        tracing::warn!(registry_index = index, "loaded fallback registry");
+                    }
+                    return Ok(provider.with_registry_index(index));
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        registry_index = index,
+                        error = %e.safe_summary(),
+                        "registry failed to load"
+                    );
+                    last_error = e;
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    /// Like [`RaindexProvider::load_first_with_headers`], but leaves the
+    /// remote token lists out of each registry's settings. Used to start
+    /// while a token list is unreachable; the provider keeps its real
+    /// registry so a reload picks the lists back up.
+    pub(crate) async fn load_first_without_token_lists(
+        registries: &[String],
+        db_path: Option<PathBuf>,
+        registry_headers: RegistryHeaders,
+    ) -> Result<Self, RaindexProviderError> {
+        let mut last_error = RaindexProviderError::RegistryLoad("no registry configured".into());
+        for (index, registry) in registries.iter().enumerate() {
+            let loaded = match registry_without_token_lists(registry, &registry_headers).await {
+                Ok(source) => {
+                    Self::load_with_headers(&source, db_path.clone(), registry_headers.clone())
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            match loaded {
+                Ok(mut provider) => {
+                    provider.registry = registry.clone();
+                    provider.token_lists_deferred = true;
+                    return Ok(provider.with_registry_index(index));
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        registry_index = index,
+                        error = %e.safe_summary(),
+                        "registry failed to load without token lists"
+                    );
+                    last_error = e;
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    /// Marks this provider as loaded from entry `index` of its registry
+    /// list, e.g. when reloading a fallback that is already active.
+    pub(crate) fn with_registry_index(mut self, index: usize) -> Self {
+        self.registry_index = index;
+        self
     }
 
     pub(crate) fn client(&self) -> &RaindexClient {
@@ -73,6 +205,262 @@ impl RaindexProvider {
     pub(crate) fn db_path(&self) -> Option<PathBuf> {
         self.db_path.clone()
     }
+
+    /// Registry URL or artifact this provider was loaded from.
+    pub(crate) fn registry(&self) -> &str {
+        &self.registry
+    }
+
+    /// Position of [`Self::registry`] in the list it was loaded from; `0`
+    /// is the primary and anything higher a fallback.
+    pub(crate) fn registry_index(&self) -> usize {
+        self.registry_index
+    }
+
+    /// Headers the registry was fetched with, reused when it is reloaded.
+    pub(crate) fn registry_headers(&self) -> &RegistryHeaders {
+        &self.registry_headers
+    }
+
+    /// Whether the registry was loaded without its remote token lists.
+    pub(crate) fn token_lists_deferred(&self) -> bool {
+        self.token_lists_deferred
+    }
+
+    /// Runs one cheap subgraph read so a misconfigured subgraph is caught at
+    /// startup instead of on the first request.
+    pub(crate) async fn self_test(&self) -> Result<(), RaindexProviderError> {
+        self.client
+            .get_orders(None, None, Some(1), Some(1))
+            .await
+            .map(|_| ())
+            .map_err(|e| RaindexProviderError::SelfTest(e.to_string()))
+    }
+
+    /// Addresses of every orderbook the registry configures on `chain_id`.
+    pub(crate) fn orderbooks_for_chain(&self, chain_id: u32) -> Vec<Address> {
+        match self.raindex_yaml.get_raindexes() {
+            Ok(raindexes) => raindexes
+                .into_values()
+                .filter(|raindex| raindex.network.chain_id == chain_id)
+                .map(|raindex| raindex.address)
+                .collect(),
+            Err(e) => {
+                tracing::warn!(error = %e, chain_id, "failed to read orderbooks from registry");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Every configured orderbook with the subgraph that indexes it and the
+    /// RPCs of its network.
+    pub(crate) fn orderbook_sources(&self) -> Vec<OrderbookSource> {
+        match self.raindex_yaml.get_raindexes() {
+            Ok(raindexes) => raindexes
+                .into_iter()
+                .map(|(key, raindex)| OrderbookSource {
+                    key,
+                    chain_id: raindex.network.chain_id,
+                    address: raindex.address,
+                    subgraph_url: raindex.subgraph.url.clone(),
+                    rpcs: raindex.network.rpcs.clone(),
+                })
+                .collect(),
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to read orderbooks from registry");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Fails when the registry configures no orderbooks, so trade lookups
+    /// report the misconfiguration instead of an empty result.
+    pub(crate) fn ensure_orderbooks_configured(&self) -> Result<(), ApiError> {
+        if self.orderbook_sources().is_empty() {
+            tracing::error!(registry = %self.registry, "registry configures no orderbooks");
+            return Err(ApiError::Internal("no orderbooks configured".into()));
+        }
+        Ok(())
+    }
+
+    /// Every configured deployer with the network it is deployed on.
+    pub(crate) fn deployer_sources(&self) -> Vec<DeployerSource> {
+        match self.raindex_yaml.get_deployers() {
+            Ok(deployers) => deployers
+                .into_iter()
+                .map(|(key, deployer)| DeployerSource {
+                    key,
+                    network: deployer.network.key.clone(),
+                    chain_id: deployer.network.chain_id,
+                    address: deployer.address,
+                    rpcs: deployer.network.rpcs.clone(),
+                })
+                .collect(),
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to read deployers from registry");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Like [`Self::rpcs_for_chain`], for features that can't work without
+    /// an RPC.
+    pub(crate) fn require_rpcs_for_chain(&self, chain_id: u32) -> Result<Vec<Url>, ApiError> {
+        require_rpcs(chain_id, self.rpcs_for_chain(chain_id))
+    }
+
+    /// RPC URLs of every configured network on `chain_id`.
+    pub(crate) fn rpcs_for_chain(&self, chain_id: u32) -> Vec<Url> {
+        match self.raindex_yaml.get_networks() {
+            Ok(networks) => networks
+                .into_values()
+                .filter(|network| network.chain_id == chain_id)
+                .flat_map(|network| network.rpcs)
+                .collect(),
+            Err(e) => {
+                tracing::warn!(error = %e, chain_id, "failed to read networks from registry");
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Passes `rpcs` through, or fails with a clear error naming `chain_id` when
+/// the registry configures none for it.
+pub(crate) fn require_rpcs(chain_id: u32, rpcs: Vec<Url>) -> Result<Vec<Url>, ApiError> {
+    if rpcs.is_empty() {
+        tracing::error!(chain_id, "no RPC configured for chain");
+        return Err(ApiError::Internal(format!(
+            "no RPC configured for chain {chain_id}"
+        )));
+    }
+    Ok(rpcs)
+}
+
+/// Reloads a provider started without its token lists every `interval`
+/// until the full registry loads. Stops without swapping if the provider
+/// was replaced in the meantime, e.g. by an admin registry upload.
+pub(crate) fn spawn_token_list_retry(shared_raindex: SharedRaindexProvider, interval: Duration) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            let (registry, registry_index, db_path, registry_headers) = {
+                let guard = shared_raindex.read().await;
+                if !guard.token_lists_deferred() {
+                    return;
+                }
+                (
+                    guard.registry().to_string(),
+                    guard.registry_index(),
+                    guard.db_path(),
+                    guard.registry_headers().clone(),
+                )
+            };
+
+            match RaindexProvider::load_with_headers(&registry, db_path, registry_headers).await {
+                Ok(provider) => {
+                    let mut guard = shared_raindex.write().await;
+                    if guard.token_lists_deferred() && guard.registry() == registry {
+                        *guard = provider.with_registry_index(registry_index);
+                        tracing::info!("token lists loaded");
+                    }
+                    return;
+                }
+                Err(e) => tracing::warn!(
+                    error = %e.safe_summary(),
+                    "token lists still unavailable; retrying"
+                ),
+            }
+        }
+    });
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct OrderbookSource {
+    pub key: String,
+    pub chain_id: u32,
+    pub address: Address,
+    pub subgraph_url: Url,
+    pub rpcs: Vec<Url>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct DeployerSource {
+    pub key: String,
+    pub network: String,
+    pub chain_id: u32,
+    pub address: Address,
+    pub rpcs: Vec<Url>,
+}
+
+/// Runs `work` on a dedicated OS thread, converting a panic into
+/// `WorkerPanicked` instead of losing it with the dropped result channel.
+async fn run_on_worker<T, F>(work: F) -> Result<T, RaindexProviderError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, RaindexProviderError> + Send + 'static,
+{
+    let span = tracing::Span::current();
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    std::thread::spawn(move || {
+        let _entered = span.enter();
+        let result = std::panic::catch_unwind(AssertUnwindSafe(work))
+            .unwrap_or_else(|payload| Err(worker_panicked(payload.as_ref())));
+        let _ = tx.send(result);
+    });
+
+    rx.await.unwrap_or_else(|_| {
+        Err(worker_panicked(
+            &"worker thread exited without sending a result",
+        ))
+    })
+}
+
+/// Builds the worker's runtime, retrying once: a failure here means the
+/// host is out of threads or file descriptors, not that the registry is bad.
+fn build_worker_runtime<F>(mut build: F) -> Result<tokio::runtime::Runtime, RaindexProviderError>
+where
+    F: FnMut() -> std::io::Result<tokio::runtime::Runtime>,
+{
+    match build() {
+        Ok(runtime) => Ok(runtime),
+        Err(e) => {
+            tracing::warn!(error = %e, "raindex worker runtime init failed, retrying");
+            build().map_err(|e| {
+                tracing::error!(error = %e, "raindex worker runtime init failed after retry");
+                RaindexProviderError::RuntimeInit(e.to_string())
+            })
+        }
+    }
+}
+
+fn worker_panicked(payload: &(dyn Any + Send)) -> RaindexProviderError {
+    let details = telemetry::take_recorded_panic().unwrap_or_else(|| PanicDetails {
+        message: telemetry::panic_payload_message(payload),
+        location: None,
+    });
+    let panic = WorkerPanic {
+        correlation_id: uuid::Uuid::new_v4().to_string(),
+        message: details.message,
+        location: details.location.map(|location| location.to_string()),
+    };
+
+    tracing::error!(
+        correlation_id = %panic.correlation_id,
+        panic.message = %panic.message,
+        panic.location = panic.location.as_deref().unwrap_or("unknown"),
+        "raindex worker thread panicked"
+    );
+
+    RaindexProviderError::WorkerPanicked(panic)
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct WorkerPanic {
+    pub correlation_id: String,
+    pub message: String,
+    pub location: Option<String>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -81,8 +469,12 @@ pub(crate) enum RaindexProviderError {
     RegistryLoad(String),
     #[error("failed to create raindex client: {0}")]
     ClientInit(String),
-    #[error("worker thread panicked")]
-    WorkerPanicked,
+    #[error("worker thread panicked (correlation id {})", .0.correlation_id)]
+    WorkerPanicked(WorkerPanic),
+    #[error("startup self-test failed: {0}")]
+    SelfTest(String),
+    #[error("failed to start worker runtime: {0}")]
+    RuntimeInit(String),
 }
 
 impl From<RaindexProviderError> for ApiError {
@@ -95,8 +487,15 @@ impl From<RaindexProviderError> for ApiError {
             RaindexProviderError::ClientInit(_) => {
                 ApiError::Internal("failed to initialize orderbook client".into())
             }
-            RaindexProviderError::WorkerPanicked => {
-                ApiError::Internal("failed to initialize client runtime".into())
+            RaindexProviderError::WorkerPanicked(panic) => ApiError::Internal(format!(
+                "failed to initialize client runtime (correlation id {})",
+                panic.correlation_id
+            )),
+            RaindexProviderError::SelfTest(_) => {
+                ApiError::Internal("failed to query orderbook subgraph".into())
+            }
+            RaindexProviderError::RuntimeInit(_) => {
+                ApiError::Internal("orderbook worker runtime unavailable".into())
             }
         }
     }
@@ -107,7 +506,9 @@ impl RaindexProviderError {
         match self {
             RaindexProviderError::RegistryLoad(_) => "registry load failed",
             RaindexProviderError::ClientInit(_) => "raindex client initialization failed",
-            RaindexProviderError::WorkerPanicked => "worker thread panicked",
+            RaindexProviderError::WorkerPanicked(_) => "worker thread panicked",
+            RaindexProviderError::SelfTest(_) => "startup self-test failed",
+            RaindexProviderError::RuntimeInit(_) => "worker runtime initialization failed",
         }
     }
 }
@@ -115,6 +516,18 @@ impl RaindexProviderError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    fn test_require_rpcs_names_chain_without_rpcs() {
+        let err = require_rpcs(8453, Vec::new()).unwrap_err();
+        assert!(
+            matches!(err, ApiError::Internal(msg) if msg == "no RPC configured for chain 8453")
+        );
+
+        let rpcs = vec![Url::parse("https://mainnet.base.org").unwrap()];
+        assert_eq!(require_rpcs(8453, rpcs.clone()).unwrap(), rpcs);
+    }
 
     #[rocket::async_test]
     async fn test_load_fails_with_unreachable_url() {
@@ -156,6 +569,215 @@ mod tests {
         crate::test_helpers::mock_raindex_config().await;
     }
 
+    #[rocket::async_test]
+    async fn test_load_first_falls_back_when_primary_is_unreachable() {
+        let fallback = crate::test_helpers::mock_raindex_registry_url().await;
+        let registries = vec![
+            "http://127.0.0.1:1/registry.txt".to_string(),
+            fallback.clone(),
+        ];
+
+        let provider =
+            RaindexProvider::load_first_with_headers(&registries, None, RegistryHeaders::default())
+                .await
+                .expect("fallback registry loads");
+
+        assert_eq!(provider.registry(), fallback);
+        assert_eq!(provider.registry_index(), 1);
+    }
+
+    #[rocket::async_test]
+    async fn test_load_first_prefers_primary() {
+        let primary = crate::test_helpers::mock_raindex_registry_url().await;
+        let registries = vec![
+            primary.clone(),
+            "http://127.0.0.1:1/registry.txt".to_string(),
+        ];
+
+        let provider =
+            RaindexProvider::load_first_with_headers(&registries, None, RegistryHeaders::default())
+                .await
+                .expect("primary registry loads");
+
+        assert_eq!(provider.registry(), primary);
+        assert_eq!(provider.registry_index(), 0);
+    }
+
+    #[rocket::async_test]
+    async fn test_load_first_fails_when_no_registry_loads() {
+        let registries = vec![
+            "http://127.0.0.1:1/registry.txt".to_string(),
+            "http://127.0.0.1:1/fallback.txt".to_string(),
+        ];
+
+        let result =
+            RaindexProvider::load_first_with_headers(&registries, None, RegistryHeaders::default())
+                .await;
+
+        assert!(matches!(result, Err(RaindexProviderError::RegistryLoad(_))));
+    }
+
+    #[rocket::async_test]
+    async fn test_self_test_fails_with_unreachable_subgraph() {
+        let settings = r#"version: 6
+networks:
+  base:
+    rpcs:
+      - https://mainnet.base.org
+    chain-id: 8453
+    currency: ETH
+subgraphs:
+  base: http://127.0.0.1:1/subgraph
+raindexes:
+  base:
+    address: 0xd2938e7c9fe3597f78832ce780feb61945c377d7
+    network: base
+    subgraph: base
+    deployment-block: 0
+deployers:
+  base:
+    address: 0xC1A14cE2fd58A3A2f99deCb8eDd866204eE07f8D
+    network: base
+"#;
+        let registry_url =
+            crate::test_helpers::mock_raindex_registry_url_with_settings(settings).await;
+        let provider = RaindexProvider::load(&registry_url, None)
+            .await
+            .expect("registry loads without querying the subgraph");
+
+        let result = provider.self_test().await;
+
+        assert!(matches!(result, Err(RaindexProviderError::SelfTest(_))));
+    }
+
+    #[rocket::async_test]
+    async fn test_empty_orderbooks_are_reported_as_misconfiguration() {
+        let settings = r#"version: 6
+networks:
+  base:
+    rpcs:
+      - https://mainnet.base.org
+    chain-id: 8453
+    currency: ETH
+subgraphs:
+  base: https://example.com/sg
+raindexes: {}
+deployers:
+  base:
+    address: 0xC1A14cE2fd58A3A2f99deCb8eDd866204eE07f8D
+    network: base
+"#;
+        let registry_url =
+            crate::test_helpers::mock_raindex_registry_url_with_settings(settings).await;
+        let provider = RaindexProvider::load(&registry_url, None)
+            .await
+            .expect("registry without orderbooks still loads");
+
+        let result = provider.ensure_orderbooks_configured();
+
+        assert!(
+            matches!(result, Err(ApiError::Internal(msg)) if msg == "no orderbooks configured")
+        );
+        crate::test_helpers::mock_raindex_config()
+            .await
+            .ensure_orderbooks_configured()
+            .expect("mock registry configures an orderbook");
+    }
+
+    fn install_recording_panic_hook() {
+        static INSTALL: std::sync::Once = std::sync::Once::new();
+        INSTALL.call_once(|| {
+            let previous = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |info| {
+                telemetry::record_panic(info);
+                previous(info);
+            }));
+        });
+    }
+
+    #[rocket::async_test]
+    #[traced_test]
+    async fn test_worker_panic_is_logged_with_location_and_correlation_id() {
+        install_recording_panic_hook();
+
+        let err = run_on_worker(|| -> Result<(), RaindexProviderError> {
+            panic!("registry worker exploded")
+        })
+        .await
+        .expect_err("panicking worker should fail");
+
+        let RaindexProviderError::WorkerPanicked(panic) = &err else {
+            panic!("expected WorkerPanicked, got {err:?}");
+        };
+        assert_eq!(panic.message, "registry worker exploded");
+        let location = panic.location.as_deref().expect("panic location");
+        assert!(location.starts_with(file!()));
+        assert!(logs_contain("raindex worker thread panicked"));
+        assert!(logs_contain("registry worker exploded"));
+        assert!(logs_contain(&panic.correlation_id));
+
+        let correlation_id = panic.correlation_id.clone();
+        let api_err: ApiError = err.into();
+        assert!(matches!(
+            api_err,
+            ApiError::Internal(msg)
+                if msg == format!("failed to initialize client runtime (correlation id {correlation_id})")
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_worker_result_is_returned_when_no_panic() {
+        let value = run_on_worker(|| Ok(7_u8)).await.expect("worker result");
+        assert_eq!(value, 7);
+    }
+
+    fn runtime() -> std::io::Result<tokio::runtime::Runtime> {
+        tokio::runtime::Builder::new_current_thread().build()
+    }
+
+    fn exhausted() -> std::io::Error {
+        std::io::Error::other("Resource temporarily unavailable")
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_runtime_init_retries_once_after_transient_failure() {
+        let mut attempts = 0;
+        let result = build_worker_runtime(|| {
+            attempts += 1;
+            if attempts == 1 {
+                Err(exhausted())
+            } else {
+                runtime()
+            }
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(attempts, 2);
+        assert!(logs_contain("raindex worker runtime init failed, retrying"));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_runtime_init_failure_is_distinct_from_registry_errors() {
+        let mut attempts = 0;
+        let err = build_worker_runtime(|| {
+            attempts += 1;
+            Err(exhausted())
+        })
+        .expect_err("runtime init should fail");
+
+        assert_eq!(attempts, 2);
+        assert!(matches!(err, RaindexProviderError::RuntimeInit(_)));
+        assert!(logs_contain(
+            "raindex worker runtime init failed after retry"
+        ));
+        let api_err: ApiError = err.into();
+        assert!(
+            matches!(api_err, ApiError::Internal(msg) if msg == "orderbook worker runtime unavailable")
+        );
+    }
+
     #[test]
     fn test_error_maps_to_api_error() {
         let err = RaindexProviderError::RegistryLoad("test".into());
@@ -169,5 +791,11 @@ mod tests {
         assert!(
             matches!(api_err, ApiError::Internal(msg) if msg == "failed to initialize orderbook client")
         );
+
+        let err = RaindexProviderError::SelfTest("test".into());
+        let api_err: ApiError = err.into();
+        assert!(
+            matches!(api_err, ApiError::Internal(msg) if msg == "failed to query orderbook subgraph")
+        );
     }
 }
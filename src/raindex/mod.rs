@@ -1,4 +1,11 @@
 pub(crate) mod config;
+pub(crate) mod registry_fetch;
+pub(crate) mod tokens;
 
-pub(crate) use config::{RaindexProvider, RaindexProviderError};
-pub(crate) type SharedRaindexProvider = tokio::sync::RwLock<RaindexProvider>;
+pub(crate) use config::{
+    require_rpcs, spawn_token_list_retry, DeployerSource, OrderbookSource, RaindexProvider,
+    RaindexProviderError, TOKEN_LIST_RETRY_INTERVAL,
+};
+pub(crate) use registry_fetch::RegistryHeaders;
+pub(crate) use tokens::dedupe_tokens;
+pub(crate) type SharedRaindexProvider = std::sync::Arc<tokio::sync::RwLock<RaindexProvider>>;
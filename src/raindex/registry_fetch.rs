@@ -0,0 +1,418 @@
+use super::RaindexProviderError;
+use base64::Engine;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+use url::Url;
+
+/// Upper bound on each registry file request, so an unresponsive registry
+/// host fails the load instead of hanging startup or a reload.
+const REGISTRY_FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Extra headers, e.g. a bearer token, sent when fetching the registry and
+/// the files it references from the registry's own origin. Values are never
+/// logged.
+#[derive(Clone, Default)]
+pub(crate) struct RegistryHeaders(HeaderMap);
+
+impl RegistryHeaders {
+    pub(crate) fn from_config(headers: &BTreeMap<String, String>) -> Result<Self, String> {
+        let mut map = HeaderMap::with_capacity(headers.len());
+        for (name, value) in headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| format!("invalid registry header name: {name}"))?;
+            let mut value = HeaderValue::from_str(value)
+                .map_err(|_| format!("invalid value for registry header {name}"))?;
+            value.set_sensitive(true);
+            map.insert(name, value);
+        }
+        Ok(Self(map))
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for RegistryHeaders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.0.keys().map(|name| format!("{name}: ****")))
+            .finish()
+    }
+}
+
+/// Fetches `registry_url` with `headers` and inlines every same-origin URL it
+/// references (settings, orders and the token lists those settings use) as a
+/// `data:` URI, so the registry loader never has to reach the authenticated
+/// gateway itself. URLs on other origins are left alone and never see the
+/// headers.
+pub(crate) async fn inline_authenticated_registry(
+    registry_url: &str,
+    headers: &RegistryHeaders,
+) -> Result<String, RaindexProviderError> {
+    let Ok(url) = Url::parse(registry_url) else {
+        return Ok(registry_url.to_string());
+    };
+    if headers.is_empty() || !matches!(url.scheme(), "http" | "https") {
+        return Ok(registry_url.to_string());
+    }
+
+    let client = reqwest::Client::new();
+    let registry = fetch(&client, &url, headers).await?;
+    let mut inlined = registry.clone();
+    for (raw, referenced) in same_origin_urls(&registry, &url) {
+        let body = fetch(&client, &referenced, headers).await?;
+        let mut nested = body.clone();
+        for (nested_raw, nested_url) in same_origin_urls(&body, &url) {
+            let nested_body = fetch(&client, &nested_url, headers).await?;
+            nested = nested.replace(&nested_raw, &data_uri(&nested_url, &nested_body));
+        }
+        inlined = inlined.replace(&raw, &data_uri(&referenced, &nested));
+    }
+
+    Ok(format!(
+        "data:text/plain;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(inlined)
+    ))
+}
+
+/// Top-level settings key listing the remote token lists to merge in.
+const TOKEN_LISTS_KEY: &str = "using-tokens-from:";
+
+/// Builds a registry source equivalent to `registry_url` with the remote
+/// token lists left out of its settings, so the registry can load while a
+/// token list host is down. Tokens defined in the settings themselves are
+/// kept. Same-origin files are fetched with `headers`, as in
+/// [`inline_authenticated_registry`].
+pub(crate) async fn registry_without_token_lists(
+    registry_url: &str,
+    headers: &RegistryHeaders,
+) -> Result<String, RaindexProviderError> {
+    let client = reqwest::Client::new();
+    let origin = Url::parse(registry_url).ok();
+    let registry = read_source(&client, registry_url, origin.as_ref(), headers).await?;
+
+    let mut lines = registry.lines();
+    let settings_source = lines
+        .next()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .ok_or_else(|| RaindexProviderError::RegistryLoad("registry is empty".into()))?;
+    let settings = read_source(&client, settings_source, origin.as_ref(), headers).await?;
+    let settings = strip_token_lists(&settings);
+
+    let mut orders = lines.collect::<Vec<_>>().join("\n");
+    if let Some(origin) = origin.as_ref().filter(|_| !headers.is_empty()) {
+        for (raw, referenced) in same_origin_urls(&orders, origin) {
+            let body = fetch(&client, &referenced, headers).await?;
+            orders = orders.replace(&raw, &data_uri(&referenced, &body));
+        }
+    }
+
+    let inlined = format!(
+        "data:application/yaml;base64,{}\n{orders}",
+        base64::engine::general_purpose::STANDARD.encode(settings)
+    );
+    Ok(format!(
+        "data:text/plain;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(inlined)
+    ))
+}
+
+/// Reads a registry file from a `data:` URI or over HTTP. Headers are only
+/// sent to `origin`, the registry's own host.
+async fn read_source(
+    client: &reqwest::Client,
+    source: &str,
+    origin: Option<&Url>,
+    headers: &RegistryHeaders,
+) -> Result<String, RaindexProviderError> {
+    if let Some(data) = source.strip_prefix("data:") {
+        let (meta, payload) = data.split_once(',').ok_or_else(|| {
+            RaindexProviderError::RegistryLoad("malformed data URI in registry".into())
+        })?;
+        if !meta.ends_with(";base64") {
+            return Ok(payload.to_string());
+        }
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(payload)
+            .map_err(|e| RaindexProviderError::RegistryLoad(format!("invalid data URI: {e}")))?;
+        return String::from_utf8(bytes)
+            .map_err(|e| RaindexProviderError::RegistryLoad(format!("invalid data URI: {e}")));
+    }
+
+    let url = Url::parse(source).map_err(|e| {
+        RaindexProviderError::RegistryLoad(format!("invalid registry file URL: {e}"))
+    })?;
+    let same_origin = origin.is_some_and(|origin| origin.origin() == url.origin());
+    let headers = if same_origin {
+        headers.clone()
+    } else {
+        RegistryHeaders::default()
+    };
+    fetch(client, &url, &headers).await
+}
+
+/// Drops the [`TOKEN_LISTS_KEY`] block, the key line plus the indented or
+/// `-` list lines under it, from settings YAML.
+fn strip_token_lists(settings: &str) -> String {
+    let mut kept = Vec::new();
+    let mut in_token_lists = false;
+    for line in settings.lines() {
+        if line.starts_with(TOKEN_LISTS_KEY) {
+            in_token_lists = true;
+            continue;
+        }
+        if in_token_lists
+            && (line.trim().is_empty() || line.starts_with(' ') || line.starts_with('-'))
+        {
+            continue;
+        }
+        in_token_lists = false;
+        kept.push(line);
+    }
+    let mut stripped = kept.join("\n");
+    stripped.push('\n');
+    stripped
+}
+
+async fn fetch(
+    client: &reqwest::Client,
+    url: &Url,
+    headers: &RegistryHeaders,
+) -> Result<String, RaindexProviderError> {
+    fetch_within(client, url, headers, REGISTRY_FETCH_TIMEOUT).await
+}
+
+async fn fetch_within(
+    client: &reqwest::Client,
+    url: &Url,
+    headers: &RegistryHeaders,
+    timeout: Duration,
+) -> Result<String, RaindexProviderError> {
+    let fetch_error = |e: reqwest::Error| {
+        if e.is_timeout() {
+            RaindexProviderError::RegistryLoad(format!(
+                "timed out fetching {} after {}ms",
+                url.path(),
+                timeout.as_millis()
+            ))
+        } else {
+            RaindexProviderError::RegistryLoad(format!("failed to fetch {}: {e}", url.path()))
+        }
+    };
+    let response = client
+        .get(url.clone())
+        .headers(headers.0.clone())
+        .timeout(timeout)
+        .send()
+        .await
+        .map_err(fetch_error)?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(RaindexProviderError::RegistryLoad(format!(
+            "failed to fetch {}: HTTP {status}",
+            url.path()
+        )));
+    }
+    response.text().await.map_err(|e| {
+        if e.is_timeout() {
+            return fetch_error(e);
+        }
+        RaindexProviderError::RegistryLoad(format!("failed to read {}: {e}", url.path()))
+    })
+}
+
+/// URLs in `text` served from the same origin as `origin`, longest first so
+/// replacing one never clobbers another it is a prefix of.
+fn same_origin_urls(text: &str, origin: &Url) -> Vec<(String, Url)> {
+    let mut urls: Vec<(String, Url)> = text
+        .split(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | ','))
+        .filter(|token| token.starts_with("http://") || token.starts_with("https://"))
+        .filter_map(|token| Some((token.to_string(), Url::parse(token).ok()?)))
+        .filter(|(_, url)| url.origin() == origin.origin())
+        .collect();
+    urls.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    urls.dedup_by(|(a, _), (b, _)| a == b);
+    urls
+}
+
+fn data_uri(url: &Url, body: &str) -> String {
+    let mime = match url.path().rsplit_once('.').map(|(_, ext)| ext) {
+        Some("yaml" | "yml") => "application/yaml",
+        Some("json") => "application/json",
+        _ => "text/plain",
+    };
+    format!(
+        "data:{mime};base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(body)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raindex::RaindexProvider;
+
+    const TOKEN: &str = "Bearer gateway-token";
+
+    /// Serves a registry, its settings and token list, answering 401 to any
+    /// request without the expected `Authorization` header.
+    async fn authenticated_registry() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock gateway");
+        let addr = listener.local_addr().expect("mock gateway address");
+        let settings = format!(
+            r#"version: 6
+networks:
+  base:
+    rpcs:
+      - https://mainnet.base.org
+    chain-id: 8453
+    currency: ETH
+subgraphs:
+  base: https://example.com/sg
+raindexes:
+  base:
+    address: 0xd2938e7c9fe3597f78832ce780feb61945c377d7
+    network: base
+    subgraph: base
+    deployment-block: 0
+deployers:
+  base:
+    address: 0xC1A14cE2fd58A3A2f99deCb8eDd866204eE07f8D
+    network: base
+using-tokens-from:
+  - http://{addr}/tokens.json
+"#
+        );
+        let tokens = serde_json::json!({
+            "name": "Gateway Token List",
+            "timestamp": "2026-03-20T00:00:00.000Z",
+            "version": { "major": 1, "minor": 0, "patch": 0 },
+            "tokens": [{
+                "chainId": 8453,
+                "address": "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913",
+                "decimals": 6,
+                "name": "USD Coin",
+                "symbol": "USDC"
+            }]
+        })
+        .to_string();
+        let registry = format!("http://{addr}/settings.yaml");
+
+        crate::test_helpers::serve_mock_http(listener, move |request| {
+            let request = request.to_lowercase();
+            if !request.contains(&format!("authorization: {}", TOKEN.to_lowercase())) {
+                return (401, String::new());
+            }
+            let body = if request.contains("/settings.yaml") {
+                &settings
+            } else if request.contains("/tokens.json") {
+                &tokens
+            } else {
+                &registry
+            };
+            (200, body.clone())
+        });
+
+        format!("http://{addr}/registry.txt")
+    }
+
+    fn gateway_headers() -> RegistryHeaders {
+        RegistryHeaders::from_config(&BTreeMap::from([(
+            "Authorization".to_string(),
+            TOKEN.to_string(),
+        )]))
+        .expect("valid headers")
+    }
+
+    #[rocket::async_test]
+    async fn test_authenticated_registry_loads_only_with_configured_headers() {
+        let registry_url = authenticated_registry().await;
+
+        let without_headers = RaindexProvider::load(&registry_url, None).await;
+        assert!(matches!(
+            without_headers,
+            Err(RaindexProviderError::RegistryLoad(_))
+        ));
+
+        let provider = RaindexProvider::load_with_headers(&registry_url, None, gateway_headers())
+            .await
+            .expect("load authenticated registry");
+        assert_eq!(provider.registry(), registry_url);
+        let tokens = provider.client().get_all_tokens().expect("tokens");
+        assert!(tokens
+            .values()
+            .any(|token| token.symbol.as_deref() == Some("USDC")));
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_gives_up_on_an_unresponsive_host() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind silent host");
+        let url = Url::parse(&format!(
+            "http://{}/registry.txt",
+            listener.local_addr().expect("silent host address")
+        ))
+        .expect("valid url");
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                held.push(socket);
+            }
+        });
+
+        let result = fetch_within(
+            &reqwest::Client::new(),
+            &url,
+            &RegistryHeaders::default(),
+            Duration::from_millis(100),
+        )
+        .await;
+
+        match result {
+            Err(RaindexProviderError::RegistryLoad(message)) => {
+                assert_eq!(message, "timed out fetching /registry.txt after 100ms")
+            }
+            other => panic!("expected registry load timeout, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_strip_token_lists_keeps_other_settings() {
+        let settings = "version: 6\nusing-tokens-from:\n  - http://lists.test/a.json\n- http://lists.test/b.json\n\ntokens:\n  usdc:\n    network: base\n";
+
+        assert_eq!(
+            strip_token_lists(settings),
+            "version: 6\ntokens:\n  usdc:\n    network: base\n"
+        );
+        assert_eq!(
+            strip_token_lists("version: 6\nusing-tokens-from: [http://lists.test/a.json]\n"),
+            "version: 6\n"
+        );
+    }
+
+    #[test]
+    fn test_debug_lists_header_names_only() {
+        let debug = format!("{:?}", gateway_headers());
+        assert_eq!(debug, r#"["authorization: ****"]"#);
+        assert!(!debug.contains("gateway-token"));
+    }
+
+    #[test]
+    fn test_invalid_header_is_rejected() {
+        let result = RegistryHeaders::from_config(&BTreeMap::from([(
+            "bad header".to_string(),
+            "x".to_string(),
+        )]));
+        assert_eq!(
+            result.unwrap_err(),
+            "invalid registry header name: bad header"
+        );
+    }
+}
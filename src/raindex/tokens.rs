@@ -0,0 +1,84 @@
+use alloy::primitives::Address;
+use rain_orderbook_app_settings::token::TokenCfg;
+use std::collections::HashMap;
+
+/// Collapses token list entries that share a chain and address. Addresses
+/// compare as bytes, so entries differing only in checksum casing collide
+/// too. Entries are taken in key order and the first one wins; each dropped
+/// entry is logged so the list can be fixed upstream.
+pub(crate) fn dedupe_tokens(tokens: impl IntoIterator<Item = (String, TokenCfg)>) -> Vec<TokenCfg> {
+    let mut entries: Vec<(String, TokenCfg)> = tokens.into_iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut kept: HashMap<(u32, Address), String> = HashMap::with_capacity(entries.len());
+    let mut unique = Vec::with_capacity(entries.len());
+    for (key, token) in entries {
+        match kept.get(&(token.network.chain_id, token.address)) {
+            Some(kept_key) => tracing::warn!(
+                chain_id = token.network.chain_id,
+                address = %token.address,
+                kept = %kept_key,
+                dropped = %key,
+                "duplicate token address in token list"
+            ),
+            None => {
+                kept.insert((token.network.chain_id, token.address), key);
+                unique.push(token);
+            }
+        }
+    }
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+    use rain_orderbook_app_settings::network::NetworkCfg;
+    use std::sync::Arc;
+    use tracing_test::traced_test;
+
+    const USDC: Address = address!("833589fcd6edb6e08f4c7c32d4f71b54bda02913");
+
+    fn token(key: &str, chain_id: u32, symbol: &str) -> (String, TokenCfg) {
+        let mut network = NetworkCfg::dummy();
+        network.chain_id = chain_id;
+        let token = TokenCfg {
+            document: rain_orderbook_app_settings::yaml::default_document(),
+            key: key.to_string(),
+            address: USDC,
+            network: Arc::new(network),
+            decimals: Some(6),
+            label: None,
+            symbol: Some(symbol.to_string()),
+            logo_uri: None,
+            extensions: None,
+        };
+        (key.to_string(), token)
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_duplicate_address_keeps_first_entry_and_warns() {
+        let tokens = vec![
+            token("usdc-legacy", 8453, "USDC.old"),
+            token("usdc", 8453, "USDC"),
+        ];
+
+        let unique = dedupe_tokens(tokens);
+
+        assert_eq!(unique.len(), 1);
+        assert_eq!(unique[0].symbol.as_deref(), Some("USDC"));
+        assert!(logs_contain("duplicate token address in token list"));
+        assert!(logs_contain("usdc-legacy"));
+    }
+
+    #[test]
+    fn test_same_address_on_other_chains_is_kept() {
+        let unique = dedupe_tokens(vec![
+            token("usdc-base", 8453, "USDC"),
+            token("usdc-eth", 1, "USDC"),
+        ]);
+        assert_eq!(unique.len(), 2);
+    }
+}
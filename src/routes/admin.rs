@@ -1,21 +1,30 @@
 use crate::app_state::ApplicationState;
 use crate::auth::AdminKey;
-use crate::db::{registry_history, DbPool};
+use crate::db::{registry_history, retention, Db, DbPool};
 use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
-use crate::raindex::{RaindexProvider, SharedRaindexProvider};
+use crate::fairings::{GlobalRateLimit, JsonContentType, RateLimiter, TracingSpan};
+use crate::raindex::{RaindexProvider, RegistryHeaders, SharedRaindexProvider};
 use crate::registry_artifact::artifact_sha256;
+use crate::telemetry::LogFilterError;
+use alloy::primitives::Address;
+use rain_orderbook_app_settings::token::TokenCfg;
 use rocket::http::Status;
 use rocket::serde::json::Json;
 use rocket::{Route, State};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use tracing::Instrument;
+use url::Url;
 use utoipa::ToSchema;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UploadRegistryArtifactRequest {
     pub registry_artifact: String,
     pub source_commit: String,
+    /// Artifacts tried in order when `registry_artifact` fails to load. The
+    /// first that loads is persisted and served.
+    #[serde(default)]
+    pub fallback_registry_artifacts: Vec<String>,
 }
 
 #[utoipa::path(
@@ -32,10 +41,12 @@ pub struct UploadRegistryArtifactRequest {
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
     )
 )]
+#[allow(clippy::too_many_arguments)]
 #[put("/registry", data = "<request>")]
 pub async fn put_registry(
     _global: GlobalRateLimit,
     admin: AdminKey,
+    _json: JsonContentType,
     shared_raindex: &State<SharedRaindexProvider>,
     app_state: &State<ApplicationState>,
     pool: &State<DbPool>,
@@ -54,12 +65,17 @@ pub async fn put_registry(
         validate_request(&req)?;
         let payload_sha256 = artifact_sha256(&req.registry_artifact);
 
-        let db_path = {
+        let (db_path, registry_headers) = {
             let guard = shared_raindex.read().await;
-            guard.db_path()
+            (guard.db_path(), guard.registry_headers().clone())
         };
 
-        let new_provider = match RaindexProvider::load(&req.registry_artifact, db_path).await {
+        let candidates: Vec<String> = std::iter::once(req.registry_artifact.clone())
+            .chain(req.fallback_registry_artifacts.iter().cloned())
+            .collect();
+        let loaded =
+            RaindexProvider::load_first_with_headers(&candidates, db_path, registry_headers).await;
+        let new_provider = match loaded {
             Ok(provider) => provider,
             Err(e) => {
                 let validation_error = e.safe_summary();
@@ -87,6 +103,10 @@ pub async fn put_registry(
             }
         };
 
+        let active_artifact = new_provider.registry().to_string();
+        let payload_sha256 = artifact_sha256(&active_artifact);
+        let registry_index = new_provider.registry_index();
+
         let artifact_store = &app_state.registry_artifact_store;
         let _update_guard = artifact_store.lock_update().await;
 
@@ -96,7 +116,7 @@ pub async fn put_registry(
         })?;
 
         artifact_store
-            .persist(&req.registry_artifact)
+            .persist(&active_artifact)
             .await
             .map_err(|e| {
                 tracing::error!(error = %e, "failed to persist private registry artifact");
@@ -134,6 +154,7 @@ pub async fn put_registry(
         tracing::info!(
             source_commit = %req.source_commit,
             payload_sha256 = %payload_sha256,
+            registry_index,
             admin_key_id = %admin.0.key_id,
             "registry artifact updated"
         );
@@ -144,20 +165,694 @@ pub async fn put_registry(
     .await
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ValidateRegistryRequest {
+    #[schema(
+        example = "https://raw.githubusercontent.com/ST0x-Technology/st0x.registry/main/registry.txt"
+    )]
+    pub registry_url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistryValidationCheck {
+    Url,
+    Load,
+    BaseOrderbook,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RegistryValidationError {
+    pub check: RegistryValidationCheck,
+    #[schema(example = "registry has no orderbook on chain 8453")]
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RegistryValidationSummary {
+    #[schema(example = 1)]
+    pub networks: usize,
+    #[schema(example = 1)]
+    pub orderbooks: usize,
+    #[schema(value_type = Vec<String>, example = json!(["0xd2938e7c9fe3597f78832ce780feb61945c377d7"]))]
+    pub base_orderbooks: Vec<Address>,
+    #[schema(example = 12)]
+    pub tokens: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RegistryValidationResponse {
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<RegistryValidationSummary>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<RegistryValidationError>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/registry/validate",
+    tag = "Admin",
+    security(("basicAuth" = [])),
+    request_body = ValidateRegistryRequest,
+    responses(
+        (status = 200, description = "Validation result; the live registry is not changed", body = RegistryValidationResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Forbidden", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[post("/registry/validate", data = "<request>")]
+pub async fn post_registry_validate(
+    _global: GlobalRateLimit,
+    admin: AdminKey,
+    _json: JsonContentType,
+    shared_raindex: &State<SharedRaindexProvider>,
+    span: TracingSpan,
+    request: Json<ValidateRegistryRequest>,
+) -> Result<Json<RegistryValidationResponse>, ApiError> {
+    let req = request.into_inner();
+    async move {
+        tracing::info!(admin_key_id = %admin.0.key_id, "request received");
+
+        let registry_headers = shared_raindex.read().await.registry_headers().clone();
+        let response = validate_registry_url(req.registry_url.trim(), registry_headers).await;
+
+        tracing::info!(
+            valid = response.valid,
+            failed_checks = response.errors.len(),
+            admin_key_id = %admin.0.key_id,
+            "registry validated"
+        );
+        Ok(Json(response))
+    }
+    .instrument(span.0)
+    .await
+}
+
+/// Runs the same load `PUT /admin/registry` performs, without a local DB and
+/// without touching the live provider, and reports each failed check.
+async fn validate_registry_url(
+    registry_url: &str,
+    registry_headers: RegistryHeaders,
+) -> RegistryValidationResponse {
+    let invalid = |check, message: String| RegistryValidationResponse {
+        valid: false,
+        summary: None,
+        errors: vec![RegistryValidationError { check, message }],
+    };
+
+    match Url::parse(registry_url) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+        Ok(url) => {
+            return invalid(
+                RegistryValidationCheck::Url,
+                format!("unsupported URL scheme: {}", url.scheme()),
+            )
+        }
+        Err(e) => {
+            return invalid(
+                RegistryValidationCheck::Url,
+                format!("invalid registry URL: {e}"),
+            )
+        }
+    }
+
+    let provider =
+        match RaindexProvider::load_with_headers(registry_url, None, registry_headers).await {
+            Ok(provider) => provider,
+            Err(e) => {
+                tracing::warn!(error = %e, "registry failed to load during validation");
+                return invalid(RegistryValidationCheck::Load, e.to_string());
+            }
+        };
+
+    let base_orderbooks = provider.orderbooks_for_chain(crate::CHAIN_ID);
+    if base_orderbooks.is_empty() {
+        return invalid(
+            RegistryValidationCheck::BaseOrderbook,
+            format!("registry has no orderbook on chain {}", crate::CHAIN_ID),
+        );
+    }
+
+    let yaml = provider.raindex_yaml();
+    let summary = RegistryValidationSummary {
+        networks: yaml
+            .get_networks()
+            .map(|networks| networks.len())
+            .unwrap_or(0),
+        orderbooks: provider.orderbook_sources().len(),
+        base_orderbooks,
+        tokens: provider
+            .client()
+            .get_all_tokens()
+            .map(|tokens| tokens.len())
+            .unwrap_or(0),
+    };
+    RegistryValidationResponse {
+        valid: true,
+        summary: Some(summary),
+        errors: Vec::new(),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateLogLevelRequest {
+    #[schema(example = "st0x_rest_api=debug,rocket=warn,warn")]
+    pub filter: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LogLevelResponse {
+    #[schema(example = "st0x_rest_api=debug,rocket=warn,warn")]
+    pub filter: String,
+    #[schema(example = "st0x_rest_api=info,rocket=warn,warn")]
+    pub previous_filter: String,
+}
+
+#[utoipa::path(
+    put,
+    path = "/admin/log-level",
+    tag = "Admin",
+    security(("basicAuth" = [])),
+    request_body = UpdateLogLevelRequest,
+    responses(
+        (status = 200, description = "Log filter updated", body = LogLevelResponse),
+        (status = 400, description = "Bad request", body = ApiErrorResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Forbidden", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[put("/log-level", data = "<request>")]
+pub async fn put_log_level(
+    _global: GlobalRateLimit,
+    admin: AdminKey,
+    _json: JsonContentType,
+    app_state: &State<ApplicationState>,
+    span: TracingSpan,
+    request: Json<UpdateLogLevelRequest>,
+) -> Result<Json<LogLevelResponse>, ApiError> {
+    let req = request.into_inner();
+    async move {
+        tracing::info!(
+            filter = %req.filter,
+            admin_key_id = %admin.0.key_id,
+            "request received"
+        );
+
+        let filter = req.filter.trim();
+        if filter.is_empty() {
+            return Err(ApiError::BadRequest("filter must not be empty".into()));
+        }
+
+        let previous_filter = app_state.log_filter.reload(filter).map_err(|e| match e {
+            LogFilterError::InvalidDirective(_) => {
+                ApiError::BadRequest(format!("invalid log filter: {e}"))
+            }
+            LogFilterError::Reload(_) => {
+                tracing::error!(error = %e, "failed to reload log filter");
+                ApiError::Internal("failed to update log filter".into())
+            }
+        })?;
+        let filter = app_state.log_filter.current().map_err(|e| {
+            tracing::error!(error = %e, "failed to read log filter");
+            ApiError::Internal("failed to update log filter".into())
+        })?;
+
+        tracing::warn!(
+            filter = %filter,
+            previous_filter = %previous_filter,
+            admin_key_id = %admin.0.key_id,
+            "log filter updated"
+        );
+
+        Ok(Json(LogLevelResponse {
+            filter,
+            previous_filter,
+        }))
+    }
+    .instrument(span.0)
+    .await
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TokenListEntry {
+    #[schema(example = 8453)]
+    pub chain_id: u32,
+    #[schema(value_type = String, example = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913")]
+    pub address: Address,
+    #[schema(example = "USDC")]
+    pub symbol: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TokenSymbolChange {
+    #[schema(example = 8453)]
+    pub chain_id: u32,
+    #[schema(value_type = String, example = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913")]
+    pub address: Address,
+    #[schema(example = "USDbC")]
+    pub previous_symbol: Option<String>,
+    #[schema(example = "USDC")]
+    pub symbol: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RefreshTokensResponse {
+    pub added: Vec<TokenListEntry>,
+    pub removed: Vec<TokenListEntry>,
+    pub changed: Vec<TokenSymbolChange>,
+    #[schema(example = 12)]
+    pub total_tokens: usize,
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/tokens/refresh",
+    tag = "Admin",
+    security(("basicAuth" = [])),
+    responses(
+        (status = 200, description = "Token list reloaded", body = RefreshTokensResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Forbidden", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[post("/tokens/refresh")]
+pub async fn post_tokens_refresh(
+    _global: GlobalRateLimit,
+    admin: AdminKey,
+    shared_raindex: &State<SharedRaindexProvider>,
+    app_state: &State<ApplicationState>,
+    span: TracingSpan,
+) -> Result<Json<RefreshTokensResponse>, ApiError> {
+    async move {
+        tracing::info!(admin_key_id = %admin.0.key_id, "request received");
+
+        let _update_guard = app_state.registry_artifact_store.lock_update().await;
+
+        let (registry, registry_index, db_path, registry_headers, previous_tokens) = {
+            let guard = shared_raindex.read().await;
+            (
+                guard.registry().to_string(),
+                guard.registry_index(),
+                guard.db_path(),
+                guard.registry_headers().clone(),
+                provider_tokens(&guard)?,
+            )
+        };
+
+        let new_provider = RaindexProvider::load_with_headers(&registry, db_path, registry_headers)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "failed to reload token list");
+                ApiError::Internal("failed to reload token list".into())
+            })?
+            .with_registry_index(registry_index);
+        let tokens = provider_tokens(&new_provider)?;
+        let response = diff_token_lists(&previous_tokens, &tokens);
+
+        let mut guard = shared_raindex.write().await;
+        *guard = new_provider;
+        drop(guard);
+        app_state.response_caches.invalidate_all();
+
+        tracing::info!(
+            added = response.added.len(),
+            removed = response.removed.len(),
+            changed = response.changed.len(),
+            total_tokens = response.total_tokens,
+            admin_key_id = %admin.0.key_id,
+            "token list refreshed"
+        );
+
+        Ok(Json(response))
+    }
+    .instrument(span.0)
+    .await
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateDenylistRequest {
+    #[schema(value_type = Vec<String>, example = json!(["0x7f367cc41522ce07553e823bf3be79a889debe1b"]))]
+    pub addresses: Vec<Address>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DenylistResponse {
+    #[schema(value_type = Vec<String>, example = json!(["0x7f367cc41522ce07553e823bf3be79a889debe1b"]))]
+    pub addresses: Vec<Address>,
+    /// Size of the list before this request; absent on reads.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 3)]
+    pub previous_count: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/denylist",
+    tag = "Admin",
+    security(("basicAuth" = [])),
+    responses(
+        (status = 200, description = "Current address denylist", body = DenylistResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Forbidden", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[get("/denylist")]
+pub async fn get_denylist(
+    _global: GlobalRateLimit,
+    admin: AdminKey,
+    app_state: &State<ApplicationState>,
+    span: TracingSpan,
+) -> Result<Json<DenylistResponse>, ApiError> {
+    async move {
+        tracing::info!(admin_key_id = %admin.0.key_id, "request received");
+        Ok(Json(DenylistResponse {
+            addresses: app_state.denylist.addresses()?,
+            previous_count: None,
+        }))
+    }
+    .instrument(span.0)
+    .await
+}
+
+#[utoipa::path(
+    put,
+    path = "/admin/denylist",
+    tag = "Admin",
+    security(("basicAuth" = [])),
+    request_body = UpdateDenylistRequest,
+    responses(
+        (status = 200, description = "Address denylist replaced", body = DenylistResponse),
+        (status = 400, description = "Bad request", body = ApiErrorResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Forbidden", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[put("/denylist", data = "<request>")]
+pub async fn put_denylist(
+    _global: GlobalRateLimit,
+    admin: AdminKey,
+    _json: JsonContentType,
+    app_state: &State<ApplicationState>,
+    span: TracingSpan,
+    request: Json<UpdateDenylistRequest>,
+) -> Result<Json<DenylistResponse>, ApiError> {
+    let req = request.into_inner();
+    async move {
+        tracing::info!(
+            count = req.addresses.len(),
+            admin_key_id = %admin.0.key_id,
+            "request received"
+        );
+
+        let previous_count = app_state.denylist.replace(req.addresses)?;
+        let addresses = app_state.denylist.addresses()?;
+
+        tracing::warn!(
+            count = addresses.len(),
+            previous_count,
+            admin_key_id = %admin.0.key_id,
+            "address denylist replaced"
+        );
+
+        Ok(Json(DenylistResponse {
+            addresses,
+            previous_count: Some(previous_count),
+        }))
+    }
+    .instrument(span.0)
+    .await
+}
+
+/// Busiest keys listed in the rate-limit snapshot.
+const RATE_LIMIT_TOP_KEYS: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RateLimitBucketResponse {
+    #[schema(example = 42)]
+    pub count: u64,
+    /// Requests per minute; 0 means the limit is disabled.
+    #[schema(example = 600)]
+    pub limit: u64,
+    #[schema(example = 1781506371)]
+    pub reset: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RateLimitKeyUsage {
+    #[schema(example = 12)]
+    pub id: i64,
+    /// Absent when the key has since been deleted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "3f2a9c1e")]
+    pub key_id: Option<String>,
+    #[schema(example = 17)]
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RateLimitResponse {
+    pub global: RateLimitBucketResponse,
+    #[schema(example = 60)]
+    pub per_key_limit: u64,
+    pub top_keys: Vec<RateLimitKeyUsage>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/rate-limit",
+    tag = "Admin",
+    security(("basicAuth" = [])),
+    responses(
+        (status = 200, description = "Current rate limiter state", body = RateLimitResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Forbidden", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[get("/rate-limit")]
+pub async fn get_rate_limit(
+    _global: GlobalRateLimit,
+    admin: AdminKey,
+    rate_limiter: &State<RateLimiter>,
+    db: &State<Db>,
+    span: TracingSpan,
+) -> Result<Json<RateLimitResponse>, ApiError> {
+    async move {
+        tracing::info!(admin_key_id = %admin.0.key_id, "request received");
+        let snapshot = rate_limiter.snapshot(RATE_LIMIT_TOP_KEYS)?;
+
+        let mut top_keys = Vec::with_capacity(snapshot.top_keys.len());
+        for (id, count) in snapshot.top_keys {
+            let key_id: Option<String> =
+                sqlx::query_scalar("SELECT key_id FROM api_keys WHERE id = ?")
+                    .bind(id)
+                    .fetch_optional(db.reads())
+                    .await
+                    .map_err(|e| {
+                        tracing::error!(error = %e, "failed to look up api key");
+                        ApiError::Internal("failed to read rate limit state".into())
+                    })?;
+            top_keys.push(RateLimitKeyUsage { id, key_id, count });
+        }
+
+        Ok(Json(RateLimitResponse {
+            global: RateLimitBucketResponse {
+                count: snapshot.global.count,
+                limit: snapshot.global.limit,
+                reset: snapshot.global.reset,
+            },
+            per_key_limit: snapshot.per_key_limit,
+            top_keys,
+        }))
+    }
+    .instrument(span.0)
+    .await
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PurgeLogsResponse {
+    /// Rows removed across all log tables.
+    #[schema(example = 1520)]
+    pub deleted: u64,
+    #[schema(example = 1500)]
+    pub usage_logs: u64,
+    /// Registry URL and private registry history rows.
+    #[schema(example = 20)]
+    pub audit_logs: u64,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/logs",
+    tag = "Admin",
+    security(("basicAuth" = [])),
+    params(("older_than" = String, Query, description = "Age cutoff such as 12h, 30d or 4w", example = "30d")),
+    responses(
+        (status = 200, description = "Usage and audit rows older than the cutoff deleted", body = PurgeLogsResponse),
+        (status = 400, description = "Bad request", body = ApiErrorResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Forbidden", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[delete("/logs?<older_than>")]
+pub async fn delete_logs(
+    _global: GlobalRateLimit,
+    admin: AdminKey,
+    db: &State<Db>,
+    span: TracingSpan,
+    older_than: Option<&str>,
+) -> Result<Json<PurgeLogsResponse>, ApiError> {
+    async move {
+        tracing::info!(older_than, admin_key_id = %admin.0.key_id, "request received");
+        let older_than_secs = parse_log_age(older_than)?;
+
+        let purged = retention::purge_logs_older_than(db.writes(), older_than_secs)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "failed to purge logs");
+                ApiError::Internal("failed to purge logs".into())
+            })?;
+
+        tracing::warn!(
+            usage_logs = purged.usage_logs,
+            registry_url_history = purged.registry_url_history,
+            private_registry_history = purged.private_registry_history,
+            older_than_secs,
+            admin_key_id = %admin.0.key_id,
+            "logs purged"
+        );
+
+        Ok(Json(PurgeLogsResponse {
+            deleted: purged.total(),
+            usage_logs: purged.usage_logs,
+            audit_logs: purged.registry_url_history + purged.private_registry_history,
+        }))
+    }
+    .instrument(span.0)
+    .await
+}
+
 pub fn routes() -> Vec<Route> {
-    rocket::routes![put_registry]
+    rocket::routes![
+        put_registry,
+        post_registry_validate,
+        put_log_level,
+        post_tokens_refresh,
+        get_denylist,
+        put_denylist,
+        get_rate_limit,
+        delete_logs
+    ]
 }
 
-fn validate_request(req: &UploadRegistryArtifactRequest) -> Result<(), ApiError> {
-    if req.registry_artifact.is_empty() {
-        return Err(ApiError::BadRequest(
-            "registry_artifact must not be empty".into(),
-        ));
+/// Parses a log age like `12h`, `30d` or `4w` into seconds.
+fn parse_log_age(value: Option<&str>) -> Result<u64, ApiError> {
+    let value = value
+        .map(str::trim)
+        .ok_or_else(|| ApiError::BadRequest("older_than is required".into()))?;
+    let invalid = || ApiError::BadRequest("older_than must look like 12h, 30d or 4w".into());
+
+    let unit = value.chars().last().ok_or_else(invalid)?;
+    let unit_secs: u64 = match unit {
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    let count: u64 = value[..value.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+    if count == 0 {
+        return Err(invalid());
     }
-    if !req.registry_artifact.starts_with("data:text/plain;base64,") {
-        return Err(ApiError::BadRequest(
-            "registry_artifact must be a data:text/plain;base64 URI".into(),
-        ));
+    count.checked_mul(unit_secs).ok_or_else(invalid)
+}
+
+fn provider_tokens(provider: &RaindexProvider) -> Result<Vec<TokenCfg>, ApiError> {
+    provider
+        .client()
+        .get_all_tokens()
+        .map(crate::raindex::dedupe_tokens)
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to get tokens from raindex");
+            ApiError::Internal("failed to retrieve token list".into())
+        })
+}
+
+fn diff_token_lists(previous: &[TokenCfg], current: &[TokenCfg]) -> RefreshTokensResponse {
+    let index = |tokens: &[TokenCfg]| -> BTreeMap<(u32, Address), Option<String>> {
+        tokens
+            .iter()
+            .map(|token| {
+                (
+                    (token.network.chain_id, token.address),
+                    token.symbol.clone(),
+                )
+            })
+            .collect()
+    };
+    let previous = index(previous);
+    let current = index(current);
+
+    let entry = |(chain_id, address): &(u32, Address), symbol: &Option<String>| TokenListEntry {
+        chain_id: *chain_id,
+        address: *address,
+        symbol: symbol.clone(),
+    };
+    let added = current
+        .iter()
+        .filter(|(key, _)| !previous.contains_key(key))
+        .map(|(key, symbol)| entry(key, symbol))
+        .collect();
+    let removed = previous
+        .iter()
+        .filter(|(key, _)| !current.contains_key(key))
+        .map(|(key, symbol)| entry(key, symbol))
+        .collect();
+    let changed = current
+        .iter()
+        .filter_map(|(&(chain_id, address), symbol)| {
+            let previous_symbol = previous.get(&(chain_id, address))?;
+            (previous_symbol != symbol).then(|| TokenSymbolChange {
+                chain_id,
+                address,
+                previous_symbol: previous_symbol.clone(),
+                symbol: symbol.clone(),
+            })
+        })
+        .collect();
+
+    RefreshTokensResponse {
+        added,
+        removed,
+        changed,
+        total_tokens: current.len(),
+    }
+}
+
+fn validate_artifact(artifact: &str, field: &str) -> Result<(), ApiError> {
+    if artifact.is_empty() {
+        return Err(ApiError::BadRequest(format!("{field} must not be empty")));
+    }
+    if !artifact.starts_with("data:text/plain;base64,") {
+        return Err(ApiError::BadRequest(format!(
+            "{field} must be a data:text/plain;base64 URI"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_request(req: &UploadRegistryArtifactRequest) -> Result<(), ApiError> {
+    validate_artifact(&req.registry_artifact, "registry_artifact")?;
+    for fallback in &req.fallback_registry_artifacts {
+        validate_artifact(fallback, "fallback_registry_artifacts")?;
     }
     if req.source_commit.is_empty() {
         return Err(ApiError::BadRequest(
@@ -210,7 +905,9 @@ mod tests {
     use super::{validate_request, UploadRegistryArtifactRequest};
     use crate::db::registry_history::{self, PrivateRegistryHistoryRow};
     use crate::test_helpers::{
-        basic_auth_header, mock_raindex_registry_artifact, seed_admin_key, seed_api_key,
+        basic_auth_header, mock_raindex_registry_artifact, mock_raindex_registry_url,
+        mock_raindex_registry_url_with_settings,
+        mock_raindex_registry_url_with_settings_and_shared_tokens, seed_admin_key, seed_api_key,
         TestClientBuilder,
     };
     use rocket::http::{ContentType, Header, Status};
@@ -327,6 +1024,7 @@ mod tests {
             let req = UploadRegistryArtifactRequest {
                 registry_artifact: registry_artifact.to_string(),
                 source_commit: source_commit.to_string(),
+                fallback_registry_artifacts: Vec::new(),
             };
             assert!(validate_request(&req).is_err());
         }
@@ -337,12 +1035,75 @@ mod tests {
         let mut req = UploadRegistryArtifactRequest {
             registry_artifact: "data:text/plain;base64,abc".to_string(),
             source_commit: format!(" {COMMIT_ONE} "),
+            fallback_registry_artifacts: Vec::new(),
         };
 
         req.source_commit = req.source_commit.trim().to_string();
 
-        assert_eq!(req.source_commit, COMMIT_ONE);
-        assert!(validate_request(&req).is_ok());
+        assert_eq!(req.source_commit, COMMIT_ONE);
+        assert!(validate_request(&req).is_ok());
+    }
+
+    #[test]
+    fn test_validate_request_rejects_invalid_fallback_artifact() {
+        let req = UploadRegistryArtifactRequest {
+            registry_artifact: "data:text/plain;base64,abc".to_string(),
+            source_commit: COMMIT_ONE.to_string(),
+            fallback_registry_artifacts: vec!["https://registry.example.com".to_string()],
+        };
+        assert!(validate_request(&req).is_err());
+    }
+
+    #[rocket::async_test]
+    async fn test_put_registry_falls_back_when_primary_artifact_fails_to_load() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let path = dir.path().join("private-registry.data");
+        let client = TestClientBuilder::new()
+            .private_registry_path(path.clone())
+            .build()
+            .await;
+        let (key_id, secret) = seed_admin_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+        let invalid_artifact = "data:text/plain;base64,dGhpcyBpcyBub3QgdmFsaWQ=";
+        let fallback_artifact = mock_raindex_registry_artifact();
+
+        let response = client
+            .put("/admin/registry")
+            .header(Header::new("Authorization", header.clone()))
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "registry_artifact": invalid_artifact,
+                    "source_commit": COMMIT_ONE,
+                    "fallback_registry_artifacts": [fallback_artifact],
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read artifact"),
+            fallback_artifact
+        );
+
+        let get_response = client
+            .get("/registry")
+            .header(Header::new("Authorization", header))
+            .dispatch()
+            .await;
+        let body: serde_json::Value =
+            serde_json::from_str(&get_response.into_string().await.unwrap()).unwrap();
+        assert_eq!(body["registry_type"], "private_artifact");
+        assert_eq!(body["active_registry_index"], 1);
+        assert_eq!(
+            body["payload_sha256"],
+            crate::registry_artifact::artifact_sha256(&fallback_artifact)
+        );
+
+        let history = history_rows(&client).await;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].validation_status, "success");
     }
 
     #[rocket::async_test]
@@ -454,4 +1215,523 @@ mod tests {
         assert!(body["payload_sha256"].as_str().is_some());
         assert!(body.get("registry_url").is_none());
     }
+
+    #[rocket::async_test]
+    async fn test_put_log_level_updates_live_filter() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_admin_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+
+        let response = client
+            .put("/admin/log-level")
+            .header(Header::new("Authorization", header))
+            .header(ContentType::JSON)
+            .body(json!({ "filter": "st0x_rest_api=debug,warn" }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(body["filter"], "st0x_rest_api=debug,warn");
+        assert!(body["previous_filter"]
+            .as_str()
+            .expect("previous filter is a string")
+            .contains("st0x_rest_api=info"));
+
+        let app_state = client
+            .rocket()
+            .state::<crate::app_state::ApplicationState>()
+            .expect("app state");
+        assert_eq!(
+            app_state.log_filter.current().expect("current filter"),
+            "st0x_rest_api=debug,warn"
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_put_log_level_rejects_invalid_filter() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_admin_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+
+        for filter in ["", "   ", "st0x_rest_api=loud"] {
+            let response = client
+                .put("/admin/log-level")
+                .header(Header::new("Authorization", header.clone()))
+                .header(ContentType::JSON)
+                .body(json!({ "filter": filter }).to_string())
+                .dispatch()
+                .await;
+
+            assert_eq!(response.status(), Status::BadRequest);
+        }
+
+        let app_state = client
+            .rocket()
+            .state::<crate::app_state::ApplicationState>()
+            .expect("app state");
+        assert!(app_state
+            .log_filter
+            .current()
+            .expect("current filter")
+            .contains("st0x_rest_api=info"));
+    }
+
+    #[rocket::async_test]
+    async fn test_put_log_level_requires_admin_key() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_api_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+
+        let response = client
+            .put("/admin/log-level")
+            .header(Header::new("Authorization", header))
+            .header(ContentType::JSON)
+            .body(json!({ "filter": "debug" }).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client
+            .put("/admin/log-level")
+            .header(ContentType::JSON)
+            .body(json!({ "filter": "debug" }).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    async fn validate_registry(
+        client: &rocket::local::asynchronous::Client,
+        registry_url: &str,
+    ) -> serde_json::Value {
+        let (key_id, secret) = seed_admin_key(client).await;
+        let response = client
+            .post("/admin/registry/validate")
+            .header(Header::new(
+                "Authorization",
+                basic_auth_header(&key_id, &secret),
+            ))
+            .header(ContentType::JSON)
+            .body(json!({ "registry_url": registry_url }).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        serde_json::from_str(&response.into_string().await.unwrap()).unwrap()
+    }
+
+    async fn live_registry(client: &rocket::local::asynchronous::Client) -> String {
+        client
+            .rocket()
+            .state::<crate::raindex::SharedRaindexProvider>()
+            .expect("raindex provider in state")
+            .read()
+            .await
+            .registry()
+            .to_string()
+    }
+
+    #[rocket::async_test]
+    async fn test_validate_registry_reports_summary_without_swapping_provider() {
+        let client = TestClientBuilder::new().build().await;
+        let live_before = live_registry(&client).await;
+        let registry_url = mock_raindex_registry_url().await;
+
+        let body = validate_registry(&client, &registry_url).await;
+
+        assert_eq!(body["valid"], true);
+        assert!(body.get("errors").is_none());
+        assert_eq!(body["summary"]["networks"], 1);
+        assert_eq!(body["summary"]["orderbooks"], 1);
+        assert_eq!(
+            body["summary"]["base_orderbooks"],
+            json!(["0xd2938e7c9fe3597f78832ce780feb61945c377d7"])
+        );
+        assert_eq!(body["summary"]["tokens"], 1);
+        assert_eq!(live_registry(&client).await, live_before);
+    }
+
+    #[rocket::async_test]
+    async fn test_validate_registry_reports_failed_checks() {
+        let client = TestClientBuilder::new().build().await;
+
+        let body = validate_registry(&client, "ftp://registry.example.com/registry.txt").await;
+        assert_eq!(body["valid"], false);
+        assert!(body.get("summary").is_none());
+        assert_eq!(body["errors"][0]["check"], "url");
+
+        let body = validate_registry(&client, "http://127.0.0.1:1/registry.txt").await;
+        assert_eq!(body["valid"], false);
+        assert_eq!(body["errors"][0]["check"], "load");
+
+        let arbitrum_only = mock_raindex_registry_url_with_settings(
+            r#"version: 6
+networks:
+  arbitrum:
+    rpcs:
+      - https://arb1.arbitrum.io/rpc
+    chain-id: 42161
+    currency: ETH
+subgraphs:
+  arbitrum: https://example.com/sg
+raindexes:
+  arbitrum:
+    address: 0xd2938e7c9fe3597f78832ce780feb61945c377d7
+    network: arbitrum
+    subgraph: arbitrum
+    deployment-block: 0
+deployers:
+  arbitrum:
+    address: 0xC1A14cE2fd58A3A2f99deCb8eDd866204eE07f8D
+    network: arbitrum
+"#,
+        )
+        .await;
+        let body = validate_registry(&client, &arbitrum_only).await;
+        assert_eq!(body["valid"], false);
+        assert_eq!(body["errors"][0]["check"], "base_orderbook");
+        assert_eq!(
+            body["errors"][0]["message"],
+            "registry has no orderbook on chain 8453"
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_validate_registry_requires_admin_key() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_api_key(&client).await;
+
+        let response = client
+            .post("/admin/registry/validate")
+            .header(Header::new(
+                "Authorization",
+                basic_auth_header(&key_id, &secret),
+            ))
+            .header(ContentType::JSON)
+            .body(json!({ "registry_url": "https://registry.example.com" }).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    const TOKEN_LIST_SETTINGS: &str = r#"version: 6
+networks:
+  base:
+    rpcs:
+      - https://mainnet.base.org
+    chain-id: 8453
+    currency: ETH
+subgraphs:
+  base: https://api.goldsky.com/api/public/project_clv14x04y9kzi01saerx7bxpg/subgraphs/ob4-base/0.9/gn
+raindexes:
+  base:
+    address: 0xd2938e7c9fe3597f78832ce780feb61945c377d7
+    network: base
+    subgraph: base
+    deployment-block: 0
+deployers:
+  base:
+    address: 0xC1A14cE2fd58A3A2f99deCb8eDd866204eE07f8D
+    network: base
+using-tokens-from:
+  - __TOKENS_URL__
+"#;
+
+    fn token_list(tokens: &[(&str, &str)]) -> String {
+        let tokens: Vec<_> = tokens
+            .iter()
+            .map(|(address, symbol)| {
+                json!({
+                    "chainId": 8453,
+                    "address": address,
+                    "decimals": 18,
+                    "name": format!("{symbol} Token"),
+                    "symbol": symbol,
+                })
+            })
+            .collect();
+        json!({
+            "name": "ST0x Base Token List",
+            "timestamp": "2026-03-20T00:00:00.000Z",
+            "version": { "major": 1, "minor": 0, "patch": 0 },
+            "tokens": tokens
+        })
+        .to_string()
+    }
+
+    #[rocket::async_test]
+    async fn test_post_tokens_refresh_reports_diff() {
+        const KEPT: &str = "0x8afba81dec38de0a18e2df5e1967a7493651eebf";
+        const REMOVED: &str = "0x4200000000000000000000000000000000000006";
+        const ADDED: &str = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913";
+
+        let remote_tokens = std::sync::Arc::new(std::sync::Mutex::new(token_list(&[
+            (KEPT, "wtCRCL"),
+            (REMOVED, "WETH"),
+        ])));
+        let registry_url = mock_raindex_registry_url_with_settings_and_shared_tokens(
+            TOKEN_LIST_SETTINGS,
+            remote_tokens.clone(),
+        )
+        .await;
+        let config = crate::raindex::RaindexProvider::load(&registry_url, None)
+            .await
+            .expect("load raindex config");
+        let client = TestClientBuilder::new()
+            .raindex_config(config)
+            .build()
+            .await;
+        let (key_id, secret) = seed_admin_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+
+        *remote_tokens.lock().unwrap() = token_list(&[(KEPT, "wtCRCL2"), (ADDED, "USDC")]);
+
+        let response = client
+            .post("/admin/tokens/refresh")
+            .header(Header::new("Authorization", header.clone()))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(
+            body["added"],
+            json!([{ "chain_id": 8453, "address": ADDED, "symbol": "USDC" }])
+        );
+        assert_eq!(
+            body["removed"],
+            json!([{ "chain_id": 8453, "address": REMOVED, "symbol": "WETH" }])
+        );
+        assert_eq!(
+            body["changed"],
+            json!([{
+                "chain_id": 8453,
+                "address": KEPT,
+                "previous_symbol": "wtCRCL",
+                "symbol": "wtCRCL2"
+            }])
+        );
+        assert_eq!(body["total_tokens"], 2);
+
+        let response = client
+            .get("/v1/tokens")
+            .header(Header::new("Authorization", header))
+            .dispatch()
+            .await;
+        let body = response.into_string().await.unwrap();
+        assert!(body.contains("wtCRCL2"));
+        assert!(!body.contains("WETH"));
+    }
+
+    #[rocket::async_test]
+    async fn test_post_tokens_refresh_requires_admin_key() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_api_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+
+        let response = client
+            .post("/admin/tokens/refresh")
+            .header(Header::new("Authorization", header))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    #[rocket::async_test]
+    async fn test_put_denylist_replaces_live_list() {
+        let denied = alloy::primitives::address!("1111111111111111111111111111111111111111");
+        let client = TestClientBuilder::new()
+            .denylist(vec![denied])
+            .build()
+            .await;
+        let (key_id, secret) = seed_admin_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+
+        let response = client
+            .put("/admin/denylist")
+            .header(Header::new("Authorization", header.clone()))
+            .header(ContentType::JSON)
+            .body(
+                json!({ "addresses": ["0x2222222222222222222222222222222222222222"] }).to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(body["previous_count"], 1);
+        assert_eq!(
+            body["addresses"],
+            json!(["0x2222222222222222222222222222222222222222"])
+        );
+
+        let app_state = client
+            .rocket()
+            .state::<crate::app_state::ApplicationState>()
+            .expect("app state");
+        assert!(app_state.denylist.check(&[denied]).is_ok());
+
+        let response = client
+            .get("/admin/denylist")
+            .header(Header::new("Authorization", header))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(
+            body,
+            json!({ "addresses": ["0x2222222222222222222222222222222222222222"] })
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_put_denylist_requires_admin_key() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_api_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+
+        let response = client
+            .put("/admin/denylist")
+            .header(Header::new("Authorization", header))
+            .header(ContentType::JSON)
+            .body(json!({ "addresses": [] }).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    #[rocket::async_test]
+    async fn test_rate_limit_snapshot_reflects_requests() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_api_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+        for _ in 0..2 {
+            client
+                .get("/v1/tokens")
+                .header(Header::new("Authorization", header.clone()))
+                .dispatch()
+                .await;
+        }
+
+        let (admin_key_id, admin_secret) = seed_admin_key(&client).await;
+        let response = client
+            .get("/admin/rate-limit")
+            .header(Header::new(
+                "Authorization",
+                basic_auth_header(&admin_key_id, &admin_secret),
+            ))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+
+        assert_eq!(body["global"]["count"], 3);
+        assert_eq!(body["global"]["limit"], 10000);
+        assert_eq!(body["top_keys"][0]["key_id"], key_id);
+        assert_eq!(body["top_keys"][0]["count"], 2);
+        assert_eq!(body["top_keys"][1]["key_id"], admin_key_id);
+        assert_eq!(body["top_keys"][1]["count"], 1);
+    }
+
+    #[rocket::async_test]
+    async fn test_rate_limit_snapshot_requires_admin_key() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_api_key(&client).await;
+
+        let response = client
+            .get("/admin/rate-limit")
+            .header(Header::new(
+                "Authorization",
+                basic_auth_header(&key_id, &secret),
+            ))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    async fn seed_usage_log(pool: &crate::db::DbPool, key_id: &str, age: &str) {
+        sqlx::query(
+            "INSERT INTO usage_logs (api_key_id, method, path, status_code, latency_ms, created_at) \
+             SELECT id, 'GET', '/seeded', 200, 1.0, datetime('now', ?) FROM api_keys WHERE key_id = ?",
+        )
+        .bind(age)
+        .bind(key_id)
+        .execute(pool)
+        .await
+        .expect("insert usage log");
+    }
+
+    #[rocket::async_test]
+    async fn test_delete_logs_purges_only_rows_older_than_cutoff() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_admin_key(&client).await;
+        let pool = client
+            .rocket()
+            .state::<crate::db::DbPool>()
+            .expect("pool in state");
+        for age in ["-90 days", "-31 days", "-29 days", "-1 hour"] {
+            seed_usage_log(pool, &key_id, age).await;
+        }
+
+        let response = client
+            .delete("/admin/logs?older_than=30d")
+            .header(Header::new(
+                "Authorization",
+                basic_auth_header(&key_id, &secret),
+            ))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(body["deleted"], 2);
+        assert_eq!(body["usage_logs"], 2);
+        assert_eq!(body["audit_logs"], 0);
+
+        let remaining: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM usage_logs WHERE path = '/seeded'")
+                .fetch_one(pool)
+                .await
+                .expect("count usage logs");
+        assert_eq!(remaining, 2);
+    }
+
+    #[rocket::async_test]
+    async fn test_delete_logs_rejects_invalid_cutoff() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_admin_key(&client).await;
+        let header = basic_auth_header(&key_id, &secret);
+
+        for uri in [
+            "/admin/logs",
+            "/admin/logs?older_than=30",
+            "/admin/logs?older_than=0d",
+        ] {
+            let response = client
+                .delete(uri)
+                .header(Header::new("Authorization", header.clone()))
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::BadRequest, "{uri}");
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_delete_logs_requires_admin_key() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_api_key(&client).await;
+
+        let response = client
+            .delete("/admin/logs?older_than=30d")
+            .header(Header::new(
+                "Authorization",
+                basic_auth_header(&key_id, &secret),
+            ))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+    }
 }
@@ -0,0 +1,245 @@
+use crate::auth::AuthenticatedKey;
+use crate::error::{ApiError, ApiErrorResponse};
+use crate::fairings::{GlobalRateLimit, TracingSpan};
+use crate::raindex::DeployerSource;
+use crate::routes::tokens::api_error_message;
+use crate::rpc::{RpcClient, RpcError};
+use crate::types::deployers::{DeployerInfo, DeployersResponse};
+use alloy::network::{Ethereum, Network, TransactionBuilder};
+use alloy::primitives::{keccak256, Address, Bytes, B256};
+use async_trait::async_trait;
+use futures::future::join_all;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use tracing::Instrument;
+use url::Url;
+
+/// Contracts a deployer was constructed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DeployerContracts {
+    pub interpreter: Address,
+    pub store: Address,
+}
+
+#[async_trait]
+pub(crate) trait DeployerContractsDataSource: Send + Sync {
+    async fn deployer_contracts(
+        &self,
+        deployer: Address,
+        rpcs: &[Url],
+    ) -> Result<DeployerContracts, ApiError>;
+}
+
+pub(crate) struct LiveDeployerContractsDataSource;
+
+#[async_trait]
+impl DeployerContractsDataSource for LiveDeployerContractsDataSource {
+    /// Reads `iInterpreter()` and `iStore()` from the first RPC that answers
+    /// both.
+    async fn deployer_contracts(
+        &self,
+        deployer: Address,
+        rpcs: &[Url],
+    ) -> Result<DeployerContracts, ApiError> {
+        for (index, rpc) in rpcs.iter().enumerate() {
+            match read_deployer_contracts(&RpcClient::new(rpc.clone()), deployer).await {
+                Ok(contracts) => return Ok(contracts),
+                Err(e) => tracing::warn!(
+                    rpc_index = index,
+                    deployer = %deployer,
+                    error = %e,
+                    "failed to read deployer contracts"
+                ),
+            }
+        }
+        Err(ApiError::Internal(
+            "failed to read deployer contracts".into(),
+        ))
+    }
+}
+
+async fn read_deployer_contracts(
+    client: &RpcClient,
+    deployer: Address,
+) -> Result<DeployerContracts, RpcError> {
+    Ok(DeployerContracts {
+        interpreter: read_address(client, deployer, "iInterpreter()").await?,
+        store: read_address(client, deployer, "iStore()").await?,
+    })
+}
+
+/// Calls a no-argument getter returning an address.
+async fn read_address(
+    client: &RpcClient,
+    contract: Address,
+    signature: &str,
+) -> Result<Address, RpcError> {
+    let selector = Bytes::copy_from_slice(&keccak256(signature)[..4]);
+    let tx = <Ethereum as Network>::TransactionRequest::default()
+        .with_to(contract)
+        .with_input(selector);
+    let output = client.call(tx).await?;
+    let word: [u8; 32] = output
+        .get(..32)
+        .and_then(|word| word.try_into().ok())
+        .ok_or_else(|| RpcError::Request {
+            method: "eth_call",
+            message: format!("{signature} returned {} bytes", output.len()),
+        })?;
+    Ok(Address::from_word(B256::from(word)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/deployers",
+    tag = "Registry",
+    security(("basicAuth" = [])),
+    responses(
+        (status = 200, description = "Deployer, interpreter and store addresses per network", body = DeployersResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 429, description = "Rate limited", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[get("/")]
+pub async fn get_deployers(
+    _global: GlobalRateLimit,
+    _key: AuthenticatedKey,
+    shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    span: TracingSpan,
+) -> Result<Json<DeployersResponse>, ApiError> {
+    async move {
+        tracing::info!("request received");
+        let sources = shared_raindex.read().await.deployer_sources();
+        Ok(process_get_deployers(&LiveDeployerContractsDataSource, sources).await)
+    }
+    .instrument(span.0)
+    .await
+}
+
+/// Lists every registry deployer. Interpreter and store are resolved on
+/// chain; a deployer whose RPCs can't answer is still listed with `error`.
+pub(crate) async fn process_get_deployers(
+    ds: &dyn DeployerContractsDataSource,
+    sources: Vec<DeployerSource>,
+) -> Json<DeployersResponse> {
+    let mut deployers = join_all(sources.into_iter().map(|source| async move {
+        let contracts = ds.deployer_contracts(source.address, &source.rpcs).await;
+        let (interpreter, store, error) = match contracts {
+            Ok(contracts) => (Some(contracts.interpreter), Some(contracts.store), None),
+            Err(e) => (None, None, Some(api_error_message(&e))),
+        };
+
+        DeployerInfo {
+            key: source.key,
+            network: source.network,
+            chain_id: source.chain_id,
+            address: source.address,
+            interpreter,
+            store,
+            error,
+        }
+    }))
+    .await;
+    deployers.sort_by(|a, b| a.key.cmp(&b.key));
+
+    Json(DeployersResponse { deployers })
+}
+
+pub fn routes() -> Vec<Route> {
+    rocket::routes![get_deployers]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TestClientBuilder;
+    use alloy::primitives::address;
+    use rocket::http::Status;
+
+    const INTERPRETER: Address = address!("1111111111111111111111111111111111111111");
+    const STORE: Address = address!("2222222222222222222222222222222222222222");
+
+    struct MockDeployerContractsDataSource {
+        resolvable: bool,
+    }
+
+    #[async_trait]
+    impl DeployerContractsDataSource for MockDeployerContractsDataSource {
+        async fn deployer_contracts(
+            &self,
+            _deployer: Address,
+            _rpcs: &[Url],
+        ) -> Result<DeployerContracts, ApiError> {
+            if self.resolvable {
+                Ok(DeployerContracts {
+                    interpreter: INTERPRETER,
+                    store: STORE,
+                })
+            } else {
+                Err(ApiError::Internal(
+                    "failed to read deployer contracts".into(),
+                ))
+            }
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_lists_registry_deployer_with_contracts() {
+        let sources = crate::test_helpers::mock_raindex_config()
+            .await
+            .deployer_sources();
+
+        let response = process_get_deployers(
+            &MockDeployerContractsDataSource { resolvable: true },
+            sources,
+        )
+        .await
+        .into_inner();
+
+        let base = &response.deployers[0];
+        assert_eq!(base.key, "base");
+        assert_eq!(base.network, "base");
+        assert_eq!(base.chain_id, 8453);
+        assert_eq!(
+            base.address,
+            address!("C1A14cE2fd58A3A2f99deCb8eDd866204eE07f8D")
+        );
+        assert_eq!(base.interpreter, Some(INTERPRETER));
+        assert_eq!(base.store, Some(STORE));
+        assert!(base.error.is_none());
+    }
+
+    #[rocket::async_test]
+    async fn test_unresolvable_contracts_still_list_deployer() {
+        let sources = crate::test_helpers::mock_raindex_config()
+            .await
+            .deployer_sources();
+
+        let response = process_get_deployers(
+            &MockDeployerContractsDataSource { resolvable: false },
+            sources,
+        )
+        .await
+        .into_inner();
+
+        let base = &response.deployers[0];
+        assert_eq!(
+            base.address,
+            address!("C1A14cE2fd58A3A2f99deCb8eDd866204eE07f8D")
+        );
+        assert_eq!(base.interpreter, None);
+        assert_eq!(base.store, None);
+        assert_eq!(
+            base.error.as_deref(),
+            Some("failed to read deployer contracts")
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_deployers_requires_auth() {
+        let client = TestClientBuilder::new().build().await;
+        let response = client.get("/v1/deployers").dispatch().await;
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+}
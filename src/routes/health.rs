@@ -1,18 +1,91 @@
+use crate::app_state::ApplicationState;
+use crate::auth::AuthenticatedKey;
 use crate::db::DbPool;
-use crate::error::ApiError;
-use crate::fairings::TracingSpan;
-use crate::raindex::SharedRaindexProvider;
+use crate::error::{ApiError, ApiErrorResponse};
+use crate::fairings::{ErrorRateWindow, TracingSpan};
+use crate::raindex::{OrderbookSource, SharedRaindexProvider};
+use crate::routes::status::{IndexingStatusDataSource, LiveIndexingStatusDataSource};
+use crate::routes::tokens::api_error_message;
 use crate::types::health::{
     DbHealthStatus, DbStatus, DetailedHealthResponse, HealthResponse, HealthStatus,
     NetworkSyncInfo, OrderbookSyncInfo, RaindexSyncStatus, RaindexSyncStatusKind,
+    ReadinessResponse, RootResponse, SubgraphReadiness,
 };
+use futures::future::join_all;
 use rain_orderbook_common::raindex_client::local_db::{
     LocalDbSyncSnapshot, NetworkSyncStatusSnapshot, RaindexSyncStatusSnapshot,
 };
+use rocket::either::Either;
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket::response::status::Custom;
+use rocket::response::Redirect;
 use rocket::serde::json::Json;
-use rocket::{Route, State};
+use rocket::{Request, Route, State};
+use std::time::Duration;
 use tracing::Instrument;
 
+/// How long a subgraph readiness probe is reused before subgraphs are
+/// queried again.
+pub(crate) const READINESS_PROBE_TTL: Duration = Duration::from_secs(10);
+
+/// Gate for health routes that go beyond bare liveness. Open by default;
+/// with `health_requires_auth` set it takes a valid API key, so sync state
+/// and orderbook addresses aren't exposed to the public internet.
+pub struct HealthAccess;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for HealthAccess {
+    type Error = ApiError;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let required = req
+            .rocket()
+            .state::<ApplicationState>()
+            .is_some_and(|state| state.health_requires_auth);
+        if !required {
+            return Outcome::Success(HealthAccess);
+        }
+        AuthenticatedKey::from_request(req)
+            .await
+            .map(|_| HealthAccess)
+    }
+}
+
+/// Where Swagger UI is served.
+const SWAGGER_UI_PATH: &str = "/swagger/";
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = "Health",
+    responses(
+        (status = 200, description = "Pointers to the API docs and health check", body = RootResponse),
+        (status = 303, description = "Redirect to Swagger UI, when the deployment is configured for it"),
+    )
+)]
+#[get("/")]
+pub async fn get_root(
+    span: TracingSpan,
+    app_state: &State<ApplicationState>,
+) -> Result<Either<Json<RootResponse>, Redirect>, ApiError> {
+    async move {
+        tracing::info!("request received");
+        if app_state.root_redirects_to_swagger {
+            return Ok(Either::Right(Redirect::to(SWAGGER_UI_PATH)));
+        }
+        Ok(Either::Left(Json(RootResponse {
+            name: "st0x REST API".to_string(),
+            version: crate::fairings::API_VERSION.to_string(),
+            docs: SWAGGER_UI_PATH.to_string(),
+            openapi: "/api-doc/openapi.json".to_string(),
+            health: "/health".to_string(),
+        })))
+    }
+    .instrument(span.0)
+    .await
+}
+
 #[utoipa::path(
     get,
     path = "/health",
@@ -37,12 +110,15 @@ pub async fn get_health(span: TracingSpan) -> Result<Json<HealthResponse>, ApiEr
     get,
     path = "/health/detailed",
     tag = "Health",
+    security((), ("basicAuth" = [])),
     responses(
         (status = 200, description = "Detailed service health including sync status", body = DetailedHealthResponse),
+        (status = 401, description = "Unauthorized, when the deployment requires auth for health details", body = ApiErrorResponse),
     )
 )]
 #[get("/health/detailed")]
 pub async fn get_health_detailed(
+    _access: HealthAccess,
     span: TracingSpan,
     pool: &State<DbPool>,
     shared_raindex: &State<SharedRaindexProvider>,
@@ -66,6 +142,102 @@ pub async fn get_health_detailed(
     .await
 }
 
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "Health",
+    security((), ("basicAuth" = [])),
+    responses(
+        (status = 200, description = "Every orderbook subgraph answered", body = ReadinessResponse),
+        (status = 401, description = "Unauthorized, when the deployment requires auth for health details", body = ApiErrorResponse),
+        (status = 503, description = "An orderbook subgraph is unreachable, none are configured, or the recent 5xx rate is above the threshold", body = ReadinessResponse),
+    )
+)]
+#[get("/health/ready")]
+pub async fn get_health_ready(
+    _access: HealthAccess,
+    span: TracingSpan,
+    app_state: &State<ApplicationState>,
+    shared_raindex: &State<SharedRaindexProvider>,
+) -> Result<Custom<Json<ReadinessResponse>>, ApiError> {
+    async move {
+        tracing::info!("readiness check request received");
+        let mut response = match app_state.readiness_probe.get(&()).await {
+            Some(cached) => cached,
+            None => {
+                let sources = shared_raindex.read().await.orderbook_sources();
+                let response = process_readiness(&LiveIndexingStatusDataSource, sources).await;
+                app_state.readiness_probe.insert((), response.clone()).await;
+                response
+            }
+        };
+        apply_error_rate(&mut response, &app_state.error_rate);
+        tracing::info!(status = ?response.status, "readiness check completed");
+
+        let status = match response.status {
+            HealthStatus::Ok => Status::Ok,
+            _ => Status::ServiceUnavailable,
+        };
+        Ok(Custom(status, Json(response)))
+    }
+    .instrument(span.0)
+    .await
+}
+
+/// Marks an otherwise ready response degraded while the recent 5xx rate is
+/// above the configured threshold, so a bad rollout is taken out of rotation
+/// even though its subgraphs answer.
+fn apply_error_rate(response: &mut ReadinessResponse, error_rate: &ErrorRateWindow) {
+    response.recent_error_rate = error_rate.rate();
+    if response.status == HealthStatus::Ok && error_rate.is_degraded() {
+        tracing::warn!(
+            recent_error_rate = ?response.recent_error_rate,
+            "recent error rate above readiness threshold"
+        );
+        response.status = HealthStatus::Degraded;
+    }
+}
+
+/// Queries every orderbook's subgraph for its indexed block. The service is
+/// ready only when each one answers; a registry that loads with a dead
+/// subgraph URL would otherwise look healthy.
+pub(crate) async fn process_readiness(
+    ds: &dyn IndexingStatusDataSource,
+    sources: Vec<OrderbookSource>,
+) -> ReadinessResponse {
+    let mut subgraphs = join_all(sources.into_iter().map(|source| async move {
+        let indexed_block = ds.indexed_block(&source.subgraph_url).await;
+        if let Err(e) = &indexed_block {
+            tracing::warn!(
+                orderbook_key = %source.key,
+                error = %e,
+                "orderbook subgraph readiness probe failed"
+            );
+        }
+        SubgraphReadiness {
+            orderbook_key: source.key,
+            chain_id: source.chain_id,
+            orderbook_address: format!("{:#x}", source.address),
+            reachable: indexed_block.is_ok(),
+            error: indexed_block.as_ref().err().map(api_error_message),
+            indexed_block: indexed_block.ok(),
+        }
+    }))
+    .await;
+    subgraphs.sort_by(|a, b| a.orderbook_key.cmp(&b.orderbook_key));
+
+    let status = if !subgraphs.is_empty() && subgraphs.iter().all(|s| s.reachable) {
+        HealthStatus::Ok
+    } else {
+        HealthStatus::Error
+    };
+    ReadinessResponse {
+        status,
+        subgraphs,
+        recent_error_rate: None,
+    }
+}
+
 async fn check_app_db(pool: &DbPool) -> DbStatus {
     match sqlx::query("SELECT 1").execute(pool).await {
         Ok(_) => DbStatus {
@@ -208,7 +380,7 @@ fn detailed_status(app_db: &DbStatus, raindex: &RaindexSyncStatus) -> HealthStat
 }
 
 pub fn routes() -> Vec<Route> {
-    rocket::routes![get_health, get_health_detailed]
+    rocket::routes![get_root, get_health, get_health_detailed, get_health_ready]
 }
 
 #[cfg(test)]
@@ -409,4 +581,209 @@ mod tests {
             Some("raindex local DB sync is not configured")
         );
     }
+
+    #[rocket::async_test]
+    async fn root_points_to_docs_and_health() {
+        let client = crate::test_helpers::TestClientBuilder::new().build().await;
+
+        let response = client.get("/").dispatch().await;
+
+        assert_eq!(response.status(), rocket::http::Status::Ok);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(body["docs"], "/swagger/");
+        assert_eq!(body["openapi"], "/api-doc/openapi.json");
+        assert_eq!(body["health"], "/health");
+        assert_eq!(body["version"], crate::fairings::API_VERSION);
+    }
+
+    #[rocket::async_test]
+    async fn root_redirects_to_swagger_when_configured() {
+        let client = crate::test_helpers::TestClientBuilder::new()
+            .root_redirects_to_swagger(true)
+            .build()
+            .await;
+
+        let response = client.get("/").dispatch().await;
+
+        assert_eq!(response.status(), rocket::http::Status::SeeOther);
+        assert_eq!(response.headers().get_one("Location"), Some("/swagger/"));
+    }
+
+    #[rocket::async_test]
+    async fn detailed_health_is_open_by_default() {
+        let client = crate::test_helpers::TestClientBuilder::new().build().await;
+        let response = client.get("/health/detailed").dispatch().await;
+        assert_eq!(response.status(), rocket::http::Status::Ok);
+    }
+
+    #[rocket::async_test]
+    async fn detailed_health_requires_key_when_configured() {
+        let client = crate::test_helpers::TestClientBuilder::new()
+            .health_requires_auth(true)
+            .build()
+            .await;
+
+        let response = client.get("/health").dispatch().await;
+        assert_eq!(response.status(), rocket::http::Status::Ok);
+
+        let response = client.get("/health/detailed").dispatch().await;
+        assert_eq!(response.status(), rocket::http::Status::Unauthorized);
+
+        let (key_id, secret) = crate::test_helpers::seed_api_key(&client).await;
+        let response = client
+            .get("/health/detailed")
+            .header(rocket::http::Header::new(
+                "Authorization",
+                crate::test_helpers::basic_auth_header(&key_id, &secret),
+            ))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), rocket::http::Status::Ok);
+    }
+
+    /// Serves a subgraph that answers every query with indexed block 100.
+    async fn mock_subgraph() -> String {
+        let base_url = crate::test_helpers::mock_http_server(|_| {
+            (
+                200,
+                r#"{"data":{"_meta":{"block":{"number":100}}}}"#.to_string(),
+            )
+        })
+        .await;
+        format!("{base_url}/subgraph")
+    }
+
+    #[rocket::async_test]
+    async fn readiness_is_unavailable_when_one_subgraph_is_down() {
+        let live = mock_subgraph().await;
+        let settings = format!(
+            r#"version: 6
+networks:
+  base:
+    rpcs:
+      - https://mainnet.base.org
+    chain-id: 8453
+    currency: ETH
+subgraphs:
+  live: {live}
+  dead: http://127.0.0.1:1/subgraph
+raindexes:
+  live:
+    address: 0xd2938e7c9fe3597f78832ce780feb61945c377d7
+    network: base
+    subgraph: live
+    deployment-block: 0
+  dead:
+    address: 0x0000000000000000000000000000000000000bad
+    network: base
+    subgraph: dead
+    deployment-block: 0
+deployers:
+  base:
+    address: 0xC1A14cE2fd58A3A2f99deCb8eDd866204eE07f8D
+    network: base
+"#
+        );
+        let registry_url =
+            crate::test_helpers::mock_raindex_registry_url_with_settings(&settings).await;
+        let raindex = crate::raindex::RaindexProvider::load(&registry_url, None)
+            .await
+            .expect("load raindex config");
+        let client = crate::test_helpers::TestClientBuilder::new()
+            .raindex_config(raindex)
+            .build()
+            .await;
+
+        let response = client.get("/health/ready").dispatch().await;
+
+        assert_eq!(response.status(), rocket::http::Status::ServiceUnavailable);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(body["status"], "error");
+        let subgraphs = body["subgraphs"].as_array().unwrap();
+        assert_eq!(subgraphs.len(), 2);
+        assert_eq!(subgraphs[0]["orderbook_key"], "dead");
+        assert_eq!(subgraphs[0]["reachable"], false);
+        assert_eq!(subgraphs[0]["error"], "failed to query subgraph");
+        assert_eq!(subgraphs[1]["orderbook_key"], "live");
+        assert_eq!(subgraphs[1]["reachable"], true);
+        assert_eq!(subgraphs[1]["indexed_block"], 100);
+    }
+
+    #[rocket::async_test]
+    async fn readiness_degrades_above_error_rate_threshold_then_recovers() {
+        let live = mock_subgraph().await;
+        let settings = format!(
+            r#"version: 6
+networks:
+  base:
+    rpcs:
+      - https://mainnet.base.org
+    chain-id: 8453
+    currency: ETH
+subgraphs:
+  live: {live}
+raindexes:
+  live:
+    address: 0xd2938e7c9fe3597f78832ce780feb61945c377d7
+    network: base
+    subgraph: live
+    deployment-block: 0
+deployers:
+  base:
+    address: 0xC1A14cE2fd58A3A2f99deCb8eDd866204eE07f8D
+    network: base
+"#
+        );
+        let registry_url =
+            crate::test_helpers::mock_raindex_registry_url_with_settings(&settings).await;
+        let raindex = crate::raindex::RaindexProvider::load(&registry_url, None)
+            .await
+            .expect("load raindex config");
+        let client = crate::test_helpers::TestClientBuilder::new()
+            .raindex_config(raindex)
+            .health_error_rate_threshold(0.5)
+            .build()
+            .await;
+        let error_rate = &client
+            .rocket()
+            .state::<ApplicationState>()
+            .expect("app state")
+            .error_rate;
+
+        let response = client.get("/health/ready").dispatch().await;
+        assert_eq!(response.status(), rocket::http::Status::Ok);
+
+        for _ in 0..30 {
+            error_rate.record(500);
+        }
+        for _ in 0..10 {
+            error_rate.record(200);
+        }
+        let response = client.get("/health/ready").dispatch().await;
+        assert_eq!(response.status(), rocket::http::Status::ServiceUnavailable);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(body["status"], "degraded");
+        assert_eq!(body["recent_error_rate"], 0.75);
+        assert_eq!(body["subgraphs"][0]["reachable"], true);
+
+        for _ in 0..200 {
+            error_rate.record(200);
+        }
+        let response = client.get("/health/ready").dispatch().await;
+        assert_eq!(response.status(), rocket::http::Status::Ok);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["recent_error_rate"], 0.0);
+    }
+
+    #[rocket::async_test]
+    async fn readiness_requires_at_least_one_subgraph() {
+        let response = process_readiness(&LiveIndexingStatusDataSource, vec![]).await;
+        assert_eq!(response.status, HealthStatus::Error);
+        assert!(response.subgraphs.is_empty());
+    }
 }
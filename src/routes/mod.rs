@@ -1,16 +1,29 @@
 pub mod admin;
+pub mod deployers;
 pub mod health;
 pub mod order;
 pub mod orders;
+pub(crate) mod pagination;
 pub mod registry;
+pub mod stats;
+pub mod status;
 pub mod swap;
 pub mod tokens;
 pub mod trades;
 pub mod vaults;
 
 use crate::error::ApiError;
+use rain_orderbook_common::raindex_client::order_quotes::RaindexOrderQuote;
 use rain_orderbook_common::raindex_client::vaults::{RaindexVault, RaindexVaultType};
 
+/// First quote that succeeded with data. Quotes come one per IO pair, and an
+/// early pair can fail while later ones still quote the order.
+pub(crate) fn first_successful_quote(quotes: &[RaindexOrderQuote]) -> Option<&RaindexOrderQuote> {
+    quotes
+        .iter()
+        .find(|quote| quote.success && quote.data.is_some())
+}
+
 pub(crate) fn resolve_io_vaults(
     order: &rain_orderbook_common::raindex_client::orders::RaindexOrder,
 ) -> Result<(RaindexVault, RaindexVault), ApiError> {
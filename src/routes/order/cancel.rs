@@ -1,16 +1,28 @@
 use super::{OrderDataSource, RaindexOrderDataSource};
 use crate::app_state::ApplicationState;
 use crate::auth::AuthenticatedKey;
-use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
+use crate::denylist::AddressDenylist;
+use crate::error::{ApiError, ApiErrorResponse, NotFoundReason};
+use crate::fairings::{GlobalRateLimit, JsonContentType, TracingSpan};
+use crate::gas::{fetch_fee_hints, FeeHints};
+use crate::tx_value::NON_PAYABLE;
 use crate::types::order::{
-    CancelOrderRequest, CancelOrderResponse, CancelSummary, CancelTransaction, TokenReturn,
+    CancelAllOrdersRequest, CancelAllOrdersResponse, CancelOrderRequest, CancelOrderResponse,
+    CancelSummary, CancelTransaction, TokenReturn,
 };
-use alloy::primitives::{B256, U256};
+use alloy::primitives::{Address, B256};
+use rain_math_float::Float;
+use rain_orderbook_common::raindex_client::orders::RaindexOrder;
 use rocket::serde::json::Json;
 use rocket::State;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::ops::Add;
 use tracing::Instrument;
 
+/// Most orders a single cancel-all response covers.
+const MAX_CANCEL_ALL_ORDERS: u16 = 25;
+
 #[utoipa::path(
     post,
     path = "/v1/order/cancel",
@@ -21,6 +33,7 @@ use tracing::Instrument;
         (status = 200, description = "Cancel order result", body = CancelOrderResponse),
         (status = 400, description = "Bad request", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Request involves a restricted address", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 404, description = "Order not found", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
@@ -30,6 +43,7 @@ use tracing::Instrument;
 pub async fn post_order_cancel(
     _global: GlobalRateLimit,
     _key: AuthenticatedKey,
+    _json: JsonContentType,
     app_state: &State<ApplicationState>,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
     span: TracingSpan,
@@ -45,56 +59,282 @@ pub async fn post_order_cancel(
             caches: &app_state.response_caches,
             pool: None,
         };
-        let response = process_cancel_order(&ds, hash).await?;
+        let mut response =
+            process_cancel_order(&ds, &app_state.denylist, hash, app_state.max_cancel_vaults)
+                .await?;
+        let fee_hints = fetch_fee_hints(&raindex.rpcs_for_chain(crate::CHAIN_ID)).await;
+        apply_fee_hints(&mut response.transactions, fee_hints);
+        Ok(Json(response))
+    }
+    .instrument(span.0)
+    .await
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/order/cancel/all",
+    tag = "Order",
+    security(("basicAuth" = [])),
+    request_body = CancelAllOrdersRequest,
+    responses(
+        (status = 200, description = "Cancel transactions for the owner's active orders", body = CancelAllOrdersResponse),
+        (status = 400, description = "Bad request", body = ApiErrorResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "API key is not bound to this owner, or the owner is restricted", body = ApiErrorResponse),
+        (status = 429, description = "Rate limited", body = ApiErrorResponse),
+        (status = 404, description = "Owner has no active orders", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[post("/cancel/all", data = "<request>")]
+pub async fn post_order_cancel_all(
+    _global: GlobalRateLimit,
+    key: AuthenticatedKey,
+    _json: JsonContentType,
+    app_state: &State<ApplicationState>,
+    shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    span: TracingSpan,
+    request: Json<CancelAllOrdersRequest>,
+) -> Result<Json<CancelAllOrdersResponse>, ApiError> {
+    let req = request.into_inner();
+    async move {
+        tracing::info!(body = ?req, "request received");
+        authorize_owner(&key, req.owner)?;
+        app_state.denylist.check(&[req.owner])?;
+        let raindex = shared_raindex.read().await;
+        let ds = RaindexOrderDataSource {
+            client: raindex.client(),
+            caches: &app_state.response_caches,
+            pool: None,
+        };
+        let mut response =
+            process_cancel_all_orders(&ds, req.owner, app_state.max_cancel_vaults).await?;
+        app_state
+            .denylist
+            .check(&returned_tokens(&response.summary))?;
+        let fee_hints = fetch_fee_hints(&raindex.rpcs_for_chain(crate::CHAIN_ID)).await;
+        apply_fee_hints(&mut response.transactions, fee_hints);
         Ok(Json(response))
     }
     .instrument(span.0)
     .await
 }
 
+/// Admin keys may act for any owner; other keys only for the address their
+/// `owner` binding names.
+fn authorize_owner(key: &AuthenticatedKey, owner: Address) -> Result<(), ApiError> {
+    if key.is_admin {
+        return Ok(());
+    }
+    match key.owner.trim().parse::<Address>() {
+        Ok(bound) if bound == owner => Ok(()),
+        _ => {
+            tracing::warn!(key_id = %key.key_id, owner = %owner, "owner not bound to API key");
+            Err(ApiError::Forbidden(
+                "API key is not bound to this owner".into(),
+            ))
+        }
+    }
+}
+
+/// Sets the same fee hints on every transaction of a response.
+fn apply_fee_hints(transactions: &mut [CancelTransaction], fee_hints: Option<FeeHints>) {
+    for tx in transactions {
+        tx.max_fee_per_gas = fee_hints.map(|hints| hints.max_fee_per_gas);
+        tx.max_priority_fee_per_gas = fee_hints.map(|hints| hints.max_priority_fee_per_gas);
+    }
+}
+
+/// Token addresses a cancel would return funds in.
+fn returned_tokens(summary: &CancelSummary) -> Vec<Address> {
+    summary
+        .tokens_returned
+        .iter()
+        .map(|token_return| token_return.token)
+        .collect()
+}
+
 async fn process_cancel_order(
     ds: &dyn OrderDataSource,
+    denylist: &AddressDenylist,
     hash: B256,
+    max_vaults: usize,
 ) -> Result<CancelOrderResponse, ApiError> {
     let orders = ds.get_orders_by_hash(hash).await?;
-    let order = orders
-        .into_iter()
-        .next()
-        .ok_or_else(|| ApiError::NotFound("order not found".into()))?;
+    let order = orders.into_iter().next().ok_or_else(|| {
+        ApiError::not_found_because(NotFoundReason::OrderNotFound, "order not found")
+    })?;
+    denylist.check(&[order.owner()])?;
 
-    let calldata = ds.get_remove_calldata(&order).await?;
+    let response = build_cancel_order_response(ds, &order, max_vaults).await?;
+    denylist.check(&returned_tokens(&response.summary))?;
+    Ok(response)
+}
+
+async fn process_cancel_all_orders(
+    ds: &dyn OrderDataSource,
+    owner: Address,
+    max_vaults: usize,
+) -> Result<CancelAllOrdersResponse, ApiError> {
+    let (orders, total_active_orders) = ds
+        .get_active_orders_by_owner(owner, MAX_CANCEL_ALL_ORDERS)
+        .await?;
+    if orders.is_empty() {
+        return Err(ApiError::not_found("no active orders for owner"));
+    }
+    let truncated = total_active_orders as usize > orders.len();
+    if truncated {
+        tracing::info!(
+            owner = %owner,
+            total_active_orders,
+            cap = MAX_CANCEL_ALL_ORDERS,
+            "cancel-all capped to the first orders"
+        );
+    }
+
+    let mut order_hashes = Vec::with_capacity(orders.len());
+    let mut transactions = Vec::with_capacity(orders.len());
+    let mut vaults_to_withdraw: u32 = 0;
+    let mut vaults_truncated = false;
+    let mut totals = TokenReturnTotals::default();
+    for order in &orders {
+        let response = build_cancel_order_response(ds, order, max_vaults).await?;
+        order_hashes.push(order.order_hash());
+        transactions.extend(response.transactions);
+        vaults_to_withdraw += response.summary.vaults_to_withdraw;
+        vaults_truncated |= response.summary.vaults_truncated;
+        for token_return in response.summary.tokens_returned {
+            let amount = Float::parse(token_return.amount.clone()).map_err(|e| {
+                tracing::error!(error = %e, amount = %token_return.amount, "failed to parse returned amount");
+                ApiError::Internal("failed to parse returned amount".into())
+            })?;
+            totals.add(token_return, amount)?;
+        }
+    }
+
+    Ok(CancelAllOrdersResponse {
+        order_hashes,
+        transactions,
+        summary: CancelSummary {
+            vaults_to_withdraw,
+            tokens_returned: totals.into_tokens_returned()?,
+            vaults_truncated,
+        },
+        total_active_orders,
+        truncated,
+    })
+}
+
+/// Per-token totals of returned amounts, ordered by token address.
+#[derive(Default)]
+struct TokenReturnTotals(BTreeMap<Address, TokenReturnTotal>);
+
+struct TokenReturnTotal {
+    token_return: TokenReturn,
+    total: Float,
+    merged: bool,
+}
+
+impl TokenReturnTotals {
+    fn add(&mut self, token_return: TokenReturn, amount: Float) -> Result<(), ApiError> {
+        match self.0.entry(token_return.token) {
+            Entry::Occupied(mut entry) => {
+                let entry = entry.get_mut();
+                entry.total = entry.total.add(amount).map_err(|e| {
+                    tracing::error!(error = %e, "failed to sum returned amounts");
+                    ApiError::Internal("failed to sum returned amounts".into())
+                })?;
+                entry.merged = true;
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(TokenReturnTotal {
+                    token_return,
+                    total: amount,
+                    merged: false,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Tokens seen once keep their original amount string; merged tokens
+    /// report the formatted sum.
+    fn into_tokens_returned(self) -> Result<Vec<TokenReturn>, ApiError> {
+        self.0
+            .into_values()
+            .map(|entry| {
+                let mut token_return = entry.token_return;
+                if entry.merged {
+                    token_return.amount = entry.total.format().map_err(|e| {
+                        tracing::error!(error = %e, "failed to format returned amount");
+                        ApiError::Internal("failed to format returned amount".into())
+                    })?;
+                }
+                Ok(token_return)
+            })
+            .collect()
+    }
+}
+
+/// Summarizes at most `max_vaults` of the order's vaults, inputs first, so
+/// an order with an unusual number of vaults can't grow the response
+/// without bound.
+async fn build_cancel_order_response(
+    ds: &dyn OrderDataSource,
+    order: &RaindexOrder,
+    max_vaults: usize,
+) -> Result<CancelOrderResponse, ApiError> {
+    let calldata = ds.get_remove_calldata(order).await?;
 
     let tx = CancelTransaction {
         to: order.raindex(),
         data: calldata,
-        value: U256::ZERO,
+        value: NON_PAYABLE,
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
     };
 
     let inputs = order.inputs_list().items();
     let outputs = order.outputs_list().items();
 
+    let vault_count = inputs.len() + outputs.len();
+    let vaults_truncated = vault_count > max_vaults;
+    if vaults_truncated {
+        tracing::info!(
+            order_hash = %order.order_hash(),
+            vault_count,
+            cap = max_vaults,
+            "cancel summary capped to the first vaults"
+        );
+    }
+
     let mut vaults_to_withdraw: u32 = 0;
-    let mut tokens_returned = Vec::new();
+    let mut totals = TokenReturnTotals::default();
 
-    for vault in inputs.iter().chain(outputs.iter()) {
+    for vault in inputs.iter().chain(outputs.iter()).take(max_vaults) {
         let balance = vault.balance();
-        let is_zero = balance
-            .is_zero()
-            .map_err(|e| ApiError::Internal(format!("failed to check vault balance: {e}")))?;
+        let is_zero = balance.is_zero().map_err(|e| {
+            tracing::error!(error = %e, vault_id = %vault.vault_id(), "failed to check vault balance");
+            ApiError::Internal("failed to check vault balance".into())
+        })?;
         if !is_zero {
             vaults_to_withdraw += 1;
             let token_info = vault.token();
-            tokens_returned.push(TokenReturn {
-                token: token_info.address(),
-                symbol: token_info.symbol().unwrap_or_default(),
-                amount: vault.formatted_balance(),
-            });
+            totals.add(
+                TokenReturn {
+                    token: token_info.address(),
+                    symbol: token_info.symbol().unwrap_or_default(),
+                    amount: vault.formatted_balance(),
+                },
+                balance,
+            )?;
         }
     }
 
     let summary = CancelSummary {
         vaults_to_withdraw,
-        tokens_returned,
+        tokens_returned: totals.into_tokens_returned()?,
+        vaults_truncated,
     };
 
     Ok(CancelOrderResponse {
@@ -108,7 +348,7 @@ mod tests {
     use super::*;
     use crate::routes::order::test_fixtures::*;
     use crate::test_helpers::TestClientBuilder;
-    use alloy::primitives::{Address, Bytes};
+    use alloy::primitives::{Address, Bytes, U256};
     use rocket::http::{ContentType, Status};
 
     fn mock_calldata() -> Bytes {
@@ -123,7 +363,9 @@ mod tests {
             quotes: Ok(vec![]),
             calldata: Ok(mock_calldata()),
         };
-        let result = process_cancel_order(&ds, test_hash()).await.unwrap();
+        let result = process_cancel_order(&ds, &AddressDenylist::default(), test_hash(), 64)
+            .await
+            .unwrap();
 
         assert_eq!(result.transactions.len(), 1);
         let tx = &result.transactions[0];
@@ -141,24 +383,150 @@ mod tests {
 
         assert_eq!(
             result.summary.tokens_returned[0].token,
-            "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
+            "0x4200000000000000000000000000000000000006"
                 .parse::<Address>()
                 .unwrap()
         );
-        assert_eq!(result.summary.tokens_returned[0].symbol, "USDC");
-        assert_eq!(result.summary.tokens_returned[0].amount, "1.000000");
+        assert_eq!(result.summary.tokens_returned[0].symbol, "WETH");
+        assert_eq!(
+            result.summary.tokens_returned[0].amount,
+            "0.500000000000000000"
+        );
 
         assert_eq!(
             result.summary.tokens_returned[1].token,
-            "0x4200000000000000000000000000000000000006"
+            "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
                 .parse::<Address>()
                 .unwrap()
         );
-        assert_eq!(result.summary.tokens_returned[1].symbol, "WETH");
-        assert_eq!(
-            result.summary.tokens_returned[1].amount,
-            "0.500000000000000000"
-        );
+        assert_eq!(result.summary.tokens_returned[1].symbol, "USDC");
+        assert_eq!(result.summary.tokens_returned[1].amount, "1.000000");
+    }
+
+    const WETH_TOKEN: &str = "0x4200000000000000000000000000000000000006";
+    const ONE_WEI_FLOAT: &str =
+        "0xffffffee00000000000000000000000000000000000000000000000000000001";
+
+    fn order_with_weth_in_both_vaults() -> RaindexOrder {
+        let mut value = order_json();
+        value["inputs"][0]["token"] = value["outputs"][0]["token"].clone();
+        value["inputs"][0]["balance"] = serde_json::json!(ONE_WEI_FLOAT);
+        value["inputs"][0]["formattedBalance"] = serde_json::json!("0.000000000000000001");
+        value["outputs"][0]["balance"] =
+            serde_json::json!("0x0000000000000000000000000000000000000000000000000000000000000001");
+        value["outputs"][0]["formattedBalance"] = serde_json::json!("1.000000000000000000");
+        serde_json::from_value(value).expect("deserialize mock RaindexOrder")
+    }
+
+    #[rocket::async_test]
+    async fn test_cancel_order_merges_token_in_two_vaults() {
+        let ds = MockOrderDataSource {
+            orders: Ok(vec![order_with_weth_in_both_vaults()]),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![]),
+            calldata: Ok(mock_calldata()),
+        };
+        let result = process_cancel_order(&ds, &AddressDenylist::default(), test_hash(), 64)
+            .await
+            .unwrap();
+
+        assert_eq!(result.summary.vaults_to_withdraw, 2);
+        assert_eq!(result.summary.tokens_returned.len(), 1);
+        let weth = &result.summary.tokens_returned[0];
+        assert_eq!(weth.token, WETH_TOKEN.parse::<Address>().unwrap());
+        assert_eq!(weth.symbol, "WETH");
+        assert!(Float::parse(weth.amount.clone())
+            .unwrap()
+            .eq(Float::parse("1.000000000000000001".to_string()).unwrap())
+            .unwrap());
+    }
+
+    #[rocket::async_test]
+    async fn test_cancel_order_keeps_one_wei_balance() {
+        let mut value = order_json();
+        value["outputs"][0]["balance"] = serde_json::json!(ONE_WEI_FLOAT);
+        value["outputs"][0]["formattedBalance"] = serde_json::json!("0.000000000000000001");
+        value["inputs"][0]["balance"] =
+            serde_json::json!("0x0000000000000000000000000000000000000000000000000000000000000000");
+        value["inputs"][0]["formattedBalance"] = serde_json::json!("0");
+        let ds = MockOrderDataSource {
+            orders: Ok(vec![serde_json::from_value(value).unwrap()]),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![]),
+            calldata: Ok(mock_calldata()),
+        };
+        let result = process_cancel_order(&ds, &AddressDenylist::default(), test_hash(), 64)
+            .await
+            .unwrap();
+
+        assert_eq!(result.summary.vaults_to_withdraw, 1);
+        assert_eq!(result.summary.tokens_returned.len(), 1);
+        let weth = &result.summary.tokens_returned[0];
+        assert_eq!(weth.token, WETH_TOKEN.parse::<Address>().unwrap());
+        assert_eq!(weth.amount, "0.000000000000000001");
+    }
+
+    fn order_with_output_vaults(count: u8) -> RaindexOrder {
+        let mut value = order_json();
+        let template = value["outputs"][0].clone();
+        value["outputs"] = (0..count)
+            .map(|i| {
+                let mut vault = template.clone();
+                let token = format!("{:#042x}", 0x1000 + u32::from(i));
+                vault["vaultId"] = serde_json::json!(format!("{:#066x}", 0x100 + u32::from(i)));
+                vault["token"]["id"] = serde_json::json!(token);
+                vault["token"]["address"] = serde_json::json!(token);
+                vault
+            })
+            .collect();
+        serde_json::from_value(value).expect("deserialize mock RaindexOrder")
+    }
+
+    #[rocket::async_test]
+    async fn test_cancel_order_caps_vaults() {
+        let ds = MockOrderDataSource {
+            orders: Ok(vec![order_with_output_vaults(40)]),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![]),
+            calldata: Ok(mock_calldata()),
+        };
+        let result = process_cancel_order(&ds, &AddressDenylist::default(), test_hash(), 10)
+            .await
+            .unwrap();
+
+        assert_eq!(result.transactions.len(), 1);
+        assert_eq!(result.summary.vaults_to_withdraw, 10);
+        assert_eq!(result.summary.tokens_returned.len(), 10);
+        assert!(result.summary.vaults_truncated);
+
+        let uncapped = process_cancel_order(&ds, &AddressDenylist::default(), test_hash(), 64)
+            .await
+            .unwrap();
+        assert_eq!(uncapped.summary.vaults_to_withdraw, 41);
+        assert!(!uncapped.summary.vaults_truncated);
+    }
+
+    #[rocket::async_test]
+    async fn test_cancel_order_sorts_tokens_by_address() {
+        let ds = MockOrderDataSource {
+            orders: Ok(vec![mock_order()]),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![]),
+            calldata: Ok(mock_calldata()),
+        };
+        let result = process_cancel_order(&ds, &AddressDenylist::default(), test_hash(), 64)
+            .await
+            .unwrap();
+
+        let tokens: Vec<Address> = result
+            .summary
+            .tokens_returned
+            .iter()
+            .map(|token_return| token_return.token)
+            .collect();
+        let mut sorted = tokens.clone();
+        sorted.sort();
+        assert_eq!(tokens, sorted);
     }
 
     #[rocket::async_test]
@@ -169,8 +537,14 @@ mod tests {
             quotes: Ok(vec![]),
             calldata: Ok(mock_calldata()),
         };
-        let result = process_cancel_order(&ds, test_hash()).await;
-        assert!(matches!(result, Err(ApiError::NotFound(_))));
+        let result = process_cancel_order(&ds, &AddressDenylist::default(), test_hash(), 64).await;
+        assert!(matches!(
+            result,
+            Err(ApiError::NotFound {
+                reason: Some(NotFoundReason::OrderNotFound),
+                ..
+            })
+        ));
     }
 
     #[rocket::async_test]
@@ -181,10 +555,177 @@ mod tests {
             quotes: Ok(vec![]),
             calldata: Err(ApiError::Internal("failed".into())),
         };
-        let result = process_cancel_order(&ds, test_hash()).await;
+        let result = process_cancel_order(&ds, &AddressDenylist::default(), test_hash(), 64).await;
         assert!(matches!(result, Err(ApiError::Internal(_))));
     }
 
+    fn owner_order(hash: &str) -> RaindexOrder {
+        let mut value = order_json();
+        value["orderHash"] = serde_json::json!(hash);
+        serde_json::from_value(value).expect("deserialize mock RaindexOrder")
+    }
+
+    fn api_key(owner: &str, is_admin: bool) -> AuthenticatedKey {
+        AuthenticatedKey {
+            id: 1,
+            key_id: "key".into(),
+            label: "label".into(),
+            owner: owner.into(),
+            is_admin,
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_cancel_all_orders_includes_every_order() {
+        let hashes = [
+            "0x000000000000000000000000000000000000000000000000000000000000abcd",
+            "0x000000000000000000000000000000000000000000000000000000000000abce",
+            "0x000000000000000000000000000000000000000000000000000000000000abcf",
+        ];
+        let ds = MockOrderDataSource {
+            orders: Ok(hashes.iter().map(|hash| owner_order(hash)).collect()),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![]),
+            calldata: Ok(mock_calldata()),
+        };
+        let owner: Address = "0x0000000000000000000000000000000000000001"
+            .parse()
+            .unwrap();
+
+        let result = process_cancel_all_orders(&ds, owner, 64).await.unwrap();
+
+        let expected: Vec<B256> = hashes.iter().map(|hash| hash.parse().unwrap()).collect();
+        assert_eq!(result.order_hashes, expected);
+        assert_eq!(result.transactions.len(), 3);
+        assert!(result
+            .transactions
+            .iter()
+            .all(|tx| tx.data == mock_calldata()));
+        assert_eq!(result.total_active_orders, 3);
+        assert!(!result.truncated);
+        assert_eq!(result.summary.vaults_to_withdraw, 6);
+
+        let tokens = &result.summary.tokens_returned;
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[1].symbol, "USDC");
+        assert!(Float::parse(tokens[1].amount.clone())
+            .unwrap()
+            .eq(Float::parse("3".to_string()).unwrap())
+            .unwrap());
+        assert_eq!(tokens[0].symbol, "WETH");
+        assert!(Float::parse(tokens[0].amount.clone())
+            .unwrap()
+            .eq(Float::parse("1.5".to_string()).unwrap())
+            .unwrap());
+    }
+
+    #[test]
+    fn test_apply_fee_hints_shares_hints_across_transactions() {
+        let tx = CancelTransaction {
+            to: Address::ZERO,
+            data: mock_calldata(),
+            value: U256::ZERO,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        };
+        let mut transactions = vec![tx.clone(), tx];
+        let hints = FeeHints {
+            max_fee_per_gas: U256::from(4_100_000_000u64),
+            max_priority_fee_per_gas: U256::from(100_000_000u64),
+        };
+
+        apply_fee_hints(&mut transactions, Some(hints));
+        assert!(transactions.iter().all(|tx| {
+            tx.max_fee_per_gas == Some(hints.max_fee_per_gas)
+                && tx.max_priority_fee_per_gas == Some(hints.max_priority_fee_per_gas)
+        }));
+
+        apply_fee_hints(&mut transactions, None);
+        assert!(transactions
+            .iter()
+            .all(|tx| tx.max_fee_per_gas.is_none() && tx.max_priority_fee_per_gas.is_none()));
+    }
+
+    #[rocket::async_test]
+    async fn test_cancel_all_orders_caps_orders() {
+        let orders: Vec<RaindexOrder> = (0..MAX_CANCEL_ALL_ORDERS + 5)
+            .map(|i| owner_order(&format!("{:#066x}", 0x1000 + u32::from(i))))
+            .collect();
+        let ds = MockOrderDataSource {
+            orders: Ok(orders),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![]),
+            calldata: Ok(mock_calldata()),
+        };
+
+        let result = process_cancel_all_orders(&ds, Address::ZERO, 64)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.order_hashes.len(),
+            usize::from(MAX_CANCEL_ALL_ORDERS)
+        );
+        assert_eq!(
+            result.transactions.len(),
+            usize::from(MAX_CANCEL_ALL_ORDERS)
+        );
+        assert_eq!(
+            result.total_active_orders,
+            u32::from(MAX_CANCEL_ALL_ORDERS) + 5
+        );
+        assert!(result.truncated);
+    }
+
+    #[rocket::async_test]
+    async fn test_cancel_all_orders_not_found_without_active_orders() {
+        let ds = MockOrderDataSource {
+            orders: Ok(vec![]),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![]),
+            calldata: Ok(mock_calldata()),
+        };
+        let result = process_cancel_all_orders(&ds, Address::ZERO, 64).await;
+        assert!(matches!(result, Err(ApiError::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_authorize_owner_checks_key_binding() {
+        let owner: Address = "0x1234567890abcdef1234567890abcdef12345678"
+            .parse()
+            .unwrap();
+
+        assert!(authorize_owner(
+            &api_key("0x1234567890ABCDEF1234567890abcdef12345678", false),
+            owner
+        )
+        .is_ok());
+        assert!(authorize_owner(&api_key("contact@example.com", true), owner).is_ok());
+        assert!(matches!(
+            authorize_owner(&api_key("contact@example.com", false), owner),
+            Err(ApiError::Forbidden(_))
+        ));
+        assert!(matches!(
+            authorize_owner(&api_key(&format!("{:#x}", Address::ZERO), false), owner),
+            Err(ApiError::Forbidden(_))
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_cancel_all_403_for_unbound_key() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = crate::test_helpers::seed_api_key(&client).await;
+        let header = crate::test_helpers::basic_auth_header(&key_id, &secret);
+        let response = client
+            .post("/v1/order/cancel/all")
+            .header(ContentType::JSON)
+            .header(rocket::http::Header::new("Authorization", header))
+            .body(r#"{"owner":"0x1234567890abcdef1234567890abcdef12345678"}"#)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
     #[rocket::async_test]
     async fn test_cancel_order_401_without_auth() {
         let client = TestClientBuilder::new().build().await;
@@ -1,6 +1,9 @@
+use crate::app_state::ApplicationState;
 use crate::auth::AuthenticatedKey;
 use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
+use crate::fairings::{
+    record_token_pair, short_address, GlobalRateLimit, JsonContentType, TracingSpan,
+};
 use crate::types::order::{DeployDcaOrderRequest, DeployOrderResponse};
 use rocket::serde::json::Json;
 use rocket::State;
@@ -16,6 +19,7 @@ use tracing::Instrument;
         (status = 200, description = "DCA order deployment result", body = DeployOrderResponse),
         (status = 400, description = "Bad request", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Request involves a restricted address or an owner not allowed to deploy", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
     )
@@ -24,16 +28,142 @@ use tracing::Instrument;
 pub async fn post_order_dca(
     _global: GlobalRateLimit,
     _key: AuthenticatedKey,
+    _json: JsonContentType,
+    app_state: &State<ApplicationState>,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
     span: TracingSpan,
     request: Json<DeployDcaOrderRequest>,
 ) -> Result<Json<DeployOrderResponse>, ApiError> {
     let req = request.into_inner();
+    record_token_pair(
+        &span.0,
+        &short_address(req.input_token),
+        &short_address(req.output_token),
+    );
     async move {
         tracing::info!(body = ?req, "request received");
+        app_state
+            .denylist
+            .check(&[req.input_token, req.output_token])?;
+        app_state.allowed_owners.check(req.owner)?;
+        super::reject_skip_deposit(req.skip_deposit)?;
+        super::reject_client_ref(req.client_ref.as_deref())?;
         let _raindex = shared_raindex.read().await;
         todo!()
     }
     .instrument(span.0)
     .await
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helpers::{basic_auth_header, seed_api_key, TestClientBuilder};
+    use alloy::primitives::address;
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn test_skip_deposit_returns_400_until_deploy_can_omit_deposit() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_api_key(&client).await;
+        let response = client
+            .post("/v1/order/dca")
+            .header(ContentType::JSON)
+            .header(Header::new("Authorization", basic_auth_header(&key_id, &secret)))
+            .body(r#"{"inputToken":"0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913","outputToken":"0x4200000000000000000000000000000000000006","budgetAmount":"1000","period":4,"periodUnit":"hours","startIo":"0.0005","floorIo":"0.0003","outputVaultId":"0x1","skipDeposit":true}"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(
+            body["error"]["message"],
+            "skipDeposit is not supported yet: deployments always include the deposit"
+        );
+    }
+
+    async fn post_with_owner(
+        allowed: Vec<alloy::primitives::Address>,
+        owner: Option<&str>,
+    ) -> (Status, serde_json::Value) {
+        let client = TestClientBuilder::new()
+            .allowed_owners(allowed)
+            .build()
+            .await;
+        let owner = owner
+            .map(|owner| format!(r#","owner":"{owner}""#))
+            .unwrap_or_default();
+        let (key_id, secret) = seed_api_key(&client).await;
+        let response = client
+            .post("/v1/order/dca")
+            .header(ContentType::JSON)
+            .header(Header::new("Authorization", basic_auth_header(&key_id, &secret)))
+            .body(format!(r#"{{"inputToken":"0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913","outputToken":"0x4200000000000000000000000000000000000006","budgetAmount":"1000","period":4,"periodUnit":"hours","startIo":"0.0005","floorIo":"0.0003","skipDeposit":true{owner}}}"#))
+            .dispatch()
+            .await;
+        let status = response.status();
+        let body = serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        (status, body)
+    }
+
+    #[rocket::async_test]
+    async fn test_disallowed_owner_returns_403() {
+        let (status, body) = post_with_owner(
+            vec![address!("1111111111111111111111111111111111111111")],
+            Some("0x2222222222222222222222222222222222222222"),
+        )
+        .await;
+
+        assert_eq!(status, Status::Forbidden);
+        assert_eq!(
+            body["error"]["message"],
+            "owner is not allowed to deploy orders"
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_allowed_owner_passes_owner_check() {
+        let (status, body) = post_with_owner(
+            vec![address!("1111111111111111111111111111111111111111")],
+            Some("0x1111111111111111111111111111111111111111"),
+        )
+        .await;
+
+        // Past the owner check, the request fails on the next validation.
+        assert_eq!(status, Status::BadRequest);
+        assert_eq!(
+            body["error"]["message"],
+            "skipDeposit is not supported yet: deployments always include the deposit"
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_missing_owner_returns_403_when_allowlist_configured() {
+        let (status, body) = post_with_owner(
+            vec![address!("1111111111111111111111111111111111111111")],
+            None,
+        )
+        .await;
+
+        assert_eq!(status, Status::Forbidden);
+        assert_eq!(
+            body["error"]["message"],
+            "owner is required to deploy orders"
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_empty_allowlist_lets_any_owner_deploy() {
+        let (status, body) = post_with_owner(
+            Vec::new(),
+            Some("0x2222222222222222222222222222222222222222"),
+        )
+        .await;
+
+        assert_eq!(status, Status::BadRequest);
+        assert_eq!(
+            body["error"]["message"],
+            "skipDeposit is not supported yet: deployments always include the deposit"
+        );
+    }
+}
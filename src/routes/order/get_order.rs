@@ -1,23 +1,35 @@
-use super::{OrderDataSource, RaindexOrderDataSource};
+use super::{CachedOrderDataSource, OrderDataSource, RaindexOrderDataSource};
 use crate::app_state::ApplicationState;
 use crate::auth::AuthenticatedKey;
 use crate::db::DbPool;
-use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
-use crate::types::common::{Denomination, TokenRef, ValidatedFixedBytes};
+use crate::error::{ApiError, ApiErrorResponse, NotFoundReason};
+use crate::fairings::{
+    record_orderbook, record_token_pair, short_address, GlobalRateLimit, KnownQueryParams,
+    TracingSpan,
+};
+use crate::fields::FieldSelection;
+use crate::routes::status::{not_found_unless_lagging, LiveIndexingStatusDataSource};
+use crate::types::common::{Denomination, InvalidPathParam, TokenRef, ValidatedFixedBytes};
 use crate::types::order::{
-    OrderDetail, OrderDetailParams, OrderDetailsInfo, OrderTradeEntry, OrderType,
+    OrderDetail, OrderDetailParams, OrderDetailsInfo, OrderIoEntry, OrderTradeEntry,
+    OrderTradesMode, OrderType, RawOrder, RawOrderIo,
 };
 use crate::wrap_ratio::WrapRatioValue;
 use alloy::primitives::{Address, B256};
+use alloy::sol_types::SolValue;
+use rain_orderbook_bindings::IRaindexV6::{OrderV4, IOV2};
 use rain_orderbook_common::parsed_meta::ParsedMeta;
 use rain_orderbook_common::raindex_client::orders::RaindexOrder;
 use rain_orderbook_common::raindex_client::trades::RaindexTrade;
+use rain_orderbook_common::raindex_client::vaults::RaindexVault;
 use rocket::serde::json::Json;
 use rocket::State;
 use std::collections::HashMap;
 use tracing::Instrument;
 
+/// Trades embedded with `trades=recent`, newest first.
+const RECENT_ORDER_TRADES: usize = 20;
+
 #[utoipa::path(
     get,
     path = "/v1/order/{order_hash}",
@@ -28,7 +40,9 @@ use tracing::Instrument;
         OrderDetailParams,
     ),
     responses(
-        (status = 200, description = "Order details", body = OrderDetail),
+        (status = 200, description = "Order details, projected to `fields` when given", body = OrderDetail),
+        (status = 202, description = "Order not found while an orderbook subgraph is lagging; retry later", body = ApiErrorResponse),
+        (status = 400, description = "Bad request", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 404, description = "Order not found", body = ApiErrorResponse),
@@ -39,62 +53,187 @@ use tracing::Instrument;
 #[get("/<order_hash>?<params..>")]
 pub async fn get_order(
     _global: GlobalRateLimit,
-    _key: AuthenticatedKey,
+    key: AuthenticatedKey,
+    _query: KnownQueryParams<OrderDetailParams>,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
     app_state: &State<ApplicationState>,
     pool: &State<DbPool>,
     span: TracingSpan,
-    order_hash: ValidatedFixedBytes,
+    order_hash: Result<ValidatedFixedBytes, InvalidPathParam>,
     params: OrderDetailParams,
-) -> Result<Json<OrderDetail>, ApiError> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     async move {
+        let order_hash = order_hash.map_err(|e| e.reject("order hash"))?;
         tracing::info!(order_hash = ?order_hash, params = ?params, "request received");
         let hash = order_hash.0;
         let denomination = params.denomination.unwrap_or_default();
+        let fields = FieldSelection::parse::<OrderDetail>(params.fields.as_deref())?;
         let raindex = shared_raindex.read().await;
-        let ds = RaindexOrderDataSource {
+        let raindex_ds = RaindexOrderDataSource {
             client: raindex.client(),
             caches: &app_state.response_caches,
             pool: Some(pool.inner()),
         };
-        let detail = process_get_order(&ds, hash, denomination).await?;
-        Ok(Json(detail))
+        let ds = CachedOrderDataSource {
+            inner: &raindex_ds,
+            caches: &app_state.response_caches,
+            bypass_cache: params.no_cache.unwrap_or(false),
+        };
+        let include_raw = parse_include(params.include.as_deref())?;
+        let trades_mode = params.trades.unwrap_or_default();
+        let lookup = async {
+            let orders = ds.get_orders_by_hash(hash).await?;
+            let detail =
+                order_detail_from_lookup(&ds, &orders, denomination, include_raw, trades_mode)
+                    .await?;
+            Ok::<_, ApiError>((detail, orders))
+        };
+        let (detail, orders) = not_found_unless_lagging(
+            &LiveIndexingStatusDataSource,
+            lookup.await,
+            app_state.not_found_max_lag_blocks,
+            || raindex.orderbook_sources(),
+        )
+        .await?;
+        let span = tracing::Span::current();
+        record_token_pair(
+            &span,
+            &token_label(&detail.input_token),
+            &token_label(&detail.output_token),
+        );
+        record_orderbook(&span, detail.orderbook_id);
+        let mut body = crate::fields::project(&detail, fields.as_ref())?;
+        if params.display.unwrap_or(false) {
+            crate::amounts::emit_display_amounts(&mut body);
+        }
+        if params.numeric_amounts.unwrap_or(false) {
+            crate::amounts::emit_numeric_amounts(
+                &mut body,
+                crate::amounts::ORDER_DETAIL_AMOUNT_KEYS,
+            );
+        }
+        crate::timestamps::apply_time_format(&mut body, params.time_format.unwrap_or_default());
+        crate::vault_ids::apply_vault_id_format(
+            &mut body,
+            params.vault_id_format.unwrap_or_default(),
+        );
+        if crate::debug_raw::debug_raw_enabled(params.debug_raw, &key) {
+            crate::debug_raw::attach_raw(&mut body, &orders);
+        }
+        Ok(Json(body))
     }
     .instrument(span.0)
     .await
 }
 
-async fn process_get_order(
+fn token_label(token: &TokenRef) -> String {
+    if token.symbol.is_empty() {
+        short_address(token.address)
+    } else {
+        token.symbol.clone()
+    }
+}
+
+/// Whether `include` asks for the raw order; any other extra is rejected.
+fn parse_include(include: Option<&str>) -> Result<bool, ApiError> {
+    let mut raw = false;
+    for value in include.unwrap_or_default().split(',').map(str::trim) {
+        match value {
+            "" => {}
+            "raw" => raw = true,
+            other => {
+                return Err(ApiError::BadRequest(format!(
+                    "unknown include: {other}; expected one of: raw"
+                )))
+            }
+        }
+    }
+    Ok(raw)
+}
+
+pub(super) async fn process_get_order(
     ds: &dyn OrderDataSource,
     hash: B256,
     denomination: Denomination,
+    include_raw: bool,
+    trades_mode: OrderTradesMode,
 ) -> Result<OrderDetail, ApiError> {
     let orders = ds.get_orders_by_hash(hash).await?;
-    let order = orders
-        .into_iter()
-        .next()
-        .ok_or_else(|| ApiError::NotFound("order not found".into()))?;
-    let quotes = ds.get_order_quotes(&order).await?;
-    let io_ratio = quotes
-        .first()
+    order_detail_from_lookup(ds, &orders, denomination, include_raw, trades_mode).await
+}
+
+/// Builds the detail from an already fetched `get_orders_by_hash` result, so
+/// the handler can reuse the lookup for `debug_raw`.
+pub(super) async fn order_detail_from_lookup(
+    ds: &dyn OrderDataSource,
+    orders: &[RaindexOrder],
+    denomination: Denomination,
+    include_raw: bool,
+    trades_mode: OrderTradesMode,
+) -> Result<OrderDetail, ApiError> {
+    let order = orders.first().ok_or_else(|| {
+        ApiError::not_found_because(NotFoundReason::OrderNotFound, "order not found")
+    })?;
+    let quotes = ds.get_order_quotes(order).await?;
+    let io_ratio = crate::routes::first_successful_quote(&quotes)
         .and_then(|q| q.data.as_ref())
-        .map(|d| d.formatted_ratio.clone())
-        .unwrap_or_else(|| "-".into());
-    let trades = ds.get_order_trades(&order).await?;
+        .map(|d| d.formatted_ratio.clone());
+    let (trades, trades_loaded) = match trades_mode {
+        OrderTradesMode::None => (Vec::new(), false),
+        OrderTradesMode::Recent | OrderTradesMode::All => match ds.get_order_trades(order).await {
+            Ok(mut trades) => {
+                if trades_mode == OrderTradesMode::Recent {
+                    trades.sort_by_key(|trade| std::cmp::Reverse(trade.timestamp()));
+                    trades.truncate(RECENT_ORDER_TRADES);
+                }
+                (trades, true)
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "returning order detail without trades");
+                (Vec::new(), false)
+            }
+        },
+    };
     let wrap_ratios =
-        current_wrap_ratios_for_order_detail(ds, denomination, &order, &trades).await?;
-    let order_type = determine_order_type(&order);
-    build_order_detail(
-        &order,
+        current_wrap_ratios_for_order_detail(ds, denomination, order, &trades).await?;
+    let order_type = determine_order_type(order);
+    let mut detail = build_order_detail(
+        order,
         order_type,
-        &io_ratio,
+        io_ratio,
         &trades,
         denomination,
         &wrap_ratios,
-    )
+    )?;
+    detail.trades_loaded = trades_loaded;
+    if include_raw {
+        detail.raw = Some(build_raw_order(order)?);
+    }
+    Ok(detail)
 }
 
-fn determine_order_type(order: &RaindexOrder) -> OrderType {
+fn build_raw_order(order: &RaindexOrder) -> Result<RawOrder, ApiError> {
+    let order_bytes = order.order_bytes();
+    let decoded = OrderV4::abi_decode(&order_bytes).map_err(|e| {
+        tracing::error!(error = %e, order_hash = %order.order_hash(), "failed to decode order bytes");
+        ApiError::Internal("failed to decode order bytes".into())
+    })?;
+    let map_io = |io: &IOV2| RawOrderIo {
+        token: io.token,
+        vault_id: io.vaultId,
+    };
+    Ok(RawOrder {
+        valid_inputs: decoded.validInputs.iter().map(map_io).collect(),
+        valid_outputs: decoded.validOutputs.iter().map(map_io).collect(),
+        order_bytes,
+    })
+}
+
+/// Classifies an order by the deployment recorded in its builder state.
+/// Orders without builder state were not deployed through st0x and are
+/// [`OrderType::Unknown`].
+pub(crate) fn determine_order_type(order: &RaindexOrder) -> OrderType {
+    let mut order_type = OrderType::Unknown;
     for meta in order.parsed_meta() {
         if let ParsedMeta::OrderBuilderStateV1(builder_state) = meta {
             if builder_state
@@ -104,15 +243,16 @@ fn determine_order_type(order: &RaindexOrder) -> OrderType {
             {
                 return OrderType::Dca;
             }
+            order_type = OrderType::Solver;
         }
     }
-    OrderType::Solver
+    order_type
 }
 
 fn build_order_detail(
     order: &RaindexOrder,
     order_type: OrderType,
-    io_ratio: &str,
+    io_ratio: Option<String>,
     trades: &[RaindexTrade],
     denomination: Denomination,
     wrap_ratios: &HashMap<Address, WrapRatioValue>,
@@ -127,6 +267,19 @@ fn build_order_detail(
         .map(|trade| map_trade(trade, denomination, wrap_ratios))
         .collect::<Result<Vec<_>, ApiError>>()?;
 
+    let inputs = order
+        .inputs_list()
+        .items()
+        .iter()
+        .map(|vault| map_io_vault(vault, denomination, wrap_ratios))
+        .collect::<Result<Vec<_>, ApiError>>()?;
+    let outputs = order
+        .outputs_list()
+        .items()
+        .iter()
+        .map(|vault| map_io_vault(vault, denomination, wrap_ratios))
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
     let created_at: u64 = order.timestamp_added().try_into().unwrap_or(0);
     let input_vault_balance = if denomination == Denomination::Unwrapped {
         crate::denomination::convert_wrapped_amount_for_token(
@@ -146,15 +299,16 @@ fn build_order_detail(
     } else {
         output.formatted_balance()
     };
-    let converted_io_ratio = if denomination == Denomination::Unwrapped {
-        crate::denomination::convert_wrapped_io_ratio(
-            io_ratio.to_string(),
-            input_token_info.address(),
-            output_token_info.address(),
-            wrap_ratios,
-        )?
-    } else {
-        io_ratio.to_string()
+    let converted_io_ratio = match io_ratio {
+        Some(io_ratio) if denomination == Denomination::Unwrapped => {
+            Some(crate::denomination::convert_wrapped_io_ratio(
+                io_ratio,
+                input_token_info.address(),
+                output_token_info.address(),
+                wrap_ratios,
+            )?)
+        }
+        io_ratio => io_ratio,
     };
 
     Ok(OrderDetail {
@@ -178,10 +332,72 @@ fn build_order_detail(
         output_vault_id: output.vault_id(),
         input_vault_balance,
         output_vault_balance,
+        inputs,
+        outputs,
         io_ratio: converted_io_ratio,
         created_at,
         orderbook_id: order.raindex(),
         trades: trade_entries,
+        trades_loaded: true,
+        raw: None,
+        client_ref: client_ref(order),
+    })
+}
+
+/// The `clientRef` recorded in the order's builder state, read through its
+/// serialized form so any field value shape carrying a string is accepted.
+/// Values that would not have passed validation are ignored.
+fn client_ref(order: &RaindexOrder) -> Option<String> {
+    for meta in order.parsed_meta() {
+        if let ParsedMeta::OrderBuilderStateV1(builder_state) = meta {
+            let client_ref = serde_json::to_value(&builder_state)
+                .ok()
+                .and_then(|state| client_ref_from_builder_state(&state));
+            if client_ref.is_some() {
+                return client_ref;
+            }
+        }
+    }
+    None
+}
+
+fn client_ref_from_builder_state(state: &serde_json::Value) -> Option<String> {
+    let field = state
+        .get("fieldValues")
+        .or_else(|| state.get("field_values"))?
+        .get(super::CLIENT_REF_FIELD)?;
+    let value = field
+        .as_str()
+        .or_else(|| field.get("value").and_then(|v| v.as_str()))?;
+    super::validate_client_ref(Some(value))
+        .is_ok()
+        .then(|| value.to_string())
+}
+
+fn map_io_vault(
+    vault: &RaindexVault,
+    denomination: Denomination,
+    wrap_ratios: &HashMap<Address, WrapRatioValue>,
+) -> Result<OrderIoEntry, ApiError> {
+    let token = vault.token();
+    let vault_balance = if denomination == Denomination::Unwrapped {
+        crate::denomination::convert_wrapped_amount_for_token(
+            vault.formatted_balance(),
+            token.address(),
+            wrap_ratios,
+        )?
+    } else {
+        vault.formatted_balance()
+    };
+
+    Ok(OrderIoEntry {
+        token: TokenRef {
+            address: token.address(),
+            symbol: token.symbol().unwrap_or_default(),
+            decimals: token.decimals(),
+        },
+        vault_id: vault.vault_id(),
+        vault_balance,
     })
 }
 
@@ -235,8 +451,13 @@ async fn current_wrap_ratios_for_order_detail(
         return Ok(HashMap::new());
     }
 
-    let (input, output) = crate::routes::resolve_io_vaults(order)?;
-    let mut token_addresses = vec![input.token().address(), output.token().address()];
+    let mut token_addresses: Vec<Address> = order
+        .inputs_list()
+        .items()
+        .iter()
+        .chain(order.outputs_list().items().iter())
+        .map(|vault| vault.token().address())
+        .collect();
     for trade in trades {
         token_addresses.push(trade.input_vault_balance_change().token().address());
         token_addresses.push(trade.output_vault_balance_change().token().address());
@@ -253,9 +474,11 @@ mod tests {
     use crate::error::ApiError;
     use crate::routes::order::test_fixtures::*;
     use crate::test_helpers::TestClientBuilder;
+    use crate::types::common::TimeFormat;
     use crate::wrap_ratio::WrapRatioValue;
     use alloy::primitives::address;
-    use alloy::primitives::{Address, Bytes};
+    use alloy::primitives::{Address, Bytes, U256};
+    use rain_orderbook_common::raindex_client::order_quotes::RaindexOrderQuote;
     use rocket::http::Status;
     use std::collections::HashMap;
 
@@ -267,9 +490,15 @@ mod tests {
             quotes: Ok(vec![mock_quote("1.5")]),
             calldata: Ok(Bytes::new()),
         };
-        let detail = process_get_order(&ds, test_hash(), Denomination::Wrapped)
-            .await
-            .unwrap();
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await
+        .unwrap();
 
         assert_eq!(detail.order_hash, test_hash());
         assert_eq!(
@@ -282,11 +511,12 @@ mod tests {
         assert_eq!(detail.output_token.symbol, "WETH");
         assert_eq!(detail.input_vault_balance, "1.000000");
         assert_eq!(detail.output_vault_balance, "0.500000000000000000");
-        assert_eq!(detail.io_ratio, "1.5");
-        assert_eq!(detail.order_details.type_, OrderType::Solver);
-        assert_eq!(detail.order_details.io_ratio, "1.5");
+        assert_eq!(detail.io_ratio.as_deref(), Some("1.5"));
+        assert_eq!(detail.order_details.type_, OrderType::Unknown);
+        assert_eq!(detail.order_details.io_ratio.as_deref(), Some("1.5"));
         assert_eq!(detail.created_at, 1700000000);
         assert_eq!(detail.trades.len(), 1);
+        assert!(detail.trades_loaded);
         assert_eq!(detail.trades[0].input_amount, "0.500000");
         assert_eq!(detail.trades[0].output_amount, "-0.250000000000000000");
         assert_eq!(detail.trades[0].timestamp, 1700001000);
@@ -300,8 +530,21 @@ mod tests {
             quotes: Ok(vec![]),
             calldata: Ok(Bytes::new()),
         };
-        let result = process_get_order(&ds, test_hash(), Denomination::Wrapped).await;
-        assert!(matches!(result, Err(ApiError::NotFound(_))));
+        let result = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ApiError::NotFound {
+                reason: Some(NotFoundReason::OrderNotFound),
+                ..
+            })
+        ));
     }
 
     #[rocket::async_test]
@@ -312,11 +555,18 @@ mod tests {
             quotes: Ok(vec![mock_quote("2.0")]),
             calldata: Ok(Bytes::new()),
         };
-        let detail = process_get_order(&ds, test_hash(), Denomination::Wrapped)
-            .await
-            .unwrap();
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await
+        .unwrap();
         assert!(detail.trades.is_empty());
-        assert_eq!(detail.io_ratio, "2.0");
+        assert!(detail.trades_loaded);
+        assert_eq!(detail.io_ratio.as_deref(), Some("2.0"));
     }
 
     #[rocket::async_test]
@@ -327,11 +577,88 @@ mod tests {
             quotes: Ok(vec![mock_failed_quote()]),
             calldata: Ok(Bytes::new()),
         };
-        let detail = process_get_order(&ds, test_hash(), Denomination::Wrapped)
-            .await
-            .unwrap();
-        assert_eq!(detail.io_ratio, "-");
-        assert_eq!(detail.order_details.io_ratio, "-");
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await
+        .unwrap();
+        assert_eq!(detail.io_ratio, None);
+        assert_eq!(detail.order_details.io_ratio, None);
+        let body = serde_json::to_value(&detail).unwrap();
+        assert!(body["ioRatio"].is_null());
+        assert!(body["orderDetails"]["ioRatio"].is_null());
+    }
+
+    #[rocket::async_test]
+    async fn test_process_get_order_checksums_orderbook_address() {
+        let ds = MockOrderDataSource {
+            orders: Ok(vec![mock_order()]),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![mock_quote("2")]),
+            calldata: Ok(Bytes::new()),
+        };
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await
+        .unwrap();
+
+        let body = serde_json::to_value(&detail).unwrap();
+        // The fixture's subgraph payload has the orderbook in lowercase.
+        assert_eq!(
+            body["orderbookId"],
+            "0xd2938E7c9fe3597F78832CE780Feb61945c377d7"
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_process_get_order_skips_failed_quote_for_io_ratio() {
+        let ds = MockOrderDataSource {
+            orders: Ok(vec![mock_order()]),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![mock_failed_quote(), mock_quote("1.75")]),
+            calldata: Ok(Bytes::new()),
+        };
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await
+        .unwrap();
+        assert_eq!(detail.io_ratio.as_deref(), Some("1.75"));
+        assert_eq!(detail.order_details.io_ratio.as_deref(), Some("1.75"));
+    }
+
+    #[rocket::async_test]
+    async fn test_process_get_order_without_quotes_has_null_io_ratio() {
+        let ds = MockOrderDataSource {
+            orders: Ok(vec![mock_order()]),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![]),
+            calldata: Ok(Bytes::new()),
+        };
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Unwrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await
+        .unwrap();
+        assert_eq!(detail.io_ratio, None);
+        assert_eq!(detail.order_details.io_ratio, None);
     }
 
     #[rocket::async_test]
@@ -342,7 +669,14 @@ mod tests {
             quotes: Ok(vec![]),
             calldata: Ok(Bytes::new()),
         };
-        let result = process_get_order(&ds, test_hash(), Denomination::Wrapped).await;
+        let result = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await;
         assert!(matches!(result, Err(ApiError::Internal(_))));
     }
 
@@ -354,20 +688,37 @@ mod tests {
             quotes: Err(ApiError::Internal("failed to query order quotes".into())),
             calldata: Ok(Bytes::new()),
         };
-        let result = process_get_order(&ds, test_hash(), Denomination::Wrapped).await;
+        let result = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await;
         assert!(matches!(result, Err(ApiError::Internal(_))));
     }
 
     #[rocket::async_test]
-    async fn test_process_get_order_trades_failure() {
+    async fn test_process_get_order_trades_failure_is_flagged() {
         let ds = MockOrderDataSource {
             orders: Ok(vec![mock_order()]),
             trades: Err(ApiError::Internal("failed to query order trades".into())),
             quotes: Ok(vec![mock_quote("1.5")]),
             calldata: Ok(Bytes::new()),
         };
-        let result = process_get_order(&ds, test_hash(), Denomination::Wrapped).await;
-        assert!(matches!(result, Err(ApiError::Internal(_))));
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await
+        .unwrap();
+        assert!(detail.trades.is_empty());
+        assert!(!detail.trades_loaded);
+        assert_eq!(detail.io_ratio.as_deref(), Some("1.5"));
     }
 
     #[rocket::async_test]
@@ -381,9 +732,15 @@ mod tests {
         let hash = "0x000000000000000000000000000000000000000000000000000000000000beef"
             .parse()
             .unwrap();
-        let detail = process_get_order(&ds, hash, Denomination::Wrapped)
-            .await
-            .unwrap();
+        let detail = process_get_order(
+            &ds,
+            hash,
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await
+        .unwrap();
 
         assert_eq!(detail.input_token.symbol, "wtMSTR");
         assert_eq!(detail.output_token.symbol, "wtMSTR");
@@ -391,6 +748,518 @@ mod tests {
         assert_eq!(detail.output_vault_balance, "0");
     }
 
+    #[rocket::async_test]
+    async fn test_process_get_order_lists_all_inputs_and_outputs() {
+        let mut value = order_json();
+        let mut second_input = value["inputs"][0].clone();
+        second_input["id"] = serde_json::json!("0x03");
+        second_input["vaultId"] =
+            serde_json::json!("0x0000000000000000000000000000000000000000000000000000000000000003");
+        second_input["formattedBalance"] = serde_json::json!("0.00000001");
+        second_input["token"] = serde_json::json!({
+            "chainId": 8453,
+            "id": "0x2260fac5e5542a773aa44fbcfedf7c193bc2c599",
+            "address": "0x2260fac5e5542a773aa44fbcfedf7c193bc2c599",
+            "name": "Wrapped BTC",
+            "symbol": "WBTC",
+            "decimals": 8
+        });
+        value["inputs"].as_array_mut().unwrap().push(second_input);
+        let order: RaindexOrder = serde_json::from_value(value).unwrap();
+
+        let ds = MockOrderDataSource {
+            orders: Ok(vec![order]),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![mock_quote("1.5")]),
+            calldata: Ok(Bytes::new()),
+        };
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(detail.inputs.len(), 2);
+        assert_eq!(detail.inputs[0].token.symbol, "USDC");
+        assert_eq!(detail.inputs[0].vault_id, U256::from(1));
+        assert_eq!(detail.inputs[0].vault_balance, "1.000000");
+        assert_eq!(detail.inputs[1].token.symbol, "WBTC");
+        assert_eq!(detail.inputs[1].token.decimals, 8);
+        assert_eq!(detail.inputs[1].vault_id, U256::from(3));
+        assert_eq!(detail.inputs[1].vault_balance, "0.00000001");
+        assert_eq!(detail.outputs.len(), 1);
+        assert_eq!(detail.outputs[0].token.symbol, "WETH");
+        assert_eq!(detail.outputs[0].vault_id, U256::from(2));
+
+        assert_eq!(detail.input_token.symbol, "USDC");
+        assert_eq!(detail.input_vault_id, detail.inputs[0].vault_id);
+        assert_eq!(detail.output_token.symbol, "WETH");
+    }
+
+    #[rocket::async_test]
+    async fn test_process_get_order_includes_raw_order_when_requested() {
+        use rain_orderbook_bindings::IRaindexV6::EvaluableV4;
+
+        let usdc = address!("833589fcd6edb6e08f4c7c32d4f71b54bda02913");
+        let weth = address!("4200000000000000000000000000000000000006");
+        let struct_order = OrderV4 {
+            owner: address!("0000000000000000000000000000000000000001"),
+            nonce: B256::with_last_byte(7),
+            evaluable: EvaluableV4 {
+                interpreter: Address::repeat_byte(2),
+                store: Address::repeat_byte(3),
+                bytecode: Bytes::from(vec![0x01, 0x02]),
+            },
+            validInputs: vec![IOV2 {
+                token: usdc,
+                vaultId: B256::with_last_byte(1),
+            }],
+            validOutputs: vec![IOV2 {
+                token: weth,
+                vaultId: B256::with_last_byte(2),
+            }],
+        };
+        let encoded = Bytes::from(struct_order.abi_encode());
+        let mut value = order_json();
+        value["orderBytes"] = serde_json::json!(encoded);
+        let order: RaindexOrder = serde_json::from_value(value).unwrap();
+        let ds = MockOrderDataSource {
+            orders: Ok(vec![order]),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![mock_quote("1.5")]),
+            calldata: Ok(Bytes::new()),
+        };
+
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            true,
+            OrderTradesMode::All,
+        )
+        .await
+        .unwrap();
+        let raw = detail.raw.expect("raw order");
+        assert_eq!(raw.order_bytes, encoded);
+        assert_eq!(raw.valid_inputs.len(), 1);
+        assert_eq!(raw.valid_inputs[0].token, usdc);
+        assert_eq!(raw.valid_inputs[0].vault_id, B256::with_last_byte(1));
+        assert_eq!(raw.valid_outputs[0].token, weth);
+        assert_eq!(raw.valid_outputs[0].vault_id, B256::with_last_byte(2));
+
+        let default = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await
+        .unwrap();
+        assert!(default.raw.is_none());
+        let body = serde_json::to_value(&default).unwrap();
+        assert!(body.get("raw").is_none());
+    }
+
+    #[test]
+    fn test_parse_include_accepts_raw_only() {
+        assert!(!parse_include(None).unwrap());
+        assert!(parse_include(Some("raw")).unwrap());
+        assert!(parse_include(Some(" raw ,")).unwrap());
+        assert!(matches!(
+            parse_include(Some("raw,trades")),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_process_get_order_projects_requested_fields() {
+        let ds = MockOrderDataSource {
+            orders: Ok(vec![mock_order()]),
+            trades: Ok(vec![mock_trade()]),
+            quotes: Ok(vec![mock_quote("1.5")]),
+            calldata: Ok(Bytes::new()),
+        };
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await
+        .unwrap();
+        let fields = FieldSelection::parse::<OrderDetail>(Some("order_hash,io_ratio,trades"))
+            .unwrap()
+            .unwrap();
+
+        let projected = crate::fields::project(&detail, Some(&fields)).unwrap();
+
+        let object = projected.as_object().unwrap();
+        assert_eq!(object.len(), 3);
+        assert_eq!(object["orderHash"], serde_json::json!(test_hash()));
+        assert_eq!(object["ioRatio"], "1.5");
+        assert_eq!(object["trades"].as_array().unwrap().len(), 1);
+        assert!(object.get("owner").is_none());
+    }
+
+    #[rocket::async_test]
+    async fn test_order_created_at_in_unix_and_iso_formats() {
+        let ds = MockOrderDataSource {
+            orders: Ok(vec![mock_order()]),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![mock_quote("1.5")]),
+            calldata: Ok(Bytes::new()),
+        };
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::None,
+        )
+        .await
+        .unwrap();
+
+        let mut unix = crate::fields::project(&detail, None).unwrap();
+        crate::timestamps::apply_time_format(&mut unix, TimeFormat::Unix);
+        assert_eq!(unix["createdAt"], serde_json::json!(1700000000));
+
+        let mut iso = crate::fields::project(&detail, None).unwrap();
+        crate::timestamps::apply_time_format(&mut iso, TimeFormat::Iso);
+        assert_eq!(iso["createdAt"], "2023-11-14T22:13:20Z");
+    }
+
+    #[test]
+    fn test_order_detail_fields_match_serialized_keys() {
+        let detail = serde_json::to_value(OrderDetail {
+            order_hash: test_hash(),
+            owner: Address::ZERO,
+            order_details: OrderDetailsInfo {
+                type_: OrderType::Solver,
+                io_ratio: None,
+            },
+            input_token: TokenRef {
+                address: Address::ZERO,
+                symbol: String::new(),
+                decimals: 0,
+            },
+            output_token: TokenRef {
+                address: Address::ZERO,
+                symbol: String::new(),
+                decimals: 0,
+            },
+            input_vault_id: Default::default(),
+            output_vault_id: Default::default(),
+            input_vault_balance: "0".into(),
+            output_vault_balance: "0".into(),
+            inputs: vec![],
+            outputs: vec![],
+            io_ratio: None,
+            created_at: 0,
+            orderbook_id: Address::ZERO,
+            trades: vec![],
+            trades_loaded: true,
+            raw: Some(RawOrder {
+                order_bytes: Bytes::new(),
+                valid_inputs: vec![],
+                valid_outputs: vec![],
+            }),
+            client_ref: Some("acme-order-1234".into()),
+        })
+        .unwrap();
+
+        let mut keys: Vec<_> = detail.as_object().unwrap().keys().cloned().collect();
+        let mut fields: Vec<_> = <OrderDetail as crate::fields::SparseFields>::FIELDS
+            .iter()
+            .map(|field| field.to_string())
+            .collect();
+        keys.sort();
+        fields.sort();
+        assert_eq!(keys, fields);
+    }
+
+    #[rocket::async_test]
+    async fn test_get_order_rejects_short_hash_with_400() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = crate::test_helpers::seed_api_key(&client).await;
+        let header = crate::test_helpers::basic_auth_header(&key_id, &secret);
+
+        let response = client
+            .get("/v1/order/0xabcdef")
+            .header(rocket::http::Header::new("Authorization", header))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(
+            body["error"]["message"],
+            "invalid order hash: expected 32-byte hex"
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_get_order_rejects_unknown_field() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = crate::test_helpers::seed_api_key(&client).await;
+        let header = crate::test_helpers::basic_auth_header(&key_id, &secret);
+
+        let response = client
+            .get(format!(
+                "/v1/order/{:#x}?fields=order_hash,status",
+                test_hash()
+            ))
+            .header(rocket::http::Header::new("Authorization", header))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    struct CountingOrderDataSource {
+        inner: MockOrderDataSource,
+        lookups: std::sync::atomic::AtomicUsize,
+        trade_queries: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl OrderDataSource for CountingOrderDataSource {
+        async fn get_orders_by_hash(&self, hash: B256) -> Result<Vec<RaindexOrder>, ApiError> {
+            self.lookups
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.inner.get_orders_by_hash(hash).await
+        }
+        async fn get_active_orders_by_owner(
+            &self,
+            owner: Address,
+            limit: u16,
+        ) -> Result<(Vec<RaindexOrder>, u32), ApiError> {
+            self.inner.get_active_orders_by_owner(owner, limit).await
+        }
+        async fn get_order_quotes(
+            &self,
+            order: &RaindexOrder,
+        ) -> Result<Vec<RaindexOrderQuote>, ApiError> {
+            self.inner.get_order_quotes(order).await
+        }
+        async fn get_order_trades(
+            &self,
+            order: &RaindexOrder,
+        ) -> Result<Vec<RaindexTrade>, ApiError> {
+            self.trade_queries
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.inner.get_order_trades(order).await
+        }
+        async fn get_remove_calldata(&self, order: &RaindexOrder) -> Result<Bytes, ApiError> {
+            self.inner.get_remove_calldata(order).await
+        }
+    }
+
+    fn counting_order_data_source() -> CountingOrderDataSource {
+        CountingOrderDataSource {
+            inner: MockOrderDataSource {
+                orders: Ok(vec![mock_order()]),
+                trades: Ok(vec![]),
+                quotes: Ok(vec![mock_quote("1.5")]),
+                calldata: Ok(Bytes::new()),
+            },
+            lookups: std::sync::atomic::AtomicUsize::new(0),
+            trade_queries: std::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+
+    fn trades_at(count: u64) -> Vec<RaindexTrade> {
+        (0..count)
+            .map(|i| {
+                let mut trade = trade_json();
+                trade["timestamp"] = serde_json::json!(format!("0x{:064x}", 1_700_000_000 + i));
+                serde_json::from_value(trade).unwrap()
+            })
+            .collect()
+    }
+
+    #[rocket::async_test]
+    async fn test_trades_none_skips_trades_query() {
+        let mut ds = counting_order_data_source();
+        ds.inner.trades = Ok(vec![mock_trade()]);
+
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::None,
+        )
+        .await
+        .unwrap();
+
+        assert!(detail.trades.is_empty());
+        assert!(!detail.trades_loaded);
+        assert_eq!(
+            ds.trade_queries.load(std::sync::atomic::Ordering::SeqCst),
+            0
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_trades_recent_keeps_newest_trades() {
+        let mut ds = counting_order_data_source();
+        ds.inner.trades = Ok(trades_at(RECENT_ORDER_TRADES as u64 + 5));
+
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::Recent,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(detail.trades.len(), RECENT_ORDER_TRADES);
+        assert_eq!(
+            detail.trades[0].timestamp,
+            1_700_000_000 + RECENT_ORDER_TRADES as u64 + 4
+        );
+        assert_eq!(
+            detail.trades[RECENT_ORDER_TRADES - 1].timestamp,
+            1_700_000_005
+        );
+        assert!(detail.trades_loaded);
+        assert_eq!(
+            ds.trade_queries.load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_trades_all_returns_every_trade() {
+        let mut ds = counting_order_data_source();
+        ds.inner.trades = Ok(trades_at(RECENT_ORDER_TRADES as u64 + 5));
+
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::All,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(detail.trades.len(), RECENT_ORDER_TRADES + 5);
+        assert!(detail.trades_loaded);
+    }
+
+    #[rocket::async_test]
+    async fn test_get_order_rejects_unknown_trades_mode() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = crate::test_helpers::seed_api_key(&client).await;
+        let header = crate::test_helpers::basic_auth_header(&key_id, &secret);
+
+        let response = client
+            .get(format!("/v1/order/{:#x}?trades=some", test_hash()))
+            .header(rocket::http::Header::new("Authorization", header))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+    }
+
+    fn lookup_caches(lookup_ttl_seconds: u64) -> crate::cache::RouteResponseCaches {
+        crate::cache::RouteResponseCaches::new(
+            10,
+            std::time::Duration::from_secs(60),
+            std::time::Duration::from_secs(lookup_ttl_seconds),
+        )
+    }
+
+    #[rocket::async_test]
+    async fn test_cached_order_lookup_hits_cache_within_ttl() {
+        let inner = counting_order_data_source();
+        let caches = lookup_caches(60);
+        let ds = CachedOrderDataSource {
+            inner: &inner,
+            caches: &caches,
+            bypass_cache: false,
+        };
+
+        for _ in 0..2 {
+            let detail = process_get_order(
+                &ds,
+                test_hash(),
+                Denomination::Wrapped,
+                false,
+                OrderTradesMode::All,
+            )
+            .await
+            .unwrap();
+            assert_eq!(detail.order_hash, test_hash());
+        }
+
+        assert_eq!(inner.lookups.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[rocket::async_test]
+    async fn test_cached_order_lookup_bypass_refetches() {
+        let inner = counting_order_data_source();
+        let caches = lookup_caches(60);
+        let cached = CachedOrderDataSource {
+            inner: &inner,
+            caches: &caches,
+            bypass_cache: false,
+        };
+        let bypass = CachedOrderDataSource {
+            bypass_cache: true,
+            ..cached
+        };
+
+        cached.get_orders_by_hash(test_hash()).await.unwrap();
+        bypass.get_orders_by_hash(test_hash()).await.unwrap();
+        cached.get_orders_by_hash(test_hash()).await.unwrap();
+
+        assert_eq!(inner.lookups.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[rocket::async_test]
+    async fn test_cached_order_lookup_does_not_cache_missing_order() {
+        let mut inner = counting_order_data_source();
+        inner.inner.orders = Ok(vec![]);
+        let caches = lookup_caches(60);
+        let ds = CachedOrderDataSource {
+            inner: &inner,
+            caches: &caches,
+            bypass_cache: false,
+        };
+
+        assert!(ds.get_orders_by_hash(test_hash()).await.unwrap().is_empty());
+        assert!(ds.get_orders_by_hash(test_hash()).await.unwrap().is_empty());
+
+        assert_eq!(inner.lookups.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[rocket::async_test]
+    async fn test_cached_order_lookup_disabled_with_zero_ttl() {
+        let inner = counting_order_data_source();
+        let caches = lookup_caches(0);
+        let ds = CachedOrderDataSource {
+            inner: &inner,
+            caches: &caches,
+            bypass_cache: false,
+        };
+
+        ds.get_orders_by_hash(test_hash()).await.unwrap();
+        ds.get_orders_by_hash(test_hash()).await.unwrap();
+
+        assert_eq!(inner.lookups.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
     #[test]
     fn test_map_trade_converts_unwrapped_amounts() {
         let wrapped_output = address!("ff05e1bd696900dc6a52ca35ca61bb1024eda8e2");
@@ -417,9 +1286,120 @@ mod tests {
     }
 
     #[rocket::async_test]
-    async fn test_determine_order_type_solver_default() {
+    async fn test_determine_order_type_without_builder_state_is_unknown() {
+        let order = mock_order();
+        assert_eq!(determine_order_type(&order), OrderType::Unknown);
+    }
+
+    #[rocket::async_test]
+    async fn test_determine_order_type_with_dca_builder_state_is_dca() {
+        assert_eq!(determine_order_type(&dca_order()), OrderType::Dca);
+    }
+
+    #[test]
+    fn test_client_ref_read_from_builder_state_field() {
+        let state = serde_json::json!({
+            "selectedDeployment": "dca-base",
+            "fieldValues": {
+                "client-ref": { "id": "client-ref", "value": "acme-order-1234" },
+                "amount-per-trade": { "id": "amount-per-trade", "value": "100" }
+            }
+        });
+        assert_eq!(
+            client_ref_from_builder_state(&state).as_deref(),
+            Some("acme-order-1234")
+        );
+
+        let bare = serde_json::json!({ "field_values": { "client-ref": "acme-1" } });
+        assert_eq!(
+            client_ref_from_builder_state(&bare).as_deref(),
+            Some("acme-1")
+        );
+
+        let invalid = serde_json::json!({ "fieldValues": { "client-ref": "has space" } });
+        assert_eq!(client_ref_from_builder_state(&invalid), None);
+    }
+
+    #[rocket::async_test]
+    async fn test_order_without_builder_state_has_no_client_ref() {
+        let ds = MockOrderDataSource {
+            orders: Ok(vec![mock_order()]),
+            trades: Ok(vec![]),
+            quotes: Ok(vec![]),
+            calldata: Ok(Bytes::new()),
+        };
+        let detail = process_get_order(
+            &ds,
+            test_hash(),
+            Denomination::Wrapped,
+            false,
+            OrderTradesMode::None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(detail.client_ref, None);
+        let body = serde_json::to_value(&detail).unwrap();
+        assert!(body.get("clientRef").is_none());
+    }
+
+    /// Requests the mock order with `debug_raw=true`, serving the order and
+    /// its quote from the lookup caches so no subgraph is needed.
+    async fn get_order_with_debug_raw(admin: bool) -> serde_json::Value {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = if admin {
+            crate::test_helpers::seed_admin_key(&client).await
+        } else {
+            crate::test_helpers::seed_api_key(&client).await
+        };
+        let caches = &client
+            .rocket()
+            .state::<ApplicationState>()
+            .expect("application state")
+            .response_caches;
         let order = mock_order();
-        assert_eq!(determine_order_type(&order), OrderType::Solver);
+        caches
+            .orders_by_hash
+            .insert(test_hash(), vec![order.clone()])
+            .await;
+        caches
+            .order_quotes
+            .insert(
+                crate::routes::orders::order_quote_cache_key(&order),
+                vec![mock_quote("1.5")],
+            )
+            .await;
+
+        let response = client
+            .get(format!(
+                "/v1/order/{:#x}?trades=none&debug_raw=true",
+                test_hash()
+            ))
+            .header(rocket::http::Header::new(
+                "Authorization",
+                crate::test_helpers::basic_auth_header(&key_id, &secret),
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        serde_json::from_str(&response.into_string().await.unwrap()).unwrap()
+    }
+
+    #[rocket::async_test]
+    async fn test_get_order_debug_raw_attaches_raw_order_for_admin_key() {
+        let body = get_order_with_debug_raw(true).await;
+
+        assert_eq!(body["orderHash"], format!("{:#x}", test_hash()));
+        assert_eq!(body["_raw"].as_array().map(Vec::len), Some(1));
+    }
+
+    #[rocket::async_test]
+    async fn test_get_order_debug_raw_is_ignored_for_normal_key() {
+        let body = get_order_with_debug_raw(false).await;
+
+        assert_eq!(body["orderHash"], format!("{:#x}", test_hash()));
+        assert!(body.get("_raw").is_none());
     }
 
     #[rocket::async_test]
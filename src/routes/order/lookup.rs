@@ -0,0 +1,240 @@
+use super::get_order::process_get_order;
+use super::{CachedOrderDataSource, OrderDataSource, RaindexOrderDataSource};
+use crate::app_state::ApplicationState;
+use crate::auth::AuthenticatedKey;
+use crate::db::DbPool;
+use crate::error::{ApiError, ApiErrorResponse};
+use crate::fairings::{GlobalRateLimit, JsonContentType, TracingSpan};
+use crate::routes::status::{not_found_unless_lagging, LiveIndexingStatusDataSource};
+use crate::types::common::Denomination;
+use crate::types::order::{OrderDetail, OrderLookupRequest, OrderTradesMode};
+use alloy::primitives::{keccak256, B256};
+use alloy::sol_types::SolValue;
+use rain_orderbook_bindings::IRaindexV6::OrderV4;
+use rocket::serde::json::Json;
+use rocket::State;
+use tracing::Instrument;
+
+#[utoipa::path(
+    post,
+    path = "/v1/order/lookup",
+    tag = "Order",
+    security(("basicAuth" = [])),
+    request_body = OrderLookupRequest,
+    responses(
+        (status = 200, description = "Order details for the encoded order", body = OrderDetail),
+        (status = 202, description = "Order not found while an orderbook subgraph is lagging; retry later", body = ApiErrorResponse),
+        (status = 400, description = "Bad request", body = ApiErrorResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 429, description = "Rate limited", body = ApiErrorResponse),
+        (status = 404, description = "Order not found", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+#[post("/lookup", data = "<request>")]
+pub async fn post_order_lookup(
+    _global: GlobalRateLimit,
+    _key: AuthenticatedKey,
+    _json: JsonContentType,
+    shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    app_state: &State<ApplicationState>,
+    pool: &State<DbPool>,
+    span: TracingSpan,
+    request: Json<OrderLookupRequest>,
+) -> Result<Json<OrderDetail>, ApiError> {
+    let req = request.into_inner();
+    async move {
+        tracing::info!(order_bytes_len = req.order_bytes.len(), "request received");
+        let raindex = shared_raindex.read().await;
+        let raindex_ds = RaindexOrderDataSource {
+            client: raindex.client(),
+            caches: &app_state.response_caches,
+            pool: Some(pool.inner()),
+        };
+        let ds = CachedOrderDataSource {
+            inner: &raindex_ds,
+            caches: &app_state.response_caches,
+            bypass_cache: false,
+        };
+        not_found_unless_lagging(
+            &LiveIndexingStatusDataSource,
+            process_lookup_order(&ds, &req.order_bytes).await,
+            app_state.not_found_max_lag_blocks,
+            || raindex.orderbook_sources(),
+        )
+        .await
+        .map(Json)
+    }
+    .instrument(span.0)
+    .await
+}
+
+async fn process_lookup_order(
+    ds: &dyn OrderDataSource,
+    order_bytes: &[u8],
+) -> Result<OrderDetail, ApiError> {
+    let hash = order_hash_from_bytes(order_bytes)?;
+    tracing::info!(order_hash = %hash, "derived order hash");
+    process_get_order(
+        ds,
+        hash,
+        Denomination::default(),
+        false,
+        OrderTradesMode::default(),
+    )
+    .await
+}
+
+/// Hashes an ABI-encoded `OrderV4` the way the orderbook does,
+/// `keccak256(abi.encode(order))`. Bytes that do not decode as an order are
+/// rejected rather than hashed, since they could never match one.
+fn order_hash_from_bytes(order_bytes: &[u8]) -> Result<B256, ApiError> {
+    let order = OrderV4::abi_decode(order_bytes).map_err(|e| {
+        tracing::warn!(error = %e, "order bytes do not decode as an order");
+        ApiError::BadRequest("orderBytes is not a valid ABI-encoded order".into())
+    })?;
+    Ok(keccak256(order.abi_encode()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::order::test_fixtures::*;
+    use crate::test_helpers::{basic_auth_header, seed_api_key, TestClientBuilder};
+    use alloy::primitives::{address, Address, Bytes};
+    use async_trait::async_trait;
+    use rain_orderbook_bindings::IRaindexV6::{EvaluableV4, IOV2};
+    use rain_orderbook_common::raindex_client::order_quotes::RaindexOrderQuote;
+    use rain_orderbook_common::raindex_client::orders::RaindexOrder;
+    use rain_orderbook_common::raindex_client::trades::RaindexTrade;
+    use rocket::http::{ContentType, Header, Status};
+    use std::sync::Mutex;
+
+    fn fixture_order_bytes() -> Bytes {
+        let order = OrderV4 {
+            owner: address!("0000000000000000000000000000000000000001"),
+            nonce: B256::with_last_byte(7),
+            evaluable: EvaluableV4 {
+                interpreter: Address::repeat_byte(2),
+                store: Address::repeat_byte(3),
+                bytecode: Bytes::from(vec![0x01, 0x02]),
+            },
+            validInputs: vec![IOV2 {
+                token: address!("833589fcd6edb6e08f4c7c32d4f71b54bda02913"),
+                vaultId: B256::with_last_byte(1),
+            }],
+            validOutputs: vec![IOV2 {
+                token: address!("4200000000000000000000000000000000000006"),
+                vaultId: B256::with_last_byte(2),
+            }],
+        };
+        Bytes::from(order.abi_encode())
+    }
+
+    /// Records the hash the lookup queried before delegating to `inner`.
+    struct RecordingOrderDataSource {
+        inner: MockOrderDataSource,
+        queried: Mutex<Option<B256>>,
+    }
+
+    #[async_trait]
+    impl OrderDataSource for RecordingOrderDataSource {
+        async fn get_orders_by_hash(&self, hash: B256) -> Result<Vec<RaindexOrder>, ApiError> {
+            *self.queried.lock().unwrap() = Some(hash);
+            self.inner.get_orders_by_hash(hash).await
+        }
+
+        async fn get_active_orders_by_owner(
+            &self,
+            owner: Address,
+            limit: u16,
+        ) -> Result<(Vec<RaindexOrder>, u32), ApiError> {
+            self.inner.get_active_orders_by_owner(owner, limit).await
+        }
+
+        async fn get_order_quotes(
+            &self,
+            order: &RaindexOrder,
+        ) -> Result<Vec<RaindexOrderQuote>, ApiError> {
+            self.inner.get_order_quotes(order).await
+        }
+
+        async fn get_order_trades(
+            &self,
+            order: &RaindexOrder,
+        ) -> Result<Vec<RaindexTrade>, ApiError> {
+            self.inner.get_order_trades(order).await
+        }
+
+        async fn get_remove_calldata(&self, order: &RaindexOrder) -> Result<Bytes, ApiError> {
+            self.inner.get_remove_calldata(order).await
+        }
+    }
+
+    fn recording_ds(orders: Vec<RaindexOrder>) -> RecordingOrderDataSource {
+        RecordingOrderDataSource {
+            inner: MockOrderDataSource {
+                orders: Ok(orders),
+                trades: Ok(vec![mock_trade()]),
+                quotes: Ok(vec![mock_quote("1.5")]),
+                calldata: Ok(Bytes::new()),
+            },
+            queried: Mutex::new(None),
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_lookup_queries_hash_of_order_bytes() {
+        let order_bytes = fixture_order_bytes();
+        let ds = recording_ds(vec![mock_order()]);
+
+        let detail = process_lookup_order(&ds, &order_bytes).await.unwrap();
+
+        assert_eq!(
+            ds.queried.lock().unwrap().expect("order queried"),
+            keccak256(&order_bytes)
+        );
+        assert_eq!(detail.order_hash, test_hash());
+        assert_eq!(detail.input_token.symbol, "USDC");
+        assert_eq!(detail.trades.len(), 1);
+    }
+
+    #[rocket::async_test]
+    async fn test_lookup_unindexed_order_is_not_found() {
+        let ds = recording_ds(vec![]);
+
+        let result = process_lookup_order(&ds, &fixture_order_bytes()).await;
+
+        assert!(matches!(result, Err(ApiError::NotFound { .. })));
+    }
+
+    #[rocket::async_test]
+    async fn test_lookup_rejects_bytes_that_are_not_an_order() {
+        let ds = recording_ds(vec![mock_order()]);
+
+        let result = process_lookup_order(&ds, &[0x01]).await;
+
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+        assert!(ds.queried.lock().unwrap().is_none());
+    }
+
+    #[rocket::async_test]
+    async fn test_lookup_route_rejects_invalid_order_bytes() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_api_key(&client).await;
+
+        let response = client
+            .post("/v1/order/lookup")
+            .header(ContentType::JSON)
+            .header(Header::new(
+                "Authorization",
+                basic_auth_header(&key_id, &secret),
+            ))
+            .body(r#"{"orderBytes": "0x01"}"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+}
@@ -2,9 +2,11 @@ mod cancel;
 mod deploy_dca;
 mod deploy_solver;
 mod get_order;
+mod lookup;
+mod next_fill;
 
 use crate::cache::RouteResponseCaches;
-use crate::error::ApiError;
+use crate::error::{upstream_error, ApiError};
 use crate::wrap_ratio::{
     persist_wrap_ratio_snapshots_best_effort, read_wrap_ratio_responses_for_addresses,
     wrap_ratio_values_from_responses, WrapRatioValue,
@@ -24,6 +26,13 @@ use std::collections::HashMap;
 #[async_trait]
 pub(crate) trait OrderDataSource: Send + Sync {
     async fn get_orders_by_hash(&self, hash: B256) -> Result<Vec<RaindexOrder>, ApiError>;
+    /// Returns up to `limit` of the owner's active orders and the owner's
+    /// total active order count.
+    async fn get_active_orders_by_owner(
+        &self,
+        owner: Address,
+        limit: u16,
+    ) -> Result<(Vec<RaindexOrder>, u32), ApiError>;
     async fn get_order_quotes(
         &self,
         order: &RaindexOrder,
@@ -57,7 +66,27 @@ impl<'a> OrderDataSource for RaindexOrderDataSource<'a> {
             .map(|r| r.orders().to_vec())
             .map_err(|e| {
                 tracing::error!(error = %e, "failed to query orders");
-                ApiError::Internal("failed to query orders".into())
+                upstream_error(&e, "failed to query orders")
+            })
+    }
+
+    async fn get_active_orders_by_owner(
+        &self,
+        owner: Address,
+        limit: u16,
+    ) -> Result<(Vec<RaindexOrder>, u32), ApiError> {
+        let filters = GetOrdersFilters {
+            owners: vec![owner],
+            active: Some(true),
+            ..Default::default()
+        };
+        self.client
+            .get_orders(None, Some(filters), Some(1), Some(limit))
+            .await
+            .map(|r| (r.orders().to_vec(), r.total_count()))
+            .map_err(|e| {
+                tracing::error!(error = %e, "failed to query owner orders");
+                upstream_error(&e, "failed to query orders")
             })
     }
 
@@ -68,7 +97,7 @@ impl<'a> OrderDataSource for RaindexOrderDataSource<'a> {
         let fetch = || async {
             order.get_quotes(None, None).await.map_err(|e| {
                 tracing::error!(error = %e, "failed to query order quotes");
-                ApiError::Internal("failed to query order quotes".into())
+                upstream_error(&e, "failed to query order quotes")
             })
         };
 
@@ -96,7 +125,7 @@ impl<'a> OrderDataSource for RaindexOrderDataSource<'a> {
             .await
             .map_err(|e| {
                 tracing::error!(error = %e, "failed to query order trades");
-                ApiError::Internal("failed to query order trades".into())
+                upstream_error(&e, "failed to query order trades")
             })?;
 
         Ok(result
@@ -137,26 +166,197 @@ impl<'a> OrderDataSource for RaindexOrderDataSource<'a> {
     }
 }
 
+/// Serves `get_orders_by_hash` from the lookup cache and delegates every
+/// other call to `inner`. With `bypass_cache` set the lookup goes to `inner`
+/// and the fresh result replaces the cached entry. Lookups that find no
+/// order are not cached.
+pub(crate) struct CachedOrderDataSource<'a> {
+    pub inner: &'a dyn OrderDataSource,
+    pub caches: &'a RouteResponseCaches,
+    pub bypass_cache: bool,
+}
+
+#[async_trait]
+impl<'a> OrderDataSource for CachedOrderDataSource<'a> {
+    async fn get_orders_by_hash(&self, hash: B256) -> Result<Vec<RaindexOrder>, ApiError> {
+        if !self.caches.lookups_enabled() {
+            return self.inner.get_orders_by_hash(hash).await;
+        }
+
+        if self.bypass_cache {
+            let orders = self.inner.get_orders_by_hash(hash).await?;
+            if !orders.is_empty() {
+                self.caches
+                    .orders_by_hash
+                    .insert(hash, orders.clone())
+                    .await;
+            }
+            return Ok(orders);
+        }
+
+        // A missing order may only be waiting to be indexed, so an empty
+        // lookup fails the fetch (`None`) and is never cached.
+        let cached = self
+            .caches
+            .orders_by_hash
+            .get_or_try_insert(hash, || async {
+                match self.inner.get_orders_by_hash(hash).await {
+                    Ok(orders) if orders.is_empty() => Err(None),
+                    Ok(orders) => Ok(orders),
+                    Err(e) => Err(Some(e)),
+                }
+            })
+            .await;
+        match cached {
+            Ok(orders) => Ok(orders),
+            Err(e) => match &*e {
+                None => Ok(Vec::new()),
+                Some(e) => Err(e.clone()),
+            },
+        }
+    }
+
+    async fn get_active_orders_by_owner(
+        &self,
+        owner: Address,
+        limit: u16,
+    ) -> Result<(Vec<RaindexOrder>, u32), ApiError> {
+        self.inner.get_active_orders_by_owner(owner, limit).await
+    }
+
+    async fn get_order_quotes(
+        &self,
+        order: &RaindexOrder,
+    ) -> Result<Vec<RaindexOrderQuote>, ApiError> {
+        self.inner.get_order_quotes(order).await
+    }
+
+    async fn get_order_trades(&self, order: &RaindexOrder) -> Result<Vec<RaindexTrade>, ApiError> {
+        self.inner.get_order_trades(order).await
+    }
+
+    async fn get_remove_calldata(&self, order: &RaindexOrder) -> Result<Bytes, ApiError> {
+        self.inner.get_remove_calldata(order).await
+    }
+
+    async fn get_wrap_ratios_for_tokens(
+        &self,
+        token_addresses: &[Address],
+    ) -> Result<HashMap<Address, WrapRatioValue>, ApiError> {
+        self.inner.get_wrap_ratios_for_tokens(token_addresses).await
+    }
+}
+
+/// Deployments can't leave out the deposit step or its approval yet, so a
+/// request asking to skip it is refused rather than deploying with a deposit.
+pub(crate) fn reject_skip_deposit(skip_deposit: bool) -> Result<(), ApiError> {
+    if skip_deposit {
+        return Err(ApiError::BadRequest(
+            "skipDeposit is not supported yet: deployments always include the deposit".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Builder state field a deployment defines to carry a `clientRef`.
+pub(crate) const CLIENT_REF_FIELD: &str = "client-ref";
+
+const MAX_CLIENT_REF_LEN: usize = 64;
+
+/// A `clientRef` ends up in logs and is meant for on-chain meta, so it is
+/// kept short and to a charset that needs no escaping.
+pub(crate) fn validate_client_ref(client_ref: Option<&str>) -> Result<(), ApiError> {
+    let Some(client_ref) = client_ref else {
+        return Ok(());
+    };
+    let valid_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':');
+    if client_ref.is_empty()
+        || client_ref.len() > MAX_CLIENT_REF_LEN
+        || !client_ref.chars().all(valid_char)
+    {
+        return Err(ApiError::BadRequest(format!(
+            "clientRef must be 1 to {MAX_CLIENT_REF_LEN} characters of letters, digits, '-', '_', '.' or ':'"
+        )));
+    }
+    Ok(())
+}
+
+/// Deployments don't write `clientRef` into order meta yet, so a request
+/// carrying one is refused rather than having the reference silently dropped.
+pub(crate) fn reject_client_ref(client_ref: Option<&str>) -> Result<(), ApiError> {
+    if client_ref.is_some() {
+        return Err(ApiError::BadRequest(
+            "clientRef is not supported yet: deployments do not record it".into(),
+        ));
+    }
+    Ok(())
+}
+
 pub use cancel::*;
 pub use deploy_dca::*;
 pub use deploy_solver::*;
 pub use get_order::*;
+pub use lookup::*;
+pub use next_fill::*;
 
 pub fn routes() -> Vec<Route> {
     rocket::routes![
         deploy_dca::post_order_dca,
         deploy_solver::post_order_solver,
         get_order::get_order,
-        cancel::post_order_cancel
+        lookup::post_order_lookup,
+        next_fill::get_order_next_fill,
+        cancel::post_order_cancel,
+        cancel::post_order_cancel_all
     ]
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reject_skip_deposit_refuses_the_flag() {
+        assert!(reject_skip_deposit(false).is_ok());
+        assert!(matches!(
+            reject_skip_deposit(true),
+            Err(ApiError::BadRequest(message)) if message.starts_with("skipDeposit is not supported yet")
+        ));
+    }
+
+    #[test]
+    fn test_validate_client_ref() {
+        assert!(validate_client_ref(None).is_ok());
+        assert!(validate_client_ref(Some("acme-order_1234.v2:a")).is_ok());
+        assert!(validate_client_ref(Some(&"a".repeat(64))).is_ok());
+        for invalid in ["", "has space", "naïve", "a/b", &"a".repeat(65)] {
+            assert!(
+                matches!(
+                    validate_client_ref(Some(invalid)),
+                    Err(ApiError::BadRequest(_))
+                ),
+                "{invalid:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_reject_client_ref_refuses_any_reference() {
+        assert!(reject_client_ref(None).is_ok());
+        assert!(matches!(
+            reject_client_ref(Some("acme-order-1234")),
+            Err(ApiError::BadRequest(message)) if message.starts_with("clientRef is not supported yet")
+        ));
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod test_fixtures {
     use super::OrderDataSource;
     use crate::error::ApiError;
-    use alloy::primitives::{Bytes, B256};
+    use alloy::primitives::{Address, Bytes, B256};
     use async_trait::async_trait;
+    use rain_orderbook_common::parsed_meta::ParsedMeta;
     use rain_orderbook_common::raindex_client::order_quotes::RaindexOrderQuote;
     use rain_orderbook_common::raindex_client::orders::RaindexOrder;
     use rain_orderbook_common::raindex_client::trades::{RaindexTrade, RaindexTradesListResult};
@@ -332,6 +532,25 @@ pub(crate) mod test_fixtures {
         serde_json::from_value(order_json()).expect("deserialize mock RaindexOrder")
     }
 
+    /// The mock order carrying the builder state of a DCA deployment, so it
+    /// classifies as [`crate::types::order::OrderType::Dca`].
+    pub fn dca_order() -> RaindexOrder {
+        let builder_state = ParsedMeta::OrderBuilderStateV1(
+            serde_json::from_value(json!({
+                "dotrainHash": format!("{:#x}", B256::ZERO),
+                "fieldValues": {},
+                "deposits": {},
+                "selectTokens": {},
+                "vaultIds": {},
+                "selectedDeployment": "dca-base"
+            }))
+            .expect("deserialize dca builder state"),
+        );
+        let mut order = order_json();
+        order["parsedMeta"] = json!([builder_state]);
+        serde_json::from_value(order).expect("deserialize dca RaindexOrder")
+    }
+
     pub fn order_with_shared_vaults_json() -> serde_json::Value {
         let rc = stub_raindex_client();
         let shared_vault = |id: &str,
@@ -480,6 +699,19 @@ pub(crate) mod test_fixtures {
                 Err(_) => Err(ApiError::Internal("failed to query orders".into())),
             }
         }
+        async fn get_active_orders_by_owner(
+            &self,
+            _owner: Address,
+            limit: u16,
+        ) -> Result<(Vec<RaindexOrder>, u32), ApiError> {
+            match &self.orders {
+                Ok(orders) => Ok((
+                    orders.iter().take(limit.into()).cloned().collect(),
+                    orders.len() as u32,
+                )),
+                Err(_) => Err(ApiError::Internal("failed to query orders".into())),
+            }
+        }
         async fn get_order_quotes(
             &self,
             _order: &RaindexOrder,
@@ -0,0 +1,178 @@
+use super::get_order::determine_order_type;
+use super::{CachedOrderDataSource, OrderDataSource, RaindexOrderDataSource};
+use crate::app_state::ApplicationState;
+use crate::auth::AuthenticatedKey;
+use crate::error::{ApiError, ApiErrorResponse, NotFoundReason};
+use crate::fairings::{GlobalRateLimit, TracingSpan};
+use crate::types::common::{InvalidPathParam, TokenRef, ValidatedFixedBytes};
+use crate::types::order::{NextFillResponse, OrderType};
+use alloy::primitives::B256;
+use rain_orderbook_common::raindex_client::orders::RaindexOrder;
+use rocket::serde::json::Json;
+use rocket::State;
+use tracing::Instrument;
+
+#[utoipa::path(
+    get,
+    path = "/v1/order/{order_hash}/next-fill",
+    tag = "Order",
+    security(("basicAuth" = [])),
+    params(("order_hash" = String, Path, description = "The order hash")),
+    responses(
+        (status = 200, description = "Expected amounts of the order's next fill", body = NextFillResponse),
+        (status = 400, description = "Bad request, or the order is not a DCA order", body = ApiErrorResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 404, description = "Order not found", body = ApiErrorResponse),
+        (status = 429, description = "Rate limited", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 503, description = "The order could not be quoted", body = ApiErrorResponse),
+    )
+)]
+#[get("/<order_hash>/next-fill")]
+pub async fn get_order_next_fill(
+    _global: GlobalRateLimit,
+    _key: AuthenticatedKey,
+    shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    app_state: &State<ApplicationState>,
+    span: TracingSpan,
+    order_hash: Result<ValidatedFixedBytes, InvalidPathParam>,
+) -> Result<Json<NextFillResponse>, ApiError> {
+    async move {
+        let order_hash = order_hash.map_err(|e| e.reject("order hash"))?;
+        tracing::info!(order_hash = ?order_hash, "request received");
+        let raindex = shared_raindex.read().await;
+        let raindex_ds = RaindexOrderDataSource {
+            client: raindex.client(),
+            caches: &app_state.response_caches,
+            pool: None,
+        };
+        let ds = CachedOrderDataSource {
+            inner: &raindex_ds,
+            caches: &app_state.response_caches,
+            bypass_cache: false,
+        };
+        process_next_fill(&ds, order_hash.0).await.map(Json)
+    }
+    .instrument(span.0)
+    .await
+}
+
+async fn process_next_fill(
+    ds: &dyn OrderDataSource,
+    hash: B256,
+) -> Result<NextFillResponse, ApiError> {
+    let order = ds
+        .get_orders_by_hash(hash)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            ApiError::not_found_because(NotFoundReason::OrderNotFound, "order not found")
+        })?;
+    next_fill_for_order(ds, &order, determine_order_type(&order)).await
+}
+
+/// A DCA order's quote is capped by what is left of its per-period budget,
+/// so the quoted max output and max input are what the next taker would
+/// move. Other strategies have no budget to spend down.
+async fn next_fill_for_order(
+    ds: &dyn OrderDataSource,
+    order: &RaindexOrder,
+    order_type: OrderType,
+) -> Result<NextFillResponse, ApiError> {
+    if order_type != OrderType::Dca {
+        return Err(ApiError::BadRequest(
+            "next fill is only available for dca orders".into(),
+        ));
+    }
+
+    let quotes = ds.get_order_quotes(order).await?;
+    let quote = crate::routes::first_successful_quote(&quotes)
+        .and_then(|q| q.data.as_ref())
+        .ok_or_else(|| {
+            tracing::warn!("dca order has no successful quote");
+            ApiError::ServiceUnavailable("order could not be quoted".into())
+        })?;
+
+    let (input, output) = crate::routes::resolve_io_vaults(order)?;
+    let (input_token, output_token) = (input.token(), output.token());
+    Ok(NextFillResponse {
+        order_hash: order.order_hash(),
+        input_token: TokenRef {
+            address: input_token.address(),
+            symbol: input_token.symbol().unwrap_or_default(),
+            decimals: input_token.decimals(),
+        },
+        output_token: TokenRef {
+            address: output_token.address(),
+            symbol: output_token.symbol().unwrap_or_default(),
+            decimals: output_token.decimals(),
+        },
+        input_amount: quote.formatted_max_input.clone(),
+        output_amount: quote.formatted_max_output.clone(),
+        io_ratio: quote.formatted_ratio.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::order::test_fixtures::*;
+    use crate::test_helpers::TestClientBuilder;
+    use alloy::primitives::Bytes;
+    use rocket::http::Status;
+
+    fn ds_with_quotes(
+        order: RaindexOrder,
+        quotes: Vec<rain_orderbook_common::raindex_client::order_quotes::RaindexOrderQuote>,
+    ) -> MockOrderDataSource {
+        MockOrderDataSource {
+            orders: Ok(vec![order]),
+            trades: Ok(vec![]),
+            quotes: Ok(quotes),
+            calldata: Ok(Bytes::new()),
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_next_fill_for_dca_order_uses_quoted_budget() {
+        let ds = ds_with_quotes(dca_order(), vec![mock_failed_quote(), mock_quote("2")]);
+
+        let fill = process_next_fill(&ds, test_hash()).await.unwrap();
+
+        assert_eq!(fill.order_hash, test_hash());
+        assert_eq!(fill.input_token.symbol, "USDC");
+        assert_eq!(fill.output_token.symbol, "WETH");
+        assert_eq!(fill.output_amount, "1");
+        assert_eq!(fill.input_amount, "2");
+        assert_eq!(fill.io_ratio, "2");
+    }
+
+    #[rocket::async_test]
+    async fn test_next_fill_rejects_non_dca_orders() {
+        let ds = ds_with_quotes(mock_order(), vec![mock_quote("2")]);
+
+        let result = process_next_fill(&ds, test_hash()).await;
+
+        assert!(matches!(result, Err(ApiError::BadRequest(msg)) if msg.contains("dca")));
+    }
+
+    #[rocket::async_test]
+    async fn test_next_fill_without_quote_is_unavailable() {
+        let ds = ds_with_quotes(dca_order(), vec![mock_failed_quote()]);
+
+        let result = process_next_fill(&ds, test_hash()).await;
+
+        assert!(matches!(result, Err(ApiError::ServiceUnavailable(_))));
+    }
+
+    #[rocket::async_test]
+    async fn test_next_fill_requires_auth() {
+        let client = TestClientBuilder::new().build().await;
+        let response = client
+            .get("/v1/order/0x000000000000000000000000000000000000000000000000000000000000abcd/next-fill")
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+}
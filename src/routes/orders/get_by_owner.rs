@@ -1,14 +1,15 @@
 use super::{
     active_filter_for_state, build_orders_list_response, current_wrap_ratios_for_orders,
-    get_order_quotes_for_summaries, OrdersListDataSource, RaindexOrdersListDataSource,
-    DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE,
+    get_order_quotes_for_summaries, hide_unknown_strategies, sort_orders_newest_first,
+    OrdersListDataSource, RaindexOrdersListDataSource, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE,
 };
 use crate::app_state::ApplicationState;
 use crate::auth::AuthenticatedKey;
 use crate::db::DbPool;
 use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
-use crate::types::common::{Denomination, ValidatedAddress};
+use crate::fairings::{GlobalRateLimit, KnownQueryParams, TracingSpan};
+use crate::routes::pagination::{validate_page, Paginated};
+use crate::types::common::{Denomination, InvalidPathParam, ValidatedAddress};
 use crate::types::orders::{OrderState, OrdersListResponse, OrdersPaginationParams};
 use alloy::primitives::Address;
 use rain_orderbook_common::raindex_client::orders::GetOrdersFilters;
@@ -24,6 +25,7 @@ pub(crate) async fn process_get_orders_by_owner(
     page_size: Option<u16>,
     denomination: Denomination,
 ) -> Result<OrdersListResponse, ApiError> {
+    validate_page(page.map(u32::from))?;
     let active_filter = active_filter_for_state(state);
     let filters = GetOrdersFilters {
         owners: vec![address],
@@ -36,9 +38,11 @@ pub(crate) async fn process_get_orders_by_owner(
     let effective_page_size = page_size
         .unwrap_or(DEFAULT_PAGE_SIZE as u16)
         .min(MAX_PAGE_SIZE);
-    let (orders, total_count) = ds
+    let (mut orders, total_count) = ds
         .get_orders_list(filters, Some(page_num), Some(effective_page_size))
         .await?;
+    let total_is_approximate = hide_unknown_strategies(ds, &mut orders);
+    sort_orders_newest_first(&mut orders);
 
     tracing::info!(
         quoted_orders = orders.len(),
@@ -47,7 +51,7 @@ pub(crate) async fn process_get_orders_by_owner(
     let quote_results = get_order_quotes_for_summaries(ds, &orders).await;
     let wrap_ratios = current_wrap_ratios_for_orders(ds, denomination, &orders).await?;
 
-    build_orders_list_response(
+    let mut response = build_orders_list_response(
         &orders,
         total_count,
         page_num.into(),
@@ -55,7 +59,9 @@ pub(crate) async fn process_get_orders_by_owner(
         quote_results,
         denomination,
         &wrap_ratios,
-    )
+    )?;
+    response.pagination.total_is_approximate = total_is_approximate;
+    Ok(response)
 }
 
 #[utoipa::path(
@@ -70,7 +76,6 @@ pub(crate) async fn process_get_orders_by_owner(
     responses(
         (status = 200, description = "Paginated list of orders", body = OrdersListResponse),
         (status = 400, description = "Bad request", body = ApiErrorResponse),
-        (status = 422, description = "Unprocessable entity", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
@@ -81,29 +86,36 @@ pub(crate) async fn process_get_orders_by_owner(
 pub async fn get_orders_by_address(
     _global: GlobalRateLimit,
     _key: AuthenticatedKey,
+    _query: KnownQueryParams<OrdersPaginationParams>,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
     app_state: &State<ApplicationState>,
     pool: &State<DbPool>,
     span: TracingSpan,
-    address: ValidatedAddress,
+    address: Result<ValidatedAddress, InvalidPathParam>,
     params: OrdersPaginationParams,
-) -> Result<Json<OrdersListResponse>, ApiError> {
+) -> Result<Paginated<Json<serde_json::Value>>, ApiError> {
     async move {
+        let address = address.map_err(|e| e.reject("address"))?;
         tracing::info!(address = ?address, params = ?params, "request received");
         let addr = address.0;
         let state = params.state;
         let page = params.page;
-        let page_size = params.page_size;
+        let page_size = params
+            .page_size
+            .or(Some(app_state.page_size_defaults.orders));
         let denomination = params.denomination.unwrap_or_default();
+        let time_format = params.time_format.unwrap_or_default();
         let raindex = shared_raindex.read().await;
         let ds = RaindexOrdersListDataSource {
             client: raindex.client(),
             caches: &app_state.response_caches,
             pool: pool.inner(),
+            strict_strategies: app_state.strict_strategies,
+            quote_chain_concurrency: app_state.quote_chain_concurrency,
         };
         let response =
             process_get_orders_by_owner(&ds, addr, state, page, page_size, denomination).await?;
-        Ok(Json(response))
+        super::paginated_orders(response, time_format)
     }
     .instrument(span.0)
     .await
@@ -113,7 +125,7 @@ pub async fn get_orders_by_address(
 mod tests {
     use super::*;
     use crate::routes::order::test_fixtures::{
-        mock_order, mock_order_with_shared_vaults, mock_quote,
+        mock_order, mock_order_with_shared_vaults, mock_quote, order_json,
     };
     use crate::routes::orders::test_fixtures::{
         MockOrdersListDataSource, RecordingOrdersListDataSource,
@@ -128,6 +140,7 @@ mod tests {
             orders: Ok(vec![mock_order()]),
             total_count: 1,
             quotes: Ok(vec![mock_quote("1.5")]),
+            strict_strategies: false,
         };
         let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
             .parse()
@@ -158,6 +171,7 @@ mod tests {
             orders: Ok(vec![]),
             total_count: 0,
             quotes: Ok(vec![]),
+            strict_strategies: false,
         };
         let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
             .parse()
@@ -178,6 +192,7 @@ mod tests {
             orders: Ok(vec![mock_order()]),
             total_count: 1,
             quotes: Err(ApiError::Internal("quote error".into())),
+            strict_strategies: false,
         };
         let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
             .parse()
@@ -197,6 +212,7 @@ mod tests {
             orders: Err(ApiError::Internal("failed".into())),
             total_count: 0,
             quotes: Ok(vec![]),
+            strict_strategies: false,
         };
         let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
             .parse()
@@ -212,6 +228,7 @@ mod tests {
             orders: Ok(vec![mock_order_with_shared_vaults()]),
             total_count: 1,
             quotes: Ok(vec![mock_quote("200.0")]),
+            strict_strategies: false,
         };
         let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
             .parse()
@@ -228,6 +245,139 @@ mod tests {
         assert_eq!(result.orders[0].io_ratio, "200.0");
     }
 
+    #[rocket::async_test]
+    async fn test_process_get_orders_by_owner_default_state_filters_active_orders() {
+        let ds = RecordingOrdersListDataSource::default();
+        let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
+            .parse()
+            .unwrap();
+
+        let result =
+            process_get_orders_by_owner(&ds, addr, None, None, None, Denomination::Wrapped).await;
+
+        assert!(result.is_ok());
+        let filters = ds.filters.lock().expect("lock filters");
+        assert_eq!(filters.len(), 1);
+        assert_eq!(filters[0].owners, vec![addr]);
+        assert_eq!(filters[0].active, Some(true));
+        assert_eq!(filters[0].has_positive_output_vault_balance, Some(true));
+        let pages = ds.pages.lock().expect("lock pages");
+        assert_eq!(pages[0], (Some(1), Some(DEFAULT_PAGE_SIZE as u16)));
+    }
+
+    #[rocket::async_test]
+    async fn test_process_get_orders_by_owner_clamps_page_size() {
+        let ds = RecordingOrdersListDataSource::default();
+        let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
+            .parse()
+            .unwrap();
+
+        let result = process_get_orders_by_owner(
+            &ds,
+            addr,
+            None,
+            Some(3),
+            Some(MAX_PAGE_SIZE + 100),
+            Denomination::Wrapped,
+        )
+        .await
+        .unwrap();
+
+        let pages = ds.pages.lock().expect("lock pages");
+        assert_eq!(pages[0], (Some(3), Some(MAX_PAGE_SIZE)));
+        assert_eq!(result.pagination.page, 3);
+        assert_eq!(result.pagination.page_size, u32::from(MAX_PAGE_SIZE));
+    }
+
+    #[rocket::async_test]
+    async fn test_process_get_orders_by_owner_pagination_envelope() {
+        let ds = MockOrdersListDataSource {
+            orders: Ok(vec![mock_order()]),
+            total_count: 45,
+            quotes: Ok(vec![mock_quote("1.5")]),
+            strict_strategies: false,
+        };
+        let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
+            .parse()
+            .unwrap();
+
+        let result =
+            process_get_orders_by_owner(&ds, addr, None, Some(2), Some(20), Denomination::Wrapped)
+                .await
+                .unwrap();
+
+        assert_eq!(result.pagination.page, 2);
+        assert_eq!(result.pagination.page_size, 20);
+        assert_eq!(result.pagination.total_orders, 45);
+        assert_eq!(result.pagination.total_pages, 3);
+        assert!(result.pagination.has_more);
+        assert!(!result.pagination.total_is_approximate);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_get_orders_by_owner_strict_strategies_marks_total_approximate() {
+        let ds = MockOrdersListDataSource {
+            orders: Ok(vec![mock_order()]),
+            total_count: 45,
+            quotes: Ok(vec![]),
+            strict_strategies: true,
+        };
+        let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
+            .parse()
+            .unwrap();
+
+        let result =
+            process_get_orders_by_owner(&ds, addr, None, Some(2), Some(20), Denomination::Wrapped)
+                .await
+                .unwrap();
+
+        assert!(result.orders.is_empty());
+        assert_eq!(result.pagination.total_orders, 45);
+        assert_eq!(result.pagination.total_pages, 3);
+        assert!(result.pagination.has_more);
+        assert!(result.pagination.total_is_approximate);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_get_orders_by_owner_sorts_merged_orderbooks() {
+        let hash = |suffix: &str| format!("0x{suffix:0>64}");
+        let order = |raindex: &str, hash_suffix: &str, timestamp: &str| {
+            let mut json = order_json();
+            json["raindex"] = serde_json::json!(raindex);
+            json["orderHash"] = serde_json::json!(hash(hash_suffix));
+            json["timestampAdded"] = serde_json::json!(format!("0x{timestamp:0>64}"));
+            serde_json::from_value(json).expect("deserialize order")
+        };
+        let orderbook_a = "0xd2938e7c9fe3597f78832ce780feb61945c377d7";
+        let orderbook_b = "0x2f209e5b67a33b8fe96e28f24628df6da301c8eb";
+        let ds = MockOrdersListDataSource {
+            orders: Ok(vec![
+                order(orderbook_a, "a1", "6553f100"),
+                order(orderbook_b, "b1", "6553f200"),
+                order(orderbook_a, "a2", "6553f200"),
+                order(orderbook_b, "b2", "6553f000"),
+            ]),
+            total_count: 4,
+            quotes: Ok(vec![mock_quote("1.5")]),
+            strict_strategies: false,
+        };
+        let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
+            .parse()
+            .unwrap();
+
+        let result =
+            process_get_orders_by_owner(&ds, addr, None, None, None, Denomination::Wrapped)
+                .await
+                .unwrap();
+
+        let hashes: Vec<String> = result
+            .orders
+            .iter()
+            .map(|order| order.order_hash.to_string())
+            .collect();
+        assert_eq!(hashes, vec![hash("a2"), hash("b1"), hash("a1"), hash("b2")]);
+    }
+
     #[rocket::async_test]
     async fn test_process_get_orders_by_owner_inactive_state_sets_active_false_filter() {
         let ds = RecordingOrdersListDataSource::default();
@@ -287,7 +437,7 @@ mod tests {
     }
 
     #[rocket::async_test]
-    async fn test_get_orders_by_owner_invalid_address_returns_422() {
+    async fn test_get_orders_by_owner_invalid_address_returns_400() {
         let client = TestClientBuilder::new().build().await;
         let (key_id, secret) = seed_api_key(&client).await;
         let header = basic_auth_header(&key_id, &secret);
@@ -296,6 +446,6 @@ mod tests {
             .header(Header::new("Authorization", header))
             .dispatch()
             .await;
-        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert_eq!(response.status(), Status::BadRequest);
     }
 }
@@ -1,14 +1,15 @@
 use super::{
     active_filter_for_state, build_orders_list_response, current_wrap_ratios_for_orders,
-    get_order_quotes_for_summaries, OrdersListDataSource, RaindexOrdersListDataSource,
-    DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE,
+    get_order_quotes_for_summaries, hide_unknown_strategies, sort_orders_newest_first,
+    OrdersListDataSource, RaindexOrdersListDataSource, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE,
 };
 use crate::app_state::ApplicationState;
 use crate::auth::AuthenticatedKey;
 use crate::db::DbPool;
 use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
-use crate::types::common::{Denomination, ValidatedAddress};
+use crate::fairings::{GlobalRateLimit, KnownQueryParams, TracingSpan};
+use crate::routes::pagination::{validate_page, Paginated};
+use crate::types::common::{Denomination, InvalidPathParam, ValidatedAddress};
 use crate::types::orders::{OrderSide, OrderState, OrdersByTokenParams, OrdersListResponse};
 use alloy::primitives::Address;
 use rain_orderbook_common::raindex_client::orders::GetOrdersFilters;
@@ -26,6 +27,7 @@ pub(crate) async fn process_get_orders_by_token(
     page_size: Option<u16>,
     denomination: Denomination,
 ) -> Result<OrdersListResponse, ApiError> {
+    validate_page(page.map(u32::from))?;
     let token_filter = match side {
         Some(OrderSide::Input) => GetOrdersTokenFilter {
             inputs: Some(vec![address]),
@@ -53,9 +55,11 @@ pub(crate) async fn process_get_orders_by_token(
     let effective_page_size = page_size
         .unwrap_or(DEFAULT_PAGE_SIZE as u16)
         .min(MAX_PAGE_SIZE);
-    let (orders, total_count) = ds
+    let (mut orders, total_count) = ds
         .get_orders_list(filters, Some(page_num), Some(effective_page_size))
         .await?;
+    let total_is_approximate = hide_unknown_strategies(ds, &mut orders);
+    sort_orders_newest_first(&mut orders);
 
     tracing::info!(
         quoted_orders = orders.len(),
@@ -64,7 +68,7 @@ pub(crate) async fn process_get_orders_by_token(
     let quote_results = get_order_quotes_for_summaries(ds, &orders).await;
     let wrap_ratios = current_wrap_ratios_for_orders(ds, denomination, &orders).await?;
 
-    build_orders_list_response(
+    let mut response = build_orders_list_response(
         &orders,
         total_count,
         page_num.into(),
@@ -72,7 +76,9 @@ pub(crate) async fn process_get_orders_by_token(
         quote_results,
         denomination,
         &wrap_ratios,
-    )
+    )?;
+    response.pagination.total_is_approximate = total_is_approximate;
+    Ok(response)
 }
 
 #[utoipa::path(
@@ -87,7 +93,6 @@ pub(crate) async fn process_get_orders_by_token(
     responses(
         (status = 200, description = "Paginated list of orders for token", body = OrdersListResponse),
         (status = 400, description = "Bad request", body = ApiErrorResponse),
-        (status = 422, description = "Unprocessable entity", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
@@ -98,32 +103,39 @@ pub(crate) async fn process_get_orders_by_token(
 pub async fn get_orders_by_token(
     _global: GlobalRateLimit,
     _key: AuthenticatedKey,
+    _query: KnownQueryParams<OrdersByTokenParams>,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
     app_state: &State<ApplicationState>,
     pool: &State<DbPool>,
     span: TracingSpan,
-    address: ValidatedAddress,
+    address: Result<ValidatedAddress, InvalidPathParam>,
     params: OrdersByTokenParams,
-) -> Result<Json<OrdersListResponse>, ApiError> {
+) -> Result<Paginated<Json<serde_json::Value>>, ApiError> {
     async move {
+        let address = address.map_err(|e| e.reject("address"))?;
         tracing::info!(address = ?address, params = ?params, "request received");
         let addr = address.0;
         let state = params.state;
         let side = params.side;
         let page = params.page;
-        let page_size = params.page_size;
+        let page_size = params
+            .page_size
+            .or(Some(app_state.page_size_defaults.orders));
         let denomination = params.denomination.unwrap_or_default();
+        let time_format = params.time_format.unwrap_or_default();
         if !app_state.response_caches.is_enabled() {
             let raindex = shared_raindex.read().await;
             let ds = RaindexOrdersListDataSource {
                 client: raindex.client(),
                 caches: &app_state.response_caches,
                 pool: pool.inner(),
+                strict_strategies: app_state.strict_strategies,
+                quote_chain_concurrency: app_state.quote_chain_concurrency,
             };
             let response =
                 process_get_orders_by_token(&ds, addr, state, side, page, page_size, denomination)
                     .await?;
-            return Ok(Json(response));
+            return super::paginated_orders(response, time_format);
         }
 
         let cache_key =
@@ -137,13 +149,15 @@ pub async fn get_orders_by_token(
                     client: raindex.client(),
                     caches: &app_state.response_caches,
                     pool: pool.inner(),
+                    strict_strategies: app_state.strict_strategies,
+                    quote_chain_concurrency: app_state.quote_chain_concurrency,
                 };
                 process_get_orders_by_token(&ds, addr, state, side, page, page_size, denomination)
                     .await
             })
             .await
             .map_err(|e| (*e).clone())?;
-        Ok(Json(response))
+        super::paginated_orders(response, time_format)
     }
     .instrument(span.0)
     .await
@@ -196,6 +210,7 @@ mod tests {
             orders: Ok(vec![mock_order()]),
             total_count: 1,
             quotes: Ok(vec![mock_quote("1.5")]),
+            strict_strategies: false,
         };
         let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
             .parse()
@@ -226,6 +241,7 @@ mod tests {
             orders: Ok(vec![]),
             total_count: 0,
             quotes: Ok(vec![]),
+            strict_strategies: false,
         };
         let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
             .parse()
@@ -253,6 +269,7 @@ mod tests {
             orders: Ok(vec![mock_order()]),
             total_count: 1,
             quotes: Err(ApiError::Internal("quote error".into())),
+            strict_strategies: false,
         };
         let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
             .parse()
@@ -272,6 +289,7 @@ mod tests {
             orders: Err(ApiError::Internal("failed".into())),
             total_count: 0,
             quotes: Ok(vec![]),
+            strict_strategies: false,
         };
         let addr: Address = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
             .parse()
@@ -288,6 +306,7 @@ mod tests {
             orders: Ok(vec![mock_order_with_shared_vaults()]),
             total_count: 1,
             quotes: Ok(vec![mock_quote("200.0")]),
+            strict_strategies: false,
         };
         let addr: Address = "0xff05e1bd696900dc6a52ca35ca61bb1024eda8e2"
             .parse()
@@ -430,7 +449,7 @@ mod tests {
     }
 
     #[rocket::async_test]
-    async fn test_get_orders_by_token_invalid_address_returns_404() {
+    async fn test_get_orders_by_token_invalid_address_returns_400() {
         let client = TestClientBuilder::new().build().await;
         let (key_id, secret) = seed_api_key(&client).await;
         let header = basic_auth_header(&key_id, &secret);
@@ -439,6 +458,6 @@ mod tests {
             .header(Header::new("Authorization", header))
             .dispatch()
             .await;
-        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert_eq!(response.status(), Status::BadRequest);
     }
 }
@@ -1,7 +1,7 @@
 use crate::auth::AuthenticatedKey;
 use crate::error::{ApiError, ApiErrorResponse};
 use crate::fairings::{GlobalRateLimit, TracingSpan};
-use crate::types::common::ValidatedFixedBytes;
+use crate::types::common::{InvalidPathParam, ValidatedFixedBytes};
 use crate::types::orders::OrdersByTxResponse;
 use rocket::serde::json::Json;
 use rocket::State;
@@ -18,6 +18,7 @@ use tracing::Instrument;
     responses(
         (status = 200, description = "Orders from transaction", body = OrdersByTxResponse),
         (status = 202, description = "Transaction not yet indexed", body = ApiErrorResponse),
+        (status = 400, description = "Invalid transaction hash", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 404, description = "Transaction not found", body = ApiErrorResponse),
@@ -30,9 +31,10 @@ pub async fn get_orders_by_tx(
     _key: AuthenticatedKey,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
     span: TracingSpan,
-    tx_hash: ValidatedFixedBytes,
+    tx_hash: Result<ValidatedFixedBytes, InvalidPathParam>,
 ) -> Result<Json<OrdersByTxResponse>, ApiError> {
     async move {
+        let tx_hash = tx_hash.map_err(|e| e.reject("transaction hash"))?;
         tracing::info!(tx_hash = ?tx_hash, "request received");
         let _raindex = shared_raindex.read().await;
         todo!()
@@ -3,8 +3,11 @@ mod get_by_token;
 mod get_by_tx;
 
 use crate::cache::RouteResponseCaches;
-use crate::error::ApiError;
-use crate::types::common::{Denomination, TokenRef};
+use crate::error::{upstream_error, ApiError};
+use crate::routes::order::determine_order_type;
+use crate::routes::pagination::{PageLinks, Paginated};
+use crate::types::common::{Denomination, TimeFormat, TokenRef};
+use crate::types::order::OrderType;
 use crate::types::orders::{
     OrderState, OrderSummary, OrderSummaryOrderType, OrdersListResponse, OrdersPagination,
 };
@@ -20,13 +23,14 @@ use rain_orderbook_common::raindex_client::order_quotes::{
 };
 use rain_orderbook_common::raindex_client::orders::{GetOrdersFilters, RaindexOrder};
 use rain_orderbook_common::raindex_client::RaindexClient;
+use rocket::serde::json::Json;
 use rocket::Route;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 
 pub(crate) const DEFAULT_PAGE_SIZE: u32 = 20;
 pub(crate) const MAX_PAGE_SIZE: u16 = 50;
-const MAX_CHAIN_BATCH_CONCURRENCY: usize = 4;
+const DEFAULT_QUOTE_CHAIN_CONCURRENCY: usize = 4;
 
 type OrderQuoteResult = Result<Vec<RaindexOrderQuote>, ApiError>;
 type OrderQuoteBatchResult = Result<Vec<Vec<RaindexOrderQuote>>, ApiError>;
@@ -48,6 +52,16 @@ pub(crate) trait OrdersListDataSource: Send + Sync {
         page_size: Option<u16>,
     ) -> Result<(Vec<RaindexOrder>, u32), ApiError>;
 
+    /// Whether listings hide orders whose strategy can't be determined.
+    fn strict_strategies(&self) -> bool {
+        false
+    }
+
+    /// Chains whose quotes are fetched at the same time.
+    fn quote_chain_concurrency(&self) -> usize {
+        DEFAULT_QUOTE_CHAIN_CONCURRENCY
+    }
+
     async fn get_order_quotes(
         &self,
         order: &RaindexOrder,
@@ -78,6 +92,9 @@ pub(crate) struct RaindexOrdersListDataSource<'a> {
     pub client: &'a RaindexClient,
     pub caches: &'a RouteResponseCaches,
     pub pool: &'a crate::db::DbPool,
+    /// Hide orders whose strategy can't be determined from listings.
+    pub strict_strategies: bool,
+    pub quote_chain_concurrency: usize,
 }
 
 pub(crate) fn order_quote_cache_key(order: &RaindexOrder) -> String {
@@ -97,6 +114,33 @@ pub(crate) fn active_filter_for_state(state: Option<OrderState>) -> Option<bool>
     }
 }
 
+/// Drops orders `classify` resolves to [`OrderType::Unknown`] and returns
+/// how many were dropped.
+fn retain_known_strategies(
+    orders: &mut Vec<RaindexOrder>,
+    classify: impl Fn(&RaindexOrder) -> OrderType,
+) -> u32 {
+    let before = orders.len();
+    orders.retain(|order| classify(order) != OrderType::Unknown);
+    (before - orders.len()) as u32
+}
+
+/// Applies the data source's strict strategy setting to a fetched page.
+///
+/// Hidden orders can't be counted without fetching every page, so the
+/// returned flag marks the page's `totalOrders` as approximate.
+pub(crate) fn hide_unknown_strategies(
+    ds: &dyn OrdersListDataSource,
+    orders: &mut Vec<RaindexOrder>,
+) -> bool {
+    if !ds.strict_strategies() {
+        return false;
+    }
+    let hidden = retain_known_strategies(orders, determine_order_type);
+    tracing::debug!(hidden, "hid orders with unknown strategies");
+    true
+}
+
 pub(crate) fn classify_order_type(order: &RaindexOrder) -> OrderSummaryOrderType {
     let source = order.rainlang().or_else(|| order.dotrain_source());
     let Some(source) = source else {
@@ -255,7 +299,7 @@ where
             fetch_quotes_for_chain_group(ds, chain_id, indexed_orders).await
         },
     ))
-    .buffer_unordered(MAX_CHAIN_BATCH_CONCURRENCY)
+    .buffer_unordered(ds.quote_chain_concurrency().max(1))
     .collect::<Vec<_>>()
     .await;
 
@@ -290,11 +334,19 @@ impl<'a> OrdersListDataSource for RaindexOrdersListDataSource<'a> {
             .await
             .map_err(|e| {
                 tracing::error!(error = %e, "failed to query orders");
-                ApiError::Internal("failed to query orders".into())
+                upstream_error(&e, "failed to query orders")
             })?;
         Ok((result.orders().to_vec(), result.total_count()))
     }
 
+    fn strict_strategies(&self) -> bool {
+        self.strict_strategies
+    }
+
+    fn quote_chain_concurrency(&self) -> usize {
+        self.quote_chain_concurrency
+    }
+
     async fn get_order_quotes(
         &self,
         order: &RaindexOrder,
@@ -302,7 +354,7 @@ impl<'a> OrdersListDataSource for RaindexOrdersListDataSource<'a> {
         let fetch = || async {
             order.get_quotes(None, None).await.map_err(|e| {
                 tracing::error!(error = %e, "failed to query order quotes");
-                ApiError::Internal("failed to query order quotes".into())
+                upstream_error(&e, "failed to query order quotes")
             })
         };
 
@@ -334,7 +386,7 @@ impl<'a> OrdersListDataSource for RaindexOrdersListDataSource<'a> {
                         error = %error,
                         "failed to batch query order quotes"
                     );
-                    ApiError::Internal("failed to query order quotes".into())
+                    upstream_error(&error, "failed to query order quotes")
                 });
         }
 
@@ -373,7 +425,7 @@ impl<'a> OrdersListDataSource for RaindexOrdersListDataSource<'a> {
                     error = %error,
                     "failed to batch query order quotes"
                 );
-                ApiError::Internal("failed to query order quotes".into())
+                upstream_error(&error, "failed to query order quotes")
             })?;
 
         if missed_quotes.len() != missed_keys.len() {
@@ -497,7 +549,8 @@ pub(crate) fn quote_result_to_summary(
 ) -> OrderQuoteSummary {
     match quotes_result {
         Ok(quotes) => {
-            let quote_data = quotes.first().and_then(|quote| quote.data.as_ref());
+            let quote_data = crate::routes::first_successful_quote(&quotes)
+                .and_then(|quote| quote.data.as_ref());
             OrderQuoteSummary {
                 io_ratio: quote_data
                     .map(|quote| quote.formatted_ratio.clone())
@@ -555,6 +608,17 @@ pub(crate) async fn get_order_quotes_for_summaries(
         .collect()
 }
 
+/// Newest orders first, ties broken by order hash, so a page merged from
+/// several orderbooks comes back in the same order on every request. Only
+/// the fetched page is sorted; the indexer decides which orders it holds.
+pub(crate) fn sort_orders_newest_first(orders: &mut [RaindexOrder]) {
+    orders.sort_by(|a, b| {
+        b.timestamp_added()
+            .cmp(&a.timestamp_added())
+            .then_with(|| a.order_hash().cmp(&b.order_hash()))
+    });
+}
+
 pub(crate) fn build_pagination(total_count: u32, page: u32, page_size: u32) -> OrdersPagination {
     let total_orders = total_count as u64;
     let total_pages = if page_size == 0 {
@@ -568,9 +632,29 @@ pub(crate) fn build_pagination(total_count: u32, page: u32, page_size: u32) -> O
         total_orders,
         total_pages,
         has_more: (page as u64) < total_pages,
+        total_is_approximate: false,
     }
 }
 
+/// Serializes an orders page with its `Link` header, rendering timestamps
+/// in `time_format`.
+pub(crate) fn paginated_orders(
+    response: OrdersListResponse,
+    time_format: TimeFormat,
+) -> Result<Paginated<Json<serde_json::Value>>, ApiError> {
+    let links = PageLinks::new(
+        response.pagination.page,
+        Some(response.pagination.total_pages),
+        response.pagination.has_more,
+    );
+    let mut body = serde_json::to_value(&response).map_err(|e| {
+        tracing::error!(error = %e, "failed to serialize response");
+        ApiError::Internal("failed to serialize response".into())
+    })?;
+    crate::timestamps::apply_time_format(&mut body, time_format);
+    Ok(Paginated::new(Json(body), links))
+}
+
 pub(crate) fn build_orders_list_response(
     orders: &[RaindexOrder],
     total_count: u32,
@@ -653,11 +737,13 @@ pub(crate) mod test_fixtures {
         pub orders: Result<Vec<RaindexOrder>, ApiError>,
         pub total_count: u32,
         pub quotes: Result<Vec<RaindexOrderQuote>, ApiError>,
+        pub strict_strategies: bool,
     }
 
     #[derive(Default)]
     pub struct RecordingOrdersListDataSource {
         pub filters: Mutex<Vec<GetOrdersFilters>>,
+        pub pages: Mutex<Vec<(Option<u16>, Option<u16>)>>,
     }
 
     #[async_trait]
@@ -674,6 +760,10 @@ pub(crate) mod test_fixtures {
             }
         }
 
+        fn strict_strategies(&self) -> bool {
+            self.strict_strategies
+        }
+
         async fn get_order_quotes(
             &self,
             _order: &RaindexOrder,
@@ -690,10 +780,14 @@ pub(crate) mod test_fixtures {
         async fn get_orders_list(
             &self,
             filters: GetOrdersFilters,
-            _page: Option<u16>,
-            _page_size: Option<u16>,
+            page: Option<u16>,
+            page_size: Option<u16>,
         ) -> Result<(Vec<RaindexOrder>, u32), ApiError> {
             self.filters.lock().expect("lock filters").push(filters);
+            self.pages
+                .lock()
+                .expect("lock pages")
+                .push((page, page_size));
             Ok((vec![], 0))
         }
 
@@ -768,6 +862,49 @@ mod tests {
         }
     }
 
+    /// Records the most chain batches in flight at once.
+    struct ConcurrencyTrackingDataSource {
+        quote_chain_concurrency: usize,
+        in_flight: Arc<Mutex<(usize, usize)>>,
+    }
+
+    #[async_trait]
+    impl OrdersListDataSource for ConcurrencyTrackingDataSource {
+        async fn get_orders_list(
+            &self,
+            _filters: GetOrdersFilters,
+            _page: Option<u16>,
+            _page_size: Option<u16>,
+        ) -> Result<(Vec<RaindexOrder>, u32), ApiError> {
+            unreachable!("not used in concurrency tests")
+        }
+
+        fn quote_chain_concurrency(&self) -> usize {
+            self.quote_chain_concurrency
+        }
+
+        async fn get_order_quotes(
+            &self,
+            _order: &RaindexOrder,
+        ) -> Result<Vec<RaindexOrderQuote>, ApiError> {
+            unreachable!("batches never fall back in concurrency tests")
+        }
+
+        async fn get_order_quotes_batch_for_chain(
+            &self,
+            orders: &[RaindexOrder],
+        ) -> OrderQuoteBatchResult {
+            {
+                let mut in_flight = self.in_flight.lock().expect("lock in flight");
+                in_flight.0 += 1;
+                in_flight.1 = in_flight.1.max(in_flight.0);
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            self.in_flight.lock().expect("lock in flight").0 -= 1;
+            Ok(vec![Vec::new(); orders.len()])
+        }
+    }
+
     fn mock_order_for_chain(chain_id: u32, order_hash: &str) -> RaindexOrder {
         let mut value = order_json();
         value["chainId"] = json!(chain_id);
@@ -966,6 +1103,26 @@ _: custom-handle-io();"#,
         assert_eq!(summary.order_bytes.as_ref(), &[1]);
     }
 
+    #[rocket::async_test]
+    async fn test_fetch_order_quotes_grouped_honours_quote_chain_concurrency() {
+        let orders = [1, 2, 3]
+            .map(|chain_id| mock_order_for_chain(chain_id, &format!("0x{chain_id:064x}")))
+            .to_vec();
+
+        for (concurrency, expected_peak) in [(1, 1), (3, 3)] {
+            let in_flight = Arc::new(Mutex::new((0, 0)));
+            let ds = ConcurrencyTrackingDataSource {
+                quote_chain_concurrency: concurrency,
+                in_flight: Arc::clone(&in_flight),
+            };
+
+            let results = fetch_order_quotes_grouped(&ds, &orders).await;
+
+            assert_eq!(results.len(), 3);
+            assert_eq!(in_flight.lock().expect("lock in flight").1, expected_peak);
+        }
+    }
+
     #[rocket::async_test]
     async fn test_fetch_order_quotes_grouped_preserves_input_order() {
         let orders = vec![
@@ -1171,5 +1328,48 @@ _: custom-handle-io();"#,
                 max_output: None,
             }
         );
+
+        let later_summary =
+            quote_result_to_summary(&order, Ok(vec![mock_failed_quote(), mock_quote("1.25")]));
+        assert_eq!(later_summary.io_ratio, "1.25");
+    }
+
+    #[test]
+    fn test_retain_known_strategies_hides_unknown_orders() {
+        let dca = "0x0000000000000000000000000000000000000000000000000000000000000201";
+        let solver = "0x0000000000000000000000000000000000000000000000000000000000000202";
+        let foreign = "0x0000000000000000000000000000000000000000000000000000000000000203";
+        let mut orders = vec![
+            mock_order_for_chain(8453, dca),
+            mock_order_for_chain(8453, foreign),
+            mock_order_for_chain(8453, solver),
+        ];
+        let classify = |order: &RaindexOrder| match order_hash_key(order).as_str() {
+            hash if hash == dca => OrderType::Dca,
+            hash if hash == solver => OrderType::Solver,
+            _ => OrderType::Unknown,
+        };
+
+        let hidden = retain_known_strategies(&mut orders, classify);
+
+        assert_eq!(hidden, 1);
+        let kept: Vec<String> = orders.iter().map(order_hash_key).collect();
+        assert_eq!(kept, vec![dca.to_string(), solver.to_string()]);
+    }
+
+    #[test]
+    fn test_retain_known_strategies_hides_orders_without_builder_state() {
+        let mut orders = vec![
+            mock_order_for_chain(
+                8453,
+                "0x0000000000000000000000000000000000000000000000000000000000000204",
+            ),
+            mock_order_with_source(Some("_ _: 1 2;")),
+        ];
+
+        let hidden = retain_known_strategies(&mut orders, determine_order_type);
+
+        assert_eq!(hidden, 2);
+        assert!(orders.is_empty());
     }
 }
@@ -0,0 +1,133 @@
+use crate::error::ApiError;
+use rocket::http::uri::Origin;
+use rocket::response::{self, Responder};
+use rocket::Request;
+
+const LINK_HEADER: &str = "Link";
+
+/// Rejects `page=0`. Pages are numbered from 1, and a zero page would
+/// otherwise be served as the first page under the wrong number.
+pub(crate) fn validate_page(page: Option<u32>) -> Result<(), ApiError> {
+    if page == Some(0) {
+        return Err(ApiError::BadRequest("page must be >= 1".into()));
+    }
+    Ok(())
+}
+
+/// Where a paginated response sits among its pages, used to build an
+/// RFC 8288 `Link` header with `first`, `prev`, `next` and `last` relations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PageLinks {
+    page: u32,
+    last_page: Option<u64>,
+    has_more: bool,
+}
+
+impl PageLinks {
+    /// `last_page` is `None` when the total isn't known, in which case no
+    /// `last` link is sent.
+    pub(crate) fn new(page: u32, last_page: Option<u64>, has_more: bool) -> Self {
+        Self {
+            page,
+            last_page,
+            has_more,
+        }
+    }
+
+    /// Links relative to `uri`, keeping every query parameter except `page`.
+    pub(crate) fn header_value(&self, uri: &Origin<'_>) -> String {
+        let mut links = vec![page_link(uri, 1, "first")];
+        if self.page > 1 {
+            links.push(page_link(uri, u64::from(self.page) - 1, "prev"));
+        }
+        if self.has_more {
+            links.push(page_link(uri, u64::from(self.page) + 1, "next"));
+        }
+        if let Some(last_page) = self.last_page.filter(|last| *last > 0) {
+            links.push(page_link(uri, last_page, "last"));
+        }
+        links.join(", ")
+    }
+}
+
+fn page_link(uri: &Origin<'_>, page: u64, rel: &str) -> String {
+    let mut query: Vec<String> = uri
+        .query()
+        .map(|query| {
+            query
+                .as_str()
+                .split('&')
+                .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some("page"))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    query.push(format!("page={page}"));
+    format!("<{}?{}>; rel=\"{rel}\"", uri.path(), query.join("&"))
+}
+
+/// Wraps a paginated response and adds its `Link` header.
+pub(crate) struct Paginated<R> {
+    inner: R,
+    links: PageLinks,
+}
+
+impl<R> Paginated<R> {
+    pub(crate) fn new(inner: R, links: PageLinks) -> Self {
+        Self { inner, links }
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Paginated<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut response = self.inner.respond_to(req)?;
+        response.set_raw_header(LINK_HEADER, self.links.header_value(req.uri()));
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn links(uri: &str, links: PageLinks) -> String {
+        links.header_value(&Origin::parse(uri).unwrap())
+    }
+
+    #[test]
+    fn test_validate_page_rejects_zero() {
+        assert!(matches!(
+            validate_page(Some(0)),
+            Err(ApiError::BadRequest(msg)) if msg == "page must be >= 1"
+        ));
+        assert!(validate_page(Some(1)).is_ok());
+        assert!(validate_page(None).is_ok());
+    }
+
+    #[test]
+    fn test_middle_page_links_every_relation() {
+        assert_eq!(
+            links(
+                "/v1/trades/0xabc?pageSize=10&page=2&startTime=5",
+                PageLinks::new(2, Some(4), true)
+            ),
+            "</v1/trades/0xabc?pageSize=10&startTime=5&page=1>; rel=\"first\", \
+             </v1/trades/0xabc?pageSize=10&startTime=5&page=1>; rel=\"prev\", \
+             </v1/trades/0xabc?pageSize=10&startTime=5&page=3>; rel=\"next\", \
+             </v1/trades/0xabc?pageSize=10&startTime=5&page=4>; rel=\"last\""
+        );
+    }
+
+    #[test]
+    fn test_first_and_last_pages_omit_prev_and_next() {
+        assert_eq!(
+            links("/v1/vaults", PageLinks::new(1, None, true)),
+            "</v1/vaults?page=1>; rel=\"first\", </v1/vaults?page=2>; rel=\"next\""
+        );
+        assert_eq!(
+            links("/v1/vaults?page=3", PageLinks::new(3, Some(3), false)),
+            "</v1/vaults?page=1>; rel=\"first\", </v1/vaults?page=2>; rel=\"prev\", \
+             </v1/vaults?page=3>; rel=\"last\""
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use crate::auth::AuthenticatedKey;
-use crate::db::{registry_history, DbPool};
+use crate::db::{registry_history, Db};
 use crate::error::{ApiError, ApiErrorResponse};
 use crate::fairings::{GlobalRateLimit, TracingSpan};
+use crate::raindex::SharedRaindexProvider;
 use rocket::serde::json::Json;
 use rocket::{Route, State};
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,9 @@ pub struct RegistryMetadataResponse {
     pub source_commit: Option<String>,
     pub payload_sha256: Option<String>,
     pub changed_at: Option<String>,
+    /// Which registry in the configured or uploaded list is loaded; `0` is
+    /// the primary and anything higher a fallback.
+    pub active_registry_index: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -61,12 +65,14 @@ impl From<registry_history::PrivateRegistryHistoryRow> for RegistryHistoryEntryR
 pub async fn get_registry(
     _global: GlobalRateLimit,
     key: AuthenticatedKey,
-    pool: &State<DbPool>,
+    shared_raindex: &State<SharedRaindexProvider>,
+    db: &State<Db>,
     span: TracingSpan,
 ) -> Result<Json<RegistryMetadataResponse>, ApiError> {
     async move {
         tracing::info!(auth_key_id = %key.key_id, auth_key_row_id = key.id, "request received");
-        let latest = registry_history::latest_successful_private_registry(pool)
+        let active_registry_index = shared_raindex.read().await.registry_index();
+        let latest = registry_history::latest_successful_private_registry(db.reads())
             .await
             .map_err(|e| {
                 tracing::error!(error = %e, "failed to query latest private registry history");
@@ -79,6 +85,7 @@ pub async fn get_registry(
                 source_commit: Some(row.source_commit),
                 payload_sha256: Some(row.payload_sha256),
                 changed_at: Some(row.changed_at),
+                active_registry_index,
             }));
         }
 
@@ -87,6 +94,7 @@ pub async fn get_registry(
             source_commit: None,
             payload_sha256: None,
             changed_at: None,
+            active_registry_index,
         }))
     }
     .instrument(span.0)
@@ -109,13 +117,13 @@ pub async fn get_registry(
 pub async fn get_registry_history(
     _global: GlobalRateLimit,
     key: AuthenticatedKey,
-    pool: &State<DbPool>,
+    db: &State<Db>,
     span: TracingSpan,
 ) -> Result<Json<Vec<RegistryHistoryEntryResponse>>, ApiError> {
     async move {
         tracing::info!(auth_key_id = %key.key_id, auth_key_row_id = key.id, "request received");
 
-        let history = registry_history::list_private_registry_history(pool)
+        let history = registry_history::list_private_registry_history(db.reads())
             .await
             .map_err(|e| {
                 tracing::error!(error = %e, "failed to query registry history");
@@ -172,6 +180,7 @@ mod tests {
         let body: serde_json::Value =
             serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
         assert_eq!(body["registry_type"], "public_url");
+        assert_eq!(body["active_registry_index"], 0);
         assert!(body.get("registry_url").is_none());
     }
 
@@ -0,0 +1,778 @@
+use crate::app_state::ApplicationState;
+use crate::auth::AuthenticatedKey;
+use crate::error::{ApiError, ApiErrorResponse};
+use crate::fairings::{GlobalRateLimit, KnownQueryParams, TracingSpan};
+use crate::routes::trades::SUBGRAPH_MAX_PAGE_SIZE;
+use crate::types::common::TokenRef;
+use crate::types::stats::{
+    OpenInterestResponse, PairOpenInterest, TokenVolume, VolumeQueryParams, VolumeStatsResponse,
+};
+use alloy::primitives::Address;
+use async_trait::async_trait;
+use futures::{stream, StreamExt, TryStreamExt};
+use rain_math_float::Float;
+use rain_orderbook_common::raindex_client::orders::{GetOrdersFilters, RaindexOrder};
+use rain_orderbook_common::raindex_client::trades::{
+    GetTradesFilters, GetTradesTokenFilter, RaindexTrade,
+};
+use rain_orderbook_common::raindex_client::types::TimeFilter;
+use rain_orderbook_common::raindex_client::RaindexClient;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use std::collections::{BTreeMap, HashSet};
+use std::ops::{Add, Sub};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::Instrument;
+
+const DEFAULT_WINDOW: &str = "24h";
+const MAX_WINDOW_SECS: u64 = 30 * 24 * 60 * 60;
+/// Upper bound on subgraph pages scanned to aggregate a single window.
+const MAX_VOLUME_PAGES: u64 = 50;
+const VOLUME_PAGE_CONCURRENCY: usize = 4;
+const OPEN_INTEREST_CACHE_KEY: &str = "stats/open-interest";
+
+#[async_trait]
+pub(crate) trait StatsDataSource: Send + Sync {
+    async fn get_trades_in_window(
+        &self,
+        token: Option<Address>,
+        time_filter: TimeFilter,
+    ) -> Result<Vec<RaindexTrade>, ApiError>;
+
+    async fn get_active_orders(&self) -> Result<Vec<RaindexOrder>, ApiError>;
+}
+
+pub(crate) struct RaindexStatsDataSource<'a> {
+    pub client: &'a RaindexClient,
+}
+
+impl RaindexStatsDataSource<'_> {
+    async fn fetch_page(
+        &self,
+        filters: GetTradesFilters,
+        page: u16,
+    ) -> Result<rain_orderbook_common::raindex_client::trades::RaindexTradesListResult, ApiError>
+    {
+        self.client
+            .get_trades(
+                None,
+                Some(filters),
+                Some(page),
+                Some(SUBGRAPH_MAX_PAGE_SIZE),
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, page, "failed to query trades for volume stats");
+                ApiError::Internal("failed to query trades".into())
+            })
+    }
+}
+
+#[async_trait]
+impl StatsDataSource for RaindexStatsDataSource<'_> {
+    async fn get_trades_in_window(
+        &self,
+        token: Option<Address>,
+        time_filter: TimeFilter,
+    ) -> Result<Vec<RaindexTrade>, ApiError> {
+        let filters = GetTradesFilters {
+            tokens: token.map(|token| GetTradesTokenFilter {
+                inputs: Some(vec![token]),
+                outputs: Some(vec![token]),
+            }),
+            time_filter: Some(time_filter),
+            ..Default::default()
+        };
+
+        let first = self.fetch_page(filters.clone(), 1).await?;
+        let page_count = first
+            .total_count()
+            .div_ceil(u64::from(SUBGRAPH_MAX_PAGE_SIZE))
+            .max(1);
+        if page_count > MAX_VOLUME_PAGES {
+            tracing::warn!(
+                total_count = first.total_count(),
+                page_count,
+                "volume window exceeds subgraph page cap"
+            );
+            return Err(ApiError::BadRequest(
+                "window contains too many trades; narrow the window or filter by token".into(),
+            ));
+        }
+
+        let mut trades = first.trades().to_vec();
+        let remaining = stream::iter(2..=page_count)
+            .map(|page| {
+                let filters = filters.clone();
+                async move {
+                    let page = u16::try_from(page)
+                        .map_err(|_| ApiError::Internal("failed to query trades".into()))?;
+                    self.fetch_page(filters, page).await
+                }
+            })
+            .buffered(VOLUME_PAGE_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await?;
+        for result in remaining {
+            trades.extend(result.trades().iter().cloned());
+        }
+
+        Ok(trades)
+    }
+
+    async fn get_active_orders(&self) -> Result<Vec<RaindexOrder>, ApiError> {
+        let filters = GetOrdersFilters {
+            active: Some(true),
+            ..Default::default()
+        };
+        let fetch_page = |page: u64| {
+            let filters = filters.clone();
+            async move {
+                let page = u16::try_from(page)
+                    .map_err(|_| ApiError::Internal("failed to query orders".into()))?;
+                self.client
+                    .get_orders(
+                        None,
+                        Some(filters),
+                        Some(page),
+                        Some(SUBGRAPH_MAX_PAGE_SIZE),
+                    )
+                    .await
+                    .map_err(|e| {
+                        tracing::error!(error = %e, page, "failed to query orders for open interest");
+                        ApiError::Internal("failed to query orders".into())
+                    })
+            }
+        };
+
+        let first = fetch_page(1).await?;
+        let page_count = u64::from(first.total_count())
+            .div_ceil(u64::from(SUBGRAPH_MAX_PAGE_SIZE))
+            .max(1);
+        if page_count > MAX_VOLUME_PAGES {
+            tracing::error!(
+                total_count = first.total_count(),
+                page_count,
+                "active orders exceed subgraph page cap"
+            );
+            return Err(ApiError::Internal(
+                "too many active orders to aggregate".into(),
+            ));
+        }
+
+        let mut orders = first.orders().to_vec();
+        let remaining = stream::iter(2..=page_count)
+            .map(fetch_page)
+            .buffered(VOLUME_PAGE_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await?;
+        for result in remaining {
+            orders.extend(result.orders().iter().cloned());
+        }
+
+        Ok(orders)
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/stats/volume",
+    tag = "Stats",
+    security(("basicAuth" = [])),
+    params(VolumeQueryParams),
+    responses(
+        (status = 200, description = "Traded volume per token over the window", body = VolumeStatsResponse),
+        (status = 400, description = "Bad request", body = ApiErrorResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 429, description = "Rate limited", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[get("/volume?<params..>")]
+pub async fn get_volume_stats(
+    _global: GlobalRateLimit,
+    _key: AuthenticatedKey,
+    _query: KnownQueryParams<VolumeQueryParams>,
+    shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    app_state: &State<ApplicationState>,
+    span: TracingSpan,
+    params: VolumeQueryParams,
+) -> Result<Json<VolumeStatsResponse>, ApiError> {
+    async move {
+        tracing::info!(params = ?params, "request received");
+        parse_window(params.window.as_deref())?;
+        let token = params
+            .token
+            .as_deref()
+            .map(|value| parse_address(value, "token"))
+            .transpose()?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        if !app_state.response_caches.is_enabled() {
+            let raindex = shared_raindex.read().await;
+            let ds = RaindexStatsDataSource {
+                client: raindex.client(),
+            };
+            return process_get_volume_stats(&ds, params, now).await;
+        }
+
+        let cache_key = volume_cache_key(params.window.as_deref(), token);
+        let response = app_state
+            .response_caches
+            .volume_stats
+            .get_or_try_insert(cache_key, || async move {
+                let raindex = shared_raindex.read().await;
+                let ds = RaindexStatsDataSource {
+                    client: raindex.client(),
+                };
+                process_get_volume_stats(&ds, params, now)
+                    .await
+                    .map(Json::into_inner)
+            })
+            .await
+            .map_err(|e| (*e).clone())?;
+        Ok(Json(response))
+    }
+    .instrument(span.0)
+    .await
+}
+
+/// Keyed by the window as given rather than its length in seconds, since
+/// the response echoes it: `7d` and `1w` get their own entries.
+fn volume_cache_key(window: Option<&str>, token: Option<Address>) -> String {
+    format!(
+        "stats/volume/{}/{}",
+        window.unwrap_or(DEFAULT_WINDOW),
+        token
+            .map(|token| token.to_string().to_ascii_lowercase())
+            .unwrap_or_else(|| "all".into())
+    )
+}
+
+pub(crate) async fn process_get_volume_stats(
+    ds: &dyn StatsDataSource,
+    params: VolumeQueryParams,
+    now: u64,
+) -> Result<Json<VolumeStatsResponse>, ApiError> {
+    let window = params
+        .window
+        .clone()
+        .unwrap_or_else(|| DEFAULT_WINDOW.to_string());
+    let window_secs = parse_window(Some(&window))?;
+    let token = params
+        .token
+        .as_deref()
+        .map(|value| parse_address(value, "token"))
+        .transpose()?;
+    let start_time = now.saturating_sub(window_secs);
+    let time_filter = TimeFilter {
+        start: Some(start_time),
+        end: Some(now),
+    };
+
+    let trades = ds.get_trades_in_window(token, time_filter).await?;
+    tracing::info!(
+        window_secs,
+        trade_count = trades.len(),
+        "aggregating trade volume"
+    );
+
+    let mut volumes = aggregate_volumes(&trades)?;
+    if let Some(token) = token {
+        volumes.retain(|volume| volume.token.address == token);
+    }
+
+    Ok(Json(VolumeStatsResponse {
+        window,
+        start_time,
+        end_time: now,
+        volumes,
+    }))
+}
+
+struct VolumeAccumulator {
+    token: TokenRef,
+    volume: Float,
+    trade_count: u64,
+}
+
+fn aggregate_volumes(trades: &[RaindexTrade]) -> Result<Vec<TokenVolume>, ApiError> {
+    let zero = Float::zero().map_err(|e| {
+        tracing::error!(error = %e, "failed to create zero float");
+        ApiError::Internal("failed to calculate trade volume".into())
+    })?;
+    let mut totals: BTreeMap<Address, VolumeAccumulator> = BTreeMap::new();
+
+    for trade in trades {
+        for change in [
+            trade.input_vault_balance_change(),
+            trade.output_vault_balance_change(),
+        ] {
+            let token = change.token();
+            let amount =
+                crate::denomination::parse_decimal_float(change.formatted_amount(), "amount")?;
+            let amount = abs_float(amount, zero)?;
+            let entry = totals
+                .entry(token.address())
+                .or_insert_with(|| VolumeAccumulator {
+                    token: TokenRef {
+                        address: token.address(),
+                        symbol: token.symbol().unwrap_or_default(),
+                        decimals: token.decimals(),
+                    },
+                    volume: zero,
+                    trade_count: 0,
+                });
+            entry.volume = entry.volume.add(amount).map_err(|e| {
+                tracing::error!(error = %e, "failed to add trade volume");
+                ApiError::Internal("failed to calculate trade volume".into())
+            })?;
+            entry.trade_count += 1;
+        }
+    }
+
+    totals
+        .into_values()
+        .map(|entry| {
+            Ok(TokenVolume {
+                token: entry.token,
+                volume: crate::denomination::format_decimal_float(entry.volume, "volume")?,
+                trade_count: entry.trade_count,
+            })
+        })
+        .collect()
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/stats/open-interest",
+    tag = "Stats",
+    security(("basicAuth" = [])),
+    responses(
+        (status = 200, description = "Output vault balances of active orders summed per pair", body = OpenInterestResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 429, description = "Rate limited", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[get("/open-interest")]
+pub async fn get_open_interest(
+    _global: GlobalRateLimit,
+    _key: AuthenticatedKey,
+    shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    app_state: &State<ApplicationState>,
+    span: TracingSpan,
+) -> Result<Json<OpenInterestResponse>, ApiError> {
+    async move {
+        tracing::info!("request received");
+        if !app_state.response_caches.is_enabled() {
+            let raindex = shared_raindex.read().await;
+            let ds = RaindexStatsDataSource {
+                client: raindex.client(),
+            };
+            return process_get_open_interest(&ds).await.map(Json);
+        }
+
+        let response = app_state
+            .response_caches
+            .open_interest
+            .get_or_try_insert(OPEN_INTEREST_CACHE_KEY.to_string(), || async move {
+                let raindex = shared_raindex.read().await;
+                let ds = RaindexStatsDataSource {
+                    client: raindex.client(),
+                };
+                process_get_open_interest(&ds).await
+            })
+            .await
+            .map_err(|e| (*e).clone())?;
+        Ok(Json(response))
+    }
+    .instrument(span.0)
+    .await
+}
+
+pub(crate) async fn process_get_open_interest(
+    ds: &dyn StatsDataSource,
+) -> Result<OpenInterestResponse, ApiError> {
+    let orders = ds.get_active_orders().await?;
+    tracing::info!(order_count = orders.len(), "aggregating open interest");
+    Ok(OpenInterestResponse {
+        pairs: aggregate_open_interest(&orders)?,
+    })
+}
+
+struct OpenInterestAccumulator {
+    input_token: TokenRef,
+    output_token: TokenRef,
+    open_interest: Float,
+    order_count: u64,
+}
+
+/// Sums each active order's output vault balance under its
+/// (input token, output token) pair. A vault shared by several orders is
+/// counted once, keyed by (owner, token, vault id). Orders whose vaults
+/// can't be resolved are skipped rather than failing the whole aggregate.
+fn aggregate_open_interest(orders: &[RaindexOrder]) -> Result<Vec<PairOpenInterest>, ApiError> {
+    let zero = Float::zero().map_err(|e| {
+        tracing::error!(error = %e, "failed to create zero float");
+        ApiError::Internal("failed to calculate open interest".into())
+    })?;
+    let mut totals: BTreeMap<(Address, Address), OpenInterestAccumulator> = BTreeMap::new();
+    let mut counted_vaults = HashSet::new();
+
+    for order in orders {
+        let Ok((input, output)) = crate::routes::resolve_io_vaults(order) else {
+            tracing::warn!(order_hash = %order.order_hash(), "skipping order without io vaults");
+            continue;
+        };
+        let (input_token, output_token) = (input.token(), output.token());
+        let entry = totals
+            .entry((input_token.address(), output_token.address()))
+            .or_insert_with(|| OpenInterestAccumulator {
+                input_token: TokenRef {
+                    address: input_token.address(),
+                    symbol: input_token.symbol().unwrap_or_default(),
+                    decimals: input_token.decimals(),
+                },
+                output_token: TokenRef {
+                    address: output_token.address(),
+                    symbol: output_token.symbol().unwrap_or_default(),
+                    decimals: output_token.decimals(),
+                },
+                open_interest: zero,
+                order_count: 0,
+            });
+        entry.order_count += 1;
+        if !counted_vaults.insert((order.owner(), output_token.address(), output.vault_id())) {
+            continue;
+        }
+        let balance =
+            crate::denomination::parse_decimal_float(output.formatted_balance(), "balance")?;
+        entry.open_interest = entry.open_interest.add(balance).map_err(|e| {
+            tracing::error!(error = %e, "failed to add vault balance");
+            ApiError::Internal("failed to calculate open interest".into())
+        })?;
+    }
+
+    totals
+        .into_values()
+        .map(|entry| {
+            Ok(PairOpenInterest {
+                input_token: entry.input_token,
+                output_token: entry.output_token,
+                open_interest: crate::denomination::format_decimal_float(
+                    entry.open_interest,
+                    "open interest",
+                )?,
+                order_count: entry.order_count,
+            })
+        })
+        .collect()
+}
+
+fn abs_float(amount: Float, zero: Float) -> Result<Float, ApiError> {
+    let negative = amount.lt(zero).map_err(|e| {
+        tracing::error!(error = %e, "failed to compare trade amount");
+        ApiError::Internal("failed to calculate trade volume".into())
+    })?;
+    if !negative {
+        return Ok(amount);
+    }
+    zero.sub(amount).map_err(|e| {
+        tracing::error!(error = %e, "failed to normalize trade amount");
+        ApiError::Internal("failed to calculate trade volume".into())
+    })
+}
+
+/// Parses windows such as `24h`, `7d` or `2w` into seconds.
+fn parse_window(window: Option<&str>) -> Result<u64, ApiError> {
+    let window = window.unwrap_or(DEFAULT_WINDOW).trim();
+    let invalid = || {
+        tracing::warn!(window, "invalid volume window");
+        ApiError::BadRequest("window must look like 24h, 7d or 2w".into())
+    };
+
+    let unit = window.chars().last().ok_or_else(invalid)?;
+    let unit_secs = match unit {
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    let count: u64 = window[..window.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+    if count == 0 {
+        return Err(invalid());
+    }
+
+    let secs = count.saturating_mul(unit_secs);
+    if secs > MAX_WINDOW_SECS {
+        return Err(ApiError::BadRequest(format!(
+            "window must not exceed {} days",
+            MAX_WINDOW_SECS / (24 * 60 * 60)
+        )));
+    }
+    Ok(secs)
+}
+
+fn parse_address(value: &str, field: &str) -> Result<Address, ApiError> {
+    value.parse::<Address>().map_err(|error| {
+        tracing::warn!(field, value, error = %error, "invalid address query parameter");
+        ApiError::BadRequest(format!("{field} must be a valid address"))
+    })
+}
+
+pub fn routes() -> Vec<Route> {
+    rocket::routes![get_volume_stats, get_open_interest]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::order::test_fixtures::{mock_order, trade_json};
+    use crate::test_helpers::TestClientBuilder;
+    use alloy::primitives::address;
+    use rocket::http::Status;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    const USDC: Address = address!("833589fcd6edb6e08f4c7c32d4f71b54bda02913");
+    const WETH: Address = address!("4200000000000000000000000000000000000006");
+    const NOW: u64 = 1_700_050_000;
+
+    #[derive(Default)]
+    struct MockStatsDataSource {
+        trades: Vec<RaindexTrade>,
+        orders: Vec<RaindexOrder>,
+        calls: Arc<Mutex<Vec<(Option<Address>, TimeFilter)>>>,
+    }
+
+    #[async_trait]
+    impl StatsDataSource for MockStatsDataSource {
+        async fn get_trades_in_window(
+            &self,
+            token: Option<Address>,
+            time_filter: TimeFilter,
+        ) -> Result<Vec<RaindexTrade>, ApiError> {
+            self.calls.lock().unwrap().push((token, time_filter));
+            Ok(self.trades.clone())
+        }
+
+        async fn get_active_orders(&self) -> Result<Vec<RaindexOrder>, ApiError> {
+            Ok(self.orders.clone())
+        }
+    }
+
+    fn fixture_trades() -> Vec<RaindexTrade> {
+        (0..2)
+            .map(|index| {
+                let mut trade = trade_json();
+                trade["id"] = json!(format!("0x{index:064x}"));
+                serde_json::from_value(trade).expect("deserialize fixture trade")
+            })
+            .collect()
+    }
+
+    fn assert_volume(volume: &TokenVolume, expected: &str) {
+        let actual = Float::parse(volume.volume.clone()).expect("parse volume");
+        let expected = Float::parse(expected.to_string()).expect("parse expected");
+        assert!(
+            actual.eq(expected).expect("compare volume"),
+            "unexpected volume {} for {}",
+            volume.volume,
+            volume.token.symbol
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_process_sums_fixture_trade_volumes_per_token() {
+        let ds = MockStatsDataSource {
+            trades: fixture_trades(),
+            ..Default::default()
+        };
+
+        let response = process_get_volume_stats(&ds, VolumeQueryParams::default(), NOW)
+            .await
+            .expect("volume stats")
+            .into_inner();
+
+        assert_eq!(response.window, "24h");
+        assert_eq!(response.start_time, NOW - 24 * 60 * 60);
+        assert_eq!(response.end_time, NOW);
+        assert_eq!(response.volumes.len(), 2);
+
+        let weth = &response.volumes[0];
+        assert_eq!(weth.token.address, WETH);
+        assert_eq!(weth.token.symbol, "WETH");
+        assert_eq!(weth.trade_count, 2);
+        assert_volume(weth, "0.5");
+
+        let usdc = &response.volumes[1];
+        assert_eq!(usdc.token.address, USDC);
+        assert_eq!(usdc.token.symbol, "USDC");
+        assert_eq!(usdc.trade_count, 2);
+        assert_volume(usdc, "1");
+    }
+
+    #[rocket::async_test]
+    async fn test_process_filters_to_requested_token() {
+        let ds = MockStatsDataSource {
+            trades: fixture_trades(),
+            ..Default::default()
+        };
+        let params = VolumeQueryParams {
+            window: Some("7d".into()),
+            token: Some(format!("{USDC:#x}")),
+        };
+
+        let response = process_get_volume_stats(&ds, params, NOW)
+            .await
+            .expect("volume stats")
+            .into_inner();
+
+        assert_eq!(response.volumes.len(), 1);
+        assert_eq!(response.volumes[0].token.address, USDC);
+        assert_volume(&response.volumes[0], "1");
+
+        let calls = ds.calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, Some(USDC));
+        assert_eq!(calls[0].1.start, Some(NOW - 7 * 24 * 60 * 60));
+        assert_eq!(calls[0].1.end, Some(NOW));
+    }
+
+    #[rocket::async_test]
+    async fn test_process_returns_empty_volumes_without_trades() {
+        let ds = MockStatsDataSource::default();
+
+        let response = process_get_volume_stats(&ds, VolumeQueryParams::default(), NOW)
+            .await
+            .expect("volume stats")
+            .into_inner();
+
+        assert!(response.volumes.is_empty());
+    }
+
+    #[rocket::async_test]
+    async fn test_process_rejects_invalid_token() {
+        let ds = MockStatsDataSource::default();
+        let params = VolumeQueryParams {
+            window: None,
+            token: Some("not-an-address".into()),
+        };
+
+        let err = process_get_volume_stats(&ds, params, NOW)
+            .await
+            .expect_err("invalid token");
+
+        assert!(matches!(err, ApiError::BadRequest(msg) if msg == "token must be a valid address"));
+        assert!(ds.calls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_volume_cache_key_follows_echoed_window() {
+        assert_ne!(
+            volume_cache_key(Some("7d"), None),
+            volume_cache_key(Some("1w"), None)
+        );
+        assert_eq!(
+            volume_cache_key(None, Some(USDC)),
+            volume_cache_key(Some("24h"), Some(USDC))
+        );
+    }
+
+    #[test]
+    fn test_parse_window() {
+        assert_eq!(parse_window(None).unwrap(), 24 * 60 * 60);
+        assert_eq!(parse_window(Some("1h")).unwrap(), 60 * 60);
+        assert_eq!(parse_window(Some("7d")).unwrap(), 7 * 24 * 60 * 60);
+        assert_eq!(parse_window(Some("2w")).unwrap(), 14 * 24 * 60 * 60);
+        assert_eq!(parse_window(Some("30d")).unwrap(), MAX_WINDOW_SECS);
+
+        for invalid in ["", "h", "0h", "24", "24m", "-1d", "abc"] {
+            assert!(
+                matches!(parse_window(Some(invalid)), Err(ApiError::BadRequest(_))),
+                "expected {invalid:?} to be rejected"
+            );
+        }
+        assert!(matches!(
+            parse_window(Some("31d")),
+            Err(ApiError::BadRequest(msg)) if msg == "window must not exceed 30 days"
+        ));
+    }
+
+    fn mock_order_with_output_vault(order_hash: &str, vault_id: &str) -> RaindexOrder {
+        let mut order = crate::routes::order::test_fixtures::order_json();
+        order["orderHash"] = json!(order_hash);
+        order["outputs"][0]["vaultId"] = json!(vault_id);
+        serde_json::from_value(order).expect("deserialize mock RaindexOrder")
+    }
+
+    #[rocket::async_test]
+    async fn test_open_interest_sums_output_balances_per_pair() {
+        let ds = MockStatsDataSource {
+            orders: vec![
+                mock_order(),
+                mock_order_with_output_vault(
+                    "0x000000000000000000000000000000000000000000000000000000000000beef",
+                    "0x0000000000000000000000000000000000000000000000000000000000000003",
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let response = process_get_open_interest(&ds).await.expect("open interest");
+
+        assert_eq!(response.pairs.len(), 1);
+        let pair = &response.pairs[0];
+        assert_eq!(pair.input_token.address, USDC);
+        assert_eq!(pair.input_token.symbol, "USDC");
+        assert_eq!(pair.output_token.address, WETH);
+        assert_eq!(pair.output_token.symbol, "WETH");
+        assert_eq!(pair.order_count, 2);
+        let actual = Float::parse(pair.open_interest.clone()).expect("parse open interest");
+        let expected = Float::parse("1".to_string()).expect("parse expected");
+        assert!(actual.eq(expected).expect("compare open interest"));
+    }
+
+    #[rocket::async_test]
+    async fn test_open_interest_counts_shared_vault_once() {
+        let ds = MockStatsDataSource {
+            orders: vec![
+                mock_order(),
+                mock_order_with_output_vault(
+                    "0x000000000000000000000000000000000000000000000000000000000000beef",
+                    "0x0000000000000000000000000000000000000000000000000000000000000002",
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let response = process_get_open_interest(&ds).await.expect("open interest");
+
+        assert_eq!(response.pairs.len(), 1);
+        let pair = &response.pairs[0];
+        assert_eq!(pair.order_count, 2);
+        let actual = Float::parse(pair.open_interest.clone()).expect("parse open interest");
+        let expected = Float::parse("0.5".to_string()).expect("parse expected");
+        assert!(actual.eq(expected).expect("compare open interest"));
+    }
+
+    #[rocket::async_test]
+    async fn test_get_open_interest_without_auth_returns_401() {
+        let client = TestClientBuilder::new().build().await;
+        let response = client.get("/v1/stats/open-interest").dispatch().await;
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[rocket::async_test]
+    async fn test_get_volume_stats_without_auth_returns_401() {
+        let client = TestClientBuilder::new().build().await;
+        let response = client.get("/v1/stats/volume?window=24h").dispatch().await;
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+}
@@ -0,0 +1,390 @@
+use crate::auth::AuthenticatedKey;
+use crate::error::{ApiError, ApiErrorResponse};
+use crate::fairings::{GlobalRateLimit, TracingSpan};
+use crate::raindex::OrderbookSource;
+use crate::routes::tokens::{api_error_message, post_graphql};
+use crate::rpc::RpcClient;
+use crate::types::status::{IndexingStatusResponse, OrderbookIndexingStatus};
+use async_trait::async_trait;
+use futures::future::join_all;
+use rocket::serde::json::Json;
+use rocket::{Route, State};
+use serde::Deserialize;
+use serde_json::json;
+use tracing::Instrument;
+use url::Url;
+
+const SUBGRAPH_META_QUERY: &str = "query IndexedBlock { _meta { block { number } } }";
+
+#[derive(Debug, Deserialize)]
+struct SubgraphMetaData {
+    #[serde(rename = "_meta")]
+    meta: SubgraphMeta,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubgraphMeta {
+    block: SubgraphMetaBlock,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubgraphMetaBlock {
+    number: u64,
+}
+
+#[async_trait]
+pub(crate) trait IndexingStatusDataSource: Send + Sync {
+    async fn indexed_block(&self, subgraph_url: &Url) -> Result<u64, ApiError>;
+
+    async fn head_block(&self, rpcs: &[Url]) -> Result<u64, ApiError>;
+}
+
+pub(crate) struct LiveIndexingStatusDataSource;
+
+#[async_trait]
+impl IndexingStatusDataSource for LiveIndexingStatusDataSource {
+    async fn indexed_block(&self, subgraph_url: &Url) -> Result<u64, ApiError> {
+        let data: SubgraphMetaData =
+            post_graphql(subgraph_url.as_str(), SUBGRAPH_META_QUERY, json!({})).await?;
+        Ok(data.meta.block.number)
+    }
+
+    /// Asks each RPC in turn and returns the first chain head reported.
+    async fn head_block(&self, rpcs: &[Url]) -> Result<u64, ApiError> {
+        for rpc in rpcs {
+            match RpcClient::new(rpc.clone()).block_number().await {
+                Ok(block) => return Ok(block),
+                Err(e) => {
+                    tracing::warn!(rpc = %rpc, error = %e, "failed to read chain head");
+                }
+            }
+        }
+        Err(ApiError::Internal("failed to read chain head block".into()))
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/status/indexing",
+    tag = "Status",
+    security(("basicAuth" = [])),
+    responses(
+        (status = 200, description = "Indexed block, chain head and lag per orderbook subgraph", body = IndexingStatusResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 429, description = "Rate limited", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[get("/indexing")]
+pub async fn get_indexing_status(
+    _global: GlobalRateLimit,
+    _key: AuthenticatedKey,
+    shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    span: TracingSpan,
+) -> Result<Json<IndexingStatusResponse>, ApiError> {
+    async move {
+        tracing::info!("request received");
+        let sources = shared_raindex.read().await.orderbook_sources();
+        Ok(process_get_indexing_status(&LiveIndexingStatusDataSource, sources).await)
+    }
+    .instrument(span.0)
+    .await
+}
+
+/// Reads every orderbook concurrently. A subgraph or RPC that can't be
+/// reached is reported on its own entry instead of failing the response.
+pub(crate) async fn process_get_indexing_status(
+    ds: &dyn IndexingStatusDataSource,
+    sources: Vec<OrderbookSource>,
+) -> Json<IndexingStatusResponse> {
+    let mut orderbooks = join_all(sources.into_iter().map(|source| async move {
+        let (indexed_block, head_block) = futures::join!(
+            ds.indexed_block(&source.subgraph_url),
+            ds.head_block(&source.rpcs)
+        );
+        let error = match (&indexed_block, &head_block) {
+            (Err(e), _) | (_, Err(e)) => Some(api_error_message(e)),
+            _ => None,
+        };
+        let indexed_block = indexed_block.ok();
+        let head_block = head_block.ok();
+        let lag_blocks = indexed_block
+            .zip(head_block)
+            .map(|(indexed, head)| head.saturating_sub(indexed));
+
+        OrderbookIndexingStatus {
+            key: source.key,
+            chain_id: source.chain_id,
+            address: source.address,
+            indexed_block,
+            head_block,
+            lag_blocks,
+            error,
+        }
+    }))
+    .await;
+    orderbooks.sort_by(|a, b| a.key.cmp(&b.key));
+
+    Json(IndexingStatusResponse { orderbooks })
+}
+
+/// Turns a lookup's `NotFound` into `NotYetIndexed` while some orderbook
+/// subgraph trails the chain head by more than `max_lag_blocks`, since the
+/// entity may simply not be indexed yet. Lag that can't be read counts as
+/// caught up. Other results pass through untouched.
+pub(crate) async fn not_found_unless_lagging<T>(
+    ds: &dyn IndexingStatusDataSource,
+    result: Result<T, ApiError>,
+    max_lag_blocks: Option<u64>,
+    sources: impl FnOnce() -> Vec<OrderbookSource>,
+) -> Result<T, ApiError> {
+    let (Err(ApiError::NotFound { message, .. }), Some(max_lag_blocks)) = (&result, max_lag_blocks)
+    else {
+        return result;
+    };
+
+    let status = process_get_indexing_status(ds, sources())
+        .await
+        .into_inner();
+    let lagging = status
+        .orderbooks
+        .iter()
+        .filter_map(|orderbook| orderbook.lag_blocks.map(|lag| (orderbook, lag)))
+        .max_by_key(|(_, lag)| *lag)
+        .filter(|(_, lag)| *lag > max_lag_blocks);
+    match lagging {
+        Some((orderbook, lag)) => {
+            tracing::info!(
+                orderbook = %orderbook.key,
+                lag_blocks = lag,
+                "not found while subgraph is lagging"
+            );
+            Err(ApiError::NotYetIndexed {
+                message: format!(
+                    "{message}; orderbook {} is {lag} blocks behind the chain head",
+                    orderbook.key
+                ),
+                attempts: 1,
+            })
+        }
+        None => result,
+    }
+}
+
+pub fn routes() -> Vec<Route> {
+    rocket::routes![get_indexing_status]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TestClientBuilder;
+    use alloy::primitives::address;
+    use rocket::http::Status;
+    use std::collections::HashMap;
+
+    struct MockIndexingStatusDataSource {
+        indexed: HashMap<Url, u64>,
+        heads: HashMap<Url, u64>,
+    }
+
+    #[async_trait]
+    impl IndexingStatusDataSource for MockIndexingStatusDataSource {
+        async fn indexed_block(&self, subgraph_url: &Url) -> Result<u64, ApiError> {
+            self.indexed
+                .get(subgraph_url)
+                .copied()
+                .ok_or_else(|| ApiError::Internal("failed to query subgraph".into()))
+        }
+
+        async fn head_block(&self, rpcs: &[Url]) -> Result<u64, ApiError> {
+            rpcs.iter()
+                .find_map(|rpc| self.heads.get(rpc).copied())
+                .ok_or_else(|| ApiError::Internal("failed to read chain head block".into()))
+        }
+    }
+
+    fn url(raw: &str) -> Url {
+        raw.parse().unwrap()
+    }
+
+    fn source(key: &str, subgraph: &str, rpc: &str) -> OrderbookSource {
+        OrderbookSource {
+            key: key.to_string(),
+            chain_id: 8453,
+            address: address!("d2938e7c9fe3597f78832ce780feb61945c377d7"),
+            subgraph_url: url(subgraph),
+            rpcs: vec![url(rpc)],
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_lag_is_head_minus_indexed_block() {
+        let ds = MockIndexingStatusDataSource {
+            indexed: HashMap::from([(url("http://subgraph.test/base"), 12_345_670)]),
+            heads: HashMap::from([(url("http://rpc.test/base"), 12_345_678)]),
+        };
+
+        let response = process_get_indexing_status(
+            &ds,
+            vec![source(
+                "base",
+                "http://subgraph.test/base",
+                "http://rpc.test/base",
+            )],
+        )
+        .await
+        .into_inner();
+
+        let status = &response.orderbooks[0];
+        assert_eq!(status.indexed_block, Some(12_345_670));
+        assert_eq!(status.head_block, Some(12_345_678));
+        assert_eq!(status.lag_blocks, Some(8));
+        assert!(status.error.is_none());
+    }
+
+    #[rocket::async_test]
+    async fn test_subgraph_ahead_of_rpc_reports_zero_lag() {
+        let ds = MockIndexingStatusDataSource {
+            indexed: HashMap::from([(url("http://subgraph.test/base"), 101)]),
+            heads: HashMap::from([(url("http://rpc.test/base"), 100)]),
+        };
+
+        let response = process_get_indexing_status(
+            &ds,
+            vec![source(
+                "base",
+                "http://subgraph.test/base",
+                "http://rpc.test/base",
+            )],
+        )
+        .await
+        .into_inner();
+
+        assert_eq!(response.orderbooks[0].lag_blocks, Some(0));
+    }
+
+    #[rocket::async_test]
+    async fn test_unreachable_subgraph_is_reported_per_orderbook() {
+        let ds = MockIndexingStatusDataSource {
+            indexed: HashMap::from([(url("http://subgraph.test/base"), 90)]),
+            heads: HashMap::from([
+                (url("http://rpc.test/base"), 100),
+                (url("http://rpc.test/arbitrum"), 500),
+            ]),
+        };
+
+        let response = process_get_indexing_status(
+            &ds,
+            vec![
+                source("base", "http://subgraph.test/base", "http://rpc.test/base"),
+                source(
+                    "arbitrum",
+                    "http://subgraph.test/arbitrum",
+                    "http://rpc.test/arbitrum",
+                ),
+            ],
+        )
+        .await
+        .into_inner();
+
+        let arbitrum = &response.orderbooks[0];
+        assert_eq!(arbitrum.key, "arbitrum");
+        assert_eq!(arbitrum.indexed_block, None);
+        assert_eq!(arbitrum.head_block, Some(500));
+        assert_eq!(arbitrum.lag_blocks, None);
+        assert_eq!(arbitrum.error.as_deref(), Some("failed to query subgraph"));
+
+        let base = &response.orderbooks[1];
+        assert_eq!(base.lag_blocks, Some(10));
+    }
+
+    fn base_source() -> Vec<OrderbookSource> {
+        vec![source(
+            "base",
+            "http://subgraph.test/base",
+            "http://rpc.test/base",
+        )]
+    }
+
+    #[rocket::async_test]
+    async fn test_not_found_while_lagging_is_not_yet_indexed() {
+        let ds = MockIndexingStatusDataSource {
+            indexed: HashMap::from([(url("http://subgraph.test/base"), 100)]),
+            heads: HashMap::from([(url("http://rpc.test/base"), 150)]),
+        };
+
+        let result: Result<(), ApiError> = not_found_unless_lagging(
+            &ds,
+            Err(ApiError::not_found("order not found")),
+            Some(10),
+            base_source,
+        )
+        .await;
+
+        match result {
+            Err(ApiError::NotYetIndexed { message, attempts }) => {
+                assert_eq!(
+                    message,
+                    "order not found; orderbook base is 50 blocks behind the chain head"
+                );
+                assert_eq!(attempts, 1);
+            }
+            other => panic!("expected NotYetIndexed, got {other:?}"),
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_not_found_while_caught_up_stays_not_found() {
+        let ds = MockIndexingStatusDataSource {
+            indexed: HashMap::from([(url("http://subgraph.test/base"), 145)]),
+            heads: HashMap::from([(url("http://rpc.test/base"), 150)]),
+        };
+
+        let result: Result<(), ApiError> = not_found_unless_lagging(
+            &ds,
+            Err(ApiError::not_found("order not found")),
+            Some(10),
+            base_source,
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiError::NotFound { .. })));
+    }
+
+    #[rocket::async_test]
+    async fn test_not_found_is_kept_without_threshold_or_lag_reading() {
+        let lagging = MockIndexingStatusDataSource {
+            indexed: HashMap::from([(url("http://subgraph.test/base"), 100)]),
+            heads: HashMap::from([(url("http://rpc.test/base"), 150)]),
+        };
+        let result: Result<(), ApiError> = not_found_unless_lagging(
+            &lagging,
+            Err(ApiError::not_found("order not found")),
+            None,
+            || panic!("indexing status read without a threshold"),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::NotFound { .. })));
+
+        let unreachable = MockIndexingStatusDataSource {
+            indexed: HashMap::new(),
+            heads: HashMap::from([(url("http://rpc.test/base"), 150)]),
+        };
+        let result: Result<(), ApiError> = not_found_unless_lagging(
+            &unreachable,
+            Err(ApiError::not_found("order not found")),
+            Some(10),
+            base_source,
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::NotFound { .. })));
+    }
+
+    #[rocket::async_test]
+    async fn test_indexing_status_requires_auth() {
+        let client = TestClientBuilder::new().build().await;
+        let response = client.get("/v1/status/indexing").dispatch().await;
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+}
@@ -1,14 +1,21 @@
-use super::{RaindexSwapDataSource, SwapDataSource};
+use super::{
+    retain_orders_on_orderbook, validate_orderbook, RaindexSwapDataSource, SwapDataSource,
+};
 use crate::app_state::ApplicationState;
 use crate::auth::AuthenticatedKey;
 use crate::db::DbPool;
-use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
+use crate::error::{ApiError, ApiErrorResponse, NotFoundReason};
+use crate::fairings::{
+    record_token_pair, short_address, GlobalRateLimit, JsonContentType, TracingSpan,
+};
+use crate::gas::{fetch_fee_hints, FeeHints};
 use crate::routes::swap::denomination::{
     normalize_calldata_request_values, normalize_calldata_response, CalldataRequestNormalization,
 };
+use crate::simulation::ensure_swap_calldata_executes;
 use crate::types::swap::{
     SwapCalldataMode, SwapCalldataRequest, SwapCalldataResponse, SwapCalldataV2Request,
+    SwapExecutePlanRequest,
 };
 use alloy::primitives::Address;
 use rain_orderbook_common::raindex_client::take_orders::TakeOrdersRequest;
@@ -27,16 +34,19 @@ use tracing::Instrument;
         (status = 200, description = "Swap calldata", body = SwapCalldataResponse),
         (status = 400, description = "Bad request", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Request involves a restricted address", body = ApiErrorResponse),
         (status = 404, description = "No liquidity found", body = ApiErrorResponse),
         (status = 422, description = "Request body could not be parsed", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
     )
 )]
+#[allow(clippy::too_many_arguments)]
 #[post("/calldata", data = "<request>")]
 pub async fn post_swap_calldata(
     _global: GlobalRateLimit,
     _key: AuthenticatedKey,
+    _json: JsonContentType,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
     app_state: &State<ApplicationState>,
     pool: &State<DbPool>,
@@ -44,16 +54,35 @@ pub async fn post_swap_calldata(
     request: Json<SwapCalldataRequest>,
 ) -> Result<Json<SwapCalldataResponse>, ApiError> {
     let req = request.into_inner();
+    record_token_pair(
+        &span.0,
+        &short_address(req.input_token),
+        &short_address(req.output_token),
+    );
     async move {
         tracing::info!(body = ?req, "request received");
+        app_state
+            .denylist
+            .check(&[req.taker, req.input_token, req.output_token])?;
         let raindex = shared_raindex.read().await;
         let ds = RaindexSwapDataSource {
             client: raindex.client(),
             caches: &app_state.response_caches,
             pool: pool.inner(),
+            orderbooks: raindex.orderbooks_for_chain(crate::CHAIN_ID),
+            max_orders: app_state.max_quote_orders,
         };
+        let taker = req.taker;
         let response = process_swap_calldata(&ds, req).await?;
-        Ok(Json(response))
+        let rpcs = if app_state.simulate_calldata {
+            let rpcs = raindex.require_rpcs_for_chain(crate::CHAIN_ID)?;
+            ensure_swap_calldata_executes(&rpcs, taker, &response).await?;
+            rpcs
+        } else {
+            raindex.rpcs_for_chain(crate::CHAIN_ID)
+        };
+        let fee_hints = fetch_fee_hints(&rpcs).await;
+        Ok(Json(with_fee_hints(response, fee_hints)))
     }
     .instrument(span.0)
     .await
@@ -69,16 +98,19 @@ pub async fn post_swap_calldata(
         (status = 200, description = "Swap calldata", body = SwapCalldataResponse),
         (status = 400, description = "Bad request", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Request involves a restricted address", body = ApiErrorResponse),
         (status = 404, description = "No liquidity found", body = ApiErrorResponse),
         (status = 422, description = "Request body could not be parsed", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
     )
 )]
+#[allow(clippy::too_many_arguments)]
 #[post("/calldata", data = "<request>")]
 pub async fn post_swap_calldata_v2(
     _global: GlobalRateLimit,
     _key: AuthenticatedKey,
+    _json: JsonContentType,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
     app_state: &State<ApplicationState>,
     pool: &State<DbPool>,
@@ -86,27 +118,46 @@ pub async fn post_swap_calldata_v2(
     request: Json<SwapCalldataV2Request>,
 ) -> Result<Json<SwapCalldataResponse>, ApiError> {
     let req = request.into_inner();
+    record_token_pair(
+        &span.0,
+        &short_address(req.input_token),
+        &short_address(req.output_token),
+    );
     async move {
         tracing::info!(
             mode = ?req.mode,
             denomination = ?req.denomination,
             "request received"
         );
+        app_state
+            .denylist
+            .check(&[req.taker, req.input_token, req.output_token])?;
         let raindex = shared_raindex.read().await;
         let ds = RaindexSwapDataSource {
             client: raindex.client(),
             caches: &app_state.response_caches,
             pool: pool.inner(),
+            orderbooks: raindex.orderbooks_for_chain(crate::CHAIN_ID),
+            max_orders: app_state.max_quote_orders,
         };
+        let taker = req.taker;
         let response = process_swap_calldata_v2(&ds, req).await?;
-        Ok(Json(response))
+        let rpcs = if app_state.simulate_calldata {
+            let rpcs = raindex.require_rpcs_for_chain(crate::CHAIN_ID)?;
+            ensure_swap_calldata_executes(&rpcs, taker, &response).await?;
+            rpcs
+        } else {
+            raindex.rpcs_for_chain(crate::CHAIN_ID)
+        };
+        let fee_hints = fetch_fee_hints(&rpcs).await;
+        Ok(Json(with_fee_hints(response, fee_hints)))
     }
     .instrument(span.0)
     .await
 }
 
 #[derive(Debug)]
-struct SwapCalldataBuildRequest {
+pub(super) struct SwapCalldataBuildRequest {
     taker: Address,
     input_token: Address,
     output_token: Address,
@@ -116,6 +167,7 @@ struct SwapCalldataBuildRequest {
     price_cap: String,
     price_cap_field: &'static str,
     denomination: crate::types::swap::SwapDenomination,
+    orderbook: Option<Address>,
 }
 
 impl From<SwapCalldataRequest> for SwapCalldataBuildRequest {
@@ -130,6 +182,7 @@ impl From<SwapCalldataRequest> for SwapCalldataBuildRequest {
             price_cap: req.maximum_io_ratio,
             price_cap_field: "maximum_io_ratio",
             denomination: req.denomination,
+            orderbook: req.orderbook,
         }
     }
 }
@@ -146,6 +199,24 @@ impl From<SwapCalldataV2Request> for SwapCalldataBuildRequest {
             price_cap: req.price_cap,
             price_cap_field: "price_cap",
             denomination: req.denomination,
+            orderbook: req.orderbook,
+        }
+    }
+}
+
+impl From<&SwapExecutePlanRequest> for SwapCalldataBuildRequest {
+    fn from(req: &SwapExecutePlanRequest) -> Self {
+        Self {
+            taker: req.taker,
+            input_token: req.input_token,
+            output_token: req.output_token,
+            mode: TakeOrdersMode::BuyExact,
+            amount: req.output_amount.clone(),
+            amount_field: "output_amount",
+            price_cap: req.maximum_io_ratio.clone(),
+            price_cap_field: "maximum_io_ratio",
+            denomination: req.denomination,
+            orderbook: req.orderbook,
         }
     }
 }
@@ -160,26 +231,81 @@ impl From<SwapCalldataMode> for TakeOrdersMode {
     }
 }
 
+fn with_fee_hints(
+    response: SwapCalldataResponse,
+    fee_hints: Option<FeeHints>,
+) -> SwapCalldataResponse {
+    SwapCalldataResponse {
+        max_fee_per_gas: fee_hints.map(|hints| hints.max_fee_per_gas),
+        max_priority_fee_per_gas: fee_hints.map(|hints| hints.max_priority_fee_per_gas),
+        ..response
+    }
+}
+
 async fn process_swap_calldata(
     ds: &dyn SwapDataSource,
     req: SwapCalldataRequest,
 ) -> Result<SwapCalldataResponse, ApiError> {
-    process_swap_calldata_build(ds, req.into()).await
+    let warning = check_taker(ds, req.taker, req.input_token, req.output_token)?;
+    let response = process_swap_calldata_build(ds, req.into()).await?;
+    Ok(SwapCalldataResponse {
+        taker_warning: warning,
+        ..response
+    })
 }
 
 async fn process_swap_calldata_v2(
     ds: &dyn SwapDataSource,
     req: SwapCalldataV2Request,
 ) -> Result<SwapCalldataResponse, ApiError> {
-    process_swap_calldata_build(ds, req.into()).await
+    let warning = check_taker(ds, req.taker, req.input_token, req.output_token)?;
+    let response = process_swap_calldata_build(ds, req.into()).await?;
+    Ok(SwapCalldataResponse {
+        taker_warning: warning,
+        ..response
+    })
+}
+
+/// The take-orders transaction is sent from `taker`, so it has to be an
+/// account that can sign. The zero address never can; the orderbook and
+/// the swapped tokens are contracts that won't, so calldata for them is
+/// still built but comes back with a warning.
+fn check_taker(
+    ds: &dyn SwapDataSource,
+    taker: Address,
+    input_token: Address,
+    output_token: Address,
+) -> Result<Option<String>, ApiError> {
+    if taker.is_zero() {
+        tracing::warn!("swap calldata requested for the zero address");
+        return Err(ApiError::BadRequest(
+            "taker must not be the zero address".into(),
+        ));
+    }
+
+    let warning = if ds.known_orderbooks().contains(&taker) {
+        Some("taker is the orderbook contract and cannot sign transactions")
+    } else if taker == input_token || taker == output_token {
+        Some("taker is a token contract and cannot sign transactions")
+    } else {
+        None
+    };
+    if let Some(warning) = warning {
+        tracing::warn!(taker = %taker, warning, "suspicious swap taker");
+    }
+    Ok(warning.map(str::to_string))
 }
 
-async fn process_swap_calldata_build(
+pub(super) async fn process_swap_calldata_build(
     ds: &dyn SwapDataSource,
     req: SwapCalldataBuildRequest,
 ) -> Result<SwapCalldataResponse, ApiError> {
     ds.validate_supported_tokens(req.input_token, req.output_token)
         .await?;
+    if let Some(orderbook) = req.orderbook {
+        validate_orderbook(ds, orderbook)?;
+        ensure_orderbook_liquidity(ds, req.input_token, req.output_token, orderbook).await?;
+    }
 
     let (amount, price_cap, wrap_ratios) = normalize_calldata_request_values(
         ds,
@@ -207,9 +333,52 @@ async fn process_swap_calldata_build(
     };
 
     let response = ds.get_calldata(take_req).await?;
+    if let Some(orderbook) = req.orderbook {
+        // The requested orderbook has liquidity, but the take-orders builder
+        // picks the best route across every orderbook and can't be pinned.
+        if response.to != orderbook {
+            tracing::info!(
+                orderbook = %orderbook,
+                routed_to = %response.to,
+                "best route is not on the requested orderbook"
+            );
+            return Err(ApiError::not_found_because(
+                NotFoundReason::NoLiquidity,
+                "best route for this pair is not on the requested orderbook",
+            ));
+        }
+    }
     normalize_calldata_response(&wrap_ratios, req.denomination, req.input_token, response)
 }
 
+/// Fails before any calldata is built when `orderbook` holds no quotable
+/// orders for the pair, the same candidates the quote path prices.
+async fn ensure_orderbook_liquidity(
+    ds: &dyn SwapDataSource,
+    input_token: Address,
+    output_token: Address,
+    orderbook: Address,
+) -> Result<(), ApiError> {
+    let orders = retain_orders_on_orderbook(
+        ds.get_orders_for_pair(input_token, output_token).await?,
+        Some(orderbook),
+    );
+    let candidates = if orders.is_empty() {
+        Vec::new()
+    } else {
+        ds.build_candidates_for_pair(&orders, input_token, output_token)
+            .await?
+    };
+    if candidates.is_empty() {
+        tracing::info!(orderbook = %orderbook, "requested orderbook has no liquidity for the pair");
+        return Err(ApiError::not_found_because(
+            NotFoundReason::NoLiquidity,
+            "no liquidity found for this pair on the requested orderbook",
+        ));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -239,6 +408,7 @@ mod tests {
             output_amount: output_amount.to_string(),
             maximum_io_ratio: max_ratio.to_string(),
             denomination: SwapDenomination::Wrapped,
+            orderbook: None,
         }
     }
 
@@ -255,6 +425,7 @@ mod tests {
             amount: amount.to_string(),
             price_cap: price_cap.to_string(),
             denomination: SwapDenomination::Wrapped,
+            orderbook: None,
         }
     }
 
@@ -271,6 +442,7 @@ mod tests {
             output_amount: output_amount.to_string(),
             maximum_io_ratio: max_ratio.to_string(),
             denomination: SwapDenomination::Unwrapped,
+            orderbook: None,
         }
     }
 
@@ -289,6 +461,7 @@ mod tests {
             amount: amount.to_string(),
             price_cap: price_cap.to_string(),
             denomination: SwapDenomination::Unwrapped,
+            orderbook: None,
         }
     }
 
@@ -297,9 +470,12 @@ mod tests {
             to: ORDERBOOK,
             data: Bytes::from(vec![0xab, 0xcd, 0xef]),
             value: U256::ZERO,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
             estimated_input: "150".to_string(),
             denomination: SwapDenomination::Wrapped,
             approvals: vec![],
+            taker_warning: None,
         }
     }
 
@@ -308,6 +484,8 @@ mod tests {
             to: ORDERBOOK,
             data: Bytes::new(),
             value: U256::ZERO,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
             estimated_input: "1000".to_string(),
             denomination: SwapDenomination::Wrapped,
             approvals: vec![Approval {
@@ -317,6 +495,7 @@ mod tests {
                 symbol: String::new(),
                 approval_data: Bytes::from(vec![0x09, 0x5e, 0xa7, 0xb3]),
             }],
+            taker_warning: None,
         }
     }
 
@@ -408,6 +587,10 @@ mod tests {
             self.base.calldata_result.clone()
         }
 
+        fn known_orderbooks(&self) -> Vec<Address> {
+            self.base.known_orderbooks()
+        }
+
         async fn get_wrap_ratios_for_tokens(
             &self,
             token_addresses: &[Address],
@@ -454,6 +637,133 @@ mod tests {
         assert!(result.approvals.is_empty());
     }
 
+    #[rocket::async_test]
+    async fn test_process_swap_calldata_restricted_to_known_orderbook() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![crate::test_helpers::mock_order()]),
+            candidates: vec![crate::test_helpers::mock_candidate("1000", "1.5")],
+            calldata_result: Ok(ready_response()),
+        };
+        let req = SwapCalldataRequest {
+            orderbook: Some(ORDERBOOK),
+            ..calldata_request("100", "2.5")
+        };
+        let result = process_swap_calldata(&ds, req).await.unwrap();
+
+        assert_eq!(result.to, ORDERBOOK);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_calldata_rejects_unknown_orderbook() {
+        let (ds, captured_request) = capture_ds(ready_response(), HashMap::new());
+        let req = SwapCalldataV2Request {
+            orderbook: Some(address!("9999999999999999999999999999999999999999")),
+            ..calldata_v2_request(SwapCalldataMode::SpendExact, "100", "2.5")
+        };
+        let result = process_swap_calldata_v2(&ds, req).await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::BadRequest(msg))
+                if msg == "unknown orderbook: 0x9999999999999999999999999999999999999999"
+        ));
+        no_take_orders_request_was_made(&captured_request);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_calldata_orderbook_without_liquidity_is_not_found() {
+        let (mut ds, captured_request) = capture_ds(ready_response(), HashMap::new());
+        ds.base.orders = Ok(vec![crate::test_helpers::mock_order()]);
+        let req = SwapCalldataRequest {
+            orderbook: Some(ORDERBOOK),
+            ..calldata_request("100", "2.5")
+        };
+
+        let result = process_swap_calldata(&ds, req).await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::NotFound {
+                reason: Some(NotFoundReason::NoLiquidity),
+                ..
+            })
+        ));
+        no_take_orders_request_was_made(&captured_request);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_calldata_route_on_other_orderbook_is_not_found() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![crate::test_helpers::mock_order()]),
+            candidates: vec![crate::test_helpers::mock_candidate("1000", "1.5")],
+            calldata_result: Ok(SwapCalldataResponse {
+                to: address!("9999999999999999999999999999999999999999"),
+                ..ready_response()
+            }),
+        };
+        let req = SwapCalldataRequest {
+            orderbook: Some(ORDERBOOK),
+            ..calldata_request("100", "2.5")
+        };
+        let result = process_swap_calldata(&ds, req).await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::NotFound {
+                reason: Some(NotFoundReason::NoLiquidity),
+                ..
+            })
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_calldata_rejects_zero_address_taker() {
+        let (ds, captured_request) = capture_ds(ready_response(), HashMap::new());
+        let req = SwapCalldataRequest {
+            taker: Address::ZERO,
+            ..calldata_request("100", "2.5")
+        };
+
+        let result = process_swap_calldata(&ds, req).await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::BadRequest(msg)) if msg == "taker must not be the zero address"
+        ));
+        no_take_orders_request_was_made(&captured_request);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_calldata_normal_taker_has_no_warning() {
+        let (ds, _) = capture_ds(ready_response(), HashMap::new());
+
+        let result = process_swap_calldata(&ds, calldata_request("100", "2.5"))
+            .await
+            .unwrap();
+
+        assert!(result.taker_warning.is_none());
+        let body = serde_json::to_value(&result).unwrap();
+        assert!(body.get("takerWarning").is_none());
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_calldata_warns_when_taker_is_a_token() {
+        let (ds, _) = capture_ds(ready_response(), HashMap::new());
+        let req = SwapCalldataRequest {
+            taker: WETH,
+            ..calldata_request("100", "2.5")
+        };
+
+        let result = process_swap_calldata(&ds, req).await.unwrap();
+
+        assert_eq!(
+            result.taker_warning.as_deref(),
+            Some("taker is a token contract and cannot sign transactions")
+        );
+    }
+
     #[rocket::async_test]
     async fn test_process_swap_calldata_needs_approval() {
         let ds = MockSwapDataSource {
@@ -733,6 +1043,24 @@ mod tests {
         no_take_orders_request_was_made(&captured_request);
     }
 
+    #[rocket::async_test]
+    async fn test_process_swap_calldata_unwrapped_out_of_range_amount_is_bad_request() {
+        let (ds, captured_request) = capture_ds(
+            ready_response(),
+            HashMap::from([(WT_MSTR, wrap_ratio(WT_MSTR, "1e-2000000000"))]),
+        );
+        let result = process_swap_calldata(
+            &ds,
+            unwrapped_calldata_request(USDC, WT_MSTR, "1e2000000000", "2.5"),
+        )
+        .await;
+
+        assert!(
+            matches!(result, Err(ApiError::BadRequest(msg)) if msg == "output_amount is out of range")
+        );
+        no_take_orders_request_was_made(&captured_request);
+    }
+
     #[rocket::async_test]
     async fn test_process_swap_calldata_unwrapped_invalid_maximum_io_ratio_is_bad_request() {
         let (ds, captured_request) = capture_ds(
@@ -874,12 +1202,12 @@ mod tests {
             supported_tokens: Ok(()),
             orders: Ok(vec![]),
             candidates: vec![],
-            calldata_result: Err(ApiError::NotFound(
-                "no liquidity found for this pair".into(),
-            )),
+            calldata_result: Err(ApiError::not_found("no liquidity found for this pair")),
         };
         let result = process_swap_calldata(&ds, calldata_request("100", "2.5")).await;
-        assert!(matches!(result, Err(ApiError::NotFound(msg)) if msg.contains("no liquidity")));
+        assert!(
+            matches!(result, Err(ApiError::NotFound { message: msg, .. }) if msg.contains("no liquidity"))
+        );
     }
 
     #[rocket::async_test]
@@ -976,6 +1304,62 @@ mod tests {
         assert_eq!(response.status(), Status::BadRequest);
     }
 
+    #[rocket::async_test]
+    async fn test_swap_calldata_403_for_denylisted_taker() {
+        let client = TestClientBuilder::new().denylist(vec![TAKER]).build().await;
+        let (key_id, secret) = crate::test_helpers::seed_api_key(&client).await;
+        let header = crate::test_helpers::basic_auth_header(&key_id, &secret);
+        let response = client
+            .post("/v1/swap/calldata")
+            .header(ContentType::JSON)
+            .header(rocket::http::Header::new("Authorization", header))
+            .body(r#"{"taker":"0x1111111111111111111111111111111111111111","inputToken":"0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913","outputToken":"0x4200000000000000000000000000000000000006","outputAmount":"100","maximumIoRatio":"2.5"}"#)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(body["error"]["code"], "FORBIDDEN_ADDRESS");
+    }
+
+    #[rocket::async_test]
+    async fn test_swap_calldata_v2_403_for_denylisted_taker() {
+        let client = TestClientBuilder::new().denylist(vec![TAKER]).build().await;
+        let (key_id, secret) = crate::test_helpers::seed_api_key(&client).await;
+        let header = crate::test_helpers::basic_auth_header(&key_id, &secret);
+        let response = client
+            .post("/v2/swap/calldata")
+            .header(ContentType::JSON)
+            .header(rocket::http::Header::new("Authorization", header))
+            .body(r#"{"taker":"0x1111111111111111111111111111111111111111","inputToken":"0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913","outputToken":"0x4200000000000000000000000000000000000006","mode":"spendExact","amount":"100","priceCap":"2.5"}"#)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(body["error"]["code"], "FORBIDDEN_ADDRESS");
+    }
+
+    #[rocket::async_test]
+    async fn test_swap_calldata_clean_taker_passes_denylist() {
+        let denied = address!("2222222222222222222222222222222222222222");
+        let client = TestClientBuilder::new()
+            .denylist(vec![denied])
+            .build()
+            .await;
+        let (key_id, secret) = crate::test_helpers::seed_api_key(&client).await;
+        let header = crate::test_helpers::basic_auth_header(&key_id, &secret);
+        let response = client
+            .post("/v1/swap/calldata")
+            .header(ContentType::JSON)
+            .header(rocket::http::Header::new("Authorization", header))
+            .body(r#"{"taker":"0x1111111111111111111111111111111111111111","inputToken":"0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913","outputToken":"0x4200000000000000000000000000000000000006","outputAmount":"100","maximumIoRatio":"2.5"}"#)
+            .dispatch()
+            .await;
+        // Past the denylist the test registry has no orders for the pair.
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
     #[rocket::async_test]
     async fn test_swap_calldata_422_for_invalid_denomination() {
         let client = TestClientBuilder::new().build().await;
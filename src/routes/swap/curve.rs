@@ -0,0 +1,266 @@
+use super::{
+    retain_orders_on_orderbook, validate_orderbook, RaindexSwapDataSource, SwapDataSource,
+};
+use crate::app_state::ApplicationState;
+use crate::auth::AuthenticatedKey;
+use crate::db::DbPool;
+use crate::error::{ApiError, ApiErrorResponse, NotFoundReason};
+use crate::fairings::{
+    record_token_pair, short_address, GlobalRateLimit, JsonContentType, TracingSpan,
+};
+use crate::routes::swap::denomination::client_value_out_of_range;
+use crate::types::swap::{SwapCurvePoint, SwapCurveRequest, SwapCurveResponse};
+use rain_math_float::Float;
+use rain_orderbook_common::take_orders::{simulate_buy_over_candidates, TakeOrderCandidate};
+use rocket::serde::json::Json;
+use rocket::State;
+use std::ops::Div;
+use tracing::Instrument;
+
+/// Most sizes one curve request may price.
+pub(crate) const MAX_CURVE_SIZES: usize = 20;
+
+#[utoipa::path(
+    post,
+    path = "/v1/swap/curve",
+    tag = "Swap",
+    security(("basicAuth" = [])),
+    request_body = SwapCurveRequest,
+    responses(
+        (status = 200, description = "Blended price at each requested output size", body = SwapCurveResponse),
+        (status = 400, description = "Bad request", body = ApiErrorResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Request involves a restricted address", body = ApiErrorResponse),
+        (status = 404, description = "No liquidity found", body = ApiErrorResponse),
+        (status = 422, description = "Request body could not be parsed", body = ApiErrorResponse),
+        (status = 429, description = "Rate limited", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+#[post("/curve", data = "<request>")]
+pub async fn post_swap_curve(
+    _global: GlobalRateLimit,
+    _key: AuthenticatedKey,
+    _json: JsonContentType,
+    shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    app_state: &State<ApplicationState>,
+    pool: &State<DbPool>,
+    span: TracingSpan,
+    request: Json<SwapCurveRequest>,
+) -> Result<Json<SwapCurveResponse>, ApiError> {
+    let req = request.into_inner();
+    record_token_pair(
+        &span.0,
+        &short_address(req.input_token),
+        &short_address(req.output_token),
+    );
+    async move {
+        tracing::info!(body = ?req, "request received");
+        app_state
+            .denylist
+            .check(&[req.input_token, req.output_token])?;
+        let raindex = shared_raindex.read().await;
+        let ds = RaindexSwapDataSource {
+            client: raindex.client(),
+            caches: &app_state.response_caches,
+            pool: pool.inner(),
+            orderbooks: raindex.orderbooks_for_chain(crate::CHAIN_ID),
+            max_orders: app_state.max_quote_orders,
+        };
+        let response = process_swap_curve(&ds, req).await?;
+        Ok(Json(response))
+    }
+    .instrument(span.0)
+    .await
+}
+
+/// Prices every size against one fetch of the pair's candidates, so the
+/// points are consistent with each other.
+async fn process_swap_curve(
+    ds: &dyn SwapDataSource,
+    req: SwapCurveRequest,
+) -> Result<SwapCurveResponse, ApiError> {
+    let sizes = parse_sizes(&req.sizes)?;
+
+    ds.validate_supported_tokens(req.input_token, req.output_token)
+        .await?;
+    if let Some(orderbook) = req.orderbook {
+        validate_orderbook(ds, orderbook)?;
+    }
+
+    let orders = retain_orders_on_orderbook(
+        ds.get_orders_for_pair(req.input_token, req.output_token)
+            .await?,
+        req.orderbook,
+    );
+    if orders.is_empty() {
+        return Err(ApiError::not_found_because(
+            NotFoundReason::NoLiquidity,
+            "no liquidity found for this pair",
+        ));
+    }
+
+    let candidates = ds
+        .build_candidates_for_pair(&orders, req.input_token, req.output_token)
+        .await?;
+    if candidates.is_empty() {
+        return Err(ApiError::not_found_because(
+            NotFoundReason::NoLiquidity,
+            "no valid quotes available",
+        ));
+    }
+
+    let points = req
+        .sizes
+        .into_iter()
+        .zip(sizes)
+        .map(|(raw, size)| curve_point(&candidates, raw, size))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(SwapCurveResponse {
+        input_token: req.input_token,
+        output_token: req.output_token,
+        points,
+    })
+}
+
+fn parse_sizes(sizes: &[String]) -> Result<Vec<Float>, ApiError> {
+    if sizes.is_empty() {
+        return Err(ApiError::BadRequest("sizes must not be empty".into()));
+    }
+    if sizes.len() > MAX_CURVE_SIZES {
+        return Err(ApiError::BadRequest(format!(
+            "at most {MAX_CURVE_SIZES} sizes can be priced per request"
+        )));
+    }
+
+    let zero = Float::zero().map_err(|e| {
+        tracing::error!(error = %e, "failed to create zero float");
+        ApiError::Internal("failed to price curve".into())
+    })?;
+    sizes
+        .iter()
+        .map(|raw| {
+            let invalid = || ApiError::BadRequest(format!("invalid size: {raw}"));
+            let size = Float::parse(raw.clone()).map_err(|e| {
+                tracing::warn!(error = %e, size = %raw, "failed to parse curve size");
+                invalid()
+            })?;
+            if !zero
+                .lt(size)
+                .map_err(|e| client_value_out_of_range(e, "sizes"))?
+            {
+                return Err(invalid());
+            }
+            Ok(size)
+        })
+        .collect()
+}
+
+fn curve_point(
+    candidates: &[TakeOrderCandidate],
+    raw_size: String,
+    size: Float,
+) -> Result<SwapCurvePoint, ApiError> {
+    fn curve_error(e: impl std::fmt::Display) -> ApiError {
+        tracing::error!(error = %e, "failed to price curve point");
+        ApiError::Internal("failed to price curve".into())
+    }
+
+    let price_cap = Float::max_positive_value().map_err(curve_error)?;
+    let sim =
+        simulate_buy_over_candidates(candidates.to_vec(), size, price_cap).map_err(curve_error)?;
+
+    let blended_ratio = if sim.total_output.is_zero().map_err(curve_error)? {
+        None
+    } else {
+        Some(
+            sim.total_input
+                .div(sim.total_output)
+                .and_then(|ratio| ratio.format())
+                .map_err(curve_error)?,
+        )
+    };
+
+    Ok(SwapCurvePoint {
+        size: raw_size,
+        estimated_input: sim.total_input.format().map_err(curve_error)?,
+        blended_ratio,
+        fully_filled: !sim.total_output.lt(size).map_err(curve_error)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::swap::test_fixtures::MockSwapDataSource;
+    use crate::test_helpers::{mock_candidate, mock_order};
+    use alloy::primitives::address;
+
+    fn curve_request(sizes: &[&str]) -> SwapCurveRequest {
+        SwapCurveRequest {
+            input_token: address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
+            output_token: address!("4200000000000000000000000000000000000006"),
+            sizes: sizes.iter().map(|size| size.to_string()).collect(),
+            orderbook: None,
+        }
+    }
+
+    fn laddered_ds() -> MockSwapDataSource {
+        MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![
+                mock_candidate("100", "3"),
+                mock_candidate("100", "1.5"),
+                mock_candidate("100", "2"),
+            ],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        }
+    }
+
+    fn ratio(point: &SwapCurvePoint) -> Float {
+        Float::parse(point.blended_ratio.clone().expect("blended ratio")).unwrap()
+    }
+
+    #[rocket::async_test]
+    async fn test_curve_ratio_worsens_with_size() {
+        let response = process_swap_curve(&laddered_ds(), curve_request(&["50", "150", "250"]))
+            .await
+            .unwrap();
+
+        let points = &response.points;
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0].size, "50");
+        assert_eq!(points[0].blended_ratio.as_deref(), Some("1.5"));
+        assert_eq!(points[2].blended_ratio.as_deref(), Some("2"));
+        assert!(ratio(&points[0]).lt(ratio(&points[1])).unwrap());
+        assert!(ratio(&points[1]).lt(ratio(&points[2])).unwrap());
+        assert!(points.iter().all(|point| point.fully_filled));
+    }
+
+    #[rocket::async_test]
+    async fn test_curve_marks_sizes_beyond_depth_unfilled() {
+        let response = process_swap_curve(&laddered_ds(), curve_request(&["300", "400"]))
+            .await
+            .unwrap();
+
+        assert!(response.points[0].fully_filled);
+        assert!(!response.points[1].fully_filled);
+        assert_eq!(response.points[1].estimated_input, "650");
+    }
+
+    #[rocket::async_test]
+    async fn test_curve_rejects_too_many_or_invalid_sizes() {
+        let sizes = vec!["1"; MAX_CURVE_SIZES + 1];
+        let result = process_swap_curve(&laddered_ds(), curve_request(&sizes)).await;
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+
+        let result = process_swap_curve(&laddered_ds(), curve_request(&["0"])).await;
+        assert!(matches!(result, Err(ApiError::BadRequest(msg)) if msg == "invalid size: 0"));
+
+        let result = process_swap_curve(&laddered_ds(), curve_request(&[])).await;
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+    }
+}
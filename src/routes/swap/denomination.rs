@@ -45,6 +45,62 @@ pub(crate) async fn normalize_quote_amounts(
     }
 }
 
+/// Converts a wrapped amount of `token` from the simulation into
+/// `denomination`, as [`normalize_quote_amounts`] does for the totals.
+pub(crate) async fn normalize_quote_amount(
+    ds: &dyn SwapDataSource,
+    denomination: SwapDenomination,
+    token: Address,
+    amount: Float,
+) -> Result<Float, ApiError> {
+    if denomination == SwapDenomination::Wrapped {
+        return Ok(amount);
+    }
+    let ratios = ds.get_wrap_ratios_for_tokens(&[token]).await?;
+    convert_wrapped_amount_for_token(amount, token, &ratios)
+}
+
+/// Converts a caller's `inputAmount` budget into the wrapped terms the
+/// simulation runs in.
+pub(crate) async fn normalize_quote_budget(
+    ds: &dyn SwapDataSource,
+    denomination: SwapDenomination,
+    input_token: Address,
+    budget: Float,
+) -> Result<Float, ApiError> {
+    if denomination == SwapDenomination::Wrapped {
+        return Ok(budget);
+    }
+    let ratios = ds.get_wrap_ratios_for_tokens(&[input_token]).await?;
+    budget
+        .div(ratio_for_token(input_token, &ratios)?)
+        .map_err(|e| client_value_out_of_range(e, "input_amount"))
+}
+
+/// Converts a caller's IO ratio cap into the wrapped terms the simulation
+/// runs in.
+pub(crate) async fn normalize_quote_price_cap(
+    ds: &dyn SwapDataSource,
+    denomination: SwapDenomination,
+    input_token: Address,
+    output_token: Address,
+    price_cap: Float,
+) -> Result<Float, ApiError> {
+    if denomination == SwapDenomination::Wrapped {
+        return Ok(price_cap);
+    }
+
+    let ratios = ds
+        .get_wrap_ratios_for_tokens(&[input_token, output_token])
+        .await?;
+    let input_assets_per_share = ratio_for_token(input_token, &ratios)?;
+    let output_assets_per_share = ratio_for_token(output_token, &ratios)?;
+    price_cap
+        .mul(output_assets_per_share)
+        .and_then(|ratio| ratio.div(input_assets_per_share))
+        .map_err(|e| client_value_out_of_range(e, "max_io_ratio"))
+}
+
 pub(crate) async fn normalize_calldata_request_values(
     ds: &dyn SwapDataSource,
     req: CalldataRequestNormalization,
@@ -75,11 +131,10 @@ pub(crate) async fn normalize_calldata_request_values(
             ) {
                 let amount = parse_user_float(req.amount, req.amount_field)?;
                 let ratio = amount_ratio(req.mode, input_assets_per_share, output_assets_per_share);
-                let wrapped_amount = amount.div(ratio).map_err(|e| {
-                    tracing::error!(error = %e, "failed to normalize calldata amount");
-                    ApiError::Internal("failed to normalize amount".into())
-                })?;
-                format_float(wrapped_amount, "amount")?
+                amount
+                    .div(ratio)
+                    .and_then(|wrapped_amount| wrapped_amount.format())
+                    .map_err(|e| client_value_out_of_range(e, req.amount_field))?
             } else {
                 req.amount
             };
@@ -87,16 +142,10 @@ pub(crate) async fn normalize_calldata_request_values(
             let normalized_io_ratio = price_cap
                 .mul(output_assets_per_share)
                 .and_then(|ratio| ratio.div(input_assets_per_share))
-                .map_err(|e| {
-                    tracing::error!(error = %e, "failed to normalize calldata IO ratio");
-                    ApiError::Internal("failed to normalize IO ratio".into())
-                })?;
-
-            Ok((
-                normalized_amount,
-                format_float(normalized_io_ratio, "IO ratio")?,
-                ratios,
-            ))
+                .and_then(|ratio| ratio.format())
+                .map_err(|e| client_value_out_of_range(e, req.price_cap_field))?;
+
+            Ok((normalized_amount, normalized_io_ratio, ratios))
         }
     }
 }
@@ -202,6 +251,13 @@ fn parse_user_float(value: String, field: &str) -> Result<Float, ApiError> {
     })
 }
 
+/// Arithmetic on a caller-supplied value that fails means the value is too
+/// large or too precise to work with, so it's the caller's error.
+pub(super) fn client_value_out_of_range(e: impl std::fmt::Display, field: &str) -> ApiError {
+    tracing::warn!(error = %e, field, "swap value out of range");
+    ApiError::BadRequest(format!("{field} is out of range"))
+}
+
 fn parse_internal_float(value: String, field: &str) -> Result<Float, ApiError> {
     Float::parse(value).map_err(|e| {
         tracing::error!(error = %e, field, "failed to parse swap denomination response value");
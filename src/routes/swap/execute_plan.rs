@@ -0,0 +1,499 @@
+use super::calldata::process_swap_calldata_build;
+use super::RaindexSwapDataSource;
+use crate::app_state::ApplicationState;
+use crate::auth::AuthenticatedKey;
+use crate::db::DbPool;
+use crate::error::{ApiError, ApiErrorResponse};
+use crate::fairings::{
+    record_token_pair, short_address, GlobalRateLimit, JsonContentType, TracingSpan,
+};
+use crate::gas::{estimate_gas, fetch_fee_hints};
+use crate::simulation::ensure_swap_calldata_executes;
+use crate::tx_value::{native_value, NATIVE_DECIMALS, NON_PAYABLE};
+use crate::types::swap::{
+    SwapCalldataResponse, SwapExecutePlanRequest, SwapExecutePlanResponse, SwapPlanStep,
+    SwapPlanStepKind,
+};
+use alloy::network::{Ethereum, Network, TransactionBuilder};
+use alloy::primitives::{address, Address, Bytes};
+use futures::future::join_all;
+use rain_math_float::Float;
+use rocket::serde::json::Json;
+use rocket::State;
+use std::ops::Mul;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::Instrument;
+use url::Url;
+
+/// Canonical WETH on Base; wrapping native ETH means calling its `deposit()`
+/// and unwrapping means calling `withdraw(uint256)`.
+const WETH: Address = address!("4200000000000000000000000000000000000006");
+/// Selector of `deposit()`.
+const WETH_DEPOSIT_SELECTOR: [u8; 4] = [0xd0, 0xe3, 0x0d, 0xb0];
+/// Selector of `withdraw(uint256)`.
+const WETH_WITHDRAW_SELECTOR: [u8; 4] = [0x2e, 0x1a, 0x7d, 0x4d];
+const DEFAULT_PLAN_TTL_SECONDS: u64 = 300;
+const MAX_PLAN_TTL_SECONDS: u64 = 3600;
+
+#[utoipa::path(
+    post,
+    path = "/v1/swap/execute-plan",
+    tag = "Swap",
+    security(("basicAuth" = [])),
+    request_body = SwapExecutePlanRequest,
+    responses(
+        (status = 200, description = "Ordered transactions for an exact-output swap", body = SwapExecutePlanResponse),
+        (status = 400, description = "Bad request", body = ApiErrorResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Request involves a restricted address", body = ApiErrorResponse),
+        (status = 404, description = "No liquidity found", body = ApiErrorResponse),
+        (status = 422, description = "Request body could not be parsed", body = ApiErrorResponse),
+        (status = 429, description = "Rate limited", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+#[post("/execute-plan", data = "<request>")]
+pub async fn post_swap_execute_plan(
+    _global: GlobalRateLimit,
+    _key: AuthenticatedKey,
+    _json: JsonContentType,
+    shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    app_state: &State<ApplicationState>,
+    pool: &State<DbPool>,
+    span: TracingSpan,
+    request: Json<SwapExecutePlanRequest>,
+) -> Result<Json<SwapExecutePlanResponse>, ApiError> {
+    let req = request.into_inner();
+    record_token_pair(
+        &span.0,
+        &short_address(req.input_token),
+        &short_address(req.output_token),
+    );
+    async move {
+        tracing::info!(body = ?req, "request received");
+        app_state
+            .denylist
+            .check(&[req.taker, req.input_token, req.output_token])?;
+        validate_execute_plan_request(&req)?;
+
+        let raindex = shared_raindex.read().await;
+        let ds = RaindexSwapDataSource {
+            client: raindex.client(),
+            caches: &app_state.response_caches,
+            pool: pool.inner(),
+            orderbooks: raindex.orderbooks_for_chain(crate::CHAIN_ID),
+            max_orders: app_state.max_quote_orders,
+        };
+        let calldata = process_swap_calldata_build(&ds, (&req).into()).await?;
+        let rpcs = if app_state.simulate_calldata {
+            let rpcs = raindex.require_rpcs_for_chain(crate::CHAIN_ID)?;
+            ensure_swap_calldata_executes(&rpcs, req.taker, &calldata).await?;
+            rpcs
+        } else {
+            raindex.rpcs_for_chain(crate::CHAIN_ID)
+        };
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let mut plan = build_execute_plan(&req, calldata, now)?;
+        plan.steps = with_gas_estimates(&rpcs, req.taker, plan.steps).await;
+        if let Some(hints) = fetch_fee_hints(&rpcs).await {
+            plan.max_fee_per_gas = Some(hints.max_fee_per_gas);
+            plan.max_priority_fee_per_gas = Some(hints.max_priority_fee_per_gas);
+        }
+        Ok(Json(plan))
+    }
+    .instrument(span.0)
+    .await
+}
+
+fn validate_execute_plan_request(req: &SwapExecutePlanRequest) -> Result<(), ApiError> {
+    if req.wrap_native && req.input_token != WETH {
+        return Err(ApiError::BadRequest(
+            "wrapNative requires inputToken to be WETH".into(),
+        ));
+    }
+    if req.unwrap_output && req.output_token != WETH {
+        return Err(ApiError::BadRequest(
+            "unwrapOutput requires outputToken to be WETH".into(),
+        ));
+    }
+    if let Some(seconds) = req.deadline_seconds {
+        if seconds == 0 || seconds > MAX_PLAN_TTL_SECONDS {
+            return Err(ApiError::BadRequest(format!(
+                "deadlineSeconds must be between 1 and {MAX_PLAN_TTL_SECONDS}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Orders the steps a wallet sends: the optional wrap, any approvals, the
+/// swap, then the optional unwrap. When approvals are needed the take-orders
+/// builder can't produce the swap yet, so the plan is marked incomplete and
+/// stops at them.
+fn build_execute_plan(
+    req: &SwapExecutePlanRequest,
+    calldata: SwapCalldataResponse,
+    now: u64,
+) -> Result<SwapExecutePlanResponse, ApiError> {
+    let output_amount = parse_plan_float(&req.output_amount, "output_amount")?;
+    let maximum_io_ratio = parse_plan_float(&req.maximum_io_ratio, "maximum_io_ratio")?;
+    let maximum_input = output_amount.mul(maximum_io_ratio).map_err(|e| {
+        tracing::error!(error = %e, "failed to compute maximum input");
+        ApiError::Internal("failed to compute maximum input".into())
+    })?;
+    let maximum_input_formatted = maximum_input.format().map_err(|e| {
+        tracing::error!(error = %e, "failed to format maximum input");
+        ApiError::Internal("failed to format maximum input".into())
+    })?;
+
+    let mut steps = Vec::with_capacity(calldata.approvals.len() + 3);
+    if req.wrap_native {
+        let value = native_value(maximum_input)?;
+        steps.push(SwapPlanStep {
+            kind: SwapPlanStepKind::Wrap,
+            description: format!("Wrap {maximum_input_formatted} ETH into WETH"),
+            to: WETH,
+            data: Bytes::from(WETH_DEPOSIT_SELECTOR.to_vec()),
+            value,
+            estimated_gas: None,
+        });
+    }
+    for approval in &calldata.approvals {
+        let token = if approval.symbol.is_empty() {
+            format!("{:#x}", approval.token)
+        } else {
+            approval.symbol.clone()
+        };
+        steps.push(SwapPlanStep {
+            kind: SwapPlanStepKind::Approve,
+            description: format!(
+                "Approve {} {token} for {:#x}",
+                approval.amount, approval.spender
+            ),
+            to: approval.token,
+            data: approval.approval_data.clone(),
+            value: NON_PAYABLE,
+            estimated_gas: None,
+        });
+    }
+    let complete = !calldata.data.is_empty();
+    if complete {
+        steps.push(SwapPlanStep {
+            kind: SwapPlanStepKind::Swap,
+            description: format!(
+                "Buy exactly {} paying at most {maximum_input_formatted}",
+                req.output_amount
+            ),
+            to: calldata.to,
+            data: calldata.data,
+            value: calldata.value,
+            estimated_gas: None,
+        });
+    }
+    let unwrapped = complete && req.unwrap_output;
+    if unwrapped {
+        // The swap reverts unless it delivers exactly `outputAmount`, so
+        // that is what the taker holds to unwrap.
+        let (amount, _) = output_amount
+            .to_fixed_decimal_lossy(NATIVE_DECIMALS)
+            .map_err(|e| {
+                tracing::error!(error = %e, "failed to convert unwrap amount to wei");
+                ApiError::Internal("failed to compute unwrap amount".into())
+            })?;
+        let mut data = WETH_WITHDRAW_SELECTOR.to_vec();
+        data.extend_from_slice(&amount.to_be_bytes::<32>());
+        steps.push(SwapPlanStep {
+            kind: SwapPlanStepKind::Unwrap,
+            description: format!("Unwrap {} WETH into ETH", req.output_amount),
+            to: WETH,
+            data: Bytes::from(data),
+            value: NON_PAYABLE,
+            estimated_gas: None,
+        });
+    }
+
+    Ok(SwapExecutePlanResponse {
+        steps,
+        complete,
+        minimum_output: req.output_amount.clone(),
+        maximum_input: maximum_input_formatted,
+        estimated_input: calldata.estimated_input,
+        deadline: now + req.deadline_seconds.unwrap_or(DEFAULT_PLAN_TTL_SECONDS),
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        denomination: calldata.denomination,
+        unwrapped,
+    })
+}
+
+async fn with_gas_estimates(
+    rpcs: &[Url],
+    taker: Address,
+    steps: Vec<SwapPlanStep>,
+) -> Vec<SwapPlanStep> {
+    join_all(steps.into_iter().map(|step| async move {
+        let tx = <Ethereum as Network>::TransactionRequest::default()
+            .with_from(taker)
+            .with_to(step.to)
+            .with_input(step.data.clone())
+            .with_value(step.value);
+        SwapPlanStep {
+            estimated_gas: estimate_gas(rpcs, tx).await,
+            ..step
+        }
+    }))
+    .await
+}
+
+fn parse_plan_float(value: &str, field: &str) -> Result<Float, ApiError> {
+    Float::parse(value.to_string()).map_err(|e| {
+        tracing::warn!(error = %e, field, "invalid execute plan amount");
+        ApiError::BadRequest(format!("invalid {field}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::swap::test_fixtures::MockSwapDataSource;
+    use crate::types::common::Approval;
+    use crate::types::swap::SwapDenomination;
+    use alloy::primitives::U256;
+
+    const USDC: Address = address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+    const TAKER: Address = address!("1111111111111111111111111111111111111111");
+    const ORDERBOOK: Address = address!("d2938e7c9fe3597f78832ce780feb61945c377d7");
+
+    fn plan_request(wrap_native: bool) -> SwapExecutePlanRequest {
+        SwapExecutePlanRequest {
+            taker: TAKER,
+            input_token: WETH,
+            output_token: USDC,
+            output_amount: "1000".to_string(),
+            maximum_io_ratio: "0.0005".to_string(),
+            wrap_native,
+            unwrap_output: false,
+            deadline_seconds: None,
+            denomination: SwapDenomination::Wrapped,
+            orderbook: None,
+        }
+    }
+
+    fn weth_approval() -> Approval {
+        Approval {
+            token: WETH,
+            spender: ORDERBOOK,
+            amount: "0.5".to_string(),
+            symbol: "WETH".to_string(),
+            approval_data: Bytes::from(vec![0x09, 0x5e, 0xa7, 0xb3]),
+        }
+    }
+
+    fn mock_ds(calldata: SwapCalldataResponse) -> MockSwapDataSource {
+        MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![]),
+            candidates: vec![],
+            calldata_result: Ok(calldata),
+        }
+    }
+
+    async fn plan(
+        ds: &MockSwapDataSource,
+        req: &SwapExecutePlanRequest,
+    ) -> Result<SwapExecutePlanResponse, ApiError> {
+        validate_execute_plan_request(req)?;
+        let calldata = process_swap_calldata_build(ds, req.into()).await?;
+        build_execute_plan(req, calldata, 1_700_000_000)
+    }
+
+    #[rocket::async_test]
+    async fn test_wrap_approve_and_swap_steps_in_order() {
+        let ds = mock_ds(SwapCalldataResponse {
+            to: ORDERBOOK,
+            data: Bytes::from(vec![0xab, 0xcd]),
+            value: U256::ZERO,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            estimated_input: "0.48".to_string(),
+            denomination: SwapDenomination::Wrapped,
+            approvals: vec![weth_approval()],
+            taker_warning: None,
+        });
+
+        let plan = plan(&ds, &plan_request(true)).await.unwrap();
+
+        let kinds: Vec<_> = plan.steps.iter().map(|step| step.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SwapPlanStepKind::Wrap,
+                SwapPlanStepKind::Approve,
+                SwapPlanStepKind::Swap
+            ]
+        );
+        assert!(plan.complete);
+
+        let wrap = &plan.steps[0];
+        assert_eq!(wrap.to, WETH);
+        assert_eq!(wrap.data, Bytes::from(WETH_DEPOSIT_SELECTOR.to_vec()));
+        assert_eq!(wrap.value, U256::from(500_000_000_000_000_000u128));
+
+        let approve = &plan.steps[1];
+        assert_eq!(approve.to, WETH);
+        assert_eq!(
+            approve.description,
+            "Approve 0.5 WETH for 0xd2938e7c9fe3597f78832ce780feb61945c377d7"
+        );
+
+        assert_eq!(plan.steps[2].to, ORDERBOOK);
+        assert_eq!(plan.minimum_output, "1000");
+        assert_eq!(plan.maximum_input, "0.5");
+        assert_eq!(plan.estimated_input, "0.48");
+        assert_eq!(plan.deadline, 1_700_000_300);
+    }
+
+    #[rocket::async_test]
+    async fn test_approval_only_plan_is_incomplete() {
+        let ds = mock_ds(SwapCalldataResponse {
+            to: ORDERBOOK,
+            data: Bytes::new(),
+            value: U256::ZERO,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            estimated_input: "0.5".to_string(),
+            denomination: SwapDenomination::Wrapped,
+            approvals: vec![weth_approval()],
+            taker_warning: None,
+        });
+
+        let plan = plan(&ds, &plan_request(false)).await.unwrap();
+
+        assert_eq!(plan.steps.len(), 1);
+        assert_eq!(plan.steps[0].kind, SwapPlanStepKind::Approve);
+        assert!(!plan.complete);
+    }
+
+    #[rocket::async_test]
+    async fn test_wrap_requires_weth_input() {
+        let ds = mock_ds(SwapCalldataResponse {
+            to: ORDERBOOK,
+            data: Bytes::new(),
+            value: U256::ZERO,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            estimated_input: "0".to_string(),
+            denomination: SwapDenomination::Wrapped,
+            approvals: vec![],
+            taker_warning: None,
+        });
+        let mut req = plan_request(true);
+        req.input_token = USDC;
+        req.output_token = WETH;
+
+        let err = plan(&ds, &req).await.unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::BadRequest(msg) if msg == "wrapNative requires inputToken to be WETH"
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_unwrap_step_follows_weth_buy() {
+        let ds = mock_ds(SwapCalldataResponse {
+            to: ORDERBOOK,
+            data: Bytes::from(vec![0xab, 0xcd]),
+            value: U256::ZERO,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            estimated_input: "2500".to_string(),
+            denomination: SwapDenomination::Wrapped,
+            approvals: vec![],
+            taker_warning: None,
+        });
+        let mut req = plan_request(false);
+        req.input_token = USDC;
+        req.output_token = WETH;
+        req.output_amount = "0.5".to_string();
+        req.maximum_io_ratio = "2600".to_string();
+        req.unwrap_output = true;
+
+        let plan = plan(&ds, &req).await.unwrap();
+
+        let kinds: Vec<_> = plan.steps.iter().map(|step| step.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![SwapPlanStepKind::Swap, SwapPlanStepKind::Unwrap]
+        );
+        assert!(plan.unwrapped);
+
+        let unwrap = &plan.steps[1];
+        assert_eq!(unwrap.to, WETH);
+        assert_eq!(unwrap.value, U256::ZERO);
+        let mut expected = WETH_WITHDRAW_SELECTOR.to_vec();
+        expected.extend_from_slice(&U256::from(500_000_000_000_000_000u128).to_be_bytes::<32>());
+        assert_eq!(unwrap.data, Bytes::from(expected));
+    }
+
+    #[rocket::async_test]
+    async fn test_unwrap_requires_weth_output() {
+        let ds = mock_ds(SwapCalldataResponse {
+            to: ORDERBOOK,
+            data: Bytes::new(),
+            value: U256::ZERO,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            estimated_input: "0".to_string(),
+            denomination: SwapDenomination::Wrapped,
+            approvals: vec![],
+            taker_warning: None,
+        });
+        let mut req = plan_request(false);
+        req.unwrap_output = true;
+
+        let err = plan(&ds, &req).await.unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::BadRequest(msg) if msg == "unwrapOutput requires outputToken to be WETH"
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_native_eth_swap_sends_value_only_on_wrap() {
+        let ds = mock_ds(SwapCalldataResponse {
+            to: ORDERBOOK,
+            data: Bytes::from(vec![0xab, 0xcd]),
+            value: NON_PAYABLE,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            estimated_input: "0.48".to_string(),
+            denomination: SwapDenomination::Wrapped,
+            approvals: vec![weth_approval()],
+            taker_warning: None,
+        });
+
+        let plan = plan(&ds, &plan_request(true)).await.unwrap();
+
+        let values: Vec<_> = plan
+            .steps
+            .iter()
+            .map(|step| (step.kind, step.value))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                (
+                    SwapPlanStepKind::Wrap,
+                    U256::from(500_000_000_000_000_000u128)
+                ),
+                (SwapPlanStepKind::Approve, NON_PAYABLE),
+                (SwapPlanStepKind::Swap, NON_PAYABLE),
+            ]
+        );
+    }
+}
@@ -1,10 +1,13 @@
 mod calldata;
+mod curve;
 mod denomination;
+mod execute_plan;
 mod quote;
+mod quote_both;
 
 use crate::cache::RouteResponseCaches;
 use crate::db::DbPool;
-use crate::error::ApiError;
+use crate::error::{upstream_error, ApiError, NotFoundReason};
 use crate::types::swap::{SwapCalldataResponse, SwapDenomination};
 use crate::wrap_ratio::{
     persist_wrap_ratio_snapshots_best_effort, read_wrap_ratio_responses_for_addresses,
@@ -24,6 +27,11 @@ use rain_orderbook_common::take_orders::{
 use rocket::Route;
 use std::collections::HashMap;
 
+/// How many orders per quoted order the pair query may fetch. Orders are
+/// only priced once quoted, so the query fetches a wider window than a
+/// quote keeps and [`cheapest_candidates`] picks from it by ratio.
+const PAIR_ORDERS_FETCH_MULTIPLE: u16 = 10;
+
 #[async_trait]
 pub(crate) trait SwapDataSource: Send + Sync {
     async fn validate_supported_tokens(
@@ -50,6 +58,14 @@ pub(crate) trait SwapDataSource: Send + Sync {
         request: TakeOrdersRequest,
     ) -> Result<SwapCalldataResponse, ApiError>;
 
+    /// Orderbook addresses the registry configures for swaps.
+    fn known_orderbooks(&self) -> Vec<Address>;
+
+    /// Symbol the curated token list gives `token`, if any.
+    fn token_symbol(&self, _token: Address) -> Option<String> {
+        None
+    }
+
     async fn get_wrap_ratios_for_tokens(
         &self,
         _token_addresses: &[Address],
@@ -62,6 +78,9 @@ pub(crate) struct RaindexSwapDataSource<'a> {
     pub client: &'a RaindexClient,
     pub caches: &'a RouteResponseCaches,
     pub pool: &'a DbPool,
+    pub orderbooks: Vec<Address>,
+    /// Most candidates a quote keeps, cheapest first.
+    pub max_orders: u16,
 }
 
 fn swap_candidates_cache_key(
@@ -85,6 +104,104 @@ fn swap_candidates_cache_key(
     format!("swap-candidates/latest/default/{input_token}/{output_token}/{order_keys}")
 }
 
+/// `Address` values are byte arrays, so the filter carries no casing of its
+/// own; the subgraph client renders them in one canonical form.
+fn pair_orders_filters(input_token: Address, output_token: Address) -> GetOrdersFilters {
+    GetOrdersFilters {
+        active: Some(true),
+        tokens: Some(GetOrdersTokenFilter {
+            inputs: Some(vec![input_token]),
+            outputs: Some(vec![output_token]),
+        }),
+        has_positive_output_vault_balance: Some(true),
+        ..Default::default()
+    }
+}
+
+/// Keeps the `max` candidates with the lowest ratio, so a capped quote
+/// drops the most expensive orders rather than whichever were fetched last.
+fn cheapest_candidates(
+    mut candidates: Vec<TakeOrderCandidate>,
+    max: usize,
+) -> Vec<TakeOrderCandidate> {
+    if candidates.len() <= max {
+        return candidates;
+    }
+    candidates.sort_by(|a, b| match (a.ratio.lt(b.ratio), b.ratio.lt(a.ratio)) {
+        (Ok(true), _) => std::cmp::Ordering::Less,
+        (_, Ok(true)) => std::cmp::Ordering::Greater,
+        _ => std::cmp::Ordering::Equal,
+    });
+    tracing::info!(
+        candidates = candidates.len(),
+        max_orders = max,
+        "dropping the most expensive order candidates"
+    );
+    candidates.truncate(max);
+    candidates
+}
+
+/// Keeps orders that take `input_token` and emit `output_token`. Addresses
+/// compare as bytes, so the casing the subgraph stored doesn't matter.
+fn retain_orders_for_pair(
+    orders: Vec<RaindexOrder>,
+    input_token: Address,
+    output_token: Address,
+) -> Vec<RaindexOrder> {
+    let total = orders.len();
+    let matched: Vec<RaindexOrder> = orders
+        .into_iter()
+        .filter(|order| {
+            let takes_input = order
+                .inputs_list()
+                .items()
+                .iter()
+                .any(|vault| vault.token().address() == input_token);
+            let emits_output = order
+                .outputs_list()
+                .items()
+                .iter()
+                .any(|vault| vault.token().address() == output_token);
+            takes_input && emits_output
+        })
+        .collect();
+    if matched.len() != total {
+        tracing::warn!(
+            total,
+            matched = matched.len(),
+            "dropped orders not matching requested pair"
+        );
+    }
+    matched
+}
+
+/// Rejects an `orderbook` the registry doesn't know, so a typo fails loudly
+/// instead of reading as "no liquidity".
+fn validate_orderbook(ds: &dyn SwapDataSource, orderbook: Address) -> Result<(), ApiError> {
+    if ds.known_orderbooks().contains(&orderbook) {
+        return Ok(());
+    }
+    tracing::warn!(orderbook = %orderbook, "swap request names unknown orderbook");
+    Err(ApiError::BadRequest(format!(
+        "unknown orderbook: {orderbook:#x}"
+    )))
+}
+
+/// Keeps orders that live on `orderbook`, or every order when none was
+/// requested.
+fn retain_orders_on_orderbook(
+    orders: Vec<RaindexOrder>,
+    orderbook: Option<Address>,
+) -> Vec<RaindexOrder> {
+    match orderbook {
+        Some(orderbook) => orders
+            .into_iter()
+            .filter(|order| order.raindex() == orderbook)
+            .collect(),
+        None => orders,
+    }
+}
+
 #[async_trait]
 impl<'a> SwapDataSource for RaindexSwapDataSource<'a> {
     async fn validate_supported_tokens(
@@ -122,23 +239,31 @@ impl<'a> SwapDataSource for RaindexSwapDataSource<'a> {
         input_token: Address,
         output_token: Address,
     ) -> Result<Vec<RaindexOrder>, ApiError> {
-        let filters = GetOrdersFilters {
-            active: Some(true),
-            tokens: Some(GetOrdersTokenFilter {
-                inputs: Some(vec![input_token]),
-                outputs: Some(vec![output_token]),
-            }),
-            has_positive_output_vault_balance: Some(true),
-            ..Default::default()
-        };
-        self.client
-            .get_orders(None, Some(filters), None, None)
+        // Prices only exist once orders are quoted, so the subgraph can't rank
+        // by them; the funded-vault filter keeps the page to orders that can
+        // fill, and candidates are ranked by ratio after quoting.
+        let filters = pair_orders_filters(input_token, output_token);
+        let fetch_limit = self.max_orders.saturating_mul(PAIR_ORDERS_FETCH_MULTIPLE);
+        let result = self
+            .client
+            .get_orders(None, Some(filters), Some(1), Some(fetch_limit))
             .await
-            .map(|r| r.orders().to_vec())
             .map_err(|e| {
                 tracing::error!(error = %e, "failed to query orders for pair");
                 ApiError::Internal("failed to query orders".into())
-            })
+            })?;
+        if result.total_count() > u32::from(fetch_limit) {
+            tracing::info!(
+                total_orders = result.total_count(),
+                fetch_limit,
+                "pair has more orders than a quote fetches"
+            );
+        }
+        Ok(retain_orders_for_pair(
+            result.orders().to_vec(),
+            input_token,
+            output_token,
+        ))
     }
 
     async fn build_candidates_for_pair(
@@ -164,18 +289,22 @@ impl<'a> SwapDataSource for RaindexSwapDataSource<'a> {
             })
         };
 
-        if !self.caches.is_enabled() {
-            return fetch().await;
-        }
-
-        self.caches
-            .swap_candidates
-            .get_or_try_insert(
-                swap_candidates_cache_key(orders, input_token, output_token),
-                fetch,
-            )
-            .await
-            .map_err(|e| (*e).clone())
+        let candidates = if self.caches.is_enabled() {
+            self.caches
+                .swap_candidates
+                .get_or_try_insert(
+                    swap_candidates_cache_key(orders, input_token, output_token),
+                    fetch,
+                )
+                .await
+                .map_err(|e| (*e).clone())?
+        } else {
+            fetch().await?
+        };
+        Ok(cheapest_candidates(
+            candidates,
+            usize::from(self.max_orders),
+        ))
     }
 
     async fn get_calldata(
@@ -193,7 +322,9 @@ impl<'a> SwapDataSource for RaindexSwapDataSource<'a> {
             Ok(SwapCalldataResponse {
                 to: approval_info.spender(),
                 data: alloy::primitives::Bytes::new(),
-                value: alloy::primitives::U256::ZERO,
+                value: crate::tx_value::NON_PAYABLE,
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
                 estimated_input: formatted_amount.clone(),
                 denomination: SwapDenomination::Wrapped,
                 approvals: vec![crate::types::common::Approval {
@@ -203,6 +334,7 @@ impl<'a> SwapDataSource for RaindexSwapDataSource<'a> {
                     symbol: String::new(),
                     approval_data: approval_info.calldata().clone(),
                 }],
+                taker_warning: None,
             })
         } else if let Some(take_orders_info) = result.take_orders_info() {
             let expected_sell = take_orders_info.expected_sell().format().map_err(|e| {
@@ -212,10 +344,13 @@ impl<'a> SwapDataSource for RaindexSwapDataSource<'a> {
             Ok(SwapCalldataResponse {
                 to: take_orders_info.raindex(),
                 data: take_orders_info.calldata().clone(),
-                value: alloy::primitives::U256::ZERO,
+                value: crate::tx_value::NON_PAYABLE,
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
                 estimated_input: expected_sell,
                 denomination: SwapDenomination::Wrapped,
                 approvals: vec![],
+                taker_warning: None,
             })
         } else {
             Err(ApiError::Internal(
@@ -224,6 +359,22 @@ impl<'a> SwapDataSource for RaindexSwapDataSource<'a> {
         }
     }
 
+    fn known_orderbooks(&self) -> Vec<Address> {
+        self.orderbooks.clone()
+    }
+
+    fn token_symbol(&self, token: Address) -> Option<String> {
+        let tokens = self
+            .client
+            .get_all_tokens()
+            .inspect_err(|e| tracing::warn!(error = %e, "failed to retrieve curated tokens"))
+            .ok()?;
+        crate::raindex::dedupe_tokens(tokens)
+            .into_iter()
+            .find(|cfg| cfg.address == token)
+            .and_then(|cfg| cfg.symbol)
+    }
+
     async fn get_wrap_ratios_for_tokens(
         &self,
         token_addresses: &[Address],
@@ -248,7 +399,10 @@ fn map_raindex_error(e: RaindexError) -> ApiError {
     match &e {
         RaindexError::NoLiquidity | RaindexError::InsufficientLiquidity { .. } => {
             tracing::warn!(error = %e, "no liquidity found");
-            ApiError::NotFound("no liquidity found for this pair".into())
+            ApiError::not_found_because(
+                NotFoundReason::NoLiquidity,
+                "no liquidity found for this pair",
+            )
         }
         RaindexError::SameTokenPair
         | RaindexError::NonPositiveAmount
@@ -264,16 +418,25 @@ fn map_raindex_error(e: RaindexError) -> ApiError {
         }
         _ => {
             tracing::error!(error = %e, "calldata generation failed");
-            ApiError::Internal("failed to generate calldata".into())
+            upstream_error(&e, "failed to generate calldata")
         }
     }
 }
 
 pub use calldata::*;
+pub use curve::*;
+pub use execute_plan::*;
 pub use quote::*;
+pub use quote_both::*;
 
 pub fn routes() -> Vec<Route> {
-    rocket::routes![quote::post_swap_quote, calldata::post_swap_calldata]
+    rocket::routes![
+        quote::post_swap_quote,
+        calldata::post_swap_calldata,
+        curve::post_swap_curve,
+        execute_plan::post_swap_execute_plan,
+        quote_both::post_swap_quote_both
+    ]
 }
 
 pub fn routes_v2() -> Vec<Route> {
@@ -282,10 +445,17 @@ pub fn routes_v2() -> Vec<Route> {
 
 #[cfg(test)]
 mod tests {
-    use super::swap_candidates_cache_key;
-    use alloy::primitives::address;
+    use super::{
+        cheapest_candidates, pair_orders_filters, retain_orders_for_pair,
+        retain_orders_on_orderbook, swap_candidates_cache_key, RaindexSwapDataSource,
+        SwapDataSource,
+    };
+    use crate::cache::RouteResponseCaches;
+    use alloy::primitives::{address, Address};
     use rain_orderbook_common::raindex_client::orders::RaindexOrder;
     use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     fn mock_order(chain_id: u32, order_hash: &str) -> RaindexOrder {
         let mut value = crate::test_helpers::order_json();
@@ -316,6 +486,224 @@ mod tests {
             swap_candidates_cache_key(&[order_b, order_a], input_token, output_token)
         );
     }
+
+    #[test]
+    fn test_retain_orders_on_orderbook_filters_only_when_requested() {
+        let default_book = address!("d2938e7c9fe3597f78832ce780feb61945c377d7");
+        let other_book = address!("9999999999999999999999999999999999999999");
+        let order_a = mock_order(
+            8453,
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+        );
+        let mut value = crate::test_helpers::order_json();
+        value["orderHash"] =
+            json!("0x0000000000000000000000000000000000000000000000000000000000000002");
+        value["raindex"] = json!(format!("{other_book:#x}"));
+        let order_b: RaindexOrder = serde_json::from_value(value).expect("deserialize mock order");
+        let orders = vec![order_a, order_b];
+
+        let restricted = retain_orders_on_orderbook(orders.clone(), Some(other_book));
+        assert_eq!(restricted.len(), 1);
+        assert_eq!(restricted[0].raindex(), other_book);
+
+        let default_only = retain_orders_on_orderbook(orders.clone(), Some(default_book));
+        assert_eq!(default_only.len(), 1);
+        assert_eq!(default_only[0].raindex(), default_book);
+
+        assert_eq!(retain_orders_on_orderbook(orders, None).len(), 2);
+    }
+
+    #[test]
+    fn test_cheapest_candidates_keeps_lowest_ratios() {
+        let candidates = vec![
+            crate::test_helpers::mock_candidate("1", "3"),
+            crate::test_helpers::mock_candidate("2", "1"),
+            crate::test_helpers::mock_candidate("3", "2"),
+        ];
+
+        let kept = cheapest_candidates(candidates, 2);
+
+        let outputs: Vec<String> = kept
+            .iter()
+            .map(|candidate| candidate.max_output.format().unwrap())
+            .collect();
+        assert_eq!(outputs, vec!["2", "3"]);
+    }
+
+    #[test]
+    fn test_cheapest_candidates_keeps_all_under_cap() {
+        let candidates = vec![
+            crate::test_helpers::mock_candidate("1", "3"),
+            crate::test_helpers::mock_candidate("2", "1"),
+        ];
+
+        assert_eq!(cheapest_candidates(candidates, 2).len(), 2);
+    }
+
+    /// Subgraph that answers every query with no orders and records the
+    /// request bodies it received.
+    async fn recording_subgraph() -> (String, Arc<Mutex<Vec<String>>>) {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        let base_url = crate::test_helpers::mock_http_server(move |request| {
+            recorded
+                .lock()
+                .expect("mock subgraph request lock")
+                .push(request.to_string());
+            (200, r#"{"data":{"orders":[]}}"#.to_string())
+        })
+        .await;
+
+        (format!("{base_url}/sg"), requests)
+    }
+
+    #[rocket::async_test]
+    async fn test_token_symbol_reads_curated_token_list() {
+        let settings = r#"version: 6
+networks:
+  base:
+    rpcs:
+      - https://mainnet.base.org
+    chain-id: 8453
+    currency: ETH
+subgraphs:
+  base: https://api.goldsky.com/api/public/project_clv14x04y9kzi01saerx7bxpg/subgraphs/ob4-base/0.9/gn
+raindexes:
+  base:
+    address: 0xd2938e7c9fe3597f78832ce780feb61945c377d7
+    network: base
+    subgraph: base
+    deployment-block: 0
+deployers:
+  base:
+    address: 0xC1A14cE2fd58A3A2f99deCb8eDd866204eE07f8D
+    network: base
+tokens:
+  usdc:
+    address: 0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913
+    network: base
+    decimals: 6
+    symbol: USDC
+  weth:
+    address: 0x4200000000000000000000000000000000000006
+    network: base
+    decimals: 18
+    symbol: WETH
+"#;
+        let registry_url =
+            crate::test_helpers::mock_raindex_registry_url_with_settings(settings).await;
+        let provider = crate::raindex::RaindexProvider::load(&registry_url, None)
+            .await
+            .expect("load raindex provider");
+        let caches = RouteResponseCaches::new(0, Duration::ZERO, Duration::ZERO);
+        let pool = crate::db::init("sqlite::memory:", 1)
+            .await
+            .expect("init pool");
+        let ds = RaindexSwapDataSource {
+            client: provider.client(),
+            caches: &caches,
+            pool: &pool,
+            orderbooks: vec![],
+            max_orders: 7,
+        };
+
+        assert_eq!(
+            ds.token_symbol(address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"))
+                .as_deref(),
+            Some("USDC")
+        );
+        assert_eq!(
+            ds.token_symbol(address!("4200000000000000000000000000000000000006"))
+                .as_deref(),
+            Some("WETH")
+        );
+        assert_eq!(ds.token_symbol(Address::from([9u8; 20])), None);
+    }
+
+    #[rocket::async_test]
+    async fn test_get_orders_for_pair_bounds_subgraph_query() {
+        let (subgraph_url, requests) = recording_subgraph().await;
+        let settings = format!(
+            r#"version: 6
+networks:
+  base:
+    rpcs:
+      - https://mainnet.base.org
+    chain-id: 8453
+    currency: ETH
+subgraphs:
+  base: {subgraph_url}
+raindexes:
+  base:
+    address: 0xd2938e7c9fe3597f78832ce780feb61945c377d7
+    network: base
+    subgraph: base
+    deployment-block: 0
+deployers:
+  base:
+    address: 0xC1A14cE2fd58A3A2f99deCb8eDd866204eE07f8D
+    network: base
+"#
+        );
+        let registry_url =
+            crate::test_helpers::mock_raindex_registry_url_with_settings(&settings).await;
+        let provider = crate::raindex::RaindexProvider::load(&registry_url, None)
+            .await
+            .expect("load raindex provider");
+        let caches = RouteResponseCaches::new(0, Duration::ZERO, Duration::ZERO);
+        let pool = crate::db::init("sqlite::memory:", 1)
+            .await
+            .expect("init pool");
+        let ds = RaindexSwapDataSource {
+            client: provider.client(),
+            caches: &caches,
+            pool: &pool,
+            orderbooks: vec![],
+            max_orders: 7,
+        };
+
+        let _ = ds
+            .get_orders_for_pair(
+                address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
+                address!("4200000000000000000000000000000000000006"),
+            )
+            .await;
+
+        let requests = requests.lock().expect("mock subgraph request lock");
+        assert!(
+            requests
+                .iter()
+                .any(|request| request.contains(r#""first":70"#)),
+            "no subgraph query was limited to 70 orders: {requests:?}"
+        );
+    }
+
+    #[test]
+    fn test_checksummed_pair_matches_lowercase_orders() {
+        let input_token: Address = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"
+            .parse()
+            .unwrap();
+        let output_token: Address = "0x4200000000000000000000000000000000000006"
+            .parse()
+            .unwrap();
+        let order = mock_order(
+            8453,
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+        );
+        let filters = pair_orders_filters(input_token, output_token);
+        let tokens = filters.tokens.expect("token filter");
+        assert_eq!(
+            tokens.inputs,
+            Some(vec![address!("833589fcd6edb6e08f4c7c32d4f71b54bda02913")])
+        );
+        assert_eq!(tokens.outputs, Some(vec![output_token]));
+
+        let matched = retain_orders_for_pair(vec![order.clone()], input_token, output_token);
+        assert_eq!(matched.len(), 1);
+
+        let reversed = retain_orders_for_pair(vec![order], output_token, input_token);
+        assert!(reversed.is_empty());
+    }
 }
 
 #[cfg(test)]
@@ -329,6 +717,18 @@ pub(crate) mod test_fixtures {
     use rain_orderbook_common::raindex_client::take_orders::TakeOrdersRequest;
     use rain_orderbook_common::take_orders::TakeOrderCandidate;
 
+    /// Symbols of the USDC and WETH tokens the mock orders trade.
+    pub const MOCK_TOKEN_SYMBOLS: &[(Address, &str)] = &[
+        (
+            alloy::primitives::address!("833589fcd6edb6e08f4c7c32d4f71b54bda02913"),
+            "USDC",
+        ),
+        (
+            alloy::primitives::address!("4200000000000000000000000000000000000006"),
+            "WETH",
+        ),
+    ];
+
     pub struct MockSwapDataSource {
         pub supported_tokens: Result<(), ApiError>,
         pub orders: Result<Vec<RaindexOrder>, ApiError>,
@@ -372,5 +772,22 @@ pub(crate) mod test_fixtures {
         ) -> Result<SwapCalldataResponse, ApiError> {
             self.calldata_result.clone()
         }
+
+        fn known_orderbooks(&self) -> Vec<Address> {
+            let mut orderbooks: Vec<Address> = match &self.orders {
+                Ok(orders) => orders.iter().map(|order| order.raindex()).collect(),
+                Err(_) => Vec::new(),
+            };
+            orderbooks.sort_unstable();
+            orderbooks.dedup();
+            orderbooks
+        }
+
+        fn token_symbol(&self, token: Address) -> Option<String> {
+            MOCK_TOKEN_SYMBOLS
+                .iter()
+                .find(|(address, _)| *address == token)
+                .map(|(_, symbol)| symbol.to_string())
+        }
     }
 }
@@ -1,18 +1,30 @@
-use super::{RaindexSwapDataSource, SwapDataSource};
+use super::{
+    retain_orders_on_orderbook, validate_orderbook, RaindexSwapDataSource, SwapDataSource,
+};
 use crate::app_state::ApplicationState;
 use crate::auth::AuthenticatedKey;
 use crate::db::DbPool;
-use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
-use crate::routes::swap::denomination::normalize_quote_amounts;
+use crate::error::{ApiError, ApiErrorResponse, NotFoundReason};
+use crate::fairings::{
+    record_token_pair, short_address, GlobalRateLimit, JsonContentType, TracingSpan,
+};
+use crate::routes::swap::denomination::{
+    client_value_out_of_range, normalize_quote_amount, normalize_quote_amounts,
+    normalize_quote_budget, normalize_quote_price_cap,
+};
 use crate::types::swap::{SwapQuoteRequest, SwapQuoteResponse};
 use rain_math_float::Float;
-use rain_orderbook_common::take_orders::simulate_buy_over_candidates;
+use rain_orderbook_common::take_orders::{simulate_buy_over_candidates, TakeOrderCandidate};
 use rocket::serde::json::Json;
 use rocket::State;
-use std::ops::Div;
+use std::ops::{Add, Div, Mul, Sub};
 use tracing::Instrument;
 
+/// How many times the pair's total depth an `outputAmount` may be before
+/// the quote is rejected instead of simulated. Partial fills stay quotable;
+/// only amounts no plausible book could fill are turned away.
+const MAX_OUTPUT_DEPTH_MULTIPLE: &str = "1000";
+
 #[utoipa::path(
     post,
     path = "/v1/swap/quote",
@@ -23,30 +35,46 @@ use tracing::Instrument;
         (status = 200, description = "Swap quote", body = SwapQuoteResponse),
         (status = 400, description = "Bad request", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Request involves a restricted address", body = ApiErrorResponse),
         (status = 404, description = "No liquidity found", body = ApiErrorResponse),
         (status = 422, description = "Request body could not be parsed", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
     )
 )]
+#[allow(clippy::too_many_arguments)]
 #[post("/quote", data = "<request>")]
 pub async fn post_swap_quote(
     _global: GlobalRateLimit,
     _key: AuthenticatedKey,
+    _json: JsonContentType,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
     app_state: &State<ApplicationState>,
     pool: &State<DbPool>,
     span: TracingSpan,
     request: Json<SwapQuoteRequest>,
 ) -> Result<Json<SwapQuoteResponse>, ApiError> {
-    let req = request.into_inner();
+    let mut req = request.into_inner();
+    if req.min_liquidity.is_none() {
+        req.min_liquidity = Some(app_state.min_quote_liquidity.clone());
+    }
+    record_token_pair(
+        &span.0,
+        &short_address(req.input_token),
+        &short_address(req.output_token),
+    );
     async move {
         tracing::info!(body = ?req, "request received");
+        app_state
+            .denylist
+            .check(&[req.input_token, req.output_token])?;
         let raindex = shared_raindex.read().await;
         let ds = RaindexSwapDataSource {
             client: raindex.client(),
             caches: &app_state.response_caches,
             pool: pool.inner(),
+            orderbooks: raindex.orderbooks_for_chain(crate::CHAIN_ID),
+            max_orders: app_state.max_quote_orders,
         };
         let response = process_swap_quote(&ds, req).await?;
         Ok(Json(response))
@@ -55,20 +83,33 @@ pub async fn post_swap_quote(
     .await
 }
 
-async fn process_swap_quote(
+pub(super) async fn process_swap_quote(
     ds: &dyn SwapDataSource,
     req: SwapQuoteRequest,
 ) -> Result<SwapQuoteResponse, ApiError> {
+    let target = QuoteTarget::from_request(&req)?;
+    let min_liquidity = req
+        .min_liquidity
+        .as_deref()
+        .map(parse_min_liquidity)
+        .transpose()?;
+
     ds.validate_supported_tokens(req.input_token, req.output_token)
         .await?;
+    if let Some(orderbook) = req.orderbook {
+        validate_orderbook(ds, orderbook)?;
+    }
 
-    let orders = ds
-        .get_orders_for_pair(req.input_token, req.output_token)
-        .await?;
+    let orders = retain_orders_on_orderbook(
+        ds.get_orders_for_pair(req.input_token, req.output_token)
+            .await?,
+        req.orderbook,
+    );
 
     if orders.is_empty() {
-        return Err(ApiError::NotFound(
-            "no liquidity found for this pair".into(),
+        return Err(ApiError::not_found_because(
+            NotFoundReason::NoLiquidity,
+            "no liquidity found for this pair",
         ));
     }
 
@@ -77,35 +118,103 @@ async fn process_swap_quote(
         .await?;
 
     if candidates.is_empty() {
-        return Err(ApiError::NotFound("no valid quotes available".into()));
+        return Err(ApiError::not_found_because(
+            NotFoundReason::NoLiquidity,
+            "no valid quotes available",
+        ));
+    }
+    if let Some(min_liquidity) = min_liquidity {
+        ensure_min_liquidity(&candidates, min_liquidity)?;
+    }
+    if let QuoteTarget::Output(buy_target) = target {
+        ensure_plausible_output(&candidates, buy_target)?;
     }
 
-    let buy_target = Float::parse(req.output_amount.clone()).map_err(|e| {
-        tracing::error!(error = %e, "failed to parse output_amount");
-        ApiError::BadRequest("invalid output_amount".into())
-    })?;
+    let target = match target {
+        QuoteTarget::Input(budget) => QuoteTarget::Input(
+            normalize_quote_budget(ds, req.denomination, req.input_token, budget).await?,
+        ),
+        output => output,
+    };
 
-    let price_cap = Float::max_positive_value().map_err(|e| {
-        tracing::error!(error = %e, "failed to create price cap");
-        ApiError::Internal("failed to create price cap".into())
-    })?;
+    let price_cap = match req.max_io_ratio.as_deref() {
+        Some(max_io_ratio) => {
+            let cap = parse_max_io_ratio(max_io_ratio)?;
+            normalize_quote_price_cap(ds, req.denomination, req.input_token, req.output_token, cap)
+                .await?
+        }
+        None => Float::max_positive_value().map_err(|e| {
+            tracing::error!(error = %e, "failed to create price cap");
+            ApiError::Internal("failed to create price cap".into())
+        })?,
+    };
 
-    let sim = simulate_buy_over_candidates(candidates, buy_target, price_cap).map_err(|e| {
-        tracing::error!(error = %e, "failed to simulate swap");
-        ApiError::Internal("failed to simulate swap".into())
-    })?;
+    let best_ratio = best_candidate_ratio(&candidates, price_cap);
+    let orders_considered = candidates.len();
+    let (total_input, total_output, leg_count) = match target {
+        QuoteTarget::Output(buy_target) => {
+            let sim =
+                simulate_buy_over_candidates(candidates, buy_target, price_cap).map_err(|e| {
+                    tracing::error!(error = %e, "failed to simulate swap");
+                    ApiError::Internal("failed to simulate swap".into())
+                })?;
+            (sim.total_input, sim.total_output, sim.legs.len())
+        }
+        QuoteTarget::Input(budget) => {
+            simulate_spend_over_candidates(&candidates, budget, price_cap)?
+        }
+    };
 
-    if sim.legs.is_empty() {
-        return Err(ApiError::NotFound("no valid quotes available".into()));
+    if leg_count == 0 {
+        if req.max_io_ratio.is_some() {
+            return Err(ApiError::not_found_because(
+                NotFoundReason::NoLiquidity,
+                "no liquidity available within max_io_ratio",
+            ));
+        }
+        return Err(ApiError::not_found_because(
+            NotFoundReason::NoLiquidity,
+            "no valid quotes available",
+        ));
     }
 
+    let (requested, filled, requested_token, requested_field) = match target {
+        QuoteTarget::Output(buy_target) => {
+            (buy_target, total_output, req.output_token, "output_amount")
+        }
+        QuoteTarget::Input(budget) => (budget, total_input, req.input_token, "input_amount"),
+    };
+    let unfilled = unfilled_amount(requested, filled, requested_field)?;
+    let fully_filled = unfilled.is_zero().map_err(|e| {
+        tracing::error!(error = %e, "failed to check unfilled output");
+        ApiError::Internal("failed to compute unfilled output".into())
+    })?;
+    let unfilled = normalize_quote_amount(ds, req.denomination, requested_token, unfilled)
+        .await?
+        .format()
+        .map_err(|e| client_value_out_of_range(e, requested_field))?;
+    let (unfilled_output, unspent_input, requested_output) = match target {
+        QuoteTarget::Output(buy_target) => {
+            let requested_output =
+                normalize_quote_amount(ds, req.denomination, req.output_token, buy_target)
+                    .await?
+                    .format()
+                    .map_err(|e| client_value_out_of_range(e, requested_field))?;
+            (Some(unfilled), None, Some(requested_output))
+        }
+        QuoteTarget::Input(_) => (None, Some(unfilled), None),
+    };
+
+    let price_impact_bps =
+        best_ratio.and_then(|best| price_impact_bps(best, total_input, total_output));
+
     let (estimated_input, estimated_output) = normalize_quote_amounts(
         ds,
         req.denomination,
         req.input_token,
         req.output_token,
-        sim.total_input,
-        sim.total_output,
+        total_input,
+        total_output,
     )
     .await?;
 
@@ -129,17 +238,299 @@ async fn process_swap_quote(
         ApiError::Internal("failed to format ratio".into())
     })?;
 
+    if !fully_filled {
+        tracing::info!(
+            output_amount = ?req.output_amount,
+            input_amount = ?req.input_amount,
+            filled_output = %formatted_output,
+            "swap quote only partially fills requested amount"
+        );
+    }
+
     Ok(SwapQuoteResponse {
+        input_symbol: ds.token_symbol(req.input_token).unwrap_or_default(),
+        output_symbol: ds.token_symbol(req.output_token).unwrap_or_default(),
         input_token: req.input_token,
         output_token: req.output_token,
         output_amount: req.output_amount,
+        input_amount: req.input_amount,
         denomination: req.denomination,
-        estimated_output: formatted_output,
+        estimated_output: formatted_output.clone(),
         estimated_input: formatted_input,
         estimated_io_ratio: formatted_ratio,
+        unfilled_output,
+        requested_output,
+        filled_output: formatted_output,
+        unspent_input,
+        fully_filled,
+        price_impact_bps,
+        orders_considered: u32::try_from(orders_considered).unwrap_or(u32::MAX),
+        orders_used: u32::try_from(leg_count).unwrap_or(u32::MAX),
     })
 }
 
+/// Lowest ratio among candidates priced within `price_cap` that have output
+/// to sell, i.e. the top of the book.
+fn best_candidate_ratio(candidates: &[TakeOrderCandidate], price_cap: Float) -> Option<Float> {
+    let mut best: Option<Float> = None;
+    for candidate in candidates {
+        let priced = !price_cap.lt(candidate.ratio).ok()?;
+        let has_output = !candidate.max_output.is_zero().ok()?;
+        let better = match best {
+            Some(best) => candidate.ratio.lt(best).ok()?,
+            None => true,
+        };
+        if priced && has_output && better {
+            best = Some(candidate.ratio);
+        }
+    }
+    best
+}
+
+/// Price impact of the blended fill ratio over `best_ratio` in basis points.
+/// It is advisory, so it is left out rather than failing the quote when it
+/// can't be computed.
+fn price_impact_bps(best_ratio: Float, total_input: Float, total_output: Float) -> Option<String> {
+    fn price_impact_error(e: impl std::fmt::Display) {
+        tracing::warn!(error = %e, "failed to compute quote price impact");
+    }
+
+    if best_ratio.is_zero().map_err(price_impact_error).ok()?
+        || total_output.is_zero().map_err(price_impact_error).ok()?
+    {
+        return None;
+    }
+    let blended = total_input
+        .div(total_output)
+        .map_err(price_impact_error)
+        .ok()?;
+    let basis_points = Float::parse("10000".to_string())
+        .map_err(price_impact_error)
+        .ok()?;
+    blended
+        .sub(best_ratio)
+        .and_then(|premium| premium.div(best_ratio))
+        .and_then(|fraction| fraction.mul(basis_points))
+        .and_then(|bps| bps.format())
+        .map_err(price_impact_error)
+        .ok()
+}
+
+/// The side of the swap the caller fixed.
+#[derive(Clone, Copy)]
+enum QuoteTarget {
+    /// Buy up to this much output.
+    Output(Float),
+    /// Spend up to this much input.
+    Input(Float),
+}
+
+impl QuoteTarget {
+    fn from_request(req: &SwapQuoteRequest) -> Result<Self, ApiError> {
+        match (req.output_amount.as_deref(), req.input_amount.as_deref()) {
+            (Some(output_amount), None) => {
+                let amount = Float::parse(output_amount.to_string()).map_err(|e| {
+                    tracing::error!(error = %e, "failed to parse output_amount");
+                    ApiError::BadRequest("invalid output_amount".into())
+                })?;
+                Ok(Self::Output(amount))
+            }
+            (None, Some(input_amount)) => {
+                let amount = Float::parse(input_amount.to_string()).map_err(|e| {
+                    tracing::error!(error = %e, "failed to parse input_amount");
+                    ApiError::BadRequest("invalid input_amount".into())
+                })?;
+                let zero = Float::zero().map_err(|e| {
+                    tracing::error!(error = %e, "failed to create zero float");
+                    ApiError::Internal("failed to validate input_amount".into())
+                })?;
+                if !zero
+                    .lt(amount)
+                    .map_err(|e| client_value_out_of_range(e, "input_amount"))?
+                {
+                    return Err(ApiError::BadRequest(
+                        "input_amount must be greater than zero".into(),
+                    ));
+                }
+                Ok(Self::Input(amount))
+            }
+            (Some(_), Some(_)) | (None, None) => Err(ApiError::BadRequest(
+                "exactly one of output_amount or input_amount is required".into(),
+            )),
+        }
+    }
+}
+
+/// Spends `budget` across candidates from the cheapest ratio upwards,
+/// skipping candidates priced above `price_cap`. Returns the total input,
+/// total output and number of candidates used.
+fn simulate_spend_over_candidates(
+    candidates: &[TakeOrderCandidate],
+    budget: Float,
+    price_cap: Float,
+) -> Result<(Float, Float, usize), ApiError> {
+    fn sim_error(e: impl std::fmt::Display) -> ApiError {
+        tracing::error!(error = %e, "failed to simulate spend");
+        ApiError::Internal("failed to simulate swap".into())
+    }
+
+    let mut priced = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        if price_cap.lt(candidate.ratio).map_err(sim_error)? {
+            continue;
+        }
+        priced.push((candidate.ratio, candidate.max_output));
+    }
+    priced.sort_by(|(a, _), (b, _)| match (a.lt(*b), b.lt(*a)) {
+        (Ok(true), _) => std::cmp::Ordering::Less,
+        (_, Ok(true)) => std::cmp::Ordering::Greater,
+        _ => std::cmp::Ordering::Equal,
+    });
+
+    let zero = Float::zero().map_err(sim_error)?;
+    let mut remaining = budget;
+    let mut total_input = zero;
+    let mut total_output = zero;
+    let mut legs = 0;
+    for (ratio, max_output) in priced {
+        if !zero.lt(remaining).map_err(sim_error)? {
+            break;
+        }
+        let full_input = max_output.mul(ratio).map_err(sim_error)?;
+        let (input, output) = if remaining.lt(full_input).map_err(sim_error)? {
+            (remaining, remaining.div(ratio).map_err(sim_error)?)
+        } else {
+            (full_input, max_output)
+        };
+        if output.is_zero().map_err(sim_error)? {
+            continue;
+        }
+        remaining = remaining.sub(input).map_err(sim_error)?;
+        total_input = total_input.add(input).map_err(sim_error)?;
+        total_output = total_output.add(output).map_err(sim_error)?;
+        legs += 1;
+    }
+
+    Ok((total_input, total_output, legs))
+}
+
+fn parse_max_io_ratio(value: &str) -> Result<Float, ApiError> {
+    let invalid = || ApiError::BadRequest("max_io_ratio must be a positive number".into());
+    let cap = Float::parse(value.to_string()).map_err(|e| {
+        tracing::warn!(error = %e, value, "failed to parse max_io_ratio");
+        invalid()
+    })?;
+    let zero = Float::zero().map_err(|e| {
+        tracing::error!(error = %e, "failed to create zero float");
+        ApiError::Internal("failed to create price cap".into())
+    })?;
+    let positive = zero.lt(cap).map_err(|e| {
+        tracing::error!(error = %e, "failed to compare max_io_ratio");
+        ApiError::Internal("failed to create price cap".into())
+    })?;
+    if !positive {
+        return Err(invalid());
+    }
+    Ok(cap)
+}
+
+/// Parses a minimum output-token depth; `0` disables the check.
+pub(crate) fn parse_min_liquidity(value: &str) -> Result<Float, ApiError> {
+    let invalid = || ApiError::BadRequest("min_liquidity must be a non-negative number".into());
+    let min = Float::parse(value.to_string()).map_err(|e| {
+        tracing::warn!(error = %e, value, "failed to parse min_liquidity");
+        invalid()
+    })?;
+    let zero = Float::zero().map_err(|e| {
+        tracing::error!(error = %e, "failed to create zero float");
+        ApiError::Internal("failed to check liquidity".into())
+    })?;
+    let negative = min.lt(zero).map_err(|e| {
+        tracing::error!(error = %e, "failed to compare min_liquidity");
+        ApiError::Internal("failed to check liquidity".into())
+    })?;
+    if negative {
+        return Err(invalid());
+    }
+    Ok(min)
+}
+
+/// Rejects pairs whose combined candidate output, at any price, is below
+/// `min_liquidity`, so a dust order isn't quoted as if it were real depth.
+fn ensure_min_liquidity(
+    candidates: &[TakeOrderCandidate],
+    min_liquidity: Float,
+) -> Result<(), ApiError> {
+    let depth = total_depth(candidates)?;
+    if depth.lt(min_liquidity).map_err(depth_error)? {
+        tracing::info!(
+            depth = %depth.format().unwrap_or_default(),
+            min_liquidity = %min_liquidity.format().unwrap_or_default(),
+            "pair depth below minimum quote liquidity"
+        );
+        return Err(ApiError::not_found_because(
+            NotFoundReason::NoLiquidity,
+            "insufficient liquidity",
+        ));
+    }
+    Ok(())
+}
+
+/// Rejects an `outputAmount` more than [`MAX_OUTPUT_DEPTH_MULTIPLE`] times
+/// the pair's combined depth before simulating it, since nothing but a
+/// negligible partial fill could come of it.
+fn ensure_plausible_output(
+    candidates: &[TakeOrderCandidate],
+    requested: Float,
+) -> Result<(), ApiError> {
+    let multiple = Float::parse(MAX_OUTPUT_DEPTH_MULTIPLE.to_string()).map_err(depth_error)?;
+    let bound = total_depth(candidates)?
+        .mul(multiple)
+        .map_err(depth_error)?;
+    if bound.lt(requested).map_err(depth_error)? {
+        tracing::info!(
+            requested = %requested.format().unwrap_or_default(),
+            bound = %bound.format().unwrap_or_default(),
+            "output amount far beyond pair depth"
+        );
+        return Err(ApiError::BadRequest(
+            "requested amount exceeds available liquidity".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Combined candidate output at any price.
+fn total_depth(candidates: &[TakeOrderCandidate]) -> Result<Float, ApiError> {
+    let mut depth = Float::zero().map_err(depth_error)?;
+    for candidate in candidates {
+        depth = depth.add(candidate.max_output).map_err(depth_error)?;
+    }
+    Ok(depth)
+}
+
+fn depth_error(e: impl std::fmt::Display) -> ApiError {
+    tracing::error!(error = %e, "failed to sum available liquidity");
+    ApiError::Internal("failed to check liquidity".into())
+}
+
+/// What's left of the caller's `requested` amount after `filled`. The
+/// subtraction only fails when the requested amount itself is out of range.
+fn unfilled_amount(requested: Float, filled: Float, field: &str) -> Result<Float, ApiError> {
+    let remainder = requested
+        .sub(filled)
+        .map_err(|e| client_value_out_of_range(e, field))?;
+    let zero = Float::zero().map_err(|e| {
+        tracing::error!(error = %e, "failed to create zero float");
+        ApiError::Internal("failed to compute unfilled output".into())
+    })?;
+    let overfilled = remainder.lt(zero).map_err(|e| {
+        tracing::error!(error = %e, "failed to compare unfilled output");
+        ApiError::Internal("failed to compute unfilled output".into())
+    })?;
+    Ok(if overfilled { zero } else { remainder })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,6 +542,7 @@ mod tests {
     use async_trait::async_trait;
     use rocket::http::{ContentType, Status};
     use std::collections::HashMap;
+    use tracing_test::traced_test;
 
     const USDC: alloy::primitives::Address = address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
     const WETH: alloy::primitives::Address = address!("4200000000000000000000000000000000000006");
@@ -159,8 +551,19 @@ mod tests {
         SwapQuoteRequest {
             input_token: USDC,
             output_token: WETH,
-            output_amount: output_amount.to_string(),
+            output_amount: Some(output_amount.to_string()),
+            input_amount: None,
             denomination: SwapDenomination::Wrapped,
+            max_io_ratio: None,
+            min_liquidity: None,
+            orderbook: None,
+        }
+    }
+
+    fn capped_quote_request(output_amount: &str, max_io_ratio: &str) -> SwapQuoteRequest {
+        SwapQuoteRequest {
+            max_io_ratio: Some(max_io_ratio.to_string()),
+            ..quote_request(output_amount)
         }
     }
 
@@ -172,8 +575,12 @@ mod tests {
         SwapQuoteRequest {
             input_token,
             output_token,
-            output_amount: output_amount.to_string(),
+            output_amount: Some(output_amount.to_string()),
+            input_amount: None,
             denomination: SwapDenomination::Unwrapped,
+            max_io_ratio: None,
+            min_liquidity: None,
+            orderbook: None,
         }
     }
 
@@ -233,6 +640,14 @@ mod tests {
             self.base.get_calldata(request).await
         }
 
+        fn known_orderbooks(&self) -> Vec<alloy::primitives::Address> {
+            self.base.known_orderbooks()
+        }
+
+        fn token_symbol(&self, token: alloy::primitives::Address) -> Option<String> {
+            self.base.token_symbol(token)
+        }
+
         async fn get_wrap_ratios_for_tokens(
             &self,
             token_addresses: &[alloy::primitives::Address],
@@ -260,13 +675,115 @@ mod tests {
 
         assert_eq!(result.input_token, USDC);
         assert_eq!(result.output_token, WETH);
-        assert_eq!(result.output_amount, "100");
+        assert_eq!(result.input_symbol, "USDC");
+        assert_eq!(result.output_symbol, "WETH");
+        assert_eq!(result.output_amount.as_deref(), Some("100"));
         assert_eq!(result.denomination, SwapDenomination::Wrapped);
         assert_eq!(result.estimated_output, "100");
         assert_eq!(result.estimated_input, "150");
         assert_eq!(result.estimated_io_ratio, "1.5");
     }
 
+    fn min_liquidity_request(min_liquidity: &str) -> SwapQuoteRequest {
+        SwapQuoteRequest {
+            min_liquidity: Some(min_liquidity.to_string()),
+            ..quote_request("100")
+        }
+    }
+
+    fn split_depth_ds() -> MockSwapDataSource {
+        MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("600", "1.5"), mock_candidate("400", "2")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_min_liquidity_below_depth_quotes() {
+        let result = process_swap_quote(&split_depth_ds(), min_liquidity_request("999"))
+            .await
+            .unwrap();
+        assert_eq!(result.estimated_output, "100");
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_min_liquidity_equal_to_depth_quotes() {
+        let result = process_swap_quote(&split_depth_ds(), min_liquidity_request("1000"))
+            .await
+            .unwrap();
+        assert_eq!(result.estimated_output, "100");
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_min_liquidity_above_depth_is_not_found() {
+        let result = process_swap_quote(&split_depth_ds(), min_liquidity_request("1000.5")).await;
+        assert!(matches!(
+            result,
+            Err(ApiError::NotFound { message: msg, .. }) if msg == "insufficient liquidity"
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_rejects_invalid_min_liquidity() {
+        for value in ["-1", "lots"] {
+            let result = process_swap_quote(&split_depth_ds(), min_liquidity_request(value)).await;
+            assert!(matches!(result, Err(ApiError::BadRequest(_))));
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_zero_min_liquidity_disables_check() {
+        let ds = MockSwapDataSource {
+            candidates: vec![mock_candidate("0.001", "1.5")],
+            ..split_depth_ds()
+        };
+        let result = process_swap_quote(&ds, min_liquidity_request("0"))
+            .await
+            .unwrap();
+        assert!(!result.fully_filled);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_restricted_to_known_orderbook() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("1000", "1.5")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let req = SwapQuoteRequest {
+            orderbook: Some(address!("d2938e7c9fe3597f78832ce780feb61945c377d7")),
+            ..quote_request("100")
+        };
+        let result = process_swap_quote(&ds, req).await.unwrap();
+
+        assert_eq!(result.estimated_output, "100");
+        assert_eq!(result.estimated_input, "150");
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_rejects_unknown_orderbook() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("1000", "1.5")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let req = SwapQuoteRequest {
+            orderbook: Some(address!("9999999999999999999999999999999999999999")),
+            ..quote_request("100")
+        };
+        let result = process_swap_quote(&ds, req).await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::BadRequest(msg))
+                if msg == "unknown orderbook: 0x9999999999999999999999999999999999999999"
+        ));
+    }
+
     #[rocket::async_test]
     async fn test_process_swap_quote_multi_leg() {
         let ds = MockSwapDataSource {
@@ -277,7 +794,7 @@ mod tests {
         };
         let result = process_swap_quote(&ds, quote_request("100")).await.unwrap();
 
-        assert_eq!(result.output_amount, "100");
+        assert_eq!(result.output_amount.as_deref(), Some("100"));
         assert_eq!(result.estimated_output, "100");
         assert_eq!(result.estimated_input, "250");
         assert_eq!(result.estimated_io_ratio, "2.5");
@@ -293,9 +810,168 @@ mod tests {
         };
         let result = process_swap_quote(&ds, quote_request("100")).await.unwrap();
 
-        assert_eq!(result.output_amount, "100");
+        assert_eq!(result.output_amount.as_deref(), Some("100"));
         assert_eq!(result.estimated_output, "30");
         assert_eq!(result.estimated_input, "60");
+        assert_eq!(result.unfilled_output.as_deref(), Some("70"));
+        assert_eq!(result.requested_output.as_deref(), Some("100"));
+        assert_eq!(result.filled_output, "30");
+        assert!(!result.fully_filled);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_multi_candidate_full_fill_flags() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("60", "2"), mock_candidate("60", "3")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let result = process_swap_quote(&ds, quote_request("100")).await.unwrap();
+
+        assert_eq!(result.requested_output.as_deref(), Some("100"));
+        assert_eq!(result.filled_output, "100");
+        assert_eq!(result.unfilled_output.as_deref(), Some("0"));
+        assert!(result.fully_filled);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_multi_candidate_partial_fill_flags() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("30", "2"), mock_candidate("40", "3")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let result = process_swap_quote(&ds, quote_request("100")).await.unwrap();
+
+        assert_eq!(result.requested_output.as_deref(), Some("100"));
+        assert_eq!(result.filled_output, "70");
+        assert_eq!(result.estimated_input, "180");
+        assert_eq!(result.unfilled_output.as_deref(), Some("30"));
+        assert!(!result.fully_filled);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_without_cap_fills_at_any_price() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("50", "2"), mock_candidate("50", "1000")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let result = process_swap_quote(&ds, quote_request("100")).await.unwrap();
+
+        assert_eq!(result.estimated_output, "100");
+        assert_eq!(result.unfilled_output.as_deref(), Some("0"));
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_tight_cap_reduces_fill() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("50", "2"), mock_candidate("50", "3")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let result = process_swap_quote(&ds, capped_quote_request("100", "2.5"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.estimated_output, "50");
+        assert_eq!(result.estimated_input, "100");
+        assert_eq!(result.estimated_io_ratio, "2");
+        assert_eq!(result.unfilled_output.as_deref(), Some("50"));
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_rejects_absurd_output_amount() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("30", "2"), mock_candidate("40", "3")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let result = process_swap_quote(&ds, quote_request("1000000000000000000000000")).await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::BadRequest(msg)) if msg == "requested amount exceeds available liquidity"
+        ));
+
+        let at_bound = process_swap_quote(&ds, quote_request("70000"))
+            .await
+            .unwrap();
+        assert_eq!(at_bound.filled_output, "70");
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_counts_orders_considered_and_used() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![
+                mock_candidate("30", "2"),
+                mock_candidate("40", "3"),
+                mock_candidate("50", "9"),
+            ],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let result = process_swap_quote(&ds, capped_quote_request("100", "5"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.filled_output, "70");
+        assert!(!result.fully_filled);
+        assert_eq!(result.orders_considered, 3);
+        assert_eq!(result.orders_used, 2);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_loose_cap_fills_everything() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("50", "2"), mock_candidate("50", "3")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let result = process_swap_quote(&ds, capped_quote_request("100", "3"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.estimated_output, "100");
+        assert_eq!(result.unfilled_output.as_deref(), Some("0"));
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_cap_below_all_candidates() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("50", "2")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let result = process_swap_quote(&ds, capped_quote_request("100", "1")).await;
+        assert!(
+            matches!(result, Err(ApiError::NotFound { message: msg, .. }) if msg.contains("within max_io_ratio"))
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_rejects_invalid_cap() {
+        for cap in ["abc", "0", "-1"] {
+            let ds = MockSwapDataSource {
+                supported_tokens: Ok(()),
+                orders: Ok(vec![mock_order()]),
+                candidates: vec![mock_candidate("50", "2")],
+                calldata_result: Err(ApiError::Internal("unused".into())),
+            };
+            let result = process_swap_quote(&ds, capped_quote_request("100", cap)).await;
+            assert!(
+                matches!(result, Err(ApiError::BadRequest(ref msg)) if msg.contains("max_io_ratio")),
+                "expected {cap} to be rejected"
+            );
+        }
     }
 
     #[rocket::async_test]
@@ -316,6 +992,35 @@ mod tests {
         assert_eq!(result.estimated_input, "15");
     }
 
+    #[rocket::async_test]
+    async fn test_process_swap_quote_reports_price_impact_over_best_order() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("500", "2.4"), mock_candidate("500", "1.6")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let result = process_swap_quote(&ds, quote_request("1000"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.estimated_io_ratio, "2");
+        assert_eq!(result.price_impact_bps.as_deref(), Some("2500"));
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_single_order_has_zero_price_impact() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("1000", "1.5"), mock_candidate("1000", "3")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let result = process_swap_quote(&ds, quote_request("100")).await.unwrap();
+
+        assert_eq!(result.price_impact_bps.as_deref(), Some("0"));
+    }
+
     #[rocket::async_test]
     async fn test_process_swap_quote_unwrapped_converts_input_amount_and_ratio() {
         let wt_mstr = address!("Ff05e1BD696900DC6A52cA35cA61bB1024eDA8e2");
@@ -333,8 +1038,10 @@ mod tests {
             .await
             .unwrap();
 
+        assert_eq!(result.input_symbol, "");
+        assert_eq!(result.output_symbol, "WETH");
         assert_eq!(result.denomination, SwapDenomination::Unwrapped);
-        assert_eq!(result.output_amount, "100");
+        assert_eq!(result.output_amount.as_deref(), Some("100"));
         assert_eq!(result.estimated_output, "100");
         assert_eq!(result.estimated_input, "300");
         assert_eq!(result.estimated_io_ratio, "3");
@@ -358,12 +1065,36 @@ mod tests {
             .unwrap();
 
         assert_eq!(result.denomination, SwapDenomination::Unwrapped);
-        assert_eq!(result.output_amount, "100");
+        assert_eq!(result.output_amount.as_deref(), Some("100"));
         assert_eq!(result.estimated_output, "200");
         assert_eq!(result.estimated_input, "150");
         assert_eq!(result.estimated_io_ratio, "0.75");
     }
 
+    #[rocket::async_test]
+    async fn test_process_swap_quote_unwrapped_partial_fill_reports_unwrapped_amounts() {
+        let wt_mstr = address!("Ff05e1BD696900DC6A52cA35cA61bB1024eDA8e2");
+        let ds = MockQuoteDataSource {
+            base: MockSwapDataSource {
+                supported_tokens: Ok(()),
+                orders: Ok(vec![mock_order()]),
+                candidates: vec![mock_candidate("1000", "1.5")],
+                calldata_result: Err(ApiError::Internal("unused".into())),
+            },
+            wrap_ratios: HashMap::from([(wt_mstr, wrap_ratio(wt_mstr, "2"))]),
+        };
+
+        let result = process_swap_quote(&ds, unwrapped_quote_request(USDC, wt_mstr, "2000"))
+            .await
+            .unwrap();
+
+        assert!(!result.fully_filled);
+        assert_eq!(result.estimated_output, "2000");
+        assert_eq!(result.requested_output.as_deref(), Some("4000"));
+        assert_eq!(result.filled_output, "2000");
+        assert_eq!(result.unfilled_output.as_deref(), Some("2000"));
+    }
+
     #[rocket::async_test]
     async fn test_process_swap_quote_unwrapped_converts_both_sides() {
         let wt_mstr = address!("Ff05e1BD696900DC6A52cA35cA61bB1024eDA8e2");
@@ -386,7 +1117,7 @@ mod tests {
             .unwrap();
 
         assert_eq!(result.denomination, SwapDenomination::Unwrapped);
-        assert_eq!(result.output_amount, "100");
+        assert_eq!(result.output_amount.as_deref(), Some("100"));
         assert_eq!(result.estimated_output, "300");
         assert_eq!(result.estimated_input, "300");
         assert_eq!(result.estimated_io_ratio, "1");
@@ -409,12 +1140,184 @@ mod tests {
             .unwrap();
 
         assert_eq!(result.denomination, SwapDenomination::Unwrapped);
-        assert_eq!(result.output_amount, "100");
+        assert_eq!(result.output_amount.as_deref(), Some("100"));
         assert_eq!(result.estimated_output, "100");
         assert_eq!(result.estimated_input, "150");
         assert_eq!(result.estimated_io_ratio, "1.5");
     }
 
+    #[rocket::async_test]
+    async fn test_process_swap_quote_malformed_amount_is_bad_request() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("1000", "1.5")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+
+        let result =
+            process_swap_quote(&ds, unwrapped_quote_request(USDC, WETH, "not-a-number")).await;
+
+        assert!(matches!(result, Err(ApiError::BadRequest(msg)) if msg == "invalid output_amount"));
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_out_of_range_price_cap_is_bad_request() {
+        let wt_mstr = address!("Ff05e1BD696900DC6A52cA35cA61bB1024eDA8e2");
+        let ds = MockQuoteDataSource {
+            base: MockSwapDataSource {
+                supported_tokens: Ok(()),
+                orders: Ok(vec![mock_order()]),
+                candidates: vec![mock_candidate("1000", "1.5")],
+                calldata_result: Err(ApiError::Internal("unused".into())),
+            },
+            wrap_ratios: HashMap::from([(wt_mstr, wrap_ratio(wt_mstr, "1e2000000000"))]),
+        };
+        let mut req = unwrapped_quote_request(USDC, wt_mstr, "100");
+        req.max_io_ratio = Some("1e2000000000".to_string());
+
+        let result = process_swap_quote(&ds, req).await;
+
+        assert!(
+            matches!(result, Err(ApiError::BadRequest(msg)) if msg == "max_io_ratio is out of range")
+        );
+    }
+
+    fn input_quote_request(input_amount: &str) -> SwapQuoteRequest {
+        SwapQuoteRequest {
+            output_amount: None,
+            input_amount: Some(input_amount.to_string()),
+            ..quote_request("0")
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_input_specified_single_leg() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("1000", "1.5")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let result = process_swap_quote(&ds, input_quote_request("150"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.input_amount.as_deref(), Some("150"));
+        assert_eq!(result.output_amount, None);
+        assert_eq!(result.estimated_input, "150");
+        assert_eq!(result.estimated_output, "100");
+        assert_eq!(result.estimated_io_ratio, "1.5");
+        assert_eq!(result.unspent_input.as_deref(), Some("0"));
+        assert_eq!(result.unfilled_output, None);
+        assert!(result.fully_filled);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_input_specified_spends_cheapest_first() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("50", "3"), mock_candidate("50", "2")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let result = process_swap_quote(&ds, input_quote_request("160"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.estimated_input, "160");
+        assert_eq!(result.estimated_output, "70");
+        assert!(result.fully_filled);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_input_specified_exceeds_liquidity() {
+        let ds = MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates: vec![mock_candidate("30", "2")],
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        };
+        let result = process_swap_quote(&ds, input_quote_request("100"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.estimated_input, "60");
+        assert_eq!(result.estimated_output, "30");
+        assert_eq!(result.unspent_input.as_deref(), Some("40"));
+        assert!(!result.fully_filled);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_rejects_non_positive_input_amount() {
+        for input_amount in ["0", "-5"] {
+            let ds = MockSwapDataSource {
+                supported_tokens: Ok(()),
+                orders: Ok(vec![mock_order()]),
+                candidates: vec![mock_candidate("1000", "1.5")],
+                calldata_result: Err(ApiError::Internal("unused".into())),
+            };
+            let result = process_swap_quote(&ds, input_quote_request(input_amount)).await;
+            assert!(matches!(
+                result,
+                Err(ApiError::BadRequest(msg)) if msg == "input_amount must be greater than zero"
+            ));
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_unwrapped_input_amount_spends_converted_budget() {
+        let wt_mstr = address!("Ff05e1BD696900DC6A52cA35cA61bB1024eDA8e2");
+        let ds = MockQuoteDataSource {
+            base: MockSwapDataSource {
+                supported_tokens: Ok(()),
+                orders: Ok(vec![mock_order()]),
+                candidates: vec![mock_candidate("1000", "1.5")],
+                calldata_result: Err(ApiError::Internal("unused".into())),
+            },
+            wrap_ratios: HashMap::from([(wt_mstr, wrap_ratio(wt_mstr, "2"))]),
+        };
+        let req = SwapQuoteRequest {
+            output_amount: None,
+            input_amount: Some("300".into()),
+            ..unwrapped_quote_request(wt_mstr, WETH, "0")
+        };
+
+        let result = process_swap_quote(&ds, req).await.unwrap();
+
+        assert_eq!(result.estimated_input, "300");
+        assert_eq!(result.estimated_output, "100");
+        assert_eq!(result.unspent_input.as_deref(), Some("0"));
+        assert!(result.fully_filled);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_requires_exactly_one_amount() {
+        let both = SwapQuoteRequest {
+            input_amount: Some("10".into()),
+            ..quote_request("10")
+        };
+        let neither = SwapQuoteRequest {
+            output_amount: None,
+            ..quote_request("10")
+        };
+
+        for req in [both, neither] {
+            let ds = MockSwapDataSource {
+                supported_tokens: Ok(()),
+                orders: Ok(vec![mock_order()]),
+                candidates: vec![mock_candidate("1000", "1.5")],
+                calldata_result: Err(ApiError::Internal("unused".into())),
+            };
+            let result = process_swap_quote(&ds, req).await;
+            assert!(matches!(
+                result,
+                Err(ApiError::BadRequest(msg))
+                    if msg == "exactly one of output_amount or input_amount is required"
+            ));
+        }
+    }
+
     #[rocket::async_test]
     async fn test_process_swap_quote_no_liquidity() {
         let ds = MockSwapDataSource {
@@ -424,7 +1327,13 @@ mod tests {
             calldata_result: Err(ApiError::Internal("unused".into())),
         };
         let result = process_swap_quote(&ds, quote_request("100")).await;
-        assert!(matches!(result, Err(ApiError::NotFound(msg)) if msg.contains("no liquidity")));
+        assert!(matches!(
+            result,
+            Err(ApiError::NotFound {
+                message: msg,
+                reason: Some(NotFoundReason::NoLiquidity),
+            }) if msg.contains("no liquidity")
+        ));
     }
 
     #[rocket::async_test]
@@ -436,7 +1345,9 @@ mod tests {
             calldata_result: Err(ApiError::Internal("unused".into())),
         };
         let result = process_swap_quote(&ds, quote_request("100")).await;
-        assert!(matches!(result, Err(ApiError::NotFound(msg)) if msg.contains("no valid quotes")));
+        assert!(
+            matches!(result, Err(ApiError::NotFound { message: msg, .. }) if msg.contains("no valid quotes"))
+        );
     }
 
     #[rocket::async_test]
@@ -506,6 +1417,43 @@ mod tests {
         assert_eq!(response.status(), Status::BadRequest);
     }
 
+    #[rocket::async_test]
+    #[traced_test]
+    async fn test_swap_quote_span_records_token_pair() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = crate::test_helpers::seed_api_key(&client).await;
+        let header = crate::test_helpers::basic_auth_header(&key_id, &secret);
+        client
+            .post("/v1/swap/quote")
+            .header(ContentType::JSON)
+            .header(rocket::http::Header::new("Authorization", header))
+            .body(r#"{"inputToken":"0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913","outputToken":"0x4200000000000000000000000000000000000006","outputAmount":"100"}"#)
+            .dispatch()
+            .await;
+
+        assert!(logs_contain("input_token=0x8335..2913"));
+        assert!(logs_contain("output_token=0x4200..0006"));
+    }
+
+    #[rocket::async_test]
+    async fn test_swap_quote_415_for_non_json_body() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = crate::test_helpers::seed_api_key(&client).await;
+        let header = crate::test_helpers::basic_auth_header(&key_id, &secret);
+        let response = client
+            .post("/v1/swap/quote")
+            .header(ContentType::Plain)
+            .header(rocket::http::Header::new("Authorization", header))
+            .body(r#"{"inputToken":"0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913","outputToken":"0x4200000000000000000000000000000000000006","outputAmount":"100"}"#)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::UnsupportedMediaType);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(body["error"]["code"], "UNSUPPORTED_MEDIA_TYPE");
+        assert!(body["request_id"].as_str().is_some());
+    }
+
     #[rocket::async_test]
     async fn test_swap_quote_422_for_invalid_denomination() {
         let client = TestClientBuilder::new().build().await;
@@ -0,0 +1,358 @@
+use super::quote::process_swap_quote;
+use super::{RaindexSwapDataSource, SwapDataSource};
+use crate::app_state::ApplicationState;
+use crate::auth::AuthenticatedKey;
+use crate::db::DbPool;
+use crate::error::{ApiError, ApiErrorResponse, NotFoundReason};
+use crate::fairings::{
+    record_token_pair, short_address, GlobalRateLimit, JsonContentType, TracingSpan,
+};
+use crate::types::swap::{
+    SwapQuoteBothRequest, SwapQuoteBothResponse, SwapQuoteRequest, SwapQuoteResponse,
+};
+use rain_math_float::Float;
+use rocket::serde::json::Json;
+use rocket::State;
+use std::ops::{Add, Div, Mul, Sub};
+use tracing::Instrument;
+
+#[utoipa::path(
+    post,
+    path = "/v1/swap/quote/both",
+    tag = "Swap",
+    security(("basicAuth" = [])),
+    request_body = SwapQuoteBothRequest,
+    responses(
+        (status = 200, description = "Buy and sell quotes for the pair, with the spread between them", body = SwapQuoteBothResponse),
+        (status = 400, description = "Bad request", body = ApiErrorResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 403, description = "Request involves a restricted address", body = ApiErrorResponse),
+        (status = 404, description = "No liquidity found in either direction", body = ApiErrorResponse),
+        (status = 422, description = "Request body could not be parsed", body = ApiErrorResponse),
+        (status = 429, description = "Rate limited", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+#[post("/quote/both", data = "<request>")]
+pub async fn post_swap_quote_both(
+    _global: GlobalRateLimit,
+    _key: AuthenticatedKey,
+    _json: JsonContentType,
+    shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    app_state: &State<ApplicationState>,
+    pool: &State<DbPool>,
+    span: TracingSpan,
+    request: Json<SwapQuoteBothRequest>,
+) -> Result<Json<SwapQuoteBothResponse>, ApiError> {
+    let req = request.into_inner();
+    record_token_pair(
+        &span.0,
+        &short_address(req.input_token),
+        &short_address(req.output_token),
+    );
+    async move {
+        tracing::info!(body = ?req, "request received");
+        app_state
+            .denylist
+            .check(&[req.input_token, req.output_token])?;
+        let raindex = shared_raindex.read().await;
+        let ds = RaindexSwapDataSource {
+            client: raindex.client(),
+            caches: &app_state.response_caches,
+            pool: pool.inner(),
+            orderbooks: raindex.orderbooks_for_chain(crate::CHAIN_ID),
+            max_orders: app_state.max_quote_orders,
+        };
+        let response = process_swap_quote_both(&ds, req).await?;
+        Ok(Json(response))
+    }
+    .instrument(span.0)
+    .await
+}
+
+/// Quotes buying `amount` of the output token and selling the same amount
+/// back. Orders only trade one way, so each side fetches its own candidates
+/// once; the two fetches run concurrently. A side with no liquidity comes
+/// back `null`, and the request only fails when neither side quotes.
+///
+/// `minLiquidity` isn't applied: the sides measure depth in different
+/// tokens, and a thin side is better reported than hidden.
+async fn process_swap_quote_both(
+    ds: &dyn SwapDataSource,
+    req: SwapQuoteBothRequest,
+) -> Result<SwapQuoteBothResponse, ApiError> {
+    let buy_request = SwapQuoteRequest {
+        input_token: req.input_token,
+        output_token: req.output_token,
+        output_amount: Some(req.amount.clone()),
+        input_amount: None,
+        denomination: req.denomination,
+        max_io_ratio: None,
+        min_liquidity: None,
+        orderbook: req.orderbook,
+    };
+    let sell_request = SwapQuoteRequest {
+        input_token: req.output_token,
+        output_token: req.input_token,
+        output_amount: None,
+        input_amount: Some(req.amount.clone()),
+        denomination: req.denomination,
+        max_io_ratio: None,
+        min_liquidity: None,
+        orderbook: req.orderbook,
+    };
+
+    let (buy, sell) = futures::future::join(
+        process_swap_quote(ds, buy_request),
+        process_swap_quote(ds, sell_request),
+    )
+    .await;
+    let buy = quoted_side(buy, "buy")?;
+    let sell = quoted_side(sell, "sell")?;
+    if buy.is_none() && sell.is_none() {
+        return Err(ApiError::not_found_because(
+            NotFoundReason::NoLiquidity,
+            "no liquidity found for this pair in either direction",
+        ));
+    }
+
+    let ask = buy.as_ref().map(|quote| quote.estimated_io_ratio.clone());
+    let bid = sell.as_ref().and_then(bid_price);
+    let (spread, spread_bps) = match (buy.as_ref(), bid) {
+        (Some(buy), Some(bid)) => spread(&buy.estimated_io_ratio, bid).unzip(),
+        _ => (None, None),
+    };
+
+    Ok(SwapQuoteBothResponse {
+        input_token: req.input_token,
+        output_token: req.output_token,
+        amount: req.amount,
+        buy,
+        sell,
+        ask,
+        bid: bid.and_then(|bid| bid.format().ok()),
+        spread,
+        spread_bps,
+    })
+}
+
+/// A side without liquidity is reported as absent; any other failure fails
+/// the whole request.
+fn quoted_side(
+    result: Result<SwapQuoteResponse, ApiError>,
+    side: &'static str,
+) -> Result<Option<SwapQuoteResponse>, ApiError> {
+    match result {
+        Ok(quote) => Ok(Some(quote)),
+        Err(ApiError::NotFound { message, .. }) => {
+            tracing::info!(side, reason = %message, "no quote for this side of the pair");
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn price_error(e: impl std::fmt::Display) {
+    tracing::warn!(error = %e, "failed to compute two-sided quote prices");
+}
+
+/// Input token received per output token sold: the inverse of the sell
+/// quote's ratio, which is priced the other way round.
+fn bid_price(sell: &SwapQuoteResponse) -> Option<Float> {
+    let ratio = Float::parse(sell.estimated_io_ratio.clone())
+        .map_err(price_error)
+        .ok()?;
+    if ratio.is_zero().map_err(price_error).ok()? {
+        return None;
+    }
+    Float::parse("1".to_string())
+        .and_then(|one| one.div(ratio))
+        .map_err(price_error)
+        .ok()
+}
+
+/// `ask - bid`, and that spread over the mid price in basis points. Prices
+/// are advisory, so they are left out rather than failing the request.
+fn spread(ask: &str, bid: Float) -> Option<(String, String)> {
+    let ask = Float::parse(ask.to_string()).map_err(price_error).ok()?;
+    let spread = ask.sub(bid).map_err(price_error).ok()?;
+    let mid = Float::parse("2".to_string())
+        .and_then(|two| ask.add(bid).and_then(|sum| sum.div(two)))
+        .map_err(price_error)
+        .ok()?;
+    if mid.is_zero().map_err(price_error).ok()? {
+        return None;
+    }
+    let spread_bps = Float::parse("10000".to_string())
+        .and_then(|basis_points| spread.div(mid).and_then(|f| f.mul(basis_points)))
+        .and_then(|bps| bps.format())
+        .map_err(price_error)
+        .ok()?;
+    let spread = spread.format().map_err(price_error).ok()?;
+    Some((spread, spread_bps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::swap::test_fixtures::MockSwapDataSource;
+    use crate::test_helpers::{mock_candidate, mock_order};
+    use crate::types::swap::{SwapCalldataResponse, SwapDenomination};
+    use alloy::primitives::{address, Address};
+    use async_trait::async_trait;
+    use rain_orderbook_common::raindex_client::orders::RaindexOrder;
+    use rain_orderbook_common::raindex_client::take_orders::TakeOrdersRequest;
+    use rain_orderbook_common::take_orders::TakeOrderCandidate;
+
+    const USDC: Address = address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+    const WETH: Address = address!("4200000000000000000000000000000000000006");
+
+    fn side(candidates: Vec<TakeOrderCandidate>) -> MockSwapDataSource {
+        MockSwapDataSource {
+            supported_tokens: Ok(()),
+            orders: Ok(vec![mock_order()]),
+            candidates,
+            calldata_result: Err(ApiError::Internal("unused".into())),
+        }
+    }
+
+    /// Serves `buy` for USDC -> WETH and `sell` for WETH -> USDC, since
+    /// `MockSwapDataSource` prices every direction the same.
+    struct DirectionalDataSource {
+        buy: MockSwapDataSource,
+        sell: MockSwapDataSource,
+    }
+
+    impl DirectionalDataSource {
+        fn direction(&self, input_token: Address) -> &MockSwapDataSource {
+            if input_token == USDC {
+                &self.buy
+            } else {
+                &self.sell
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SwapDataSource for DirectionalDataSource {
+        async fn validate_supported_tokens(
+            &self,
+            input_token: Address,
+            output_token: Address,
+        ) -> Result<(), ApiError> {
+            self.direction(input_token)
+                .validate_supported_tokens(input_token, output_token)
+                .await
+        }
+
+        async fn get_orders_for_pair(
+            &self,
+            input_token: Address,
+            output_token: Address,
+        ) -> Result<Vec<RaindexOrder>, ApiError> {
+            self.direction(input_token)
+                .get_orders_for_pair(input_token, output_token)
+                .await
+        }
+
+        async fn build_candidates_for_pair(
+            &self,
+            orders: &[RaindexOrder],
+            input_token: Address,
+            output_token: Address,
+        ) -> Result<Vec<TakeOrderCandidate>, ApiError> {
+            self.direction(input_token)
+                .build_candidates_for_pair(orders, input_token, output_token)
+                .await
+        }
+
+        async fn get_calldata(
+            &self,
+            request: TakeOrdersRequest,
+        ) -> Result<SwapCalldataResponse, ApiError> {
+            self.buy.get_calldata(request).await
+        }
+
+        fn known_orderbooks(&self) -> Vec<Address> {
+            self.buy.known_orderbooks()
+        }
+
+        fn token_symbol(&self, token: Address) -> Option<String> {
+            self.buy.token_symbol(token)
+        }
+    }
+
+    fn both_request(amount: &str) -> SwapQuoteBothRequest {
+        SwapQuoteBothRequest {
+            input_token: USDC,
+            output_token: WETH,
+            amount: amount.to_string(),
+            denomination: SwapDenomination::Wrapped,
+            orderbook: None,
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_both_reports_both_sides_and_spread() {
+        let ds = DirectionalDataSource {
+            buy: side(vec![mock_candidate("10", "3000")]),
+            sell: side(vec![mock_candidate("5000", "0.0005")]),
+        };
+        let result = process_swap_quote_both(&ds, both_request("1"))
+            .await
+            .unwrap();
+
+        let buy = result.buy.unwrap();
+        assert_eq!(buy.input_token, USDC);
+        assert_eq!(buy.output_token, WETH);
+        assert_eq!(buy.estimated_output, "1");
+        assert_eq!(buy.estimated_input, "3000");
+
+        let sell = result.sell.unwrap();
+        assert_eq!(sell.input_token, WETH);
+        assert_eq!(sell.output_token, USDC);
+        assert_eq!(sell.estimated_input, "1");
+        assert_eq!(sell.estimated_output, "2000");
+
+        assert_eq!(result.ask.as_deref(), Some("3000"));
+        assert_eq!(result.bid.as_deref(), Some("2000"));
+        assert_eq!(result.spread.as_deref(), Some("1000"));
+        assert_eq!(result.spread_bps.as_deref(), Some("4000"));
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_both_omits_side_without_liquidity() {
+        let ds = DirectionalDataSource {
+            buy: side(vec![mock_candidate("10", "3000")]),
+            sell: side(vec![]),
+        };
+        let result = process_swap_quote_both(&ds, both_request("1"))
+            .await
+            .unwrap();
+
+        assert!(result.buy.is_some());
+        assert!(result.sell.is_none());
+        assert_eq!(result.ask.as_deref(), Some("3000"));
+        assert!(result.bid.is_none());
+        assert!(result.spread.is_none());
+        assert!(result.spread_bps.is_none());
+    }
+
+    #[rocket::async_test]
+    async fn test_process_swap_quote_both_not_found_without_liquidity() {
+        let ds = DirectionalDataSource {
+            buy: side(vec![]),
+            sell: side(vec![]),
+        };
+        let result = process_swap_quote_both(&ds, both_request("1")).await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::NotFound {
+                reason: Some(NotFoundReason::NoLiquidity),
+                ..
+            })
+        ));
+    }
+}
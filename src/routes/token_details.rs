@@ -2,11 +2,12 @@ use super::{
     api_error_message, matches_token_proof_address, post_graphql, registry_tokens,
     resolve_sft_subgraph_url, TimestampValue, SFT_PAGE_SIZE,
 };
+use crate::app_state::ApplicationState;
 use crate::auth::AuthenticatedKey;
 use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
+use crate::fairings::{GlobalRateLimit, KnownQueryParams, TracingSpan};
 use crate::raindex::SharedRaindexProvider;
-use crate::types::common::ValidatedAddress;
+use crate::types::common::{InvalidPathParam, ValidatedAddress};
 use crate::wrap_ratio::is_st0x_token;
 use alloy::primitives::{Address, U256};
 use moka::future::Cache;
@@ -110,6 +111,10 @@ pub struct TokenDetailsQueryParams {
     #[param(rename = "activityLimit", example = 5, minimum = 1, maximum = 50)]
     #[field(name = "activityLimit")]
     activity_limit: Option<u32>,
+    /// Skip the token details lookup cache.
+    #[param(rename = "noCache", example = false)]
+    #[field(name = "noCache")]
+    no_cache: Option<bool>,
 }
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -264,6 +269,16 @@ fn token_details_cache_key(sft_subgraph_url: &str, address: Address) -> String {
     format!("token-details:{sft_subgraph_url}:{address:#x}")
 }
 
+/// Keyed by SFT subgraph too, so a registry change that points the token at
+/// another subgraph doesn't serve details read from the old one.
+pub(super) fn token_details_lookup_cache_key(
+    sft_subgraph_url: &str,
+    address: Address,
+    activity_limit: u32,
+) -> String {
+    format!("{sft_subgraph_url}:{address:#x}:{activity_limit}")
+}
+
 fn token_details_list_cache_key(items: &[TokenDetailsBatchItem]) -> String {
     let mut parts = items
         .iter()
@@ -725,7 +740,7 @@ query TokenDetails($address: String!, $activityLimit: Int!) {
         .next()
         .ok_or_else(|| {
             tracing::warn!(address = %address, "SFT vault not found for token details");
-            ApiError::NotFound("SFT vault not found for token".into())
+            ApiError::not_found("SFT vault not found for token")
         })
 }
 
@@ -910,7 +925,7 @@ pub async fn get_token_details(
                         address = %item.token.address,
                         "SFT vault not found for token details"
                     );
-                    ApiError::NotFound("SFT vault not found for token".into())
+                    ApiError::not_found("SFT vault not found for token")
                 })
                 .and_then(|vault| build_token_details_list_summary_response(&item.token, vault));
 
@@ -963,23 +978,27 @@ pub async fn get_token_details(
     ),
     responses(
         (status = 200, description = "ST0x token details and recent deposit/withdraw activity", body = TokenDetailsResponse),
+        (status = 400, description = "Invalid token address", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
         (status = 404, description = "Wrapped ST0x token or SFT vault not found", body = ApiErrorResponse),
-        (status = 422, description = "Invalid token address", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
     )
 )]
+#[allow(clippy::too_many_arguments)]
 #[get("/<address>/details?<params..>", rank = 10)]
 pub async fn get_token_details_by_address(
     _global: GlobalRateLimit,
     _key: AuthenticatedKey,
+    _query: KnownQueryParams<TokenDetailsQueryParams>,
     span: TracingSpan,
     shared_raindex: &State<SharedRaindexProvider>,
-    address: ValidatedAddress,
+    app_state: &State<ApplicationState>,
+    address: Result<ValidatedAddress, InvalidPathParam>,
     params: TokenDetailsQueryParams,
 ) -> Result<Json<TokenDetailsResponse>, ApiError> {
     async move {
+        let address = address.map_err(|e| e.reject("address"))?;
         tracing::info!(address = %address.0, "request received");
 
         let tokens = registry_tokens(shared_raindex).await?;
@@ -988,7 +1007,7 @@ pub async fn get_token_details_by_address(
             .find(|token| is_st0x_token(token) && matches_token_proof_address(token, address.0))
         else {
             tracing::warn!(address = %address.0, "wrapped ST0x token not found");
-            return Err(ApiError::NotFound("wrapped ST0x token not found".into()));
+            return Err(ApiError::not_found("wrapped ST0x token not found"));
         };
 
         let sft_subgraph_url = {
@@ -1005,8 +1024,38 @@ pub async fn get_token_details_by_address(
             "querying token details"
         );
 
-        let response =
-            read_token_details_response(token, &sft_subgraph_url, activity_limit).await?;
+        let caches = &app_state.response_caches;
+        let response = if !caches.lookups_enabled() {
+            read_token_details_response(token, &sft_subgraph_url, activity_limit).await?
+        } else if params.no_cache.unwrap_or(false) {
+            let response =
+                read_token_details_response(token, &sft_subgraph_url, activity_limit).await?;
+            caches
+                .token_details
+                .insert(
+                    token_details_lookup_cache_key(
+                        &sft_subgraph_url,
+                        token.address,
+                        activity_limit,
+                    ),
+                    response.clone(),
+                )
+                .await;
+            response
+        } else {
+            caches
+                .token_details
+                .get_or_try_insert(
+                    token_details_lookup_cache_key(
+                        &sft_subgraph_url,
+                        token.address,
+                        activity_limit,
+                    ),
+                    || read_token_details_response(token, &sft_subgraph_url, activity_limit),
+                )
+                .await
+                .map_err(|e| (*e).clone())?
+        };
 
         tracing::info!(
             wrapped_address = %token.address,
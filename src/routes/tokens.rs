@@ -3,11 +3,12 @@ use crate::db::wrapped_exchange_rate_history::{
     count_wrapped_exchange_rate_snapshots_for_share,
     list_wrapped_exchange_rate_snapshots_for_share, WrappedExchangeRateSnapshot,
 };
-use crate::db::DbPool;
+use crate::db::{Db, DbPool};
 use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
+use crate::fairings::{GlobalRateLimit, KnownQueryParams, TracingSpan};
 use crate::raindex::SharedRaindexProvider;
-use crate::types::common::ValidatedAddress;
+use crate::routes::pagination::{PageLinks, Paginated};
+use crate::types::common::{InvalidPathParam, ValidatedAddress};
 use crate::wrap_ratio::{
     build_wrap_ratio_response, find_wrap_ratio_item, is_st0x_token,
     persist_wrap_ratio_snapshots_best_effort, read_wrap_ratios_batch, unwrapped_address,
@@ -260,10 +261,8 @@ pub(super) async fn registry_tokens(
     let tokens = raindex
         .client()
         .get_all_tokens()
-        .map_err(token_lookup_error)?
-        .into_values()
-        .collect();
-    Ok(tokens)
+        .map_err(token_lookup_error)?;
+    Ok(crate::raindex::dedupe_tokens(tokens))
 }
 
 fn normalize_address(address: Address) -> String {
@@ -273,10 +272,8 @@ fn normalize_address(address: Address) -> String {
 fn wrap_ratio_history_pagination_params(
     params: WrapRatioHistoryParams,
 ) -> Result<(u32, u32, u32), ApiError> {
+    crate::routes::pagination::validate_page(params.page)?;
     let page = params.page.unwrap_or(1);
-    if page == 0 {
-        return Err(ApiError::BadRequest("page must be greater than 0".into()));
-    }
 
     let page_size = params.page_size.unwrap_or(20).min(100);
     if page_size == 0 {
@@ -313,10 +310,7 @@ fn wrap_ratio_history_event_from_snapshot(
 ) -> Result<WrapRatioHistorySnapshotEvent, ApiError> {
     Ok(WrapRatioHistorySnapshotEvent {
         event_type: "snapshot".to_string(),
-        block_number: snapshot
-            .block_number
-            .try_into()
-            .map_err(|_| ApiError::Internal("block number overflow".into()))?,
+        block_number: crate::block_number::block_number_u64(snapshot.block_number)?,
         block_timestamp: snapshot
             .block_timestamp
             .map(u64::try_from)
@@ -586,7 +580,7 @@ fn build_token_proofs_response(
 ) -> Result<TokenProofsResponse, ApiError> {
     let Some(vault) = sft.offchain_asset_receipt_vaults.first() else {
         tracing::warn!(address = %address, "SFT vault not found for token");
-        return Err(ApiError::NotFound("SFT vault not found for token".into()));
+        return Err(ApiError::not_found("SFT vault not found for token"));
     };
 
     let schemas = vault
@@ -654,10 +648,13 @@ pub(super) fn api_error_message(error: &ApiError) -> String {
         ApiError::BadRequest(message)
         | ApiError::Unauthorized(message)
         | ApiError::Forbidden(message)
-        | ApiError::NotFound(message)
+        | ApiError::ForbiddenAddress(message)
+        | ApiError::NotFound { message, .. }
         | ApiError::Internal(message)
+        | ApiError::ServiceUnavailable(message)
         | ApiError::RateLimited(message)
-        | ApiError::NotYetIndexed(message) => message.clone(),
+        | ApiError::TooManyConcurrent(message)
+        | ApiError::NotYetIndexed { message, .. } => message.clone(),
     }
 }
 
@@ -795,7 +792,10 @@ pub async fn get_tokens(
                 ApiError::Internal("failed to retrieve token list".into())
             })?;
 
-        let result: Vec<TokenResponse> = tokens.into_values().map(TokenResponse::from).collect();
+        let result: Vec<TokenResponse> = crate::raindex::dedupe_tokens(tokens)
+            .into_iter()
+            .map(TokenResponse::from)
+            .collect();
         tracing::info!(count = result.len(), "returning tokens");
         Ok(Json(result))
     }
@@ -916,9 +916,9 @@ pub async fn get_wrap_ratios(
     ),
     responses(
         (status = 200, description = "Wrapped token ratio", body = WrapRatioResponse),
+        (status = 400, description = "Invalid wrapped token address", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
         (status = 404, description = "Wrapped ST0x token not found", body = ApiErrorResponse),
-        (status = 422, description = "Invalid wrapped token address", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
     )
@@ -930,9 +930,10 @@ pub async fn get_wrap_ratio_by_address(
     span: TracingSpan,
     shared_raindex: &State<SharedRaindexProvider>,
     pool: &State<DbPool>,
-    address: ValidatedAddress,
+    address: Result<ValidatedAddress, InvalidPathParam>,
 ) -> Result<Json<WrapRatioResponse>, ApiError> {
     async move {
+        let address = address.map_err(|e| e.reject("address"))?;
         tracing::info!(share_address = %address.0, "request received");
 
         let tokens = registry_tokens(shared_raindex).await?;
@@ -941,7 +942,7 @@ pub async fn get_wrap_ratio_by_address(
             .find(|token| token.address == address.0 && is_st0x_token(token))
         else {
             tracing::warn!(share_address = %address.0, "wrapped ST0x token not found");
-            return Err(ApiError::NotFound("wrapped ST0x token not found".into()));
+            return Err(ApiError::not_found("wrapped ST0x token not found"));
         };
 
         let expected_asset_address = unwrapped_address(token).map_err(|error| {
@@ -1017,25 +1018,27 @@ pub async fn get_wrap_ratio_by_address(
     ),
     responses(
         (status = 200, description = "Wrapped token ratio snapshot history", body = WrapRatioHistoryResponse),
-        (status = 400, description = "Invalid pagination parameters", body = ApiErrorResponse),
+        (status = 400, description = "Invalid wrapped token address or pagination parameters", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
         (status = 404, description = "Wrapped ST0x token not found", body = ApiErrorResponse),
-        (status = 422, description = "Invalid wrapped token address", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
     )
 )]
+#[allow(clippy::too_many_arguments)]
 #[get("/wrap-ratio/<address>/history?<params..>")]
 pub async fn get_wrap_ratio_history_by_address(
     _global: GlobalRateLimit,
     _key: AuthenticatedKey,
+    _query: KnownQueryParams<WrapRatioHistoryParams>,
     span: TracingSpan,
     shared_raindex: &State<SharedRaindexProvider>,
-    pool: &State<DbPool>,
-    address: ValidatedAddress,
+    db: &State<Db>,
+    address: Result<ValidatedAddress, InvalidPathParam>,
     params: WrapRatioHistoryParams,
-) -> Result<Json<WrapRatioHistoryResponse>, ApiError> {
+) -> Result<Paginated<Json<WrapRatioHistoryResponse>>, ApiError> {
     async move {
+        let address = address.map_err(|e| e.reject("address"))?;
         tracing::info!(share_address = %address.0, "request received");
 
         let tokens = registry_tokens(shared_raindex).await?;
@@ -1044,7 +1047,7 @@ pub async fn get_wrap_ratio_history_by_address(
             .find(|token| token.address == address.0 && is_st0x_token(token))
         else {
             tracing::warn!(share_address = %address.0, "wrapped ST0x token not found");
-            return Err(ApiError::NotFound("wrapped ST0x token not found".into()));
+            return Err(ApiError::not_found("wrapped ST0x token not found"));
         };
 
         let asset_address = unwrapped_address(token).map_err(|error| {
@@ -1059,7 +1062,7 @@ pub async fn get_wrap_ratio_history_by_address(
         let (page, page_size, offset) = wrap_ratio_history_pagination_params(params)?;
         let share_token_address = normalize_address(token.address);
         let total_events =
-            count_wrapped_exchange_rate_snapshots_for_share(pool.inner(), &share_token_address)
+            count_wrapped_exchange_rate_snapshots_for_share(db.reads(), &share_token_address)
                 .await
                 .map_err(|error| {
                     tracing::error!(
@@ -1070,7 +1073,7 @@ pub async fn get_wrap_ratio_history_by_address(
                     ApiError::Internal("failed to query wrapped token ratio history".into())
                 })?;
         let snapshots = list_wrapped_exchange_rate_snapshots_for_share(
-            pool.inner(),
+            db.reads(),
             &share_token_address,
             page_size,
             offset,
@@ -1099,12 +1102,16 @@ pub async fn get_wrap_ratio_history_by_address(
             page_size,
             "returning wrapped token ratio history"
         );
-        Ok(Json(WrapRatioHistoryResponse {
-            share_address: token.address,
-            asset_address,
-            events,
-            pagination,
-        }))
+        let links = PageLinks::new(page, Some(pagination.total_pages), pagination.has_more);
+        Ok(Paginated::new(
+            Json(WrapRatioHistoryResponse {
+                share_address: token.address,
+                asset_address,
+                events,
+                pagination,
+            }),
+            links,
+        ))
     }
     .instrument(span.0)
     .await
@@ -1120,9 +1127,9 @@ pub async fn get_wrap_ratio_history_by_address(
     ),
     responses(
         (status = 200, description = "Raw ST0x proof metadata, schemas, and receipts", body = TokenProofsResponse),
+        (status = 400, description = "Invalid token address", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
         (status = 404, description = "Wrapped ST0x token or SFT vault not found", body = ApiErrorResponse),
-        (status = 422, description = "Invalid token address", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
     )
@@ -1133,9 +1140,10 @@ pub async fn get_token_proofs(
     _key: AuthenticatedKey,
     span: TracingSpan,
     shared_raindex: &State<SharedRaindexProvider>,
-    address: ValidatedAddress,
+    address: Result<ValidatedAddress, InvalidPathParam>,
 ) -> Result<Json<TokenProofsResponse>, ApiError> {
     async move {
+        let address = address.map_err(|e| e.reject("address"))?;
         tracing::info!(address = %address.0, "request received");
 
         let tokens = registry_tokens(shared_raindex).await?;
@@ -1144,7 +1152,7 @@ pub async fn get_token_proofs(
             .find(|token| is_st0x_token(token) && matches_token_proof_address(token, address.0))
         else {
             tracing::warn!(address = %address.0, "wrapped ST0x token not found");
-            return Err(ApiError::NotFound("wrapped ST0x token not found".into()));
+            return Err(ApiError::not_found("wrapped ST0x token not found"));
         };
 
         let (sft_subgraph_url, metadata_subgraph_url) = {
@@ -1189,7 +1197,9 @@ pub fn routes() -> Vec<Route> {
 
 #[cfg(test)]
 mod tests {
-    use super::token_details::clear_token_details_aggregate_cache;
+    use super::token_details::{
+        clear_token_details_aggregate_cache, token_details_lookup_cache_key,
+    };
     use super::{
         api_error_message, post_graphql, read_limited_response_body, SFT_PAGE_SIZE,
         SUBGRAPH_ERROR_BODY_LOG_LIMIT,
@@ -2455,7 +2465,7 @@ using-tokens-from:
         let response =
             authorized_get(&client, "/v1/tokens/not-an-address/proofs".to_string()).await;
 
-        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert_eq!(response.status(), Status::BadRequest);
     }
 
     #[rocket::async_test]
@@ -2572,6 +2582,45 @@ using-tokens-from:
         assert_eq!(transfer_page_request_count, 2);
     }
 
+    #[rocket::async_test]
+    async fn test_get_token_details_by_address_serves_repeat_lookups_from_cache() {
+        let _cache_guard = TOKEN_DETAILS_CACHE_TEST_LOCK.lock().await;
+        clear_token_details_aggregate_cache();
+        let (sft_url, requests) = mock_token_details_subgraph().await;
+        let client = token_details_client(&sft_url).await;
+        let detail_request_count = || {
+            requests
+                .lock()
+                .expect("mock requests")
+                .iter()
+                .filter(|request| request.contains("query TokenDetails("))
+                .count()
+        };
+
+        for _ in 0..2 {
+            let response =
+                authorized_get(&client, format!("/v1/tokens/{WT_MSTR:#x}/details")).await;
+            assert_eq!(response.status(), Status::Ok);
+        }
+        assert_eq!(detail_request_count(), 1);
+
+        let response = authorized_get(
+            &client,
+            format!("/v1/tokens/{WT_MSTR:#x}/details?noCache=true"),
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(detail_request_count(), 2);
+    }
+
+    #[test]
+    fn test_token_details_lookup_cache_key_includes_sft_subgraph() {
+        assert_ne!(
+            token_details_lookup_cache_key("https://a.example/sg", WT_MSTR, 5),
+            token_details_lookup_cache_key("https://b.example/sg", WT_MSTR, 5)
+        );
+    }
+
     #[rocket::async_test]
     async fn test_post_graphql_retries_retryable_status_then_succeeds() {
         let success_body = json!({
@@ -3181,7 +3230,7 @@ using-tokens-from:
             .dispatch()
             .await;
 
-        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert_eq!(response.status(), Status::BadRequest);
     }
 
     #[rocket::async_test]
@@ -3222,7 +3271,7 @@ using-tokens-from:
             .dispatch()
             .await;
 
-        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert_eq!(response.status(), Status::BadRequest);
     }
 
     #[rocket::async_test]
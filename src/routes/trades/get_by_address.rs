@@ -1,11 +1,14 @@
 use super::{
-    build_trades_list_response, trades_pagination_params, RaindexTradesDataSource, TradesDataSource,
+    build_trades_list_response, trades_list_json, trades_pagination_params,
+    RaindexTradesDataSource, TradesDataSource, TradesPage,
 };
+use crate::app_state::ApplicationState;
 use crate::auth::AuthenticatedKey;
 use crate::db::DbPool;
 use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
-use crate::types::common::ValidatedAddress;
+use crate::fairings::{GlobalRateLimit, KnownQueryParams, TracingSpan};
+use crate::routes::pagination::Paginated;
+use crate::types::common::{InvalidPathParam, ValidatedAddress};
 use crate::types::trades::{TradesByAddressResponse, TradesPaginationParams};
 use alloy::primitives::Address;
 use rain_orderbook_common::raindex_client::types::PaginationParams;
@@ -30,24 +33,48 @@ use tracing::Instrument;
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
     )
 )]
+#[allow(clippy::too_many_arguments)]
 #[get("/<address>?<params..>", rank = 2)]
 pub async fn get_trades_by_address(
     _global: GlobalRateLimit,
-    _key: AuthenticatedKey,
+    key: AuthenticatedKey,
+    _query: KnownQueryParams<TradesPaginationParams>,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    app_state: &State<ApplicationState>,
     pool: &State<DbPool>,
     span: TracingSpan,
-    address: ValidatedAddress,
+    address: Result<ValidatedAddress, InvalidPathParam>,
     params: TradesPaginationParams,
-) -> Result<Json<TradesByAddressResponse>, ApiError> {
+) -> Result<Paginated<Json<serde_json::Value>>, ApiError> {
     async move {
+        let address = address.map_err(|e| e.reject("address"))?;
         tracing::info!(address = ?address, params = ?params, "request received");
+        let mut params = params;
+        params.start_time = Some(super::bounded_start_time(
+            params.start_time,
+            params.end_time,
+            app_state.max_trade_window_secs,
+            super::unix_now(),
+        )?);
+        let display = params.display.unwrap_or(false);
+        let time_format = params.time_format.unwrap_or_default();
+        let denomination = params.denomination.unwrap_or_default();
+        let debug_raw = crate::debug_raw::debug_raw_enabled(params.debug_raw, &key);
         let raindex = shared_raindex.read().await;
+        raindex.ensure_orderbooks_configured()?;
         let ds = RaindexTradesDataSource {
             client: raindex.client(),
             pool: pool.inner(),
+            orderbook_count: raindex.orderbook_sources().len(),
+            subgraph_page_size: app_state.subgraph_page_size,
         };
-        process_get_trades_by_address(&ds, address.0, params).await
+        let (page, page_size, result) =
+            fetch_trades_by_address(&ds, address.0, params, app_state.page_size_defaults.trades)
+                .await?;
+        let raw_trades = debug_raw.then(|| result.trades.clone());
+        let response =
+            build_trades_list_response(&ds, result, page, page_size, denomination).await?;
+        trades_list_json(&response, display, time_format, raw_trades.as_deref())
     }
     .instrument(span.0)
     .await
@@ -57,9 +84,24 @@ pub(super) async fn process_get_trades_by_address(
     ds: &dyn TradesDataSource,
     owner: Address,
     params: TradesPaginationParams,
+    default_page_size: u16,
 ) -> Result<Json<TradesByAddressResponse>, ApiError> {
     let denomination = params.denomination.unwrap_or_default();
-    let (page, page_size, sdk_page, sdk_page_size, time_filter) = trades_pagination_params(params)?;
+    let (page, page_size, result) =
+        fetch_trades_by_address(ds, owner, params, default_page_size).await?;
+    build_trades_list_response(ds, result, page, page_size, denomination).await
+}
+
+/// Fetches the owner's trades with the page and page size the response
+/// reports, so the handler can reuse the fetched page for `debug_raw`.
+async fn fetch_trades_by_address(
+    ds: &dyn TradesDataSource,
+    owner: Address,
+    params: TradesPaginationParams,
+    default_page_size: u16,
+) -> Result<(u32, u32, TradesPage), ApiError> {
+    let (page, page_size, sdk_page, sdk_page_size, time_filter) =
+        trades_pagination_params(params, default_page_size)?;
 
     let result = ds
         .get_trades_for_owner(
@@ -71,8 +113,7 @@ pub(super) async fn process_get_trades_by_address(
             time_filter,
         )
         .await?;
-
-    build_trades_list_response(ds, result, page, page_size, denomination).await
+    Ok((page, page_size, result))
 }
 
 #[cfg(test)]
@@ -81,14 +122,18 @@ mod tests {
     use crate::error::ApiError;
     use crate::routes::order::test_fixtures::*;
     use crate::test_helpers::TestClientBuilder;
+    use crate::types::common::TimeFormat;
+    use crate::types::trades::TradesPagination;
     use alloy::primitives::{address, B256};
     use async_trait::async_trait;
     use rain_orderbook_common::raindex_client::trades::RaindexTradesListResult;
     use rain_orderbook_common::raindex_client::types::{PaginationParams, TimeFilter};
     use rocket::http::Status;
+    use rocket::response::Responder;
 
     struct MockTradesDataSource {
         owner_result: Result<RaindexTradesListResult, ApiError>,
+        requested_page_size: std::sync::Mutex<Option<u16>>,
     }
 
     #[async_trait]
@@ -103,11 +148,12 @@ mod tests {
         async fn get_trades_for_owner(
             &self,
             _owner: Address,
-            _pagination: PaginationParams,
+            pagination: PaginationParams,
             _time_filter: TimeFilter,
-        ) -> Result<RaindexTradesListResult, ApiError> {
+        ) -> Result<crate::routes::trades::TradesPage, ApiError> {
+            *self.requested_page_size.lock().unwrap() = pagination.page_size;
             match &self.owner_result {
-                Ok(r) => Ok(r.clone()),
+                Ok(r) => Ok(r.clone().into()),
                 Err(e) => Err(e.clone()),
             }
         }
@@ -144,10 +190,74 @@ mod tests {
         }
     }
 
+    #[rocket::async_test]
+    async fn test_process_uses_configured_default_page_size() {
+        let ds = MockTradesDataSource {
+            owner_result: Ok(mock_trades_list_result()),
+            requested_page_size: Default::default(),
+        };
+        let params = TradesPaginationParams {
+            page: None,
+            page_size: None,
+            start_time: None,
+            end_time: None,
+            denomination: None,
+            display: None,
+            time_format: None,
+            debug_raw: None,
+        };
+
+        let response = process_get_trades_by_address(
+            &ds,
+            address!("0000000000000000000000000000000000000001"),
+            params,
+            7,
+        )
+        .await
+        .unwrap()
+        .into_inner();
+
+        assert_eq!(*ds.requested_page_size.lock().unwrap(), Some(7));
+        assert_eq!(response.pagination.page_size, 7);
+    }
+
+    #[rocket::async_test]
+    async fn test_process_rejects_page_zero() {
+        let ds = MockTradesDataSource {
+            owner_result: Ok(mock_trades_list_result()),
+            requested_page_size: Default::default(),
+        };
+        let params = TradesPaginationParams {
+            page: Some(0),
+            page_size: Some(20),
+            start_time: None,
+            end_time: None,
+            denomination: None,
+            display: None,
+            time_format: None,
+            debug_raw: None,
+        };
+
+        let result = process_get_trades_by_address(
+            &ds,
+            address!("0000000000000000000000000000000000000001"),
+            params,
+            20,
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::BadRequest(msg)) if msg == "page must be >= 1"
+        ));
+        assert_eq!(*ds.requested_page_size.lock().unwrap(), None);
+    }
+
     #[rocket::async_test]
     async fn test_process_success() {
         let ds = MockTradesDataSource {
             owner_result: Ok(mock_trades_list_result()),
+            requested_page_size: Default::default(),
         };
         let params = TradesPaginationParams {
             page: Some(1),
@@ -155,11 +265,15 @@ mod tests {
             start_time: None,
             end_time: None,
             denomination: None,
+            display: None,
+            time_format: None,
+            debug_raw: None,
         };
         let result = process_get_trades_by_address(
             &ds,
             address!("0000000000000000000000000000000000000001"),
             params,
+            20,
         )
         .await
         .unwrap();
@@ -183,6 +297,7 @@ mod tests {
     async fn test_process_no_trades() {
         let ds = MockTradesDataSource {
             owner_result: Ok(mock_empty_trades_list_result()),
+            requested_page_size: Default::default(),
         };
         let params = TradesPaginationParams {
             page: Some(1),
@@ -190,11 +305,15 @@ mod tests {
             start_time: None,
             end_time: None,
             denomination: None,
+            display: None,
+            time_format: None,
+            debug_raw: None,
         };
         let result = process_get_trades_by_address(
             &ds,
             address!("0000000000000000000000000000000000000001"),
             params,
+            20,
         )
         .await
         .unwrap();
@@ -210,6 +329,7 @@ mod tests {
     async fn test_process_query_failure() {
         let ds = MockTradesDataSource {
             owner_result: Err(ApiError::Internal("subgraph error".into())),
+            requested_page_size: Default::default(),
         };
         let params = TradesPaginationParams {
             page: Some(1),
@@ -217,16 +337,57 @@ mod tests {
             start_time: None,
             end_time: None,
             denomination: None,
+            display: None,
+            time_format: None,
+            debug_raw: None,
         };
         let result = process_get_trades_by_address(
             &ds,
             address!("0000000000000000000000000000000000000001"),
             params,
+            20,
         )
         .await;
         assert!(matches!(result, Err(ApiError::Internal(_))));
     }
 
+    async fn link_header(page: u32, total_pages: u64) -> Option<String> {
+        let client = TestClientBuilder::new().build().await;
+        let request = client.get(format!(
+            "/v1/trades/0x0000000000000000000000000000000000000001?page={page}&pageSize=20"
+        ));
+        let response = TradesByAddressResponse {
+            trades: vec![],
+            pagination: TradesPagination {
+                page,
+                page_size: 20,
+                total_trades: total_pages * 20,
+                total_pages,
+                has_more: u64::from(page) < total_pages,
+            },
+        };
+        let rendered = trades_list_json(&response, false, TimeFormat::Unix, None)
+            .unwrap()
+            .respond_to(request.inner())
+            .unwrap();
+        rendered.headers().get_one("Link").map(str::to_string)
+    }
+
+    #[rocket::async_test]
+    async fn test_link_header_has_next_only_while_more_pages_remain() {
+        let first = link_header(1, 3).await.expect("link header");
+        assert!(first.contains(
+            "</v1/trades/0x0000000000000000000000000000000000000001?pageSize=20&page=2>; rel=\"next\""
+        ));
+        assert!(first.contains("&page=3>; rel=\"last\""));
+        assert!(!first.contains("rel=\"prev\""));
+
+        let last = link_header(3, 3).await.expect("link header");
+        assert!(!last.contains("rel=\"next\""));
+        assert!(last.contains("&page=2>; rel=\"prev\""));
+        assert!(last.contains("&page=1>; rel=\"first\""));
+    }
+
     #[rocket::async_test]
     async fn test_401_without_auth() {
         let client = TestClientBuilder::new().build().await;
@@ -236,4 +397,63 @@ mod tests {
             .await;
         assert_eq!(response.status(), Status::Unauthorized);
     }
+
+    #[rocket::async_test]
+    async fn test_non_hex_address_returns_400() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = crate::test_helpers::seed_api_key(&client).await;
+        let header = crate::test_helpers::basic_auth_header(&key_id, &secret);
+        let response = client
+            .get("/v1/trades/0xZZZZ89fCD6eDb6E08f4c7C32D4f71b54bdA02913")
+            .header(rocket::http::Header::new("Authorization", header))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::BadRequest);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(body["error"]["code"], "BAD_REQUEST");
+        assert_eq!(
+            body["error"]["message"],
+            "invalid address: expected 20-byte hex"
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_strict_mode_rejects_misspelled_page_size() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = crate::test_helpers::seed_api_key(&client).await;
+        let header = crate::test_helpers::basic_auth_header(&key_id, &secret);
+        let response = client
+            .get("/v1/trades/0x0000000000000000000000000000000000000001?page_size=5&strict=true")
+            .header(rocket::http::Header::new("Authorization", header))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::BadRequest);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert_eq!(body["error"]["code"], "BAD_REQUEST");
+        assert_eq!(
+            body["error"]["message"],
+            "unknown query parameters: page_size"
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_time_range_beyond_limit_returns_400() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = crate::test_helpers::seed_api_key(&client).await;
+        let header = crate::test_helpers::basic_auth_header(&key_id, &secret);
+        let response = client
+            .get("/v1/trades/0x0000000000000000000000000000000000000001?startTime=0&endTime=31622400")
+            .header(rocket::http::Header::new("Authorization", header))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::BadRequest);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+        assert!(body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("time range must not exceed 365 days"));
+    }
 }
@@ -1,10 +1,11 @@
 use super::{
     current_wrap_ratios_for_trades, map_trade_for_list, RaindexTradesDataSource, TradesDataSource,
 };
+use crate::app_state::ApplicationState;
 use crate::auth::AuthenticatedKey;
 use crate::db::DbPool;
 use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
+use crate::fairings::{GlobalRateLimit, JsonContentType, TracingSpan};
 use crate::types::common::Denomination;
 use crate::types::trades::{
     TradesByOrderHashEntry, TradesByOrderHashesRequest, TradesByOrderHashesResponse,
@@ -31,30 +32,41 @@ use tracing::Instrument;
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
     )
 )]
+#[allow(clippy::too_many_arguments)]
 #[post("/query", data = "<request>")]
 pub async fn get_trades_by_order_hashes(
     _global: GlobalRateLimit,
     _key: AuthenticatedKey,
+    _json: JsonContentType,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    app_state: &State<ApplicationState>,
     pool: &State<DbPool>,
     span: TracingSpan,
     request: Json<TradesByOrderHashesRequest>,
 ) -> Result<Json<TradesByOrderHashesResponse>, ApiError> {
     async move {
-        let request = request.into_inner();
+        let mut request = request.into_inner();
         tracing::info!(
             order_hashes_count = request.order_hashes.len(),
             start_time = request.start_time,
             end_time = request.end_time,
             "request received"
         );
-        let client = {
+        request.start_time = Some(super::bounded_start_time(
+            request.start_time,
+            request.end_time,
+            app_state.max_trade_window_secs,
+            super::unix_now(),
+        )?);
+        let (client, orderbook_count) = {
             let raindex = shared_raindex.read().await;
-            raindex.client().clone()
+            (raindex.client().clone(), raindex.orderbook_sources().len())
         };
         let ds = RaindexTradesDataSource {
             client: &client,
             pool: pool.inner(),
+            orderbook_count,
+            subgraph_page_size: app_state.subgraph_page_size,
         };
         process_get_trades_by_order_hashes(&ds, request).await
     }
@@ -166,7 +178,7 @@ mod tests {
             _owner: Address,
             _pagination: PaginationParams,
             _time_filter: TimeFilter,
-        ) -> Result<RaindexTradesListResult, ApiError> {
+        ) -> Result<crate::routes::trades::TradesPage, ApiError> {
             unimplemented!()
         }
 
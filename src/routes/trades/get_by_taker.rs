@@ -5,8 +5,9 @@ use crate::app_state::ApplicationState;
 use crate::auth::AuthenticatedKey;
 use crate::db::DbPool;
 use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
-use crate::types::common::ValidatedAddress;
+use crate::fairings::{GlobalRateLimit, KnownQueryParams, TracingSpan};
+use crate::routes::pagination::Paginated;
+use crate::types::common::{InvalidPathParam, ValidatedAddress};
 use crate::types::trades::{TradesByAddressResponse, TradesPaginationParams};
 use alloy::primitives::Address;
 use rocket::serde::json::Json;
@@ -25,7 +26,6 @@ use tracing::Instrument;
     responses(
         (status = 200, description = "Paginated list of trades for taker", body = TradesByAddressResponse),
         (status = 400, description = "Bad request", body = ApiErrorResponse),
-        (status = 422, description = "Unprocessable entity", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
@@ -36,48 +36,67 @@ use tracing::Instrument;
 pub async fn get_trades_by_taker(
     _global: GlobalRateLimit,
     _key: AuthenticatedKey,
+    _query: KnownQueryParams<TradesPaginationParams>,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
     app_state: &State<ApplicationState>,
     pool: &State<DbPool>,
     span: TracingSpan,
-    address: ValidatedAddress,
+    address: Result<ValidatedAddress, InvalidPathParam>,
     params: TradesPaginationParams,
-) -> Result<Json<TradesByAddressResponse>, ApiError> {
+) -> Result<Paginated<Json<serde_json::Value>>, ApiError> {
     async move {
+        let address = address.map_err(|e| e.reject("address"))?;
         tracing::info!(address = ?address, params = ?params, "request received");
+        let mut params = params;
+        params.start_time = Some(super::bounded_start_time(
+            params.start_time,
+            params.end_time,
+            app_state.max_trade_window_secs,
+            super::unix_now(),
+        )?);
+        let display = params.display.unwrap_or(false);
+        let time_format = params.time_format.unwrap_or_default();
         let addr = address.0;
+        let default_page_size = app_state.page_size_defaults.trades;
         if !app_state.response_caches.is_enabled() {
-            let client = {
+            let (client, orderbook_count) = {
                 let raindex = shared_raindex.read().await;
-                raindex.client().clone()
+                (raindex.client().clone(), raindex.orderbook_sources().len())
             };
             let ds = RaindexTradesDataSource {
                 client: &client,
                 pool: pool.inner(),
+                orderbook_count,
+                subgraph_page_size: app_state.subgraph_page_size,
             };
-            return process_get_trades_by_taker(&ds, addr, params).await;
+            let response =
+                process_get_trades_by_taker(&ds, addr, params, default_page_size).await?;
+            return super::trades_list_json(&response, display, time_format, None);
         }
 
-        let cache_key = super::get_by_token::trades_cache_key("trades/taker", addr, &params);
+        let cache_key =
+            super::get_by_token::trades_cache_key("trades/taker", addr, &params, default_page_size);
         let response = app_state
             .response_caches
             .trades_by_taker
             .get_or_try_insert(cache_key, || async move {
-                let client = {
+                let (client, orderbook_count) = {
                     let raindex = shared_raindex.read().await;
-                    raindex.client().clone()
+                    (raindex.client().clone(), raindex.orderbook_sources().len())
                 };
                 let ds = RaindexTradesDataSource {
                     client: &client,
                     pool: pool.inner(),
+                    orderbook_count,
+                    subgraph_page_size: app_state.subgraph_page_size,
                 };
-                process_get_trades_by_taker(&ds, addr, params)
+                process_get_trades_by_taker(&ds, addr, params, default_page_size)
                     .await
                     .map(Json::into_inner)
             })
             .await
             .map_err(|e| (*e).clone())?;
-        Ok(Json(response))
+        super::trades_list_json(&response, display, time_format, None)
     }
     .instrument(span.0)
     .await
@@ -87,16 +106,18 @@ pub(super) async fn process_get_trades_by_taker(
     ds: &dyn TradesDataSource,
     taker: Address,
     params: TradesPaginationParams,
+    default_page_size: u16,
 ) -> Result<Json<TradesByAddressResponse>, ApiError> {
     let denomination = params.denomination.unwrap_or_default();
-    let (page, page_size, sdk_page, sdk_page_size, time_filter) = trades_pagination_params(params)?;
+    let (page, page_size, sdk_page, sdk_page_size, time_filter) =
+        trades_pagination_params(params, default_page_size)?;
 
     tracing::info!(taker = ?taker, page, page_size, "querying trades by taker");
     let result = ds
         .get_trades_for_taker(taker, sdk_page, sdk_page_size, time_filter)
         .await?;
 
-    build_trades_list_response(ds, result, page, page_size, denomination).await
+    build_trades_list_response(ds, result.into(), page, page_size, denomination).await
 }
 
 #[cfg(test)]
@@ -141,7 +162,7 @@ mod tests {
             _owner: Address,
             _pagination: PaginationParams,
             _time_filter: TimeFilter,
-        ) -> Result<RaindexTradesListResult, ApiError> {
+        ) -> Result<crate::routes::trades::TradesPage, ApiError> {
             unimplemented!()
         }
 
@@ -200,8 +221,11 @@ mod tests {
             start_time: Some(1700000000),
             end_time: Some(1700002000),
             denomination: None,
+            display: None,
+            time_format: None,
+            debug_raw: None,
         };
-        let result = process_get_trades_by_taker(&ds, taker, params)
+        let result = process_get_trades_by_taker(&ds, taker, params, 20)
             .await
             .unwrap();
 
@@ -241,11 +265,15 @@ mod tests {
             start_time: None,
             end_time: None,
             denomination: None,
+            display: None,
+            time_format: None,
+            debug_raw: None,
         };
         let result = process_get_trades_by_taker(
             &ds,
             address!("cccccccccccccccccccccccccccccccccccccccc"),
             params,
+            20,
         )
         .await
         .unwrap();
@@ -269,11 +297,15 @@ mod tests {
             start_time: None,
             end_time: None,
             denomination: None,
+            display: None,
+            time_format: None,
+            debug_raw: None,
         };
         let result = process_get_trades_by_taker(
             &ds,
             address!("cccccccccccccccccccccccccccccccccccccccc"),
             params,
+            20,
         )
         .await;
         assert!(matches!(result, Err(ApiError::Internal(_))));
@@ -290,7 +322,7 @@ mod tests {
     }
 
     #[rocket::async_test]
-    async fn test_invalid_address_returns_422() {
+    async fn test_invalid_address_returns_400() {
         let client = TestClientBuilder::new().build().await;
         let (key_id, secret) = seed_api_key(&client).await;
         let header = basic_auth_header(&key_id, &secret);
@@ -299,7 +331,7 @@ mod tests {
             .header(Header::new("Authorization", header))
             .dispatch()
             .await;
-        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert_eq!(response.status(), Status::BadRequest);
     }
 
     #[test]
@@ -5,8 +5,9 @@ use crate::app_state::ApplicationState;
 use crate::auth::AuthenticatedKey;
 use crate::db::DbPool;
 use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
-use crate::types::common::ValidatedAddress;
+use crate::fairings::{GlobalRateLimit, KnownQueryParams, TracingSpan};
+use crate::routes::pagination::Paginated;
+use crate::types::common::{InvalidPathParam, ValidatedAddress};
 use crate::types::trades::{TradesByAddressResponse, TradesPaginationParams};
 use alloy::primitives::Address;
 use rocket::serde::json::Json;
@@ -25,7 +26,6 @@ use tracing::Instrument;
     responses(
         (status = 200, description = "Paginated list of trades for token", body = TradesByAddressResponse),
         (status = 400, description = "Bad request", body = ApiErrorResponse),
-        (status = 422, description = "Unprocessable entity", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
@@ -36,26 +36,42 @@ use tracing::Instrument;
 pub async fn get_trades_by_token(
     _global: GlobalRateLimit,
     _key: AuthenticatedKey,
+    _query: KnownQueryParams<TradesPaginationParams>,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
     app_state: &State<ApplicationState>,
     pool: &State<DbPool>,
     span: TracingSpan,
-    address: ValidatedAddress,
+    address: Result<ValidatedAddress, InvalidPathParam>,
     params: TradesPaginationParams,
-) -> Result<Json<TradesByAddressResponse>, ApiError> {
+) -> Result<Paginated<Json<serde_json::Value>>, ApiError> {
     async move {
+        let address = address.map_err(|e| e.reject("address"))?;
         tracing::info!(address = ?address, params = ?params, "request received");
+        let mut params = params;
+        params.start_time = Some(super::bounded_start_time(
+            params.start_time,
+            params.end_time,
+            app_state.max_trade_window_secs,
+            super::unix_now(),
+        )?);
+        let display = params.display.unwrap_or(false);
+        let time_format = params.time_format.unwrap_or_default();
         let addr = address.0;
+        let default_page_size = app_state.page_size_defaults.trades;
         if !app_state.response_caches.is_enabled() {
             let raindex = shared_raindex.read().await;
             let ds = RaindexTradesDataSource {
                 client: raindex.client(),
                 pool: pool.inner(),
+                orderbook_count: raindex.orderbook_sources().len(),
+                subgraph_page_size: app_state.subgraph_page_size,
             };
-            return process_get_trades_by_token(&ds, addr, params).await;
+            let response =
+                process_get_trades_by_token(&ds, addr, params, default_page_size).await?;
+            return super::trades_list_json(&response, display, time_format, None);
         }
 
-        let cache_key = trades_cache_key("trades/token", addr, &params);
+        let cache_key = trades_cache_key("trades/token", addr, &params, default_page_size);
         let response = app_state
             .response_caches
             .trades_by_token
@@ -64,14 +80,16 @@ pub async fn get_trades_by_token(
                 let ds = RaindexTradesDataSource {
                     client: raindex.client(),
                     pool: pool.inner(),
+                    orderbook_count: raindex.orderbook_sources().len(),
+                    subgraph_page_size: app_state.subgraph_page_size,
                 };
-                process_get_trades_by_token(&ds, addr, params)
+                process_get_trades_by_token(&ds, addr, params, default_page_size)
                     .await
                     .map(Json::into_inner)
             })
             .await
             .map_err(|e| (*e).clone())?;
-        Ok(Json(response))
+        super::trades_list_json(&response, display, time_format, None)
     }
     .instrument(span.0)
     .await
@@ -81,12 +99,13 @@ pub(super) fn trades_cache_key(
     route: &str,
     address: Address,
     params: &TradesPaginationParams,
+    default_page_size: u16,
 ) -> String {
     format!(
         "{route}/{}/{}/{}/{}/{}/{:?}",
         address.to_string().to_ascii_lowercase(),
         params.page.unwrap_or(1),
-        params.page_size.unwrap_or(20),
+        params.page_size.unwrap_or(u32::from(default_page_size)),
         params
             .start_time
             .map(|value| value.to_string())
@@ -103,16 +122,18 @@ pub(super) async fn process_get_trades_by_token(
     ds: &dyn TradesDataSource,
     token: Address,
     params: TradesPaginationParams,
+    default_page_size: u16,
 ) -> Result<Json<TradesByAddressResponse>, ApiError> {
     let denomination = params.denomination.unwrap_or_default();
-    let (page, page_size, sdk_page, sdk_page_size, time_filter) = trades_pagination_params(params)?;
+    let (page, page_size, sdk_page, sdk_page_size, time_filter) =
+        trades_pagination_params(params, default_page_size)?;
 
     tracing::info!(token = ?token, page, page_size, "querying trades by token");
     let result = ds
         .get_trades_for_token(token, sdk_page, sdk_page_size, time_filter)
         .await?;
 
-    build_trades_list_response(ds, result, page, page_size, denomination).await
+    build_trades_list_response(ds, result.into(), page, page_size, denomination).await
 }
 
 #[cfg(test)]
@@ -147,7 +168,7 @@ mod tests {
             _owner: Address,
             _pagination: PaginationParams,
             _time_filter: TimeFilter,
-        ) -> Result<RaindexTradesListResult, ApiError> {
+        ) -> Result<crate::routes::trades::TradesPage, ApiError> {
             unimplemented!()
         }
 
@@ -197,11 +218,15 @@ mod tests {
             start_time: None,
             end_time: None,
             denomination: None,
+            display: None,
+            time_format: None,
+            debug_raw: None,
         };
         let result = process_get_trades_by_token(
             &ds,
             address!("833589fcd6edb6e08f4c7c32d4f71b54bda02913"),
             params,
+            20,
         )
         .await
         .unwrap();
@@ -233,6 +258,9 @@ mod tests {
             start_time: None,
             end_time: None,
             denomination: None,
+            display: None,
+            time_format: None,
+            debug_raw: None,
         };
         let explicit_params = TradesPaginationParams {
             page: Some(1),
@@ -240,11 +268,14 @@ mod tests {
             start_time: None,
             end_time: None,
             denomination: Some(crate::types::common::Denomination::Wrapped),
+            display: None,
+            time_format: None,
+            debug_raw: None,
         };
 
         assert_eq!(
-            trades_cache_key("trades/token", lower, &default_params),
-            trades_cache_key("trades/token", mixed, &explicit_params)
+            trades_cache_key("trades/token", lower, &default_params, 20),
+            trades_cache_key("trades/token", mixed, &explicit_params, 20)
         );
     }
 
@@ -259,11 +290,15 @@ mod tests {
             start_time: None,
             end_time: None,
             denomination: None,
+            display: None,
+            time_format: None,
+            debug_raw: None,
         };
         let result = process_get_trades_by_token(
             &ds,
             address!("833589fcd6edb6e08f4c7c32d4f71b54bda02913"),
             params,
+            20,
         )
         .await
         .unwrap();
@@ -286,11 +321,15 @@ mod tests {
             start_time: None,
             end_time: None,
             denomination: None,
+            display: None,
+            time_format: None,
+            debug_raw: None,
         };
         let result = process_get_trades_by_token(
             &ds,
             address!("833589fcd6edb6e08f4c7c32d4f71b54bda02913"),
             params,
+            20,
         )
         .await;
         assert!(matches!(result, Err(ApiError::Internal(_))));
@@ -307,7 +346,7 @@ mod tests {
     }
 
     #[rocket::async_test]
-    async fn test_invalid_address_returns_422() {
+    async fn test_invalid_address_returns_400() {
         let client = TestClientBuilder::new().build().await;
         let (key_id, secret) = seed_api_key(&client).await;
         let header = basic_auth_header(&key_id, &secret);
@@ -316,6 +355,6 @@ mod tests {
             .header(Header::new("Authorization", header))
             .dispatch()
             .await;
-        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert_eq!(response.status(), Status::BadRequest);
     }
 }
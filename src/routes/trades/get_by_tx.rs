@@ -1,17 +1,19 @@
 use super::{
-    current_wrap_ratios_for_trades, trade_block_number, wrap_ratio_map_for_trade,
-    RaindexTradesDataSource, TradesDataSource,
+    current_wrap_ratios_for_trades, dedupe_trades_by_id, trade_block_number,
+    wrap_ratio_map_for_trade, RaindexTradesDataSource, TradesDataSource,
 };
+use crate::app_state::ApplicationState;
 use crate::auth::AuthenticatedKey;
 use crate::db::DbPool;
-use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
-use crate::types::common::{Denomination, ValidatedFixedBytes};
+use crate::error::{ApiError, ApiErrorResponse, NotFoundReason};
+use crate::fairings::{GlobalRateLimit, KnownQueryParams, TracingSpan};
+use crate::types::common::{Denomination, InvalidPathParam, ValidatedFixedBytes};
 use crate::types::trades::{
     TradeByTxEntry, TradeRequest, TradeResult, TradesByTxParams, TradesByTxResponse, TradesTotals,
 };
 use alloy::primitives::{Address, B256};
 use rain_math_float::Float;
+use rain_orderbook_common::raindex_client::trades::RaindexTradesListResult;
 use rocket::serde::json::Json;
 use rocket::State;
 use std::ops::{Add, Div, Sub};
@@ -29,35 +31,54 @@ use tracing::Instrument;
     responses(
         (status = 200, description = "Trades from transaction", body = TradesByTxResponse),
         (status = 202, description = "Transaction not yet indexed", body = ApiErrorResponse),
+        (status = 400, description = "Invalid transaction hash", body = ApiErrorResponse),
         (status = 401, description = "Unauthorized", body = ApiErrorResponse),
         (status = 429, description = "Rate limited", body = ApiErrorResponse),
         (status = 404, description = "Transaction not found", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse),
     )
 )]
+#[allow(clippy::too_many_arguments)]
 #[get("/tx/<tx_hash>?<params..>")]
 pub async fn get_trades_by_tx(
     _global: GlobalRateLimit,
-    _key: AuthenticatedKey,
+    key: AuthenticatedKey,
+    _query: KnownQueryParams<TradesByTxParams>,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    app_state: &State<ApplicationState>,
     pool: &State<DbPool>,
     span: TracingSpan,
-    tx_hash: ValidatedFixedBytes,
+    tx_hash: Result<ValidatedFixedBytes, InvalidPathParam>,
     params: TradesByTxParams,
-) -> Result<Json<TradesByTxResponse>, ApiError> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     async move {
+        let tx_hash = tx_hash.map_err(|e| e.reject("transaction hash"))?;
         tracing::info!(tx_hash = ?tx_hash, params = ?params, "request received");
         let raindex = shared_raindex.read().await;
+        raindex.ensure_orderbooks_configured()?;
         let trades_ds = RaindexTradesDataSource {
             client: raindex.client(),
             pool: pool.inner(),
+            orderbook_count: raindex.orderbook_sources().len(),
+            subgraph_page_size: app_state.subgraph_page_size,
         };
-        process_get_trades_by_tx(
+        let result = trades_ds.get_trades_by_tx(tx_hash.0).await?;
+        let response = trades_by_tx_response(
             &trades_ds,
             tx_hash.0,
             params.denomination.unwrap_or_default(),
+            &result,
         )
-        .await
+        .await?;
+        let mut body = serde_json::to_value(response).map_err(|e| {
+            tracing::error!(error = %e, "failed to serialize trades response");
+            ApiError::Internal("failed to serialize response".into())
+        })?;
+        crate::timestamps::apply_time_format(&mut body, params.time_format.unwrap_or_default());
+        if crate::debug_raw::debug_raw_enabled(params.debug_raw, &key) {
+            crate::debug_raw::attach_raw(&mut body, &result.trades());
+        }
+        Ok(Json(body))
     }
     .instrument(span.0)
     .await
@@ -69,25 +90,38 @@ pub(super) async fn process_get_trades_by_tx(
     denomination: Denomination,
 ) -> Result<Json<TradesByTxResponse>, ApiError> {
     let result = trades_ds.get_trades_by_tx(tx_hash).await?;
-    let trades = result.trades();
+    trades_by_tx_response(trades_ds, tx_hash, denomination, &result)
+        .await
+        .map(Json)
+}
+
+/// Builds the response for trades already fetched for `tx_hash`, so the
+/// handler can reuse the same subgraph result for `debug_raw`.
+async fn trades_by_tx_response(
+    trades_ds: &dyn TradesDataSource,
+    tx_hash: B256,
+    denomination: Denomination,
+    result: &RaindexTradesListResult,
+) -> Result<TradesByTxResponse, ApiError> {
+    let trades = dedupe_trades_by_id(result.trades().to_vec());
+    let had_duplicates = trades.len() < result.trades().len();
 
     if trades.is_empty() {
-        return Err(ApiError::NotFound(
-            "transaction has no associated trades".into(),
+        return Err(ApiError::not_found_because(
+            NotFoundReason::NoTrades,
+            "transaction has no associated trades",
         ));
     }
 
     let first_tx = trades[0].transaction();
-    let block_number: u64 = first_tx.block_number().try_into().map_err(|_| {
-        tracing::error!("block number does not fit in u64");
-        ApiError::Internal("block number overflow".into())
-    })?;
+    let block_number = trade_block_number(&trades[0])?;
     let timestamp: u64 = first_tx.timestamp().try_into().map_err(|_| {
         tracing::error!("timestamp does not fit in u64");
         ApiError::Internal("timestamp overflow".into())
     })?;
     let sender: Address = first_tx.from();
-    let trade_wrap_ratios = current_wrap_ratios_for_trades(trades_ds, denomination, trades).await?;
+    let trade_wrap_ratios =
+        current_wrap_ratios_for_trades(trades_ds, denomination, &trades).await?;
 
     let trade_entries: Vec<TradeByTxEntry> = trades
         .iter()
@@ -154,14 +188,15 @@ pub(super) async fn process_get_trades_by_tx(
         })
         .collect::<Result<Vec<_>, ApiError>>()?;
 
-    let summary = result.summary().and_then(|s| s.first()).ok_or_else(|| {
-        tracing::error!("no pair summary in trades result");
-        ApiError::Internal("missing pair summary".into())
-    })?;
-
-    let totals = if denomination == Denomination::Unwrapped {
+    // The subgraph summary counts duplicate trades too, so totals are rebuilt
+    // from the de-duplicated entries whenever any were dropped.
+    let totals = if denomination == Denomination::Unwrapped || had_duplicates {
         totals_from_trade_entries(&trade_entries)?
     } else {
+        let summary = result.summary().and_then(|s| s.first()).ok_or_else(|| {
+            tracing::error!("no pair summary in trades result");
+            ApiError::Internal("missing pair summary".into())
+        })?;
         TradesTotals {
             total_input_amount: summary.formatted_total_input().to_string(),
             total_output_amount: summary.formatted_total_output().to_string(),
@@ -169,14 +204,14 @@ pub(super) async fn process_get_trades_by_tx(
         }
     };
 
-    Ok(Json(TradesByTxResponse {
+    Ok(TradesByTxResponse {
         tx_hash,
         block_number,
         timestamp,
         sender,
         trades: trade_entries,
         totals,
-    }))
+    })
 }
 
 fn totals_from_trade_entries(trades: &[TradeByTxEntry]) -> Result<TradesTotals, ApiError> {
@@ -281,7 +316,7 @@ mod tests {
             _owner: Address,
             _pagination: PaginationParams,
             _time_filter: TimeFilter,
-        ) -> Result<RaindexTradesListResult, ApiError> {
+        ) -> Result<crate::routes::trades::TradesPage, ApiError> {
             unimplemented!()
         }
 
@@ -389,6 +424,47 @@ mod tests {
         assert_eq!(response.totals.average_io_ratio, "1");
     }
 
+    #[rocket::async_test]
+    async fn test_process_counts_duplicate_trade_ids_once() {
+        let duplicated: RaindexTradesListResult = serde_json::from_value(serde_json::json!({
+            "trades": [trade_json(), trade_json()],
+            "totalCount": 2,
+            "summary": [{
+                "chainId": 8453,
+                "inputToken": "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913",
+                "outputToken": "0x4200000000000000000000000000000000000006",
+                "totalInput": "0xffffffff0000000000000000000000000000000000000000000000000000000a",
+                "formattedTotalInput": "1.000000",
+                "totalOutput": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "formattedTotalOutput": "-0.500000000000000000",
+                "averageIoRatio": "0xffffffff00000000000000000000000000000000000000000000000000000005",
+                "formattedAverageIoRatio": "2.0",
+                "tradeCount": 2
+            }]
+        }))
+        .unwrap();
+        let trades_ds = MockTradesDataSource {
+            result: Ok(duplicated),
+            current_wrap_ratios: Default::default(),
+        };
+
+        let response = process_get_trades_by_tx(
+            &trades_ds,
+            "0x0000000000000000000000000000000000000000000000000000000000000088"
+                .parse()
+                .unwrap(),
+            Denomination::Wrapped,
+        )
+        .await
+        .unwrap()
+        .into_inner();
+
+        assert_eq!(response.trades.len(), 1);
+        assert_eq!(response.totals.total_input_amount, "0.5");
+        assert_eq!(response.totals.total_output_amount, "0.25");
+        assert_eq!(response.totals.average_io_ratio, "2");
+    }
+
     #[rocket::async_test]
     async fn test_process_tx_not_found() {
         let trades_ds = MockTradesDataSource {
@@ -403,13 +479,22 @@ mod tests {
             Denomination::Wrapped,
         )
         .await;
-        assert!(matches!(result, Err(ApiError::NotFound(_))));
+        assert!(matches!(
+            result,
+            Err(ApiError::NotFound {
+                reason: Some(NotFoundReason::NoTrades),
+                ..
+            })
+        ));
     }
 
     #[rocket::async_test]
     async fn test_process_tx_not_indexed() {
         let trades_ds = MockTradesDataSource {
-            result: Err(ApiError::NotYetIndexed("not indexed".into())),
+            result: Err(ApiError::NotYetIndexed {
+                message: "not indexed".into(),
+                attempts: 3,
+            }),
             current_wrap_ratios: Default::default(),
         };
         let result = process_get_trades_by_tx(
@@ -420,7 +505,7 @@ mod tests {
             Denomination::Wrapped,
         )
         .await;
-        assert!(matches!(result, Err(ApiError::NotYetIndexed(_))));
+        assert!(matches!(result, Err(ApiError::NotYetIndexed { .. })));
     }
 
     #[rocket::async_test]
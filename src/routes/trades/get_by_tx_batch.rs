@@ -0,0 +1,576 @@
+use super::get_by_tx::process_get_trades_by_tx;
+use super::{RaindexTradesDataSource, TradesDataSource};
+use crate::app_state::ApplicationState;
+use crate::auth::AuthenticatedKey;
+use crate::db::DbPool;
+use crate::error::{ApiError, ApiErrorResponse};
+use crate::fairings::{GlobalRateLimit, JsonContentType, TracingSpan};
+use crate::routes::tokens::api_error_message;
+use crate::types::trades::{
+    TradesByTxBatchEntry, TradesByTxBatchRequest, TradesByTxBatchResponse, TradesByTxBatchStatus,
+    TradesByTxResponse,
+};
+use alloy::primitives::B256;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use futures::{stream, StreamExt};
+use rocket::serde::json::Json;
+use rocket::State;
+use std::collections::{BTreeMap, HashSet};
+use std::str::FromStr;
+use tracing::Instrument;
+
+/// Most transaction hashes accepted in one batch request.
+pub(crate) const MAX_TX_BATCH_SIZE: usize = 50;
+
+/// Serialized size the batch results may reach before the rest of the
+/// hashes are deferred to a continuation.
+pub(crate) const MAX_TX_BATCH_RESPONSE_BYTES: usize = 2 * 1024 * 1024;
+
+#[utoipa::path(
+    post,
+    path = "/v1/trades/tx/batch",
+    tag = "Trades",
+    security(("basicAuth" = [])),
+    request_body = TradesByTxBatchRequest,
+    responses(
+        (status = 200, description = "Per-transaction trades or resolution status", body = TradesByTxBatchResponse),
+        (status = 400, description = "Bad request", body = ApiErrorResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 429, description = "Rate limited", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+#[post("/tx/batch", data = "<request>")]
+pub async fn get_trades_by_tx_batch(
+    _global: GlobalRateLimit,
+    _key: AuthenticatedKey,
+    _json: JsonContentType,
+    shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
+    app_state: &State<ApplicationState>,
+    pool: &State<DbPool>,
+    span: TracingSpan,
+    request: Json<TradesByTxBatchRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    async move {
+        let request = request.into_inner();
+        let time_format = request.time_format.unwrap_or_default();
+        tracing::info!(
+            tx_hashes_count = request.tx_hashes.len(),
+            denomination = ?request.denomination,
+            "request received"
+        );
+        let (client, orderbook_count) = {
+            let raindex = shared_raindex.read().await;
+            raindex.ensure_orderbooks_configured()?;
+            (raindex.client().clone(), raindex.orderbook_sources().len())
+        };
+        let ds = RaindexTradesDataSource {
+            client: &client,
+            pool: pool.inner(),
+            orderbook_count,
+            subgraph_page_size: app_state.subgraph_page_size,
+        };
+        let Json(response) = process_get_trades_by_tx_batch(
+            &ds,
+            request,
+            app_state.batch_concurrency,
+            MAX_TX_BATCH_RESPONSE_BYTES,
+        )
+        .await?;
+        let mut body = serde_json::to_value(response).map_err(|e| {
+            tracing::error!(error = %e, "failed to serialize trades batch response");
+            ApiError::Internal("failed to serialize response".into())
+        })?;
+        crate::timestamps::apply_time_format(&mut body, time_format);
+        Ok(Json(body))
+    }
+    .instrument(span.0)
+    .await
+}
+
+/// Resolves each hash in `request`, running at most `concurrency`
+/// transaction lookups against the subgraph at the same time. Results are
+/// taken in request order until they would exceed `max_response_bytes`;
+/// the hashes left over are returned as a continuation instead of failing
+/// the batch.
+pub(super) async fn process_get_trades_by_tx_batch(
+    ds: &dyn TradesDataSource,
+    request: TradesByTxBatchRequest,
+    concurrency: usize,
+    max_response_bytes: usize,
+) -> Result<Json<TradesByTxBatchResponse>, ApiError> {
+    let mut tx_hashes = parse_tx_hashes(&request.tx_hashes)?;
+    if let Some(token) = request.continuation.as_deref() {
+        let remaining = decode_continuation(token, request.tx_hashes.len())?;
+        tx_hashes.retain(|(index, _)| remaining.contains(index));
+    }
+    let denomination = request.denomination.unwrap_or_default();
+
+    tracing::info!(
+        tx_hashes_count = tx_hashes.len(),
+        concurrency,
+        "querying trades for transaction batch"
+    );
+    let mut entries = stream::iter(tx_hashes.iter().map(|&(index, tx_hash)| async move {
+        let entry = batch_entry(process_get_trades_by_tx(ds, tx_hash, denomination).await);
+        (index, format!("{tx_hash:#x}"), entry)
+    }))
+    .buffered(concurrency.max(1));
+
+    let mut results = BTreeMap::new();
+    let mut response_bytes = 0;
+    let mut deferred = Vec::new();
+    while let Some((index, key, entry)) = entries.next().await {
+        let entry_bytes = key.len() + serde_json::to_vec(&entry).map_or(0, |bytes| bytes.len());
+        if !results.is_empty() && response_bytes + entry_bytes > max_response_bytes {
+            deferred.push(index);
+            break;
+        }
+        response_bytes += entry_bytes;
+        results.insert(key, entry);
+    }
+    drop(entries);
+
+    let continuation = if deferred.is_empty() {
+        None
+    } else {
+        let first_deferred = deferred[0];
+        deferred.extend(
+            tx_hashes
+                .iter()
+                .map(|(index, _)| *index)
+                .filter(|index| *index > first_deferred),
+        );
+        tracing::info!(
+            included = results.len(),
+            deferred = deferred.len(),
+            "batch response hit size cap; returning continuation"
+        );
+        Some(encode_continuation(&deferred))
+    };
+
+    Ok(Json(TradesByTxBatchResponse {
+        results,
+        continuation,
+    }))
+}
+
+/// Parses the requested hashes with their position in the request,
+/// dropping repeats; one malformed hash rejects the whole batch since the
+/// client has to fix the request anyway.
+fn parse_tx_hashes(tx_hashes: &[String]) -> Result<Vec<(usize, B256)>, ApiError> {
+    if tx_hashes.is_empty() {
+        return Err(ApiError::BadRequest("txHashes must not be empty".into()));
+    }
+    if tx_hashes.len() > MAX_TX_BATCH_SIZE {
+        return Err(ApiError::BadRequest(format!(
+            "at most {MAX_TX_BATCH_SIZE} transaction hashes are allowed per batch"
+        )));
+    }
+    let mut seen = HashSet::with_capacity(tx_hashes.len());
+    let mut parsed = Vec::with_capacity(tx_hashes.len());
+    for (index, hash) in tx_hashes.iter().enumerate() {
+        let tx_hash = B256::from_str(hash).map_err(|e| {
+            tracing::warn!(input = %hash, error = %e, "invalid transaction hash");
+            ApiError::BadRequest("invalid transaction hash".into())
+        })?;
+        if seen.insert(tx_hash) {
+            parsed.push((index, tx_hash));
+        }
+    }
+    Ok(parsed)
+}
+
+/// Encodes the request indices still to resolve as an opaque token.
+fn encode_continuation(indices: &[usize]) -> String {
+    let joined = indices
+        .iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    URL_SAFE_NO_PAD.encode(joined)
+}
+
+fn decode_continuation(token: &str, request_len: usize) -> Result<HashSet<usize>, ApiError> {
+    let invalid = || {
+        tracing::warn!(continuation = %token, "invalid batch continuation");
+        ApiError::BadRequest("invalid continuation".into())
+    };
+    let decoded = URL_SAFE_NO_PAD.decode(token).map_err(|_| invalid())?;
+    let decoded = String::from_utf8(decoded).map_err(|_| invalid())?;
+    decoded
+        .split(',')
+        .map(|index| match index.parse::<usize>() {
+            Ok(index) if index < request_len => Ok(index),
+            _ => Err(invalid()),
+        })
+        .collect()
+}
+
+fn batch_entry(result: Result<Json<TradesByTxResponse>, ApiError>) -> TradesByTxBatchEntry {
+    let status = match &result {
+        Ok(_) => TradesByTxBatchStatus::Indexed,
+        Err(ApiError::NotYetIndexed { .. }) => TradesByTxBatchStatus::NotYetIndexed,
+        Err(ApiError::NotFound { .. }) => TradesByTxBatchStatus::NotFound,
+        Err(_) => TradesByTxBatchStatus::Error,
+    };
+    match result {
+        Ok(response) => TradesByTxBatchEntry {
+            status,
+            result: Some(response.into_inner()),
+            message: None,
+        },
+        Err(error) => TradesByTxBatchEntry {
+            status,
+            result: None,
+            message: Some(api_error_message(&error)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::order::test_fixtures::*;
+    use crate::test_helpers::{basic_auth_header, seed_api_key, TestClientBuilder};
+    use alloy::primitives::{b256, Address, U256};
+    use async_trait::async_trait;
+    use rain_orderbook_common::raindex_client::trades::{
+        RaindexTradesByOrderHashResult, RaindexTradesListResult,
+    };
+    use rain_orderbook_common::raindex_client::types::{PaginationParams, TimeFilter};
+    use rocket::http::{ContentType, Header, Status};
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    const INDEXED_TX: B256 =
+        b256!("0x0000000000000000000000000000000000000000000000000000000000000088");
+    const PENDING_TX: B256 =
+        b256!("0x0000000000000000000000000000000000000000000000000000000000000099");
+
+    struct MockTradesDataSource {
+        results: HashMap<B256, Result<RaindexTradesListResult, ApiError>>,
+    }
+
+    #[async_trait]
+    impl TradesDataSource for MockTradesDataSource {
+        async fn get_trades_by_tx(
+            &self,
+            tx_hash: B256,
+        ) -> Result<RaindexTradesListResult, ApiError> {
+            self.results
+                .get(&tx_hash)
+                .cloned()
+                .unwrap_or_else(|| Err(ApiError::Internal("failed to query trades".into())))
+        }
+
+        async fn get_trades_for_owner(
+            &self,
+            _owner: Address,
+            _pagination: PaginationParams,
+            _time_filter: TimeFilter,
+        ) -> Result<crate::routes::trades::TradesPage, ApiError> {
+            unimplemented!()
+        }
+
+        async fn get_trades_for_token(
+            &self,
+            _token: Address,
+            _page: u16,
+            _page_size: u16,
+            _time_filter: TimeFilter,
+        ) -> Result<RaindexTradesListResult, ApiError> {
+            unimplemented!()
+        }
+
+        async fn get_trades_for_taker(
+            &self,
+            _taker: Address,
+            _page: u16,
+            _page_size: u16,
+            _time_filter: TimeFilter,
+        ) -> Result<RaindexTradesListResult, ApiError> {
+            unimplemented!()
+        }
+
+        async fn get_trades_by_order_hashes(
+            &self,
+            _order_hashes: Vec<B256>,
+            _time_filter: TimeFilter,
+        ) -> Result<RaindexTradesByOrderHashResult, ApiError> {
+            unimplemented!()
+        }
+    }
+
+    /// Counts overlapping `get_trades_by_tx` calls, holding each one open
+    /// briefly so concurrent lookups overlap.
+    #[derive(Default)]
+    struct InFlightTradesDataSource {
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl TradesDataSource for InFlightTradesDataSource {
+        async fn get_trades_by_tx(
+            &self,
+            _tx_hash: B256,
+        ) -> Result<RaindexTradesListResult, ApiError> {
+            let current = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(mock_empty_trades_list_result())
+        }
+
+        async fn get_trades_for_owner(
+            &self,
+            _owner: Address,
+            _pagination: PaginationParams,
+            _time_filter: TimeFilter,
+        ) -> Result<crate::routes::trades::TradesPage, ApiError> {
+            unimplemented!()
+        }
+
+        async fn get_trades_for_token(
+            &self,
+            _token: Address,
+            _page: u16,
+            _page_size: u16,
+            _time_filter: TimeFilter,
+        ) -> Result<RaindexTradesListResult, ApiError> {
+            unimplemented!()
+        }
+
+        async fn get_trades_for_taker(
+            &self,
+            _taker: Address,
+            _page: u16,
+            _page_size: u16,
+            _time_filter: TimeFilter,
+        ) -> Result<RaindexTradesListResult, ApiError> {
+            unimplemented!()
+        }
+
+        async fn get_trades_by_order_hashes(
+            &self,
+            _order_hashes: Vec<B256>,
+            _time_filter: TimeFilter,
+        ) -> Result<RaindexTradesByOrderHashResult, ApiError> {
+            unimplemented!()
+        }
+    }
+
+    fn request(tx_hashes: &[B256]) -> TradesByTxBatchRequest {
+        TradesByTxBatchRequest {
+            tx_hashes: tx_hashes.iter().map(|hash| format!("{hash:#x}")).collect(),
+            denomination: None,
+            continuation: None,
+            time_format: None,
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_batch_mixes_indexed_and_not_yet_indexed() {
+        let ds = MockTradesDataSource {
+            results: HashMap::from([
+                (INDEXED_TX, Ok(mock_trades_list_result())),
+                (
+                    PENDING_TX,
+                    Err(ApiError::NotYetIndexed {
+                        message: "transaction not yet indexed after 10 attempts".into(),
+                        attempts: 10,
+                    }),
+                ),
+            ]),
+        };
+
+        let response = process_get_trades_by_tx_batch(
+            &ds,
+            request(&[INDEXED_TX, PENDING_TX]),
+            8,
+            MAX_TX_BATCH_RESPONSE_BYTES,
+        )
+        .await
+        .expect("partial indexing does not fail the batch")
+        .into_inner();
+
+        assert_eq!(response.results.len(), 2);
+        let indexed = &response.results[&format!("{INDEXED_TX:#x}")];
+        assert_eq!(indexed.status, TradesByTxBatchStatus::Indexed);
+        assert_eq!(indexed.result.as_ref().unwrap().trades.len(), 1);
+        assert!(indexed.message.is_none());
+
+        let pending = &response.results[&format!("{PENDING_TX:#x}")];
+        assert_eq!(pending.status, TradesByTxBatchStatus::NotYetIndexed);
+        assert!(pending.result.is_none());
+        assert_eq!(
+            pending.message.as_deref(),
+            Some("transaction not yet indexed after 10 attempts")
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_batch_reports_not_found_and_errors_per_tx() {
+        let ds = MockTradesDataSource {
+            results: HashMap::from([(INDEXED_TX, Ok(mock_empty_trades_list_result()))]),
+        };
+
+        let response = process_get_trades_by_tx_batch(
+            &ds,
+            request(&[INDEXED_TX, PENDING_TX]),
+            8,
+            MAX_TX_BATCH_RESPONSE_BYTES,
+        )
+        .await
+        .unwrap()
+        .into_inner();
+
+        assert_eq!(
+            response.results[&format!("{INDEXED_TX:#x}")].status,
+            TradesByTxBatchStatus::NotFound
+        );
+        let failed = &response.results[&format!("{PENDING_TX:#x}")];
+        assert_eq!(failed.status, TradesByTxBatchStatus::Error);
+        assert_eq!(failed.message.as_deref(), Some("failed to query trades"));
+    }
+
+    #[rocket::async_test]
+    async fn test_batch_deduplicates_hashes() {
+        let ds = MockTradesDataSource {
+            results: HashMap::from([(INDEXED_TX, Ok(mock_trades_list_result()))]),
+        };
+        let mut body = request(&[INDEXED_TX, INDEXED_TX]);
+        body.tx_hashes[1] = body.tx_hashes[1].to_uppercase().replacen("0X", "0x", 1);
+
+        let response = process_get_trades_by_tx_batch(&ds, body, 8, MAX_TX_BATCH_RESPONSE_BYTES)
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(response.results.len(), 1);
+    }
+
+    #[rocket::async_test]
+    async fn test_batch_caps_concurrent_lookups() {
+        let ds = InFlightTradesDataSource::default();
+        let hashes: Vec<B256> = (0..MAX_TX_BATCH_SIZE as u64)
+            .map(|i| B256::from(U256::from(i + 1)))
+            .collect();
+
+        let response =
+            process_get_trades_by_tx_batch(&ds, request(&hashes), 3, MAX_TX_BATCH_RESPONSE_BYTES)
+                .await
+                .unwrap()
+                .into_inner();
+
+        assert_eq!(response.results.len(), MAX_TX_BATCH_SIZE);
+        assert_eq!(ds.max_in_flight.load(Ordering::SeqCst), 3);
+    }
+
+    #[rocket::async_test]
+    async fn test_batch_over_size_cap_returns_continuation_for_remainder() {
+        let hashes: Vec<B256> = (0..5u64).map(|i| B256::from(U256::from(i + 1))).collect();
+        let ds = MockTradesDataSource {
+            results: hashes
+                .iter()
+                .map(|hash| (*hash, Ok(mock_trades_list_result())))
+                .collect(),
+        };
+        let entry_bytes = {
+            let entry =
+                batch_entry(process_get_trades_by_tx(&ds, hashes[0], Default::default()).await);
+            format!("{:#x}", hashes[0]).len() + serde_json::to_vec(&entry).unwrap().len()
+        };
+
+        let first = process_get_trades_by_tx_batch(&ds, request(&hashes), 8, entry_bytes * 2)
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(first.results.len(), 2);
+        assert!(first.results.contains_key(&format!("{:#x}", hashes[0])));
+        assert!(first.results.contains_key(&format!("{:#x}", hashes[1])));
+        let continuation = first.continuation.expect("continuation for the remainder");
+        assert_eq!(
+            decode_continuation(&continuation, hashes.len()).unwrap(),
+            HashSet::from([2, 3, 4])
+        );
+
+        let mut resumed = request(&hashes);
+        resumed.continuation = Some(continuation);
+        let rest = process_get_trades_by_tx_batch(&ds, resumed, 8, MAX_TX_BATCH_RESPONSE_BYTES)
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(rest.continuation.is_none());
+        let keys: Vec<_> = rest.results.keys().cloned().collect();
+        let expected: Vec<_> = hashes[2..]
+            .iter()
+            .map(|hash| format!("{hash:#x}"))
+            .collect();
+        assert_eq!(keys, expected);
+    }
+
+    #[rocket::async_test]
+    async fn test_batch_rejects_continuation_outside_request() {
+        let ds = MockTradesDataSource {
+            results: HashMap::new(),
+        };
+        let mut body = request(&[INDEXED_TX]);
+        body.continuation = Some(encode_continuation(&[3]));
+
+        let result =
+            process_get_trades_by_tx_batch(&ds, body, 8, MAX_TX_BATCH_RESPONSE_BYTES).await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::BadRequest(msg)) if msg == "invalid continuation"
+        ));
+    }
+
+    #[test]
+    fn test_parse_tx_hashes_rejects_empty_oversized_and_invalid() {
+        assert!(matches!(
+            parse_tx_hashes(&[]),
+            Err(ApiError::BadRequest(msg)) if msg == "txHashes must not be empty"
+        ));
+
+        let oversized = vec![format!("{INDEXED_TX:#x}"); MAX_TX_BATCH_SIZE + 1];
+        assert!(matches!(
+            parse_tx_hashes(&oversized),
+            Err(ApiError::BadRequest(msg))
+                if msg == "at most 50 transaction hashes are allowed per batch"
+        ));
+
+        assert!(matches!(
+            parse_tx_hashes(&["not-a-hash".to_string()]),
+            Err(ApiError::BadRequest(msg)) if msg == "invalid transaction hash"
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_batch_route_rejects_oversized_batch() {
+        let client = TestClientBuilder::new().build().await;
+        let (key_id, secret) = seed_api_key(&client).await;
+        let body = serde_json::json!({
+            "txHashes": vec![format!("{INDEXED_TX:#x}"); MAX_TX_BATCH_SIZE + 1],
+        });
+
+        let response = client
+            .post("/v1/trades/tx/batch")
+            .header(ContentType::JSON)
+            .header(Header::new(
+                "Authorization",
+                basic_auth_header(&key_id, &secret),
+            ))
+            .body(body.to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+}
@@ -3,9 +3,11 @@ pub(crate) mod get_by_order_hashes;
 pub(crate) mod get_by_taker;
 pub(crate) mod get_by_token;
 pub(crate) mod get_by_tx;
+pub(crate) mod get_by_tx_batch;
 
-use crate::error::ApiError;
-use crate::types::common::{Denomination, TokenRef};
+use crate::error::{upstream_error, ApiError};
+use crate::routes::pagination::{PageLinks, Paginated};
+use crate::types::common::{Denomination, TimeFormat, TokenRef};
 use crate::types::trades::{
     TradeByAddress, TradesByAddressResponse, TradesPagination, TradesPaginationParams,
 };
@@ -23,10 +25,21 @@ use rain_orderbook_common::raindex_client::types::{PaginationParams, TimeFilter}
 use rain_orderbook_common::raindex_client::{RaindexClient, RaindexError};
 use rocket::serde::json::Json;
 use rocket::Route;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub(crate) type TradeWrapRatioMap = HashMap<(Address, u64), WrapRatioValue>;
 
+/// Largest page the subgraph serves per query; bigger windows are assembled
+/// from several subgraph pages.
+pub(crate) const SUBGRAPH_MAX_PAGE_SIZE: u16 = 100;
+/// Most subgraph pages a single API request's `pageSize` may span. A window
+/// that doesn't start on a subgraph page boundary takes one more query.
+pub(crate) const MAX_SUBGRAPH_PAGES_PER_REQUEST: u32 = 10;
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
 #[async_trait]
 pub(crate) trait TradesDataSource: Send + Sync {
     async fn get_trades_by_tx(&self, tx_hash: B256) -> Result<RaindexTradesListResult, ApiError>;
@@ -36,7 +49,7 @@ pub(crate) trait TradesDataSource: Send + Sync {
         owner: Address,
         pagination: PaginationParams,
         time_filter: TimeFilter,
-    ) -> Result<RaindexTradesListResult, ApiError>;
+    ) -> Result<TradesPage, ApiError>;
 
     async fn get_trades_for_token(
         &self,
@@ -71,6 +84,27 @@ pub(crate) trait TradesDataSource: Send + Sync {
 pub(crate) struct RaindexTradesDataSource<'a> {
     pub client: &'a RaindexClient,
     pub pool: &'a crate::db::DbPool,
+    /// Orderbooks the client queries; owner trade pages are merged across
+    /// them when there is more than one.
+    pub orderbook_count: usize,
+    /// Trades asked for per subgraph query when assembling owner trade pages.
+    pub subgraph_page_size: u16,
+}
+
+/// A window of trades and the total count it was cut from.
+#[derive(Debug, Clone)]
+pub(crate) struct TradesPage {
+    pub trades: Vec<RaindexTrade>,
+    pub total_count: u64,
+}
+
+impl From<RaindexTradesListResult> for TradesPage {
+    fn from(result: RaindexTradesListResult) -> Self {
+        Self {
+            trades: result.trades().to_vec(),
+            total_count: result.total_count(),
+        }
+    }
 }
 
 #[async_trait]
@@ -81,13 +115,16 @@ impl TradesDataSource for RaindexTradesDataSource<'_> {
             .await
             .map_err(|e| match e {
                 RaindexError::TransactionIndexingTimeout { tx_hash, attempts } => {
-                    ApiError::NotYetIndexed(format!(
-                        "transaction {tx_hash:#x} not yet indexed after {attempts} attempts"
-                    ))
+                    ApiError::NotYetIndexed {
+                        message: format!(
+                            "transaction {tx_hash:#x} not yet indexed after {attempts} attempts"
+                        ),
+                        attempts: u32::try_from(attempts).unwrap_or(u32::MAX),
+                    }
                 }
                 other => {
                     tracing::error!(error = %other, "failed to query trades for transaction");
-                    ApiError::Internal("failed to query trades".into())
+                    upstream_error(&other, "failed to query trades")
                 }
             })
     }
@@ -97,20 +134,35 @@ impl TradesDataSource for RaindexTradesDataSource<'_> {
         owner: Address,
         pagination: PaginationParams,
         time_filter: TimeFilter,
-    ) -> Result<RaindexTradesListResult, ApiError> {
+    ) -> Result<TradesPage, ApiError> {
         let filters = GetTradesFilters {
             owners: vec![owner],
             time_filter: Some(time_filter),
             ..Default::default()
         };
 
-        self.client
-            .get_trades(None, Some(filters), pagination.page, pagination.page_size)
-            .await
-            .map_err(|e| {
-                tracing::error!(error = %e, "failed to query trades for owner");
-                ApiError::Internal("failed to query trades".into())
-            })
+        let page = pagination.page.unwrap_or(1);
+        let page_size = pagination.page_size.unwrap_or(20);
+
+        fetch_trades_window(
+            page,
+            page_size,
+            self.subgraph_page_size,
+            self.orderbook_count,
+            |sub_page, sub_size| {
+                let filters = filters.clone();
+                async move {
+                    self.client
+                        .get_trades(None, Some(filters), Some(sub_page), Some(sub_size))
+                        .await
+                        .map_err(|e| {
+                            tracing::error!(error = %e, "failed to query trades for owner");
+                            upstream_error(&e, "failed to query trades")
+                        })
+                }
+            },
+        )
+        .await
     }
 
     async fn get_trades_for_token(
@@ -134,7 +186,7 @@ impl TradesDataSource for RaindexTradesDataSource<'_> {
             .await
             .map_err(|e| {
                 tracing::error!(error = %e, "failed to query trades for token");
-                ApiError::Internal("failed to query trades".into())
+                upstream_error(&e, "failed to query trades")
             })
     }
 
@@ -156,7 +208,7 @@ impl TradesDataSource for RaindexTradesDataSource<'_> {
             .await
             .map_err(|e| {
                 tracing::error!(error = %e, "failed to query trades for taker");
-                ApiError::Internal("failed to query trades".into())
+                upstream_error(&e, "failed to query trades")
             })
     }
 
@@ -175,7 +227,7 @@ impl TradesDataSource for RaindexTradesDataSource<'_> {
             .await
             .map_err(|e| {
                 tracing::error!(error = %e, "failed to query trades by order hashes");
-                ApiError::Internal("failed to query trades".into())
+                upstream_error(&e, "failed to query trades")
             })
     }
 
@@ -199,6 +251,186 @@ impl TradesDataSource for RaindexTradesDataSource<'_> {
     }
 }
 
+/// Fetches the `page`/`page_size` window of trades, splitting it into
+/// subgraph-sized queries when `page_size` exceeds `subgraph_page_size` so the
+/// subgraph cap cannot silently truncate the result. With more than one
+/// orderbook the window is cut from the merged trades instead, see
+/// [`fetch_merged_trades_window`].
+pub(crate) async fn fetch_trades_window<F, Fut>(
+    page: u16,
+    page_size: u16,
+    subgraph_page_size: u16,
+    orderbook_count: usize,
+    fetch_page: F,
+) -> Result<TradesPage, ApiError>
+where
+    F: Fn(u16, u16) -> Fut,
+    Fut: Future<Output = Result<RaindexTradesListResult, ApiError>>,
+{
+    if orderbook_count > 1 {
+        return fetch_merged_trades_window(page, page_size, subgraph_page_size, fetch_page).await;
+    }
+    if page_size <= subgraph_page_size || subgraph_page_size == 0 {
+        let result = fetch_page(page, page_size).await?;
+        return Ok(TradesPage {
+            trades: dedupe_trades_by_id(result.trades().to_vec()),
+            total_count: result.total_count(),
+        });
+    }
+
+    let max_page_size = u32::from(subgraph_page_size) * MAX_SUBGRAPH_PAGES_PER_REQUEST;
+    if u32::from(page_size) > max_page_size {
+        tracing::warn!(
+            page,
+            page_size,
+            max_page_size,
+            "requested trades window exceeds subgraph page cap"
+        );
+        return Err(ApiError::BadRequest(format!(
+            "pageSize must not exceed {max_page_size}"
+        )));
+    }
+
+    let offset = u64::from(page.max(1) - 1) * u64::from(page_size);
+    let end = offset + u64::from(page_size);
+    let sub_size = u64::from(subgraph_page_size);
+    let first_sub_page = offset / sub_size + 1;
+    let last_sub_page = (end - 1) / sub_size + 1;
+
+    let mut total_count: Option<u64> = None;
+    let mut trades: Vec<RaindexTrade> = Vec::new();
+    for sub_page in first_sub_page..=last_sub_page {
+        let sub_page = u16::try_from(sub_page)
+            .map_err(|_| ApiError::BadRequest("page value too large".into()))?;
+        let result = fetch_page(sub_page, subgraph_page_size).await?;
+        let received = result.trades().len();
+        trades.extend(result.trades().iter().cloned());
+        total_count.get_or_insert(result.total_count());
+        if received < usize::from(subgraph_page_size) {
+            break;
+        }
+    }
+
+    tracing::info!(
+        page,
+        page_size,
+        subgraph_pages = last_sub_page - first_sub_page + 1,
+        assembled_trades = trades.len(),
+        "assembled trades window from subgraph pages"
+    );
+
+    let skip = usize::try_from(offset - (first_sub_page - 1) * sub_size).unwrap_or(usize::MAX);
+    let window: Vec<RaindexTrade> = dedupe_trades_by_id(trades)
+        .into_iter()
+        .skip(skip)
+        .take(usize::from(page_size))
+        .collect();
+
+    let total_count = total_count.ok_or_else(|| {
+        tracing::error!("no subgraph pages fetched for trades window");
+        ApiError::Internal("failed to query trades".into())
+    })?;
+    Ok(TradesPage {
+        trades: window,
+        total_count,
+    })
+}
+
+/// The client pages every orderbook separately and concatenates the pages, so
+/// page `n` of the combined list is neither `page_size` long nor in global
+/// order. Fetches each orderbook's leading trades up to the end of the window
+/// instead, orders them newest first and cuts the window from that; the
+/// summed `totalCount` then matches the trades being paged.
+async fn fetch_merged_trades_window<F, Fut>(
+    page: u16,
+    page_size: u16,
+    subgraph_page_size: u16,
+    fetch_page: F,
+) -> Result<TradesPage, ApiError>
+where
+    F: Fn(u16, u16) -> Fut,
+    Fut: Future<Output = Result<RaindexTradesListResult, ApiError>>,
+{
+    let subgraph_page_size = subgraph_page_size.max(1);
+    let offset = u64::from(page.max(1) - 1) * u64::from(page_size);
+    let end = offset + u64::from(page_size);
+    let sub_page_count = end.div_ceil(u64::from(subgraph_page_size)).max(1);
+    if sub_page_count > u64::from(MAX_SUBGRAPH_PAGES_PER_REQUEST) {
+        tracing::warn!(
+            page,
+            page_size,
+            sub_page_count,
+            "requested trades window is too deep to merge across orderbooks"
+        );
+        return Err(ApiError::BadRequest(format!(
+            "page * pageSize must not exceed {} when trades span several orderbooks",
+            u32::from(subgraph_page_size) * MAX_SUBGRAPH_PAGES_PER_REQUEST
+        )));
+    }
+
+    let mut total_count: Option<u64> = None;
+    let mut trades: Vec<RaindexTrade> = Vec::new();
+    for sub_page in 1..=sub_page_count {
+        let sub_page = u16::try_from(sub_page)
+            .map_err(|_| ApiError::BadRequest("page value too large".into()))?;
+        let result = fetch_page(sub_page, subgraph_page_size).await?;
+        let received = result.trades().len();
+        trades.extend(result.trades().iter().cloned());
+        total_count.get_or_insert(result.total_count());
+        // Every orderbook returns at most a full page, so a short combined
+        // page means all of them ran out.
+        if received < usize::from(subgraph_page_size) {
+            break;
+        }
+    }
+
+    let mut trades = dedupe_trades_by_id(trades);
+    // Trades in the same block share a timestamp; breaking ties on id keeps
+    // the window boundaries stable between requests.
+    trades
+        .sort_by_cached_key(|trade| (std::cmp::Reverse(trade.timestamp()), trade.id().to_string()));
+    tracing::info!(
+        page,
+        page_size,
+        merged_trades = trades.len(),
+        "merged trades window across orderbooks"
+    );
+    let window: Vec<RaindexTrade> = trades
+        .into_iter()
+        .skip(usize::try_from(offset).unwrap_or(usize::MAX))
+        .take(usize::from(page_size))
+        .collect();
+
+    let total_count = total_count.ok_or_else(|| {
+        tracing::error!("no subgraph pages fetched for trades window");
+        ApiError::Internal("failed to query trades".into())
+    })?;
+    Ok(TradesPage {
+        trades: window,
+        total_count,
+    })
+}
+
+/// Drops repeated trade ids, keeping the first occurrence. Around a reorg the
+/// subgraph can briefly serve the same trade twice, which would otherwise be
+/// listed and totalled twice.
+pub(super) fn dedupe_trades_by_id(trades: Vec<RaindexTrade>) -> Vec<RaindexTrade> {
+    let received = trades.len();
+    let mut seen = HashSet::with_capacity(received);
+    let trades: Vec<RaindexTrade> = trades
+        .into_iter()
+        .filter(|trade| seen.insert(trade.id().to_string()))
+        .collect();
+    if trades.len() < received {
+        tracing::warn!(
+            received,
+            dropped = received - trades.len(),
+            "dropped duplicate trade ids from subgraph response"
+        );
+    }
+    trades
+}
+
 pub(super) fn map_trade_for_list(
     trade: &RaindexTrade,
     denomination: Denomination,
@@ -267,20 +499,20 @@ pub(super) fn map_trade_for_list(
 
 pub(super) async fn build_trades_list_response(
     ds: &dyn TradesDataSource,
-    result: RaindexTradesListResult,
+    result: TradesPage,
     page: u32,
     page_size: u32,
     denomination: Denomination,
 ) -> Result<Json<TradesByAddressResponse>, ApiError> {
     let trade_wrap_ratios =
-        current_wrap_ratios_for_trades(ds, denomination, result.trades()).await?;
+        current_wrap_ratios_for_trades(ds, denomination, &result.trades).await?;
     let trades = result
-        .trades()
+        .trades
         .iter()
         .map(|trade| map_trade_for_list(trade, denomination, &trade_wrap_ratios))
         .collect::<Result<Vec<_>, ApiError>>()?;
 
-    let total_trades = result.total_count();
+    let total_trades = result.total_count;
     let total_pages = if page_size > 0 {
         total_trades.div_ceil(u64::from(page_size))
     } else {
@@ -300,6 +532,38 @@ pub(super) async fn build_trades_list_response(
     }))
 }
 
+/// Serializes a trades listing, adding `*Display` amounts when `display`
+/// is set and rendering timestamps in `time_format`.
+/// Renders a trades listing, attaching `raw_trades` under `_raw` when
+/// `debug_raw` applies.
+pub(super) fn trades_list_json(
+    response: &TradesByAddressResponse,
+    display: bool,
+    time_format: TimeFormat,
+    raw_trades: Option<&[RaindexTrade]>,
+) -> Result<Paginated<Json<serde_json::Value>>, ApiError> {
+    let mut body = serde_json::to_value(response).map_err(|e| {
+        tracing::error!(error = %e, "failed to serialize response");
+        ApiError::Internal("failed to serialize response".into())
+    })?;
+    if display {
+        crate::amounts::emit_display_amounts(&mut body);
+    }
+    crate::timestamps::apply_time_format(&mut body, time_format);
+    if let Some(raw_trades) = raw_trades {
+        crate::debug_raw::attach_raw(&mut body, &raw_trades);
+    }
+    let pagination = &response.pagination;
+    Ok(Paginated::new(
+        Json(body),
+        PageLinks::new(
+            pagination.page,
+            Some(pagination.total_pages),
+            pagination.has_more,
+        ),
+    ))
+}
+
 pub(super) async fn current_wrap_ratios_for_trades(
     ds: &dyn TradesDataSource,
     denomination: Denomination,
@@ -354,17 +618,16 @@ pub(super) fn wrap_ratio_map_for_trade(
 }
 
 pub(super) fn trade_block_number(trade: &RaindexTrade) -> Result<u64, ApiError> {
-    trade.transaction().block_number().try_into().map_err(|_| {
-        tracing::error!("block number does not fit in u64");
-        ApiError::Internal("block number overflow".into())
-    })
+    crate::block_number::block_number_u64(trade.transaction().block_number())
 }
 
 pub(super) fn trades_pagination_params(
     params: TradesPaginationParams,
+    default_page_size: u16,
 ) -> Result<(u32, u32, u16, u16, TimeFilter), ApiError> {
+    crate::routes::pagination::validate_page(params.page)?;
     let page = params.page.unwrap_or(1);
-    let page_size = params.page_size.unwrap_or(20);
+    let page_size = params.page_size.unwrap_or(u32::from(default_page_size));
 
     let sdk_page = page
         .try_into()
@@ -380,12 +643,360 @@ pub(super) fn trades_pagination_params(
     Ok((page, page_size, sdk_page, sdk_page_size, time_filter))
 }
 
+/// The `start` a trade query runs from, keeping `start..end` within
+/// `max_window_secs` with an open `end` running to `now`. A missing `start`
+/// defaults to the widest window ending at `end`, so no query scans the full
+/// history; an explicit `start` further back is rejected.
+pub(super) fn bounded_start_time(
+    start: Option<u64>,
+    end: Option<u64>,
+    max_window_secs: u64,
+    now: u64,
+) -> Result<u64, ApiError> {
+    let end = end.unwrap_or(now);
+    let Some(start) = start else {
+        return Ok(end.saturating_sub(max_window_secs));
+    };
+    if end.saturating_sub(start) > max_window_secs {
+        tracing::warn!(start, end, max_window_secs, "trade time range too wide");
+        return Err(ApiError::BadRequest(format!(
+            "time range must not exceed {} days; narrow startTime..endTime and page through it",
+            max_window_secs / SECS_PER_DAY
+        )));
+    }
+    Ok(start)
+}
+
+pub(super) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 pub fn routes() -> Vec<Route> {
     rocket::routes![
         get_by_tx::get_trades_by_tx,
+        get_by_tx_batch::get_trades_by_tx_batch,
         get_by_order_hashes::get_trades_by_order_hashes,
         get_by_token::get_trades_by_token,
         get_by_taker::get_trades_by_taker,
         get_by_address::get_trades_by_address
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::order::test_fixtures::trade_json;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    const NOW: u64 = 1_750_000_000;
+    const NINETY_DAYS: u64 = 90 * SECS_PER_DAY;
+
+    #[test]
+    fn test_bounded_start_time_accepts_range_at_limit() {
+        let start = NOW - NINETY_DAYS;
+        assert_eq!(
+            bounded_start_time(Some(start), Some(NOW), NINETY_DAYS, NOW).unwrap(),
+            start
+        );
+        assert_eq!(
+            bounded_start_time(Some(start), None, NINETY_DAYS, NOW).unwrap(),
+            start
+        );
+    }
+
+    #[test]
+    fn test_bounded_start_time_bounds_a_missing_start() {
+        assert_eq!(
+            bounded_start_time(None, Some(NOW), NINETY_DAYS, NOW).unwrap(),
+            NOW - NINETY_DAYS
+        );
+        assert_eq!(
+            bounded_start_time(None, None, NINETY_DAYS, NOW).unwrap(),
+            NOW - NINETY_DAYS
+        );
+
+        let end = NOW - 10 * SECS_PER_DAY;
+        assert_eq!(
+            bounded_start_time(None, Some(end), NINETY_DAYS, NOW).unwrap(),
+            end - NINETY_DAYS
+        );
+    }
+
+    #[test]
+    fn test_bounded_start_time_rejects_range_beyond_limit() {
+        let start = NOW - NINETY_DAYS - 1;
+        for end in [Some(NOW), None] {
+            let result = bounded_start_time(Some(start), end, NINETY_DAYS, NOW);
+            assert!(matches!(
+                result,
+                Err(ApiError::BadRequest(msg)) if msg.starts_with("time range must not exceed 90 days")
+            ));
+        }
+    }
+
+    fn trade_id(index: usize) -> String {
+        format!("0x{index:064x}")
+    }
+
+    fn paged_subgraph(total: usize, page: u16, page_size: u16) -> RaindexTradesListResult {
+        let start = (usize::from(page) - 1) * usize::from(page_size);
+        let end = (start + usize::from(page_size)).min(total);
+        let trades: Vec<_> = (start..end.max(start))
+            .map(|index| {
+                let mut trade = trade_json();
+                trade["id"] = json!(trade_id(index));
+                trade
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "trades": trades,
+            "totalCount": total,
+            "summary": null
+        }))
+        .expect("deserialize paged trades")
+    }
+
+    /// Pages two orderbooks of `per_orderbook` trades the way the client does:
+    /// each is paged on its own and the pages are concatenated. The orderbooks
+    /// alternate in time, so `rank` is a trade's position newest first.
+    fn two_orderbook_subgraph(
+        per_orderbook: usize,
+        page: u16,
+        page_size: u16,
+    ) -> RaindexTradesListResult {
+        let start = (usize::from(page) - 1) * usize::from(page_size);
+        let end = (start + usize::from(page_size)).min(per_orderbook);
+        let trades: Vec<_> = [0, 1]
+            .into_iter()
+            .flat_map(|orderbook| {
+                (start..end.max(start)).map(move |index| {
+                    let rank = index * 2 + orderbook;
+                    let mut trade = trade_json();
+                    trade["id"] = json!(trade_id(rank));
+                    trade["timestamp"] = json!(format!("0x{:064x}", 1_000_000 - rank));
+                    trade
+                })
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "trades": trades,
+            "totalCount": per_orderbook * 2,
+            "summary": null
+        }))
+        .expect("deserialize two orderbook trades")
+    }
+
+    /// Four trades from one block, as two orderbooks would return them, in
+    /// either concatenation order.
+    fn same_block_subgraph(reversed: bool) -> RaindexTradesListResult {
+        let mut ranks = vec![3, 0, 2, 1];
+        if reversed {
+            ranks.reverse();
+        }
+        let trades: Vec<_> = ranks
+            .into_iter()
+            .map(|rank| {
+                let mut trade = trade_json();
+                trade["id"] = json!(trade_id(rank));
+                trade["timestamp"] = json!(format!("0x{:064x}", 1_000_000));
+                trade
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "trades": trades,
+            "totalCount": 4,
+            "summary": null
+        }))
+        .expect("deserialize same block trades")
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_trades_window_merges_pages_across_orderbooks() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let result = fetch_trades_window(2, 20, 100, 2, |page, page_size| {
+            let calls = Arc::clone(&calls);
+            async move {
+                calls.lock().unwrap().push((page, page_size));
+                Ok(two_orderbook_subgraph(30, page, page_size))
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result.trades.len(), 20);
+        assert_eq!(result.total_count, 60);
+        let ids: Vec<String> = result
+            .trades()
+            .iter()
+            .map(|trade| trade.id().to_string())
+            .collect();
+        let expected: Vec<String> = (20..40).map(trade_id).collect();
+        assert_eq!(ids, expected);
+        assert_eq!(*calls.lock().unwrap(), vec![(1, 100)]);
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_trades_window_breaks_timestamp_ties_by_id() {
+        for reversed in [false, true] {
+            let result = fetch_trades_window(1, 4, 100, 2, |_, _| async move {
+                Ok(same_block_subgraph(reversed))
+            })
+            .await
+            .unwrap();
+            let ids: Vec<String> = result
+                .trades()
+                .iter()
+                .map(|trade| trade.id().to_string())
+                .collect();
+            let expected: Vec<String> = (0..4).map(trade_id).collect();
+            assert_eq!(ids, expected);
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_trades_window_last_merged_page_is_partial() {
+        let result = fetch_trades_window(3, 25, 100, 2, |page, page_size| async move {
+            Ok(two_orderbook_subgraph(30, page, page_size))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result.trades.len(), 10);
+        assert_eq!(result.trades[0].id().to_string(), trade_id(50));
+        assert_eq!(result.trades[9].id().to_string(), trade_id(59));
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_trades_window_rejects_merged_windows_past_cap() {
+        let result = fetch_trades_window(11, 100, 100, 2, |page, page_size| async move {
+            Ok(two_orderbook_subgraph(5000, page, page_size))
+        })
+        .await;
+
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_trades_window_assembles_multiple_subgraph_pages() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let result = fetch_trades_window(1, 250, 100, 1, |page, page_size| {
+            let calls = Arc::clone(&calls);
+            async move {
+                calls.lock().unwrap().push((page, page_size));
+                Ok(paged_subgraph(300, page, page_size))
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result.trades.len(), 250);
+        assert_eq!(result.total_count, 300);
+        assert_eq!(result.trades[0].id().to_string(), trade_id(0));
+        assert_eq!(result.trades[249].id().to_string(), trade_id(249));
+        assert_eq!(*calls.lock().unwrap(), vec![(1, 100), (2, 100), (3, 100)]);
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_trades_window_offsets_into_unaligned_pages() {
+        let result = fetch_trades_window(2, 150, 100, 1, |page, page_size| async move {
+            Ok(paged_subgraph(1000, page, page_size))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result.trades.len(), 150);
+        assert_eq!(result.trades[0].id().to_string(), trade_id(150));
+        assert_eq!(result.trades[149].id().to_string(), trade_id(299));
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_trades_window_stops_at_last_subgraph_page() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let result = fetch_trades_window(1, 500, 100, 1, |page, page_size| {
+            let calls = Arc::clone(&calls);
+            async move {
+                calls.lock().unwrap().push(page);
+                Ok(paged_subgraph(120, page, page_size))
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result.trades.len(), 120);
+        assert_eq!(result.total_count, 120);
+        assert_eq!(*calls.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_trades_window_passes_small_pages_through() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let result = fetch_trades_window(3, 20, 100, 1, |page, page_size| {
+            let calls = Arc::clone(&calls);
+            async move {
+                calls.lock().unwrap().push((page, page_size));
+                Ok(paged_subgraph(100, page, page_size))
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result.trades.len(), 20);
+        assert_eq!(*calls.lock().unwrap(), vec![(3, 20)]);
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_trades_window_drops_duplicate_trade_ids() {
+        let result = fetch_trades_window(1, 20, 100, 1, |_, _| async move {
+            Ok(serde_json::from_value(json!({
+                "trades": [trade_json(), trade_json()],
+                "totalCount": 2,
+                "summary": null
+            }))
+            .expect("deserialize duplicated trades"))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result.trades.len(), 1);
+        assert_eq!(
+            result.trades[0].id().to_string(),
+            trade_json()["id"].as_str().unwrap()
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_trades_window_rejects_windows_over_safety_cap() {
+        let result = fetch_trades_window(1, 1001, 100, 1, |page, page_size| async move {
+            Ok(paged_subgraph(5000, page, page_size))
+        })
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::BadRequest(msg)) if msg == "pageSize must not exceed 1000"
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_fetch_trades_window_allows_unaligned_window_at_cap() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let result = fetch_trades_window(2, 999, 100, 1, |page, page_size| {
+            let calls = Arc::clone(&calls);
+            async move {
+                calls.lock().unwrap().push(page);
+                Ok(paged_subgraph(5000, page, page_size))
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result.trades.len(), 999);
+        assert_eq!(result.trades[0].id().to_string(), trade_id(999));
+        assert_eq!(result.trades[998].id().to_string(), trade_id(1997));
+        assert_eq!(*calls.lock().unwrap(), (10..=20).collect::<Vec<u16>>());
+    }
+}
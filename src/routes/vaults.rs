@@ -1,6 +1,7 @@
 use crate::auth::AuthenticatedKey;
 use crate::error::{ApiError, ApiErrorResponse};
-use crate::fairings::{GlobalRateLimit, TracingSpan};
+use crate::fairings::{GlobalRateLimit, KnownQueryParams, TracingSpan};
+use crate::routes::pagination::{PageLinks, Paginated};
 use crate::types::vaults::{
     VaultOrderRef, VaultPositionResponse, VaultTokenResponse, VaultTotalResponse,
     VaultTotalTokenResponse, VaultTotalsResponse, VaultsPagination, VaultsQueryParams,
@@ -151,12 +152,8 @@ fn parse_address(value: &str, field: &str) -> Result<Address, ApiError> {
 }
 
 fn pagination(params: &VaultsQueryParams) -> Result<(u16, u16), ApiError> {
+    crate::routes::pagination::validate_page(params.page.map(u32::from))?;
     let page = params.page.unwrap_or(DEFAULT_PAGE);
-    if page == 0 {
-        return Err(ApiError::BadRequest(
-            "page must be greater than zero".into(),
-        ));
-    }
 
     let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
     if page_size == 0 {
@@ -308,10 +305,11 @@ pub(crate) async fn process_get_vault_totals(
 pub async fn get_vaults(
     _global: GlobalRateLimit,
     _key: AuthenticatedKey,
+    _query: KnownQueryParams<VaultsQueryParams>,
     shared_raindex: &State<crate::raindex::SharedRaindexProvider>,
     span: TracingSpan,
     params: VaultsQueryParams,
-) -> Result<Json<VaultsResponse>, ApiError> {
+) -> Result<Paginated<Json<VaultsResponse>>, ApiError> {
     async move {
         tracing::info!(params = ?params, "request received");
         let raindex = shared_raindex.read().await;
@@ -329,7 +327,17 @@ pub async fn get_vaults(
             total_items = response.pagination.total_items,
             "returning vault positions"
         );
-        Ok(Json(response))
+        let pagination = &response.pagination;
+        let links = PageLinks::new(
+            pagination.page,
+            (pagination.page_size > 0).then(|| {
+                pagination
+                    .total_items
+                    .div_ceil(u64::from(pagination.page_size))
+            }),
+            pagination.has_more,
+        );
+        Ok(Paginated::new(Json(response), links))
     }
     .instrument(span.0)
     .await
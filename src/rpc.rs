@@ -0,0 +1,259 @@
+use alloy::network::{Ethereum, Network};
+use alloy::primitives::Bytes;
+use alloy::providers::{DynProvider, Provider, ProviderBuilder};
+use alloy::transports::TransportError;
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+use url::Url;
+
+/// JSON-RPC methods the API may send to upstream nodes. Anything else is
+/// refused before a request is built, so adding an on-chain feature means
+/// extending this list deliberately.
+pub(crate) const ALLOWED_METHODS: &[&str] = &[
+    "eth_blockNumber",
+    "eth_call",
+    "eth_estimateGas",
+    "eth_feeHistory",
+    "eth_maxPriorityFeePerGas",
+];
+
+pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JSON-RPC error code nodes answer a reverted `eth_call` with.
+const EXECUTION_REVERTED_CODE: i64 = 3;
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum RpcError {
+    #[error("RPC method {0} is not allowlisted")]
+    MethodNotAllowed(&'static str),
+    #[error("RPC method {method} timed out after {timeout:?}")]
+    Timeout {
+        method: &'static str,
+        timeout: Duration,
+    },
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    #[error("RPC method {method} failed: {message}")]
+    Request {
+        method: &'static str,
+        message: String,
+    },
+}
+
+impl RpcError {
+    /// Message of the node's answer when the call itself reverted. Other
+    /// JSON-RPC errors (rate limits, unsupported methods, node faults) say
+    /// nothing about the transaction and return `None`.
+    pub(crate) fn revert_message(&self) -> Option<&str> {
+        let RpcError::Transport(e) = self else {
+            return None;
+        };
+        let payload = e.as_error_resp()?;
+        (payload.code == EXECUTION_REVERTED_CODE
+            || payload.message.starts_with("execution reverted"))
+        .then_some(&*payload.message)
+    }
+}
+
+/// The only way the API talks to an RPC node. Every call names its JSON-RPC
+/// method, is checked against the allowlist and is bounded by a timeout.
+pub(crate) struct RpcClient {
+    provider: DynProvider<Ethereum>,
+    allowed_methods: &'static [&'static str],
+    timeout: Duration,
+}
+
+impl RpcClient {
+    pub(crate) fn new(rpc: Url) -> Self {
+        Self {
+            provider: ProviderBuilder::new().connect_http(rpc).erased(),
+            allowed_methods: ALLOWED_METHODS,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    pub(crate) fn with_allowed_methods(mut self, methods: &'static [&'static str]) -> Self {
+        self.allowed_methods = methods;
+        self
+    }
+
+    pub(crate) fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn ensure_allowed(&self, method: &'static str) -> Result<(), RpcError> {
+        if self.allowed_methods.contains(&method) {
+            return Ok(());
+        }
+        tracing::error!(method, "refusing non-allowlisted RPC method");
+        Err(RpcError::MethodNotAllowed(method))
+    }
+
+    /// Checks `method` before polling `request`, so a refused call never
+    /// reaches the node.
+    async fn send<T>(
+        &self,
+        method: &'static str,
+        request: impl Future<Output = Result<T, RpcError>>,
+    ) -> Result<T, RpcError> {
+        self.ensure_allowed(method)?;
+        tokio::time::timeout(self.timeout, request)
+            .await
+            .map_err(|_| RpcError::Timeout {
+                method,
+                timeout: self.timeout,
+            })?
+    }
+
+    pub(crate) async fn call(
+        &self,
+        tx: <Ethereum as Network>::TransactionRequest,
+    ) -> Result<Bytes, RpcError> {
+        self.send("eth_call", async { Ok(self.provider.call(tx).await?) })
+            .await
+    }
+
+    pub(crate) async fn estimate_gas(
+        &self,
+        tx: <Ethereum as Network>::TransactionRequest,
+    ) -> Result<u64, RpcError> {
+        self.send("eth_estimateGas", async {
+            Ok(self.provider.estimate_gas(tx).await?)
+        })
+        .await
+    }
+
+    pub(crate) async fn block_number(&self) -> Result<u64, RpcError> {
+        self.send("eth_blockNumber", async {
+            Ok(self.provider.get_block_number().await?)
+        })
+        .await
+    }
+
+    pub(crate) async fn max_priority_fee_per_gas(&self) -> Result<u128, RpcError> {
+        self.send("eth_maxPriorityFeePerGas", async {
+            Ok(self.provider.get_max_priority_fee_per_gas().await?)
+        })
+        .await
+    }
+
+    /// Base fee of the next block, read from a one-block `eth_feeHistory`.
+    pub(crate) async fn next_block_base_fee(&self) -> Result<u128, RpcError> {
+        self.send("eth_feeHistory", async {
+            let history = self
+                .provider
+                .get_fee_history(1, Default::default(), &[])
+                .await?;
+            history
+                .next_block_base_fee()
+                .ok_or_else(|| RpcError::Request {
+                    method: "eth_feeHistory",
+                    message: "fee history has no base fee".to_string(),
+                })
+        })
+        .await
+    }
+
+    /// Hands the provider to a library helper that issues `method` itself,
+    /// e.g. a multicall read.
+    pub(crate) async fn with_provider<'a, T, E, Fut>(
+        &'a self,
+        method: &'static str,
+        request: impl FnOnce(&'a DynProvider<Ethereum>) -> Fut,
+    ) -> Result<T, RpcError>
+    where
+        Fut: Future<Output = Result<T, E>>,
+        E: Display,
+    {
+        self.send(method, async {
+            request(&self.provider)
+                .await
+                .map_err(|e| RpcError::Request {
+                    method,
+                    message: e.to_string(),
+                })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    async fn mock_rpc(hits: Arc<AtomicUsize>) -> Url {
+        crate::test_helpers::mock_json_rpc_server(move |_| {
+            hits.fetch_add(1, Ordering::SeqCst);
+            json!({ "result": "0x5f5e100" })
+        })
+        .await
+        .parse()
+        .expect("mock rpc url")
+    }
+
+    #[rocket::async_test]
+    async fn test_allowlisted_method_reaches_node() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let client = RpcClient::new(mock_rpc(hits.clone()).await);
+
+        let fee = client
+            .max_priority_fee_per_gas()
+            .await
+            .expect("priority fee");
+
+        assert_eq!(fee, 100_000_000);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[rocket::async_test]
+    async fn test_non_allowlisted_method_is_refused_without_a_request() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let client =
+            RpcClient::new(mock_rpc(hits.clone()).await).with_allowed_methods(&["eth_call"]);
+
+        let result = client.max_priority_fee_per_gas().await;
+
+        assert!(matches!(
+            result,
+            Err(RpcError::MethodNotAllowed("eth_maxPriorityFeePerGas"))
+        ));
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+
+        let result = client
+            .with_provider("eth_sendRawTransaction", |_| async { Ok::<_, String>(()) })
+            .await;
+        assert!(matches!(
+            result,
+            Err(RpcError::MethodNotAllowed("eth_sendRawTransaction"))
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_slow_node_times_out() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind silent rpc");
+        let addr = listener.local_addr().expect("silent rpc address");
+        tokio::spawn(async move {
+            let _held = listener.accept().await;
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+        let client = RpcClient::new(format!("http://{addr}/rpc").parse().unwrap())
+            .with_timeout(Duration::from_millis(100));
+
+        let result = client.next_block_base_fee().await;
+
+        assert!(matches!(
+            result,
+            Err(RpcError::Timeout {
+                method: "eth_feeHistory",
+                ..
+            })
+        ));
+    }
+}
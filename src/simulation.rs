@@ -0,0 +1,158 @@
+use crate::error::ApiError;
+use crate::rpc::RpcClient;
+use crate::types::swap::SwapCalldataResponse;
+use alloy::network::{Ethereum, Network, TransactionBuilder};
+use alloy::primitives::Address;
+use url::Url;
+
+/// `eth_call`s ready swap calldata from `taker` and rejects it when the call
+/// reverts. Approval-only responses carry no calldata and are skipped. Any
+/// other failure moves on to the next RPC; if none gives an answer the
+/// calldata is returned unchecked.
+pub(crate) async fn ensure_swap_calldata_executes(
+    rpcs: &[Url],
+    taker: Address,
+    response: &SwapCalldataResponse,
+) -> Result<(), ApiError> {
+    if !response.approvals.is_empty() || response.data.is_empty() {
+        return Ok(());
+    }
+
+    let tx = <Ethereum as Network>::TransactionRequest::default()
+        .with_from(taker)
+        .with_to(response.to)
+        .with_input(response.data.clone())
+        .with_value(response.value);
+
+    for (index, rpc) in rpcs.iter().enumerate() {
+        match RpcClient::new(rpc.clone()).call(tx.clone()).await {
+            Ok(_) => return Ok(()),
+            Err(e) => match e.revert_message() {
+                Some(message) => {
+                    let reason = message
+                        .strip_prefix("execution reverted")
+                        .map(|rest| rest.trim_start_matches(':').trim())
+                        .filter(|rest| !rest.is_empty())
+                        .unwrap_or(message)
+                        .to_string();
+                    tracing::warn!(reason = %reason, "swap calldata simulation reverted");
+                    return Err(ApiError::BadRequest(format!("swap would revert: {reason}")));
+                }
+                None => {
+                    tracing::warn!(rpc_index = index, error = %e, "failed to simulate swap calldata")
+                }
+            },
+        }
+    }
+
+    tracing::warn!("returning swap calldata without simulation; no RPC answered");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::common::Approval;
+    use crate::types::swap::SwapDenomination;
+    use alloy::primitives::{address, Bytes, U256};
+    use serde_json::json;
+
+    const TAKER: Address = address!("1111111111111111111111111111111111111111");
+    const ORDERBOOK: Address = address!("d2938e7c9fe3597f78832ce780feb61945c377d7");
+
+    async fn mock_call_rpc(error: Option<(i64, &'static str)>) -> Url {
+        crate::test_helpers::mock_json_rpc_server(move |_| match error {
+            Some((code, message)) => json!({
+                "error": { "code": code, "message": message, "data": "0x" }
+            }),
+            None => json!({ "result": "0x" }),
+        })
+        .await
+        .parse()
+        .expect("mock rpc url")
+    }
+
+    fn ready_response() -> SwapCalldataResponse {
+        SwapCalldataResponse {
+            to: ORDERBOOK,
+            data: Bytes::from(vec![0xab, 0xcd, 0xef]),
+            value: U256::ZERO,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            estimated_input: "150".to_string(),
+            denomination: SwapDenomination::Wrapped,
+            approvals: vec![],
+            taker_warning: None,
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_reverting_calldata_is_bad_request() {
+        let rpc = mock_call_rpc(Some((3, "execution reverted: MinimumIO"))).await;
+
+        let result = ensure_swap_calldata_executes(&[rpc], TAKER, &ready_response()).await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::BadRequest(msg)) if msg == "swap would revert: MinimumIO"
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_revert_message_without_code_is_bad_request() {
+        let rpc = mock_call_rpc(Some((-32000, "execution reverted"))).await;
+
+        let result = ensure_swap_calldata_executes(&[rpc], TAKER, &ready_response()).await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::BadRequest(msg)) if msg == "swap would revert: execution reverted"
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_node_errors_other_than_revert_skip_simulation() {
+        let rate_limited = mock_call_rpc(Some((-32005, "rate limit exceeded"))).await;
+        let unsupported = mock_call_rpc(Some((-32601, "method not found"))).await;
+
+        let result =
+            ensure_swap_calldata_executes(&[rate_limited, unsupported], TAKER, &ready_response())
+                .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[rocket::async_test]
+    async fn test_successful_call_passes() {
+        let rpc = mock_call_rpc(None).await;
+        let result = ensure_swap_calldata_executes(&[rpc], TAKER, &ready_response()).await;
+        assert!(result.is_ok());
+    }
+
+    #[rocket::async_test]
+    async fn test_approval_responses_are_not_simulated() {
+        let rpc = mock_call_rpc(Some((3, "execution reverted"))).await;
+        let response = SwapCalldataResponse {
+            data: Bytes::new(),
+            approvals: vec![Approval {
+                token: ORDERBOOK,
+                spender: ORDERBOOK,
+                amount: "1000".to_string(),
+                symbol: String::new(),
+                approval_data: Bytes::from(vec![0x09, 0x5e, 0xa7, 0xb3]),
+            }],
+            ..ready_response()
+        };
+
+        let result = ensure_swap_calldata_executes(&[rpc], TAKER, &response).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[rocket::async_test]
+    async fn test_unreachable_rpc_returns_calldata_unchecked() {
+        let unreachable: Url = "http://127.0.0.1:1/rpc".parse().unwrap();
+        let result = ensure_swap_calldata_executes(&[unreachable], TAKER, &ready_response()).await;
+        assert!(result.is_ok());
+    }
+}
@@ -1,23 +1,128 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic::PanicHookInfo;
 use std::sync::Once;
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use tracing_subscriber::{
+    fmt, layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry,
+};
 
 static TELEMETRY_INIT: Once = Once::new();
 
-pub fn init(log_dir: &str) -> Result<WorkerGuard, String> {
-    let mut guard_slot: Option<WorkerGuard> = None;
+pub(crate) const DEFAULT_LOG_FILTER: &str = "st0x_rest_api=info,rocket=warn,warn";
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<PanicDetails>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PanicLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl std::fmt::Display for PanicLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PanicDetails {
+    pub message: String,
+    pub location: Option<PanicLocation>,
+}
+
+pub(crate) fn panic_payload_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Stores the panic details for the current thread so `catch_unwind` callers can
+/// report the location, which is otherwise only available inside the hook.
+pub(crate) fn record_panic(info: &PanicHookInfo<'_>) -> PanicDetails {
+    let details = PanicDetails {
+        message: panic_payload_message(info.payload()),
+        location: info.location().map(|loc| PanicLocation {
+            file: loc.file().to_string(),
+            line: loc.line(),
+            column: loc.column(),
+        }),
+    };
+    LAST_PANIC.with(|slot| *slot.borrow_mut() = Some(details.clone()));
+    details
+}
+
+pub(crate) fn take_recorded_panic() -> Option<PanicDetails> {
+    LAST_PANIC.with(|slot| slot.borrow_mut().take())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum LogFilterError {
+    #[error("invalid log filter directive: {0}")]
+    InvalidDirective(#[from] tracing_subscriber::filter::ParseError),
+    #[error("failed to reload log filter: {0}")]
+    Reload(#[from] reload::Error),
+}
+
+/// Handle to the live `EnvFilter`, used to change log levels without a restart.
+pub(crate) struct LogFilterHandle {
+    handle: reload::Handle<EnvFilter, Registry>,
+    #[cfg(test)]
+    _detached_layer: Option<reload::Layer<EnvFilter, Registry>>,
+}
+
+impl LogFilterHandle {
+    fn new(handle: reload::Handle<EnvFilter, Registry>) -> Self {
+        Self {
+            handle,
+            #[cfg(test)]
+            _detached_layer: None,
+        }
+    }
+
+    /// A handle whose filter layer is not installed in any subscriber.
+    #[cfg(test)]
+    pub(crate) fn detached(directives: &str) -> Self {
+        let (layer, handle) = reload::Layer::new(EnvFilter::new(directives));
+        Self {
+            handle,
+            _detached_layer: Some(layer),
+        }
+    }
+
+    pub(crate) fn current(&self) -> Result<String, LogFilterError> {
+        Ok(self.handle.with_current(|filter| filter.to_string())?)
+    }
+
+    /// Parses `directives` and swaps the active filter, returning the previous one.
+    pub(crate) fn reload(&self, directives: &str) -> Result<String, LogFilterError> {
+        let filter = EnvFilter::builder().parse(directives)?;
+        let previous = self.current()?;
+        self.handle.reload(filter)?;
+        Ok(previous)
+    }
+}
+
+pub fn init(log_dir: &str) -> Result<(WorkerGuard, LogFilterHandle), String> {
+    let mut init_slot: Option<(WorkerGuard, LogFilterHandle)> = None;
     let log_dir = log_dir.to_string();
 
     TELEMETRY_INIT.call_once(|| {
         let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|e| {
             eprintln!("invalid RUST_LOG filter, using default: {e}");
-            EnvFilter::new("st0x_rest_api=info,rocket=warn,warn")
+            EnvFilter::new(DEFAULT_LOG_FILTER)
         });
+        let (filter_layer, filter_handle) = reload::Layer::new(env_filter);
         let file_appender = tracing_appender::rolling::daily(&log_dir, "st0x-rest-api.log");
         let (file_writer, file_guard) = tracing_appender::non_blocking(file_appender);
 
         let init_result = tracing_subscriber::registry()
-            .with(env_filter)
+            .with(filter_layer)
             .with(fmt::layer().json().with_current_span(false))
             .with(
                 fmt::layer()
@@ -33,28 +138,106 @@ pub fn init(log_dir: &str) -> Result<WorkerGuard, String> {
         }
 
         std::panic::set_hook(Box::new(|info| {
-            let message = info
-                .payload()
-                .downcast_ref::<&str>()
-                .map(|s| s.to_string())
-                .or_else(|| info.payload().downcast_ref::<String>().cloned())
-                .unwrap_or_else(|| "unknown panic".to_string());
-
-            if let Some(loc) = info.location() {
+            let details = record_panic(info);
+
+            if let Some(loc) = details.location {
                 tracing::error!(
-                    panic.message = %message,
-                    panic.file = loc.file(),
-                    panic.line = loc.line(),
-                    panic.column = loc.column(),
+                    panic.message = %details.message,
+                    panic.file = %loc.file,
+                    panic.line = loc.line,
+                    panic.column = loc.column,
                     "panic occurred"
                 );
             } else {
-                tracing::error!(panic.message = %message, "panic occurred");
+                tracing::error!(panic.message = %details.message, "panic occurred");
             }
         }));
 
-        guard_slot = Some(file_guard);
+        init_slot = Some((file_guard, LogFilterHandle::new(filter_handle)));
     });
 
-    guard_slot.ok_or_else(|| "telemetry::init() called more than once".to_string())
+    init_slot.ok_or_else(|| "telemetry::init() called more than once".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl CapturedLogs {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().expect("lock logs").clone()).expect("utf8 logs")
+        }
+    }
+
+    impl Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().expect("lock logs").extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_reload_enables_debug_logs() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let (layer, handle) = reload::Layer::new(EnvFilter::new(DEFAULT_LOG_FILTER));
+        let handle = LogFilterHandle::new(handle);
+        let subscriber = tracing_subscriber::registry()
+            .with(layer)
+            .with(fmt::layer().with_writer(move || writer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!("debug before reload");
+            let previous = handle
+                .reload("st0x_rest_api=debug,warn")
+                .expect("reload filter");
+            tracing::debug!("debug after reload");
+
+            assert_eq!(previous, EnvFilter::new(DEFAULT_LOG_FILTER).to_string());
+        });
+
+        let contents = logs.contents();
+        assert!(!contents.contains("debug before reload"));
+        assert!(contents.contains("debug after reload"));
+    }
+
+    #[test]
+    fn test_panic_payload_message_handles_str_string_and_other() {
+        assert_eq!(panic_payload_message(&"static str"), "static str");
+        assert_eq!(panic_payload_message(&"owned".to_string()), "owned");
+        assert_eq!(panic_payload_message(&42_u32), "unknown panic");
+    }
+
+    #[test]
+    fn test_reload_rejects_invalid_directive() {
+        let handle = LogFilterHandle::detached(DEFAULT_LOG_FILTER);
+
+        let err = handle
+            .reload("st0x_rest_api=notalevel")
+            .expect_err("invalid directive");
+
+        assert!(matches!(err, LogFilterError::InvalidDirective(_)));
+        assert_eq!(
+            handle.current().expect("current filter"),
+            EnvFilter::new(DEFAULT_LOG_FILTER).to_string()
+        );
+    }
+
+    #[test]
+    fn test_current_reflects_reloaded_filter() {
+        let handle = LogFilterHandle::detached(DEFAULT_LOG_FILTER);
+
+        handle.reload("rocket=info").expect("reload filter");
+
+        assert_eq!(handle.current().expect("current filter"), "rocket=info");
+    }
 }
@@ -17,6 +17,13 @@ pub(crate) struct TestClientBuilder {
     raindex_config: Option<crate::raindex::RaindexProvider>,
     private_registry_path: Option<std::path::PathBuf>,
     database_url: Option<String>,
+    read_database_url: Option<String>,
+    response_cache_ttl: std::time::Duration,
+    denylist: Vec<Address>,
+    allowed_owners: Vec<Address>,
+    health_requires_auth: bool,
+    root_redirects_to_swagger: bool,
+    health_error_rate_threshold: Option<f64>,
 }
 
 impl TestClientBuilder {
@@ -27,6 +34,13 @@ impl TestClientBuilder {
             raindex_config: None,
             private_registry_path: None,
             database_url: None,
+            read_database_url: None,
+            response_cache_ttl: std::time::Duration::ZERO,
+            denylist: Vec::new(),
+            allowed_owners: Vec::new(),
+            health_requires_auth: false,
+            root_redirects_to_swagger: false,
+            health_error_rate_threshold: None,
         }
     }
 
@@ -50,6 +64,42 @@ impl TestClientBuilder {
         self
     }
 
+    /// Serves read-only queries from `url`, which must already exist.
+    pub(crate) fn read_database_url(mut self, url: String) -> Self {
+        self.read_database_url = Some(url);
+        self
+    }
+
+    pub(crate) fn response_cache_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.response_cache_ttl = ttl;
+        self
+    }
+
+    pub(crate) fn denylist(mut self, addresses: Vec<Address>) -> Self {
+        self.denylist = addresses;
+        self
+    }
+
+    pub(crate) fn allowed_owners(mut self, owners: Vec<Address>) -> Self {
+        self.allowed_owners = owners;
+        self
+    }
+
+    pub(crate) fn health_requires_auth(mut self, required: bool) -> Self {
+        self.health_requires_auth = required;
+        self
+    }
+
+    pub(crate) fn root_redirects_to_swagger(mut self, redirect: bool) -> Self {
+        self.root_redirects_to_swagger = redirect;
+        self
+    }
+
+    pub(crate) fn health_error_rate_threshold(mut self, threshold: f64) -> Self {
+        self.health_error_rate_threshold = Some(threshold);
+        self
+    }
+
     pub(crate) async fn build(self) -> Client {
         let id = uuid::Uuid::new_v4();
         let database_url = self
@@ -58,6 +108,14 @@ impl TestClientBuilder {
         let pool = crate::db::init(&database_url, 5)
             .await
             .expect("database init");
+        let read_pool = match &self.read_database_url {
+            Some(url) => Some(
+                crate::db::init_read(url, 5)
+                    .await
+                    .expect("database read pool init"),
+            ),
+            None => None,
+        };
 
         let private_registry_path = self.private_registry_path.unwrap_or_else(|| {
             std::env::temp_dir().join(format!(
@@ -82,18 +140,46 @@ impl TestClientBuilder {
             }
         };
 
-        let shared_raindex = tokio::sync::RwLock::new(raindex_config);
+        let shared_raindex = std::sync::Arc::new(tokio::sync::RwLock::new(raindex_config));
         let artifact_store =
             crate::registry_artifact::RegistryArtifactStore::new(private_registry_path);
-        let response_caches =
-            crate::cache::RouteResponseCaches::new(100, std::time::Duration::from_secs(10));
-        let app_state = crate::app_state::ApplicationState::new(artifact_store, response_caches);
+        let response_caches = crate::cache::RouteResponseCaches::new(
+            100,
+            std::time::Duration::from_secs(10),
+            std::time::Duration::from_secs(10),
+        );
+        let log_filter =
+            crate::telemetry::LogFilterHandle::detached(crate::telemetry::DEFAULT_LOG_FILTER);
+        let app_state = crate::app_state::ApplicationState::new(
+            artifact_store,
+            response_caches,
+            log_filter,
+            false,
+            "0".to_string(),
+            100,
+            crate::denylist::AddressDenylist::new(self.denylist),
+            crate::error::IndexingRetryHint::default(),
+            crate::config::PageSizeDefaults::default(),
+            crate::owner_allowlist::DeployOwnerAllowlist::new(self.allowed_owners),
+            false,
+            self.health_requires_auth,
+            8,
+            4,
+            64,
+            self.root_redirects_to_swagger,
+            false,
+            365 * 24 * 60 * 60,
+            100,
+            self.health_error_rate_threshold,
+            None,
+        );
         let docs_dir = std::env::temp_dir().to_string_lossy().into_owned();
         let rocket = crate::rocket(
-            pool,
+            crate::db::Db::new(pool, read_pool),
             self.rate_limiter,
             shared_raindex,
             app_state,
+            crate::fairings::ResponseCache::new(100, self.response_cache_ttl),
             docs_dir,
             2,
         )
@@ -184,9 +270,18 @@ pub(crate) fn mock_raindex_registry_artifact_with_settings(settings: &str) -> St
 pub(crate) async fn mock_raindex_registry_url_with_settings_and_tokens(
     settings: &str,
     remote_tokens: &str,
+) -> String {
+    let remote_tokens = std::sync::Arc::new(std::sync::Mutex::new(remote_tokens.to_string()));
+    mock_raindex_registry_url_with_settings_and_shared_tokens(settings, remote_tokens).await
+}
+
+/// Like [`mock_raindex_registry_url_with_settings_and_tokens`], but serves
+/// whatever token list `remote_tokens` holds at request time.
+pub(crate) async fn mock_raindex_registry_url_with_settings_and_shared_tokens(
+    settings: &str,
+    remote_tokens: std::sync::Arc<std::sync::Mutex<String>>,
 ) -> String {
     let settings = settings.to_string();
-    let remote_tokens = remote_tokens.to_string();
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
         .await
@@ -214,11 +309,11 @@ pub(crate) async fn mock_raindex_registry_url_with_settings_and_tokens(
                 let request = String::from_utf8_lossy(&buf[..n]);
 
                 let body = if request.contains("/settings.yaml") {
-                    &settings_body
+                    settings_body
                 } else if request.contains("/tokens.json") {
-                    &remote_tokens
+                    remote_tokens.lock().expect("lock remote tokens").clone()
                 } else {
-                    &registry_body
+                    registry_body
                 };
 
                 let response = format!(
@@ -233,6 +328,82 @@ pub(crate) async fn mock_raindex_registry_url_with_settings_and_tokens(
     format!("http://{addr}/registry.txt")
 }
 
+/// Binds a local HTTP server and answers every request with the status and
+/// body `respond` returns for the raw request text. Returns the server's
+/// `http://host:port` base URL.
+pub(crate) async fn mock_http_server<F>(respond: F) -> String
+where
+    F: Fn(&str) -> (u16, String) + Send + Sync + 'static,
+{
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind mock http server");
+    let base_url = format!(
+        "http://{}",
+        listener.local_addr().expect("mock http server address")
+    );
+    serve_mock_http(listener, respond);
+    base_url
+}
+
+/// Serves [`mock_http_server`] responses on an already bound `listener`,
+/// for mocks whose bodies need to embed their own address.
+pub(crate) fn serve_mock_http<F>(listener: tokio::net::TcpListener, respond: F)
+where
+    F: Fn(&str) -> (u16, String) + Send + Sync + 'static,
+{
+    let respond = std::sync::Arc::new(respond);
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                break;
+            };
+            let respond = respond.clone();
+
+            tokio::spawn(async move {
+                let mut buf = [0u8; 8192];
+                let n = tokio::io::AsyncReadExt::read(&mut socket, &mut buf)
+                    .await
+                    .unwrap_or(0);
+                let (status, body) = respond(&String::from_utf8_lossy(&buf[..n]));
+                let reason = match status {
+                    200 => "OK",
+                    401 => "Unauthorized",
+                    404 => "Not Found",
+                    _ => "Error",
+                };
+                let response = format!(
+                    "HTTP/1.1 {status} {reason}\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = tokio::io::AsyncWriteExt::write_all(&mut socket, response.as_bytes()).await;
+            });
+        }
+    });
+}
+
+/// [`mock_http_server`] speaking JSON-RPC. `respond` gets the raw request
+/// and returns the response members (`result` or `error`); `jsonrpc` and
+/// the request's `id` are filled in. Returns the server's `/rpc` URL.
+pub(crate) async fn mock_json_rpc_server<F>(respond: F) -> String
+where
+    F: Fn(&str) -> serde_json::Value + Send + Sync + 'static,
+{
+    let base_url = mock_http_server(move |request| {
+        let body = request.split("\r\n\r\n").nth(1).unwrap_or_default();
+        let id = serde_json::from_str::<serde_json::Value>(body)
+            .ok()
+            .and_then(|request| request.get("id").cloned())
+            .unwrap_or(json!(0));
+        let mut response = respond(request);
+        response["jsonrpc"] = json!("2.0");
+        response["id"] = id;
+        (200, response.to_string())
+    })
+    .await;
+    format!("{base_url}/rpc")
+}
+
 pub(crate) async fn seed_api_key(client: &Client) -> (String, String) {
     let key_id = uuid::Uuid::new_v4().to_string();
     let secret = uuid::Uuid::new_v4().to_string();
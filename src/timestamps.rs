@@ -0,0 +1,128 @@
+use crate::types::common::TimeFormat;
+use serde_json::Value;
+
+/// JSON keys holding unix-second timestamps in order and trade responses.
+const TIMESTAMP_KEYS: &[&str] = &["createdAt", "removedAt", "timestamp"];
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Renders the timestamps of a serialized response in `format`. Unix
+/// seconds are what the response types hold, so only `iso` rewrites.
+pub(crate) fn apply_time_format(value: &mut Value, format: TimeFormat) {
+    if format == TimeFormat::Iso {
+        emit_iso_timestamps(value);
+    }
+}
+
+/// Rewrites integer timestamps under [`TIMESTAMP_KEYS`] as RFC 3339 UTC
+/// strings, e.g. `"2024-06-15T12:00:00Z"`. `null` values are left alone.
+fn emit_iso_timestamps(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, child) in object.iter_mut() {
+                if TIMESTAMP_KEYS.contains(&key.as_str()) {
+                    if let Some(seconds) = child.as_u64() {
+                        *child = Value::String(rfc3339(seconds));
+                        continue;
+                    }
+                }
+                emit_iso_timestamps(child);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(emit_iso_timestamps),
+        _ => {}
+    }
+}
+
+/// Formats unix seconds as an RFC 3339 UTC timestamp.
+pub(crate) fn rfc3339(seconds: u64) -> String {
+    let (year, month, day) = civil_from_days(seconds / SECONDS_PER_DAY);
+    let time = seconds % SECONDS_PER_DAY;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/// Proleptic Gregorian date of `days` since 1970-01-01, after Howard
+/// Hinnant's `civil_from_days`.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rfc3339_formats_known_instants() {
+        assert_eq!(rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(1_718_452_800), "2024-06-15T12:00:00Z");
+        assert_eq!(rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(rfc3339(1_704_067_199), "2023-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn test_iso_rewrites_nested_timestamps_only() {
+        let mut value = json!({
+            "createdAt": 1718452800,
+            "removedAt": null,
+            "trades": [{ "timestamp": 0, "blockNumber": 12 }],
+            "pagination": { "page": 1 }
+        });
+
+        apply_time_format(&mut value, TimeFormat::Iso);
+
+        assert_eq!(
+            value,
+            json!({
+                "createdAt": "2024-06-15T12:00:00Z",
+                "removedAt": null,
+                "trades": [{ "timestamp": "1970-01-01T00:00:00Z", "blockNumber": 12 }],
+                "pagination": { "page": 1 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_iso_rewrites_batch_results() {
+        let mut value = json!({
+            "results": {
+                "0xabc": { "status": "indexed", "result": { "timestamp": 1718452800 } },
+                "0xdef": { "status": "not_found", "message": "no trades" }
+            }
+        });
+
+        apply_time_format(&mut value, TimeFormat::Iso);
+
+        assert_eq!(
+            value["results"]["0xabc"]["result"]["timestamp"],
+            "2024-06-15T12:00:00Z"
+        );
+        assert_eq!(value["results"]["0xdef"]["message"], "no trades");
+    }
+
+    #[test]
+    fn test_unix_leaves_timestamps_as_integers() {
+        let mut value = json!({ "createdAt": 1718452800 });
+        apply_time_format(&mut value, TimeFormat::Unix);
+        assert_eq!(value, json!({ "createdAt": 1718452800 }));
+    }
+}
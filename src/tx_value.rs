@@ -0,0 +1,40 @@
+use crate::error::ApiError;
+use alloy::primitives::U256;
+use rain_math_float::Float;
+
+/// Decimals of native ETH, and of WETH which wraps it one to one.
+pub(crate) const NATIVE_DECIMALS: u8 = 18;
+
+/// `value` of a transaction that spends no native ETH. Orderbook calls
+/// (taking, adding and removing orders) and ERC-20 approvals aren't payable,
+/// so every swap, deploy and cancel transaction sends this.
+pub(crate) const NON_PAYABLE: U256 = U256::ZERO;
+
+/// `value`, in wei, of a transaction spending `amount` native ETH, such as
+/// wrapping ETH into WETH ahead of a swap.
+pub(crate) fn native_value(amount: Float) -> Result<U256, ApiError> {
+    let (wei, _) = amount
+        .to_fixed_decimal_lossy(NATIVE_DECIMALS)
+        .map_err(|e| {
+            tracing::error!(error = %e, "failed to convert native amount to wei");
+            ApiError::Internal("failed to compute transaction value".into())
+        })?;
+    Ok(wei)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_native_value_converts_to_wei() {
+        let value = native_value(Float::parse("0.5".to_string()).unwrap()).unwrap();
+        assert_eq!(value, U256::from(500_000_000_000_000_000u128));
+    }
+
+    #[test]
+    fn test_native_value_of_zero_matches_non_payable() {
+        let value = native_value(Float::parse("0".to_string()).unwrap()).unwrap();
+        assert_eq!(value, NON_PAYABLE);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::ApiError;
 use alloy::primitives::{Address, Bytes, FixedBytes};
 use rocket::form::FromFormField;
 use serde::{Deserialize, Serialize};
@@ -13,6 +14,40 @@ pub enum Denomination {
     Unwrapped,
 }
 
+/// How timestamps are rendered: unix seconds, or RFC 3339 strings.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, FromFormField, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeFormat {
+    #[default]
+    Unix,
+    Iso,
+}
+
+/// Schema for a timestamp field: unix seconds by default, or an RFC 3339
+/// string when the request sets `time_format=iso`.
+#[allow(dead_code)]
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum Timestamp {
+    #[schema(example = 1718452800)]
+    Unix(u64),
+    #[schema(example = "2024-06-15T12:00:00Z")]
+    Iso(String),
+}
+
+/// How vault ids are rendered: `0x` hex, or decimal strings.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, FromFormField, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum VaultIdFormat {
+    #[default]
+    Hex,
+    Dec,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenRef {
@@ -39,16 +74,32 @@ pub struct Approval {
     pub approval_data: Bytes,
 }
 
+/// A path segment that did not parse. Handlers take
+/// `Result<Validated*, InvalidPathParam>` and turn this into a 400 naming the
+/// parameter, instead of letting Rocket forward the request to a 422.
+#[derive(Debug)]
+pub struct InvalidPathParam {
+    expected: &'static str,
+}
+
+impl InvalidPathParam {
+    pub fn reject(self, name: &str) -> ApiError {
+        ApiError::BadRequest(format!("invalid {name}: expected {}", self.expected))
+    }
+}
+
 #[derive(Debug)]
 pub struct ValidatedAddress(pub Address);
 
 impl<'a> rocket::request::FromParam<'a> for ValidatedAddress {
-    type Error = &'a str;
+    type Error = InvalidPathParam;
 
     fn from_param(param: &'a str) -> Result<Self, Self::Error> {
         param.parse::<Address>().map(ValidatedAddress).map_err(|e| {
             tracing::warn!(input = %param, error = %e, "invalid address parameter");
-            param
+            InvalidPathParam {
+                expected: "20-byte hex",
+            }
         })
     }
 }
@@ -57,19 +108,33 @@ impl<'a> rocket::request::FromParam<'a> for ValidatedAddress {
 pub struct ValidatedFixedBytes(pub FixedBytes<32>);
 
 impl<'a> rocket::request::FromParam<'a> for ValidatedFixedBytes {
-    type Error = &'a str;
+    type Error = InvalidPathParam;
 
     fn from_param(param: &'a str) -> Result<Self, Self::Error> {
-        param
-            .parse::<FixedBytes<32>>()
+        parse_fixed_bytes_32(param)
             .map(ValidatedFixedBytes)
             .map_err(|e| {
                 tracing::warn!(input = %param, error = %e, "invalid fixed bytes parameter");
-                param
+                InvalidPathParam {
+                    expected: "32-byte hex",
+                }
             })
     }
 }
 
+/// Accepts 32 bytes of hex with or without a `0x` prefix, since explorers
+/// often show hashes unprefixed.
+fn parse_fixed_bytes_32(input: &str) -> Result<FixedBytes<32>, String> {
+    let digits = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+        .unwrap_or(input);
+    if digits.len() != 64 {
+        return Err(format!("expected 64 hex digits, got {}", digits.len()));
+    }
+    digits.parse::<FixedBytes<32>>().map_err(|e| e.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,6 +172,34 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_path_fixed_bytes_accepts_unprefixed_hex() {
+        let hex = "abcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890";
+        let prefixed = ValidatedFixedBytes::from_param(&format!("0x{hex}")).unwrap();
+        let unprefixed = ValidatedFixedBytes::from_param(hex).unwrap();
+        let upper_prefix = ValidatedFixedBytes::from_param(&format!("0X{hex}")).unwrap();
+        assert_eq!(unprefixed.0, prefixed.0);
+        assert_eq!(upper_prefix.0, prefixed.0);
+    }
+
+    #[test]
+    fn test_path_fixed_bytes_rejects_invalid_unprefixed_hex() {
+        let short = "abcdef1234567890abcdef1234567890abcdef1234567890abcdef123456789";
+        assert!(ValidatedFixedBytes::from_param(short).is_err());
+
+        let long = "abcdef1234567890abcdef1234567890abcdef1234567890abcdef12345678900";
+        assert!(ValidatedFixedBytes::from_param(long).is_err());
+
+        let non_hex = "zzcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890";
+        let err = ValidatedFixedBytes::from_param(non_hex).unwrap_err();
+        assert!(matches!(
+            err.reject("transaction hash"),
+            ApiError::BadRequest(msg) if msg == "invalid transaction hash: expected 32-byte hex"
+        ));
+
+        assert!(ValidatedFixedBytes::from_param("0x0xabcdef").is_err());
+    }
+
     #[test]
     fn test_path_fixed_bytes_rejects_garbage() {
         let result = ValidatedFixedBytes::from_param("not-a-hash");
@@ -119,6 +212,21 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_invalid_path_param_names_parameter() {
+        let err = ValidatedFixedBytes::from_param("0xabcdef").unwrap_err();
+        assert!(matches!(
+            err.reject("order hash"),
+            ApiError::BadRequest(msg) if msg == "invalid order hash: expected 32-byte hex"
+        ));
+
+        let err = ValidatedAddress::from_param("not-an-address").unwrap_err();
+        assert!(matches!(
+            err.reject("address"),
+            ApiError::BadRequest(msg) if msg == "invalid address: expected 20-byte hex"
+        ));
+    }
+
     #[test]
     fn test_path_fixed_bytes_rejects_non_hex() {
         let result = ValidatedFixedBytes::from_param(
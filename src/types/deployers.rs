@@ -0,0 +1,31 @@
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployerInfo {
+    #[schema(example = "base")]
+    pub key: String,
+    #[schema(example = "base")]
+    pub network: String,
+    #[schema(example = 8453)]
+    pub chain_id: u32,
+    #[schema(value_type = String, example = "0xC1A14cE2fd58A3A2f99deCb8eDd866204eE07f8D")]
+    pub address: Address,
+    /// Interpreter the deployer was built with, read from the deployer.
+    #[schema(value_type = Option<String>, example = "0x1b8e7A6ad7bd4D4e3B54d0a4c7D14dBbF4C1fA2e")]
+    pub interpreter: Option<Address>,
+    /// Store the deployer was built with, read from the deployer.
+    #[schema(value_type = Option<String>, example = "0x2a4c1Ce9A7a4C4f0E0b1A9e8A8F1c3c6E1B1C0d3")]
+    pub store: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "failed to read deployer contracts")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployersResponse {
+    pub deployers: Vec<DeployerInfo>,
+}
@@ -2,6 +2,28 @@ use rain_orderbook_common::raindex_client::local_db::LocalDbStatus;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RootResponse {
+    #[schema(example = "st0x REST API")]
+    pub name: String,
+
+    /// API version, as in the `X-API-Version` header
+    #[schema(example = "0.1.0")]
+    pub version: String,
+
+    /// Swagger UI
+    #[schema(example = "/swagger/")]
+    pub docs: String,
+
+    /// OpenAPI document
+    #[schema(example = "/api-doc/openapi.json")]
+    pub openapi: String,
+
+    /// Liveness check
+    #[schema(example = "/health")]
+    pub health: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct HealthResponse {
     #[schema(example = "ok")]
@@ -29,6 +51,51 @@ pub struct DetailedHealthResponse {
     pub raindex: RaindexSyncStatus,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReadinessResponse {
+    /// "ok" when every orderbook subgraph answered, "degraded" when they did
+    /// but the recent 5xx rate is above the configured threshold, otherwise
+    /// "error"
+    #[schema(example = "ok")]
+    pub status: HealthStatus,
+
+    /// Probe result for each configured orderbook's subgraph
+    pub subgraphs: Vec<SubgraphReadiness>,
+
+    /// Share of recent responses that were 5xx, once enough have been seen
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 0.02)]
+    pub recent_error_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SubgraphReadiness {
+    /// Orderbook key from raindex settings.
+    #[schema(example = "base")]
+    pub orderbook_key: String,
+
+    /// Chain ID (e.g. 8453 for Base)
+    #[schema(example = 8453)]
+    pub chain_id: u32,
+
+    /// Orderbook contract address
+    #[schema(example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook_address: String,
+
+    /// Whether the subgraph answered the probe query
+    #[schema(example = true)]
+    pub reachable: bool,
+
+    /// Latest block the subgraph has indexed, when reachable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 12345678)]
+    pub indexed_block: Option<u64>,
+
+    /// Error message if the subgraph could not be queried
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DbStatus {
     /// Component status: "ok" or "error"
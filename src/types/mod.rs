@@ -1,7 +1,10 @@
 pub mod common;
+pub mod deployers;
 pub mod health;
 pub mod order;
 pub mod orders;
+pub mod stats;
+pub mod status;
 pub mod swap;
 pub mod trades;
 pub mod vaults;
@@ -1,6 +1,8 @@
-use crate::types::common::{Approval, Denomination, TokenRef};
+use crate::types::common::{
+    Approval, Denomination, TimeFormat, Timestamp, TokenRef, VaultIdFormat,
+};
 use alloy::primitives::{Address, Bytes, FixedBytes, U256};
-use rocket::form::FromForm;
+use rocket::form::{FromForm, FromFormField};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
@@ -33,6 +35,19 @@ pub struct DeployDcaOrderRequest {
     pub input_vault_id: Option<U256>,
     #[schema(value_type = Option<String>)]
     pub output_vault_id: Option<U256>,
+    /// Not supported yet: deployments always include the deposit, so `true`
+    /// is rejected with `400`.
+    #[serde(default)]
+    pub skip_deposit: bool,
+    /// Address that will own the order. Required when the deployment only
+    /// allows specific owners.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0x1234567890abcdef1234567890abcdef12345678")]
+    pub owner: Option<Address>,
+    /// Integrator reference for reconciliation. Not supported yet:
+    /// deployments do not record it, so any value is rejected with `400`.
+    #[serde(default)]
+    pub client_ref: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -50,6 +65,19 @@ pub struct DeploySolverOrderRequest {
     pub input_vault_id: Option<U256>,
     #[schema(value_type = Option<String>)]
     pub output_vault_id: Option<U256>,
+    /// Not supported yet: deployments always include the deposit, so `true`
+    /// is rejected with `400`.
+    #[serde(default)]
+    pub skip_deposit: bool,
+    /// Address that will own the order. Required when the deployment only
+    /// allows specific owners.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0x1234567890abcdef1234567890abcdef12345678")]
+    pub owner: Option<Address>,
+    /// Integrator reference for reconciliation. Not supported yet:
+    /// deployments do not record it, so any value is rejected with `400`.
+    #[serde(default)]
+    pub client_ref: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -61,6 +89,16 @@ pub struct DeployOrderResponse {
     pub data: Bytes,
     #[schema(value_type = String, example = "0x0")]
     pub value: U256,
+    /// Suggested EIP-1559 max fee per gas in wei; `null` when the RPC
+    /// could not be queried.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0xf4610900")]
+    pub max_fee_per_gas: Option<U256>,
+    /// Suggested EIP-1559 priority fee per gas in wei; `null` when the RPC
+    /// could not be queried.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0x5f5e100")]
+    pub max_priority_fee_per_gas: Option<U256>,
     pub approvals: Vec<Approval>,
 }
 
@@ -71,6 +109,36 @@ pub struct CancelOrderRequest {
     pub order_hash: FixedBytes<32>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderLookupRequest {
+    /// ABI-encoded order struct, as returned in `raw.orderBytes`.
+    #[schema(value_type = String, example = "0x0000000000000000000000000000000000000000000000000000000000000020...")]
+    pub order_bytes: Bytes,
+}
+
+/// Expected amounts of a DCA order's next fill at its current quote.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NextFillResponse {
+    #[schema(value_type = String, example = "0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890ab")]
+    pub order_hash: FixedBytes<32>,
+    /// Token the order receives on the fill.
+    pub input_token: TokenRef,
+    /// Token the order spends on the fill.
+    pub output_token: TokenRef,
+    /// Amount of `inputToken` the order expects to receive.
+    #[schema(example = "0.5")]
+    pub input_amount: String,
+    /// Amount of `outputToken` the order expects to spend, capped by what
+    /// is left of the current period's budget.
+    #[schema(example = "1000")]
+    pub output_amount: String,
+    /// Quoted input received per unit of output spent.
+    #[schema(example = "0.0005")]
+    pub io_ratio: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CancelTransaction {
@@ -80,6 +148,16 @@ pub struct CancelTransaction {
     pub data: Bytes,
     #[schema(value_type = String, example = "0x0")]
     pub value: U256,
+    /// Suggested EIP-1559 max fee per gas in wei; `null` when the RPC
+    /// could not be queried.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0xf4610900")]
+    pub max_fee_per_gas: Option<U256>,
+    /// Suggested EIP-1559 priority fee per gas in wei; `null` when the RPC
+    /// could not be queried.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0x5f5e100")]
+    pub max_priority_fee_per_gas: Option<U256>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -99,6 +177,10 @@ pub struct CancelSummary {
     #[schema(example = 2)]
     pub vaults_to_withdraw: u32,
     pub tokens_returned: Vec<TokenReturn>,
+    /// Whether an order had more vaults than the server summarizes, so
+    /// `tokensReturned` covers only the first of them.
+    #[schema(example = false)]
+    pub vaults_truncated: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -108,6 +190,30 @@ pub struct CancelOrderResponse {
     pub summary: CancelSummary,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelAllOrdersRequest {
+    #[schema(value_type = String, example = "0x1234567890abcdef1234567890abcdef12345678")]
+    pub owner: Address,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelAllOrdersResponse {
+    /// Orders covered by `transactions`, in the order the transactions run.
+    #[schema(value_type = Vec<String>)]
+    pub order_hashes: Vec<FixedBytes<32>>,
+    pub transactions: Vec<CancelTransaction>,
+    /// Vaults and token amounts returned across all covered orders.
+    pub summary: CancelSummary,
+    /// Active orders the owner has, including any beyond the cap.
+    #[schema(example = 3)]
+    pub total_active_orders: u32,
+    /// Whether the owner has more active orders than this response covers.
+    #[schema(example = false)]
+    pub truncated: bool,
+}
+
 #[derive(Debug, Clone, FromForm, Serialize, Deserialize, IntoParams)]
 #[into_params(parameter_in = Query)]
 #[serde(rename_all = "camelCase")]
@@ -115,6 +221,59 @@ pub struct OrderDetailParams {
     #[field(name = "denomination")]
     #[param(example = "wrapped")]
     pub denomination: Option<Denomination>,
+    /// Comma-separated top-level fields to return, e.g. `order_hash,io_ratio`.
+    #[field(name = "fields")]
+    #[param(example = "order_hash,io_ratio")]
+    pub fields: Option<String>,
+    /// Emit amounts as JSON numbers when an `f64` holds them exactly.
+    #[field(name = "numeric_amounts")]
+    #[serde(rename = "numeric_amounts")]
+    #[param(example = false)]
+    pub numeric_amounts: Option<bool>,
+    /// Add `*Display` amounts grouped by thousands with the token symbol.
+    #[field(name = "display")]
+    #[param(example = false)]
+    pub display: Option<bool>,
+    /// Skip the order lookup cache and fetch the order from the subgraph.
+    #[field(name = "noCache")]
+    #[param(example = false)]
+    pub no_cache: Option<bool>,
+    /// Comma-separated extras to embed; `raw` adds the encoded order struct
+    /// and its decoded inputs and outputs.
+    #[field(name = "include")]
+    #[param(example = "raw")]
+    pub include: Option<String>,
+    /// Which trades to embed: none, the most recent ones, or all of them.
+    #[field(name = "trades")]
+    #[param(example = "recent")]
+    pub trades: Option<OrderTradesMode>,
+    /// `iso` renders timestamps as RFC 3339 strings instead of unix seconds.
+    #[field(name = "time_format")]
+    #[serde(rename = "time_format")]
+    #[param(example = "unix")]
+    pub time_format: Option<TimeFormat>,
+    /// `dec` renders vault ids as decimal strings instead of `0x` hex.
+    #[field(name = "vault_id_format")]
+    #[serde(rename = "vault_id_format")]
+    #[param(example = "hex")]
+    pub vault_id_format: Option<VaultIdFormat>,
+    /// Admin keys only: attach the raw subgraph order under `_raw`.
+    /// Ignored for other keys.
+    #[field(name = "debug_raw")]
+    #[serde(rename = "debug_raw")]
+    #[param(example = false)]
+    pub debug_raw: Option<bool>,
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, FromFormField, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderTradesMode {
+    None,
+    #[default]
+    Recent,
+    All,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq)]
@@ -122,6 +281,7 @@ pub struct OrderDetailParams {
 pub enum OrderType {
     Dca,
     Solver,
+    Unknown,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -130,8 +290,9 @@ pub struct OrderDetailsInfo {
     #[serde(rename = "type")]
     #[schema(example = "dca")]
     pub type_: OrderType,
-    #[schema(example = "0.0005")]
-    pub io_ratio: String,
+    /// Current quoted IO ratio; `null` when the order could not be quoted.
+    #[schema(nullable, example = "0.0005")]
+    pub io_ratio: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -145,12 +306,43 @@ pub struct OrderTradeEntry {
     pub input_amount: String,
     #[schema(example = "500000")]
     pub output_amount: String,
-    #[schema(example = 1718452800)]
+    #[schema(value_type = Timestamp, example = 1718452800)]
     pub timestamp: u64,
     #[schema(value_type = String, example = "0x1234567890abcdef1234567890abcdef12345678")]
     pub sender: Address,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderIoEntry {
+    pub token: TokenRef,
+    #[schema(value_type = String, example = "0x1")]
+    pub vault_id: U256,
+    #[schema(example = "1000000")]
+    pub vault_balance: String,
+}
+
+/// One `IOV2` entry of the on-chain order struct.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RawOrderIo {
+    #[schema(value_type = String, example = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913")]
+    pub token: Address,
+    #[schema(value_type = String, example = "0x0000000000000000000000000000000000000000000000000000000000000001")]
+    pub vault_id: FixedBytes<32>,
+}
+
+/// The order as stored on-chain, for clients that rebuild it off-chain.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RawOrder {
+    /// ABI-encoded `OrderV4`.
+    #[schema(value_type = String, example = "0x0000000000000000000000000000000000000000000000000000000000000020")]
+    pub order_bytes: Bytes,
+    pub valid_inputs: Vec<RawOrderIo>,
+    pub valid_outputs: Vec<RawOrderIo>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderDetail {
@@ -159,23 +351,71 @@ pub struct OrderDetail {
     #[schema(value_type = String, example = "0x1234567890abcdef1234567890abcdef12345678")]
     pub owner: Address,
     pub order_details: OrderDetailsInfo,
+    /// Deprecated: the first input, kept for compatibility. Use `inputs`.
     pub input_token: TokenRef,
+    /// Deprecated: the first output, kept for compatibility. Use `outputs`.
     pub output_token: TokenRef,
+    /// Deprecated: use `inputs[].vaultId`.
     #[schema(value_type = String, example = "0x1")]
     pub input_vault_id: U256,
+    /// Deprecated: use `outputs[].vaultId`.
     #[schema(value_type = String, example = "0x2")]
     pub output_vault_id: U256,
+    /// Deprecated: use `inputs[].vaultBalance`.
     #[schema(example = "1000000")]
     pub input_vault_balance: String,
+    /// Deprecated: use `outputs[].vaultBalance`.
     #[schema(example = "500000")]
     pub output_vault_balance: String,
-    #[schema(example = "0.0005")]
-    pub io_ratio: String,
-    #[schema(example = 1718452800)]
+    /// Every input token of the order with its vault.
+    pub inputs: Vec<OrderIoEntry>,
+    /// Every output token of the order with its vault.
+    pub outputs: Vec<OrderIoEntry>,
+    /// Current quoted IO ratio; `null` when the order could not be quoted.
+    #[schema(nullable, example = "0.0005")]
+    pub io_ratio: Option<String>,
+    #[schema(value_type = Timestamp, example = 1718452800)]
     pub created_at: u64,
-    #[schema(value_type = String, example = "0x1234567890abcdef1234567890abcdef12345678")]
+    /// Orderbook contract address, EIP-55 checksummed like every other
+    /// address in responses, whatever casing the subgraph used.
+    #[schema(value_type = String, example = "0xd2938E7c9fe3597F78832CE780Feb61945c377d7")]
     pub orderbook_id: Address,
     pub trades: Vec<OrderTradeEntry>,
+    /// `false` when the order's trades could not be loaded, in which case
+    /// `trades` is empty rather than authoritative.
+    #[schema(example = true)]
+    pub trades_loaded: bool,
+    /// Present only with `include=raw`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<RawOrder>,
+    /// Value of the `client-ref` field in the order's builder state, when
+    /// it has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "acme-order-1234")]
+    pub client_ref: Option<String>,
+}
+
+impl crate::fields::SparseFields for OrderDetail {
+    const FIELDS: &'static [&'static str] = &[
+        "orderHash",
+        "owner",
+        "orderDetails",
+        "inputToken",
+        "outputToken",
+        "inputVaultId",
+        "outputVaultId",
+        "inputVaultBalance",
+        "outputVaultBalance",
+        "inputs",
+        "outputs",
+        "ioRatio",
+        "createdAt",
+        "orderbookId",
+        "trades",
+        "tradesLoaded",
+        "raw",
+        "clientRef",
+    ];
 }
 
 #[cfg(test)]
@@ -209,7 +449,7 @@ mod tests {
     fn test_order_details_info_type_rename() {
         let info = OrderDetailsInfo {
             type_: OrderType::Dca,
-            io_ratio: "0.0005".into(),
+            io_ratio: Some("0.0005".into()),
         };
         let json = serde_json::to_string(&info).unwrap();
         assert!(json.contains("\"type\":\"dca\""));
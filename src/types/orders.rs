@@ -1,4 +1,4 @@
-use crate::types::common::{Denomination, TokenRef};
+use crate::types::common::{Denomination, TimeFormat, Timestamp, TokenRef};
 use alloy::primitives::{Address, Bytes, FixedBytes};
 use rocket::form::{FromForm, FromFormField};
 use serde::{Deserialize, Serialize};
@@ -19,6 +19,11 @@ pub struct OrdersPaginationParams {
     #[field(name = "denomination")]
     #[param(example = "wrapped")]
     pub denomination: Option<Denomination>,
+    /// `iso` renders timestamps as RFC 3339 strings instead of unix seconds.
+    #[field(name = "time_format")]
+    #[serde(rename = "time_format")]
+    #[param(example = "unix")]
+    pub time_format: Option<TimeFormat>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromFormField, ToSchema)]
@@ -67,6 +72,11 @@ pub struct OrdersByTokenParams {
     #[field(name = "denomination")]
     #[param(example = "wrapped")]
     pub denomination: Option<Denomination>,
+    /// `iso` renders timestamps as RFC 3339 strings instead of unix seconds.
+    #[field(name = "time_format")]
+    #[serde(rename = "time_format")]
+    #[param(example = "unix")]
+    pub time_format: Option<TimeFormat>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -82,7 +92,7 @@ pub struct OrderSummary {
     pub order_bytes: Bytes,
     #[schema(example = true)]
     pub active: bool,
-    #[schema(example = 1718452900)]
+    #[schema(value_type = Option<Timestamp>, example = 1718452900)]
     pub removed_at: Option<u64>,
     #[schema(example = "limit")]
     pub order_type: OrderSummaryOrderType,
@@ -94,7 +104,7 @@ pub struct OrderSummary {
     pub max_output: Option<String>,
     #[schema(example = "0.0005")]
     pub io_ratio: String,
-    #[schema(example = 1718452800)]
+    #[schema(value_type = Timestamp, example = 1718452800)]
     pub created_at: u64,
     #[schema(value_type = String, example = "0x1234567890abcdef1234567890abcdef12345678")]
     pub orderbook_id: Address,
@@ -113,6 +123,10 @@ pub struct OrdersPagination {
     pub total_pages: u64,
     #[schema(example = true)]
     pub has_more: bool,
+    /// `true` when orders may be hidden from pages, so `totalOrders` and
+    /// `totalPages` count orders that are never listed.
+    #[schema(example = false)]
+    pub total_is_approximate: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
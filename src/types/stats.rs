@@ -0,0 +1,57 @@
+use crate::types::common::TokenRef;
+use rocket::form::FromForm;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Default, FromForm, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeQueryParams {
+    #[field(name = "window")]
+    #[param(example = "24h")]
+    pub window: Option<String>,
+    #[field(name = "token")]
+    #[param(example = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913")]
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenVolume {
+    pub token: TokenRef,
+    #[schema(example = "125000.5")]
+    pub volume: String,
+    #[schema(example = 42)]
+    pub trade_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PairOpenInterest {
+    pub input_token: TokenRef,
+    pub output_token: TokenRef,
+    /// Sum of the active orders' output vault balances, in the output token.
+    /// A vault shared by several orders is counted once.
+    #[schema(example = "250000.75")]
+    pub open_interest: String,
+    #[schema(example = 12)]
+    pub order_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenInterestResponse {
+    pub pairs: Vec<PairOpenInterest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeStatsResponse {
+    #[schema(example = "24h")]
+    pub window: String,
+    #[schema(example = 1718366400)]
+    pub start_time: u64,
+    #[schema(example = 1718452800)]
+    pub end_time: u64,
+    pub volumes: Vec<TokenVolume>,
+}
@@ -0,0 +1,32 @@
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderbookIndexingStatus {
+    #[schema(example = "base")]
+    pub key: String,
+    #[schema(example = 8453)]
+    pub chain_id: u32,
+    #[schema(value_type = String, example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub address: Address,
+    /// Latest block the orderbook subgraph has indexed.
+    #[schema(example = 12345670)]
+    pub indexed_block: Option<u64>,
+    /// Chain head reported by the network RPC.
+    #[schema(example = 12345678)]
+    pub head_block: Option<u64>,
+    /// Blocks the subgraph is behind the chain head.
+    #[schema(example = 8)]
+    pub lag_blocks: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "failed to query subgraph")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexingStatusResponse {
+    pub orderbooks: Vec<OrderbookIndexingStatus>,
+}
@@ -18,11 +18,32 @@ pub struct SwapQuoteRequest {
     pub input_token: Address,
     #[schema(value_type = String, example = "0x4200000000000000000000000000000000000006")]
     pub output_token: Address,
+    /// Output to buy. Exactly one of `outputAmount` and `inputAmount` is required.
+    #[serde(default)]
     #[schema(example = "0.5")]
-    pub output_amount: String,
+    pub output_amount: Option<String>,
+    /// Input budget to spend, greater than zero and in `denomination`.
+    /// Exactly one of `outputAmount` and `inputAmount` is required.
+    #[serde(default)]
+    #[schema(example = "1250")]
+    pub input_amount: Option<String>,
     #[serde(default)]
     #[schema(example = "wrapped", default = "wrapped")]
     pub denomination: SwapDenomination,
+    /// Highest IO ratio to fill at; omit to quote at any price.
+    #[serde(default)]
+    #[schema(example = "2600")]
+    pub max_io_ratio: Option<String>,
+    /// Least output-token depth, in orderbook units, the pair must offer
+    /// before it is quoted. Overrides the server default; `"0"` disables it.
+    #[serde(default)]
+    #[schema(example = "0.1")]
+    pub min_liquidity: Option<String>,
+    /// Restricts routing to this orderbook; omit to aggregate across every
+    /// orderbook in the registry.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook: Option<Address>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -32,8 +53,17 @@ pub struct SwapQuoteResponse {
     pub input_token: Address,
     #[schema(value_type = String, example = "0x4200000000000000000000000000000000000006")]
     pub output_token: Address,
+    /// Symbol of `inputToken` from the token list; empty when unknown.
+    #[schema(example = "USDC")]
+    pub input_symbol: String,
+    /// Symbol of `outputToken` from the token list; empty when unknown.
+    #[schema(example = "WETH")]
+    pub output_symbol: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[schema(example = "0.5")]
-    pub output_amount: String,
+    pub output_amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_amount: Option<String>,
     #[schema(example = "wrapped")]
     pub denomination: SwapDenomination,
     #[schema(example = "0.5")]
@@ -42,6 +72,91 @@ pub struct SwapQuoteResponse {
     pub estimated_input: String,
     #[schema(example = "2501.5")]
     pub estimated_io_ratio: String,
+    /// Part of `outputAmount` that could not be filled within `maxIoRatio`
+    /// or the available liquidity, in `denomination`. Absent for
+    /// input-specified quotes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "0")]
+    pub unfilled_output: Option<String>,
+    /// The `outputAmount` the quote was requested for, in `denomination`.
+    /// Absent for input-specified quotes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "0.5")]
+    pub requested_output: Option<String>,
+    /// Output the simulation filled, in `denomination`; equal to
+    /// `estimatedOutput`.
+    #[schema(example = "0.5")]
+    pub filled_output: String,
+    /// Part of `inputAmount` left unspent, in `denomination`. Only present
+    /// for input-specified quotes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unspent_input: Option<String>,
+    /// Whether the full requested amount (output to buy or input to spend)
+    /// can be filled.
+    #[schema(example = true)]
+    pub fully_filled: bool,
+    /// Advisory price impact: how far `estimatedIoRatio` sits above the best
+    /// single order's ratio, in basis points. Absent when it cannot be
+    /// derived.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "12.5")]
+    pub price_impact_bps: Option<String>,
+    /// Quoted orders with a price for the pair that the simulation could
+    /// draw on.
+    #[schema(example = 4)]
+    pub orders_considered: u32,
+    /// Orders the simulation actually filled against, cheapest first.
+    #[schema(example = 2)]
+    pub orders_used: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapQuoteBothRequest {
+    #[schema(value_type = String, example = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913")]
+    pub input_token: Address,
+    #[schema(value_type = String, example = "0x4200000000000000000000000000000000000006")]
+    pub output_token: Address,
+    /// Amount of `outputToken` to buy on one side and sell on the other.
+    #[schema(example = "0.5")]
+    pub amount: String,
+    #[serde(default)]
+    #[schema(example = "wrapped", default = "wrapped")]
+    pub denomination: SwapDenomination,
+    /// Restricts routing to this orderbook; omit to aggregate across every
+    /// orderbook in the registry.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook: Option<Address>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapQuoteBothResponse {
+    #[schema(value_type = String, example = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913")]
+    pub input_token: Address,
+    #[schema(value_type = String, example = "0x4200000000000000000000000000000000000006")]
+    pub output_token: Address,
+    #[schema(example = "0.5")]
+    pub amount: String,
+    /// Buying `amount` of `outputToken` with `inputToken`; `null` when
+    /// nothing sells `outputToken` for `inputToken`.
+    pub buy: Option<SwapQuoteResponse>,
+    /// Selling `amount` of `outputToken` for `inputToken`, quoted as a swap
+    /// from `outputToken` to `inputToken`; `null` when nothing buys it.
+    pub sell: Option<SwapQuoteResponse>,
+    /// `inputToken` paid per `outputToken` bought.
+    #[schema(example = "2501.5")]
+    pub ask: Option<String>,
+    /// `inputToken` received per `outputToken` sold.
+    #[schema(example = "2498.5")]
+    pub bid: Option<String>,
+    /// `ask` minus `bid`; `null` unless both sides quote.
+    #[schema(example = "3")]
+    pub spread: Option<String>,
+    /// `spread` over the mid price, in basis points.
+    #[schema(example = "12")]
+    pub spread_bps: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -60,6 +175,11 @@ pub struct SwapCalldataRequest {
     #[serde(default)]
     #[schema(example = "wrapped", default = "wrapped")]
     pub denomination: SwapDenomination,
+    /// Restricts routing to this orderbook; omit to aggregate across every
+    /// orderbook in the registry.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook: Option<Address>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
@@ -88,6 +208,11 @@ pub struct SwapCalldataV2Request {
     #[serde(default)]
     #[schema(example = "wrapped", default = "wrapped")]
     pub denomination: SwapDenomination,
+    /// Restricts routing to this orderbook; omit to aggregate across every
+    /// orderbook in the registry.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook: Option<Address>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -99,9 +224,170 @@ pub struct SwapCalldataResponse {
     pub data: Bytes,
     #[schema(value_type = String, example = "0x0")]
     pub value: U256,
+    /// Suggested EIP-1559 max fee per gas in wei; `null` when the RPC
+    /// could not be queried.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0xf4610900")]
+    pub max_fee_per_gas: Option<U256>,
+    /// Suggested EIP-1559 priority fee per gas in wei; `null` when the RPC
+    /// could not be queried.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0x5f5e100")]
+    pub max_priority_fee_per_gas: Option<U256>,
     #[schema(example = "1250.75")]
     pub estimated_input: String,
     #[schema(example = "wrapped")]
     pub denomination: SwapDenomination,
     pub approvals: Vec<Approval>,
+    /// Set when `taker` is a contract known not to sign transactions, such
+    /// as the orderbook or one of the swapped tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "taker is the orderbook contract and cannot sign transactions")]
+    pub taker_warning: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapExecutePlanRequest {
+    #[schema(value_type = String, example = "0x1234567890abcdef1234567890abcdef12345678")]
+    pub taker: Address,
+    #[schema(value_type = String, example = "0x4200000000000000000000000000000000000006")]
+    pub input_token: Address,
+    #[schema(value_type = String, example = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913")]
+    pub output_token: Address,
+    /// Exact amount of `outputToken` to receive; the swap reverts otherwise.
+    #[schema(example = "1000")]
+    pub output_amount: String,
+    /// Highest input paid per unit of output.
+    #[schema(example = "0.0005")]
+    pub maximum_io_ratio: String,
+    /// Prepends a step wrapping native ETH into WETH. Only valid when
+    /// `inputToken` is WETH.
+    #[serde(default)]
+    #[schema(example = false, default = false)]
+    pub wrap_native: bool,
+    /// Appends a step unwrapping the bought WETH into native ETH. Only valid
+    /// when `outputToken` is WETH.
+    #[serde(default)]
+    #[schema(example = false, default = false)]
+    pub unwrap_output: bool,
+    /// Seconds the plan stays valid for; defaults to 300.
+    #[serde(default)]
+    #[schema(example = 300)]
+    pub deadline_seconds: Option<u64>,
+    #[serde(default)]
+    #[schema(example = "wrapped", default = "wrapped")]
+    pub denomination: SwapDenomination,
+    /// Restricts routing to this orderbook; omit to aggregate across every
+    /// orderbook in the registry.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook: Option<Address>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SwapPlanStepKind {
+    Wrap,
+    Approve,
+    Swap,
+    Unwrap,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapPlanStep {
+    #[schema(example = "approve")]
+    pub kind: SwapPlanStepKind,
+    #[schema(example = "Approve 0.5 WETH for the orderbook")]
+    pub description: String,
+    #[schema(value_type = String, example = "0x4200000000000000000000000000000000000006")]
+    pub to: Address,
+    #[schema(value_type = String, example = "0x095ea7b3...")]
+    pub data: Bytes,
+    #[schema(value_type = String, example = "0x0")]
+    pub value: U256,
+    /// `null` when no RPC could estimate the step, e.g. because it depends
+    /// on an earlier step being mined.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0xb5f0")]
+    pub estimated_gas: Option<U256>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapExecutePlanResponse {
+    /// Transactions to send in order.
+    pub steps: Vec<SwapPlanStep>,
+    /// `false` when approvals must be mined before the swap can be built;
+    /// send them, then request the plan again.
+    #[schema(example = true)]
+    pub complete: bool,
+    /// Output the swap guarantees; it reverts rather than deliver less.
+    #[schema(example = "1000")]
+    pub minimum_output: String,
+    /// Most input the swap can spend at `maximumIoRatio`.
+    #[schema(example = "0.5")]
+    pub maximum_input: String,
+    #[schema(example = "0.48")]
+    pub estimated_input: String,
+    /// Unix time after which the plan should be rebuilt instead of sent.
+    #[schema(example = 1781506671)]
+    pub deadline: u64,
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0xf4610900")]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0x5f5e100")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    #[schema(example = "wrapped")]
+    pub denomination: SwapDenomination,
+    /// Whether the plan ends by unwrapping the bought WETH, delivering
+    /// native ETH instead.
+    #[schema(example = false)]
+    pub unwrapped: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapCurveRequest {
+    #[schema(value_type = String, example = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913")]
+    pub input_token: Address,
+    #[schema(value_type = String, example = "0x4200000000000000000000000000000000000006")]
+    pub output_token: Address,
+    /// Output amounts to price, at most 20.
+    #[schema(example = json!(["0.1", "1", "10"]))]
+    pub sizes: Vec<String>,
+    /// Restricts routing to this orderbook; omit to aggregate across every
+    /// orderbook in the registry.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook: Option<Address>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapCurvePoint {
+    /// Output amount this point was priced for, as requested.
+    #[schema(example = "1")]
+    pub size: String,
+    #[schema(example = "2501.5")]
+    pub estimated_input: String,
+    /// Input paid per unit of output filled; `null` when nothing fills.
+    #[schema(example = "2501.5")]
+    pub blended_ratio: Option<String>,
+    /// `false` when the available liquidity can't fill `size`.
+    #[schema(example = true)]
+    pub fully_filled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapCurveResponse {
+    #[schema(value_type = String, example = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913")]
+    pub input_token: Address,
+    #[schema(value_type = String, example = "0x4200000000000000000000000000000000000006")]
+    pub output_token: Address,
+    /// One point per requested size, in request order.
+    pub points: Vec<SwapCurvePoint>,
 }
@@ -1,4 +1,4 @@
-use crate::types::common::{Denomination, TokenRef};
+use crate::types::common::{Denomination, TimeFormat, Timestamp, TokenRef};
 use alloy::primitives::{Address, FixedBytes, B256};
 use rocket::form::FromForm;
 use serde::{Deserialize, Serialize};
@@ -23,6 +23,22 @@ pub struct TradesPaginationParams {
     #[field(name = "denomination")]
     #[param(example = "wrapped")]
     pub denomination: Option<Denomination>,
+    /// Add `*Display` amounts grouped by thousands with the token symbol.
+    #[field(name = "display")]
+    #[param(example = false)]
+    pub display: Option<bool>,
+    /// `iso` renders timestamps as RFC 3339 strings instead of unix seconds.
+    #[field(name = "time_format")]
+    #[serde(rename = "time_format")]
+    #[param(example = "unix")]
+    pub time_format: Option<TimeFormat>,
+    /// Admin keys only: attach the page's raw subgraph trades under `_raw`
+    /// on `GET /v1/trades/{address}`. Ignored for other keys and on the
+    /// cached token and taker listings.
+    #[field(name = "debug_raw")]
+    #[serde(rename = "debug_raw")]
+    #[param(example = false)]
+    pub debug_raw: Option<bool>,
 }
 
 #[derive(Debug, Clone, FromForm, Serialize, Deserialize, IntoParams)]
@@ -32,6 +48,17 @@ pub struct TradesByTxParams {
     #[field(name = "denomination")]
     #[param(example = "wrapped")]
     pub denomination: Option<Denomination>,
+    /// Admin keys only: attach the raw subgraph trades under `_raw`.
+    /// Ignored for other keys.
+    #[field(name = "debug_raw")]
+    #[serde(rename = "debug_raw")]
+    #[param(example = false)]
+    pub debug_raw: Option<bool>,
+    /// `iso` renders timestamps as RFC 3339 strings instead of unix seconds.
+    #[field(name = "time_format")]
+    #[serde(rename = "time_format")]
+    #[param(example = "unix")]
+    pub time_format: Option<TimeFormat>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -47,7 +74,7 @@ pub struct TradeByAddress {
     pub output_token: TokenRef,
     #[schema(value_type = Option<String>)]
     pub order_hash: Option<FixedBytes<32>>,
-    #[schema(example = 1718452800)]
+    #[schema(value_type = Timestamp, example = 1718452800)]
     pub timestamp: u64,
     #[schema(example = 12345678)]
     pub block_number: u64,
@@ -160,10 +187,64 @@ pub struct TradesByTxResponse {
     pub tx_hash: FixedBytes<32>,
     #[schema(example = 12345678)]
     pub block_number: u64,
-    #[schema(example = 1718452800)]
+    #[schema(value_type = Timestamp, example = 1718452800)]
     pub timestamp: u64,
     #[schema(value_type = String, example = "0x1234567890abcdef1234567890abcdef12345678")]
     pub sender: Address,
     pub trades: Vec<TradeByTxEntry>,
     pub totals: TradesTotals,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TradesByTxBatchRequest {
+    #[schema(
+        value_type = Vec<String>,
+        example = json!(["0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890"])
+    )]
+    pub tx_hashes: Vec<String>,
+    #[schema(example = "wrapped")]
+    pub denomination: Option<Denomination>,
+    /// `continuation` from a truncated response. Send it with the same
+    /// `txHashes` to resolve the hashes left out.
+    #[serde(default)]
+    #[schema(example = "MiwzLDQ")]
+    pub continuation: Option<String>,
+    /// `iso` renders timestamps as RFC 3339 strings instead of unix seconds.
+    #[serde(default)]
+    #[schema(example = "unix")]
+    pub time_format: Option<TimeFormat>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TradesByTxBatchStatus {
+    Indexed,
+    NotYetIndexed,
+    NotFound,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TradesByTxBatchEntry {
+    pub status: TradesByTxBatchStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<TradesByTxResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "transaction not yet indexed")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TradesByTxBatchResponse {
+    /// One entry per distinct requested transaction hash, keyed by the
+    /// lowercase 0x-prefixed hash.
+    pub results: std::collections::BTreeMap<String, TradesByTxBatchEntry>,
+    /// Present when the response hit its size cap before every hash was
+    /// included; pass it back as `continuation` to fetch the rest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "MiwzLDQ")]
+    pub continuation: Option<String>,
+}
@@ -0,0 +1,103 @@
+use crate::types::common::VaultIdFormat;
+use alloy::primitives::U256;
+use serde_json::Value;
+
+/// JSON keys holding vault ids in order responses.
+const VAULT_ID_KEYS: &[&str] = &["inputVaultId", "outputVaultId", "vaultId"];
+
+/// The encoded on-chain order, whose vault ids stay as `bytes32` hex.
+const RAW_ORDER_KEY: &str = "raw";
+
+/// Renders the vault ids of a serialized response in `format`. `U256`
+/// serializes as `0x` hex, so only `dec` rewrites.
+pub(crate) fn apply_vault_id_format(value: &mut Value, format: VaultIdFormat) {
+    if format == VaultIdFormat::Dec {
+        emit_decimal_vault_ids(value);
+    }
+}
+
+/// Rewrites hex vault ids under [`VAULT_ID_KEYS`] as base-10 strings. The
+/// conversion goes through `U256`, so full 256-bit ids keep every digit.
+fn emit_decimal_vault_ids(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, child) in object.iter_mut() {
+                if key == RAW_ORDER_KEY {
+                    continue;
+                }
+                if VAULT_ID_KEYS.contains(&key.as_str()) {
+                    if let Some(decimal) = child.as_str().and_then(hex_to_decimal) {
+                        *child = Value::String(decimal);
+                        continue;
+                    }
+                }
+                emit_decimal_vault_ids(child);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(emit_decimal_vault_ids),
+        _ => {}
+    }
+}
+
+fn hex_to_decimal(hex: &str) -> Option<String> {
+    let digits = hex.strip_prefix("0x")?;
+    U256::from_str_radix(digits, 16)
+        .ok()
+        .map(|id| id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_dec_renders_full_width_vault_ids_exactly() {
+        let large = U256::MAX - U256::from(1);
+        let hex = serde_json::to_value(large).unwrap();
+        assert_eq!(
+            hex,
+            json!("0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe")
+        );
+        let mut value = json!({
+            "inputVaultId": hex,
+            "outputVaultId": "0x2",
+            "inputs": [{ "vaultId": hex, "vaultBalance": "1" }],
+        });
+
+        apply_vault_id_format(&mut value, VaultIdFormat::Dec);
+
+        let decimal =
+            "115792089237316195423570985008687907853269984665640564039457584007913129639934";
+        assert_eq!(decimal.parse::<U256>().unwrap(), large);
+        assert_eq!(
+            value,
+            json!({
+                "inputVaultId": decimal,
+                "outputVaultId": "2",
+                "inputs": [{ "vaultId": decimal, "vaultBalance": "1" }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_dec_leaves_raw_order_bytes_alone() {
+        let raw_id = "0x0000000000000000000000000000000000000000000000000000000000000001";
+        let mut value = json!({
+            "inputVaultId": "0x1",
+            "raw": { "validInputs": [{ "vaultId": raw_id }] },
+        });
+
+        apply_vault_id_format(&mut value, VaultIdFormat::Dec);
+
+        assert_eq!(value["inputVaultId"], "1");
+        assert_eq!(value["raw"]["validInputs"][0]["vaultId"], raw_id);
+    }
+
+    #[test]
+    fn test_hex_leaves_vault_ids_unchanged() {
+        let mut value = json!({ "inputVaultId": "0x1" });
+        apply_vault_id_format(&mut value, VaultIdFormat::Hex);
+        assert_eq!(value, json!({ "inputVaultId": "0x1" }));
+    }
+}
@@ -1,9 +1,11 @@
+use crate::block_number::block_number_i64;
 use crate::db::wrapped_exchange_rate_history::{
     insert_wrapped_exchange_rate_snapshots, NewWrappedExchangeRateSnapshot,
 };
 use crate::db::DbPool;
 use crate::erc4626::batch_share_ratios;
 use crate::error::ApiError;
+use crate::raindex::require_rpcs;
 use alloy::primitives::Address;
 use rain_erc::erc4626::{Erc4626BatchItem, Erc4626BatchResponse, Erc4626BatchVault};
 use rain_orderbook_app_settings::token::TokenCfg;
@@ -243,7 +245,8 @@ pub(crate) async fn read_wrap_ratios_batch(
             .map(|input| Erc4626BatchVault::new(input.token.address))
             .collect();
 
-        let response = batch_share_ratios(&first_input.token.network.rpcs, vaults, None)
+        let rpcs = require_rpcs(chain_id, first_input.token.network.rpcs.clone())?;
+        let response = batch_share_ratios(&rpcs, vaults, None)
             .await
             .map_err(|error| {
                 tracing::error!(
@@ -385,13 +388,17 @@ pub(crate) async fn persist_wrap_ratio_snapshots_best_effort(
 
 fn wrap_ratio_snapshot_from_response(
     response: &WrapRatioResponse,
-) -> Result<NewWrappedExchangeRateSnapshot, std::num::TryFromIntError> {
+) -> Result<NewWrappedExchangeRateSnapshot, ApiError> {
     Ok(NewWrappedExchangeRateSnapshot {
         share_token_address: normalized_address(response.share_address),
         asset_token_address: normalized_address(response.asset_address),
         assets_per_share: response.assets_per_share.clone(),
-        block_number: i64::try_from(response.block_number)?,
-        block_timestamp: response.block_timestamp.map(i64::try_from).transpose()?,
+        block_number: block_number_i64(response.block_number)?,
+        block_timestamp: response
+            .block_timestamp
+            .map(i64::try_from)
+            .transpose()
+            .map_err(|_| ApiError::Internal("block timestamp overflow".into()))?,
         captured_at: response.captured_at.clone(),
     })
 }
@@ -591,4 +598,36 @@ mod tests {
         assert_eq!(snapshot.block_timestamp, Some(456));
         assert_eq!(snapshot.captured_at, "2026-06-02T13:00:00Z");
     }
+
+    #[test]
+    fn test_wrap_ratio_snapshot_from_response_rejects_overflowing_block_number() {
+        let item = successful_batch_item(WT_MSTR, T_MSTR);
+        let mut response =
+            build_wrap_ratio_response(&item, T_MSTR, &metadata()).expect("ratio should build");
+        response.block_number = u64::MAX;
+
+        assert!(matches!(
+            wrap_ratio_snapshot_from_response(&response),
+            Err(ApiError::Internal(message)) if message == "block number overflow"
+        ));
+    }
+
+    #[rocket::async_test]
+    async fn test_read_wrap_ratios_batch_names_chain_without_rpcs() {
+        let mut share = token(WT_MSTR, None);
+        let mut network = (*share.network).clone();
+        network.rpcs.clear();
+        share.network = Arc::new(network);
+
+        let result = read_wrap_ratios_batch(&[WrapRatioBatchInput {
+            token: &share,
+            expected_asset_address: T_MSTR,
+        }])
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::Internal(msg)) if msg == "no RPC configured for chain 8453"
+        ));
+    }
 }